@@ -131,6 +131,31 @@ pub struct RunCmd {
 	#[arg(long)]
 	pub overseer_channel_capacity_override: Option<usize>,
 
+	/// Compress responses carrying PoVs for peers supporting it.
+	///
+	/// Collations, PoVs and available data are then also served and requested on the versions of
+	/// their request protocols with compressed responses.
+	#[arg(long)]
+	pub pov_compression: bool,
+
+	/// A zstd dictionary to compress PoVs of a para with, as trained with `zstd --train`.
+	///
+	/// Of format `PARA_ID=PATH`, or `CODE_HASH=PATH` for a dictionary following the validation
+	/// code of hash `CODE_HASH`. May be given multiple times.
+	///
+	/// Dictionaries are node-local only: they are neither fetched from the chain nor exchanged
+	/// with peers. A dictionary is only used with peers that were given the same dictionary, all
+	/// other peers get PoVs compressed without one.
+	#[arg(long = "pov-dictionary", value_name = "PARA_ID=PATH", requires = "pov_compression")]
+	pub pov_dictionaries: Vec<String>,
+
+	/// Limit the notification bytes per second a single peer may send on the collation peer-set.
+	///
+	/// Peers exceeding the limit get their notifications dropped and their reputation reduced,
+	/// and get disconnected when exceeding it repeatedly.
+	#[arg(long, value_name = "BYTES_PER_SECOND")]
+	pub collation_peer_rate_limit: Option<u64>,
+
 	/// Path to the directory where auxiliary worker binaries reside. If not specified, the main
 	/// binary's directory is searched first, then `/usr/lib/polkadot` is searched. TESTING ONLY:
 	/// if the path points to an executable rather then directory, that executable is used both as
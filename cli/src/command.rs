@@ -168,6 +168,30 @@ impl SubstrateCli for Cli {
 	}
 }
 
+/// Load the dictionaries given as `PARA_ID=PATH` or `CODE_HASH=PATH`.
+fn pov_dictionaries(specs: &[String]) -> Result<service::PoVDictionaries> {
+	let mut dictionaries = service::PoVDictionaries::default();
+	for spec in specs {
+		let (key, path) = spec
+			.split_once('=')
+			.ok_or_else(|| format!("Invalid PoV dictionary `{}`, expected `PARA_ID=PATH`", spec))?;
+		let dictionary = service::PoVDictionary::new(
+			std::fs::read(path)
+				.map_err(|e| format!("Failed to read PoV dictionary from `{}`: {}", path, e))?,
+		);
+		if key.starts_with("0x") {
+			let code_hash = key
+				.parse::<service::Hash>()
+				.map_err(|_| format!("Invalid validation code hash `{}`", key))?;
+			dictionaries.register_for_code_hash(code_hash.into(), dictionary);
+		} else {
+			let para_id = key.parse::<u32>().map_err(|_| format!("Invalid para id `{}`", key))?;
+			dictionaries.register_for_para(para_id.into(), dictionary);
+		}
+	}
+	Ok(dictionaries)
+}
+
 fn set_default_ss58_version(spec: &Box<dyn service::ChainSpec>) {
 	let ss58_version = if spec.is_kusama() {
 		Ss58AddressFormatRegistry::KusamaAccount
@@ -290,6 +314,12 @@ where
 	let node_version =
 		if cli.run.disable_worker_version_check { None } else { Some(NODE_VERSION.to_string()) };
 
+	let pov_compression = if cli.run.pov_compression {
+		Some(pov_dictionaries(&cli.run.pov_dictionaries)?)
+	} else {
+		None
+	};
+
 	runner.run_node_until_exit(move |config| async move {
 		let hwbench = (!cli.run.no_hardware_benchmarks)
 			.then_some(config.database.path().map(|database_path| {
@@ -316,6 +346,8 @@ where
 					.overseer_channel_capacity_override,
 				malus_finality_delay: maybe_malus_finality_delay,
 				hwbench,
+				pov_compression,
+				collation_peer_rate_limit: cli.run.collation_peer_rate_limit,
 			},
 		)
 		.map(|full| full.task_manager)?;
@@ -22,12 +22,14 @@
 //!
 //! * If there is no collation generation config, ignore.
 //! * Otherwise, for each `activated` head in the update:
-//!   * Determine if the para is scheduled on any core by fetching the `availability_cores` Runtime
+//!   * Determine the cores the para is scheduled on by fetching the `availability_cores` Runtime
 //!     API.
 //!   * Use the Runtime API subsystem to fetch the full validation data.
-//!   * Invoke the `collator`, and use its outputs to produce a [`CandidateReceipt`], signed with
-//!     the configuration's `key`.
-//!   * Dispatch a [`CollatorProtocolMessage::DistributeCollation`]`(receipt, pov)`.
+//!   * For each of these cores, invoke the `collator`, and use its outputs to produce a
+//!     [`CandidateReceipt`], signed with the configuration's `key`. With asynchronous backing, each
+//!     candidate builds on the head-data of the previous one. Only one core is used unless the
+//!     runtime accepts several candidates of a para in a block.
+//!   * Dispatch a [`CollatorProtocolMessage::DistributeCollation`]`(receipt, pov)` for the core.
 
 #![deny(missing_docs)]
 
@@ -38,19 +40,19 @@ use polkadot_node_primitives::{
 	SubmitCollationParams,
 };
 use polkadot_node_subsystem::{
-	messages::{CollationGenerationMessage, CollatorProtocolMessage},
+	messages::{CollationGenerationMessage, CollatorProtocolMessage, RuntimeApiRequest},
 	overseer, ActiveLeavesUpdate, FromOrchestra, OverseerSignal, RuntimeApiError, SpawnedSubsystem,
 	SubsystemContext, SubsystemError, SubsystemResult,
 };
 use polkadot_node_subsystem_util::{
-	request_availability_cores, request_persisted_validation_data,
+	request_availability_cores, request_persisted_validation_data, request_runtime_api_version,
 	request_staging_async_backing_params, request_validation_code, request_validation_code_hash,
 	request_validators,
 };
 use polkadot_primitives::{
 	collator_signature_payload, CandidateCommitments, CandidateDescriptor, CandidateReceipt,
-	CollatorPair, CoreState, Hash, Id as ParaId, OccupiedCoreAssumption, PersistedValidationData,
-	ValidationCodeHash,
+	CollatorPair, CoreIndex, CoreState, Hash, Id as ParaId, OccupiedCoreAssumption,
+	PersistedValidationData, ValidationCodeHash,
 };
 use sp_core::crypto::Pair;
 use std::sync::Arc;
@@ -215,6 +217,8 @@ async fn handle_new_activations<Context>(
 		let n_validators = validators??.len();
 		let async_backing_params = async_backing_params?.ok();
 
+		// The cores our para is assigned to, along with the assumption to build on them with.
+		let mut our_cores = Vec::new();
 		for (core_idx, core) in availability_cores.into_iter().enumerate() {
 			let _availability_core_timer = metrics.time_new_activations_availability_core();
 
@@ -268,13 +272,41 @@ async fn handle_new_activations<Context>(
 				continue
 			}
 
-			// we get validation data and validation code synchronously for each core instead of
-			// within the subtask loop, because we have only a single mutable handle to the
-			// context, so the work can't really be distributed
+			our_cores.push((CoreIndex(core_idx as u32), assumption));
+		}
 
+		// With asynchronous backing, a para holding several cores builds a chain of candidates
+		// on the relay parent, one per core, each to be backed by the group of its core, as long
+		// as the runtime accepts several candidates of a para in a block. Otherwise, only one
+		// candidate of the para may be pending at a time.
+		//
+		// Cores occupied by a candidate of ours go first, so that the chain extends the candidate
+		// pending availability instead of forking it off the included head.
+		let chain_supported = our_cores.len() > 1 &&
+			request_runtime_api_version(relay_parent, ctx.sender()).await.await?.map_or(
+				false,
+				|version| {
+					version >= RuntimeApiRequest::MULTIPLE_CANDIDATES_PER_PARA_RUNTIME_REQUIREMENT
+				},
+			);
+		let max_chain_length = match async_backing_params {
+			Some(params) if chain_supported => params.max_candidate_depth as usize + 1,
+			_ => 1,
+		};
+		our_cores.sort_by_key(|(_, assumption)| *assumption != OccupiedCoreAssumption::Included);
+		our_cores.truncate(max_chain_length);
+
+		// we get validation data and validation code synchronously instead of within the subtask,
+		// because we have only a single mutable handle to the context, so the work can't really
+		// be distributed
+		//
+		// Every link of the chain is built with the validation data and code its own core
+		// assumes. The chain stops at the first core for which these are not available.
+		let mut links = Vec::with_capacity(our_cores.len());
+		for (core_index, assumption) in our_cores {
 			let validation_data = match request_persisted_validation_data(
 				relay_parent,
-				scheduled_core.para_id,
+				config.para_id,
 				assumption,
 				ctx.sender(),
 			)
@@ -285,19 +317,18 @@ async fn handle_new_activations<Context>(
 				None => {
 					gum::trace!(
 						target: LOG_TARGET,
-						core_idx = %core_idx,
+						core_idx = %core_index.0,
 						relay_parent = ?relay_parent,
 						our_para = %config.para_id,
-						their_para = %scheduled_core.para_id,
 						"validation data is not available",
 					);
-					continue
+					break
 				},
 			};
 
 			let validation_code_hash = match obtain_validation_code_hash_with_assumption(
 				relay_parent,
-				scheduled_core.para_id,
+				config.para_id,
 				assumption,
 				ctx.sender(),
 			)
@@ -307,26 +338,39 @@ async fn handle_new_activations<Context>(
 				None => {
 					gum::trace!(
 						target: LOG_TARGET,
-						core_idx = %core_idx,
+						core_idx = %core_index.0,
 						relay_parent = ?relay_parent,
 						our_para = %config.para_id,
-						their_para = %scheduled_core.para_id,
 						"validation code hash is not found.",
 					);
-					continue
+					break
 				},
 			};
 
-			let task_config = config.clone();
-			let metrics = metrics.clone();
-			let mut task_sender = ctx.sender().clone();
-			ctx.spawn(
-				"collation-builder",
-				Box::pin(async move {
-					let collator_fn = match task_config.collator.as_ref() {
-						Some(x) => x,
-						None => return,
-					};
+			links.push((core_index, validation_data, validation_code_hash));
+		}
+
+		if links.is_empty() {
+			continue
+		}
+
+		let task_config = config.clone();
+		let metrics = metrics.clone();
+		let mut task_sender = ctx.sender().clone();
+		ctx.spawn(
+			"collation-builder",
+			Box::pin(async move {
+				let collator_fn = match task_config.collator.as_ref() {
+					Some(x) => x,
+					None => return,
+				};
+
+				// The head the next link of the chain builds on, once the first one is built.
+				let mut parent_head = None;
+				for (core_index, mut validation_data, validation_code_hash) in links {
+					if let Some(parent_head) = parent_head.take() {
+						validation_data.parent_head = parent_head;
+					}
 
 					let (collation, result_sender) =
 						match collator_fn(relay_parent, &validation_data).await {
@@ -334,21 +378,26 @@ async fn handle_new_activations<Context>(
 							None => {
 								gum::debug!(
 									target: LOG_TARGET,
-									para_id = %scheduled_core.para_id,
+									para_id = %task_config.para_id,
+									core_idx = %core_index.0,
 									"collator returned no collation on collate",
 								);
 								return
 							},
 						};
 
+					// The next candidate of the chain builds on this one.
+					parent_head = Some(collation.head_data.clone());
+
 					construct_and_distribute_receipt(
 						PreparedCollation {
 							collation,
-							para_id: scheduled_core.para_id,
+							para_id: task_config.para_id,
 							relay_parent,
 							validation_data,
 							validation_code_hash,
 							n_validators,
+							core_index: Some(core_index),
 						},
 						task_config.key.clone(),
 						&mut task_sender,
@@ -356,9 +405,9 @@ async fn handle_new_activations<Context>(
 						&metrics,
 					)
 					.await;
-				}),
-			)?;
-		}
+				}
+			}),
+		)?;
 	}
 
 	Ok(())
@@ -379,6 +428,7 @@ async fn handle_submit_collation<Context>(
 		parent_head,
 		validation_code_hash,
 		result_sender,
+		core_index,
 	} = params;
 
 	let validators = request_validators(relay_parent, ctx.sender()).await.await??;
@@ -415,6 +465,7 @@ async fn handle_submit_collation<Context>(
 		validation_data,
 		validation_code_hash,
 		n_validators,
+		core_index,
 	};
 
 	construct_and_distribute_receipt(
@@ -436,6 +487,7 @@ struct PreparedCollation {
 	validation_data: PersistedValidationData,
 	validation_code_hash: ValidationCodeHash,
 	n_validators: usize,
+	core_index: Option<CoreIndex>,
 }
 
 /// Takes a prepared collation, along with its context, and produces a candidate receipt
@@ -454,6 +506,7 @@ async fn construct_and_distribute_receipt(
 		validation_data,
 		validation_code_hash,
 		n_validators,
+		core_index,
 	} = collation;
 
 	let persisted_validation_data_hash = validation_data.hash();
@@ -547,6 +600,7 @@ async fn construct_and_distribute_receipt(
 			parent_head_data_hash,
 			pov,
 			result_sender,
+			core_index,
 		))
 		.await;
 }
@@ -29,11 +29,12 @@ use polkadot_node_subsystem::{
 use polkadot_node_subsystem_test_helpers::{subsystem_test_harness, TestSubsystemContextHandle};
 use polkadot_node_subsystem_util::TimeoutExt;
 use polkadot_primitives::{
-	CollatorPair, HeadData, Id as ParaId, PersistedValidationData, ScheduledCore, ValidationCode,
+	vstaging::AsyncBackingParams, CollatorPair, GroupIndex, HeadData, Id as ParaId, OccupiedCore,
+	PersistedValidationData, ScheduledCore, ValidationCode,
 };
 use sp_keyring::sr25519::Keyring as Sr25519Keyring;
 use std::pin::Pin;
-use test_helpers::{dummy_hash, dummy_head_data, dummy_validator};
+use test_helpers::{dummy_candidate_descriptor, dummy_hash, dummy_head_data, dummy_validator};
 
 type VirtualOverseer = TestSubsystemContextHandle<CollationGenerationMessage>;
 
@@ -540,6 +541,358 @@ fn fallback_when_no_validation_code_hash_api() {
 	}
 }
 
+#[test]
+fn distributes_chained_collations_to_all_cores_of_para() {
+	let activated_hash = Hash::repeat_byte(1);
+	let para_id = ParaId::from(16);
+
+	// empty vec doesn't allocate on the heap, so it's ok we throw it away
+	let to_collator_protocol = Arc::new(Mutex::new(Vec::new()));
+	let inner_to_collator_protocol = to_collator_protocol.clone();
+
+	let overseer = |mut handle: TestSubsystemContextHandle<CollationGenerationMessage>| async move {
+		loop {
+			match handle.try_recv().await {
+				None => break,
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::AvailabilityCores(tx),
+				))) => {
+					tx.send(Ok(vec![
+						CoreState::Scheduled(scheduled_core_for(para_id)),
+						CoreState::Scheduled(scheduled_core_for(17)),
+						CoreState::Scheduled(scheduled_core_for(para_id)),
+					]))
+					.unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::PersistedValidationData(_para_id, _assumption, tx),
+				))) => {
+					tx.send(Ok(Some(test_validation_data()))).unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::Validators(tx),
+				))) => {
+					tx.send(Ok(vec![dummy_validator(); 3])).unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::ValidationCodeHash(_para_id, _assumption, tx),
+				))) => {
+					tx.send(Ok(Some(ValidationCode(vec![1, 2, 3]).hash()))).unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::StagingAsyncBackingParams(tx),
+				))) => {
+					tx.send(Ok(AsyncBackingParams {
+						max_candidate_depth: 3,
+						allowed_ancestry_len: 2,
+					}))
+					.unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::Version(tx),
+				))) => {
+					tx.send(Ok(
+						RuntimeApiRequest::MULTIPLE_CANDIDATES_PER_PARA_RUNTIME_REQUIREMENT,
+					))
+					.unwrap();
+				},
+				Some(msg @ AllMessages::CollatorProtocol(_)) => {
+					inner_to_collator_protocol.lock().await.push(msg);
+				},
+				Some(msg) => {
+					panic!("didn't expect any other overseer requests; got {:?}", msg)
+				},
+			}
+		}
+	};
+
+	// Each collation extends the parent head, so that the chain can be checked.
+	let config = Arc::new(CollationGenerationConfig {
+		key: CollatorPair::generate().0,
+		collator: Some(Box::new(|_: Hash, vd: &PersistedValidationData| {
+			let mut collation = test_collation();
+			collation.head_data = HeadData([&vd.parent_head.0[..], &[1]].concat());
+			async move { Some(CollationResult { collation, result_sender: None }) }.boxed()
+		})),
+		para_id,
+	});
+
+	subsystem_test_harness(overseer, |mut ctx| async move {
+		handle_new_activations(config, vec![activated_hash], &mut ctx, Metrics(None))
+			.await
+			.unwrap();
+	});
+
+	let to_collator_protocol = Arc::try_unwrap(to_collator_protocol)
+		.expect("subsystem should have shut down by now")
+		.into_inner();
+
+	let genesis_head = test_validation_data().parent_head;
+	let mut expected_parent_head = genesis_head.clone();
+	assert_eq!(to_collator_protocol.len(), 2);
+	for (msg, expected_core) in to_collator_protocol.into_iter().zip([0, 2]) {
+		match msg {
+			AllMessages::CollatorProtocol(CollatorProtocolMessage::DistributeCollation(
+				CandidateReceipt { descriptor, .. },
+				parent_head_data_hash,
+				_pov,
+				_result_sender,
+				core_index,
+			)) => {
+				assert_eq!(core_index, Some(CoreIndex(expected_core)));
+				assert_eq!(parent_head_data_hash, expected_parent_head.hash());
+				let head = HeadData([&expected_parent_head.0[..], &[1]].concat());
+				assert_eq!(descriptor.para_head, head.hash());
+				expected_parent_head = head;
+			},
+			_ => panic!("received wrong message type"),
+		}
+	}
+	assert_ne!(expected_parent_head, genesis_head);
+}
+
+#[test]
+fn builds_one_collation_if_runtime_does_not_accept_chains() {
+	let activated_hash = Hash::repeat_byte(1);
+	let para_id = ParaId::from(16);
+
+	let to_collator_protocol = Arc::new(Mutex::new(Vec::new()));
+	let inner_to_collator_protocol = to_collator_protocol.clone();
+
+	let overseer = |mut handle: TestSubsystemContextHandle<CollationGenerationMessage>| async move {
+		loop {
+			match handle.try_recv().await {
+				None => break,
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::AvailabilityCores(tx),
+				))) => {
+					tx.send(Ok(vec![
+						CoreState::Scheduled(scheduled_core_for(para_id)),
+						CoreState::Scheduled(scheduled_core_for(para_id)),
+					]))
+					.unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::PersistedValidationData(_para_id, _assumption, tx),
+				))) => {
+					tx.send(Ok(Some(test_validation_data()))).unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::Validators(tx),
+				))) => {
+					tx.send(Ok(vec![dummy_validator(); 3])).unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::ValidationCodeHash(_para_id, _assumption, tx),
+				))) => {
+					tx.send(Ok(Some(ValidationCode(vec![1, 2, 3]).hash()))).unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::StagingAsyncBackingParams(tx),
+				))) => {
+					tx.send(Ok(AsyncBackingParams {
+						max_candidate_depth: 3,
+						allowed_ancestry_len: 2,
+					}))
+					.unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::Version(tx),
+				))) => {
+					tx.send(Ok(RuntimeApiRequest::STAGING_AUTHORIZED_COLLATORS)).unwrap();
+				},
+				Some(msg @ AllMessages::CollatorProtocol(_)) => {
+					inner_to_collator_protocol.lock().await.push(msg);
+				},
+				Some(msg) => {
+					panic!("didn't expect any other overseer requests; got {:?}", msg)
+				},
+			}
+		}
+	};
+
+	subsystem_test_harness(overseer, |mut ctx| async move {
+		handle_new_activations(
+			Arc::new(test_config(para_id)),
+			vec![activated_hash],
+			&mut ctx,
+			Metrics(None),
+		)
+		.await
+		.unwrap();
+	});
+
+	let to_collator_protocol = Arc::try_unwrap(to_collator_protocol)
+		.expect("subsystem should have shut down by now")
+		.into_inner();
+
+	// Only the first core of the para gets a collation.
+	assert_matches!(
+		&to_collator_protocol[..],
+		[AllMessages::CollatorProtocol(CollatorProtocolMessage::DistributeCollation(
+			_,
+			_,
+			_,
+			_,
+			Some(CoreIndex(0)),
+		))]
+	);
+}
+
+#[test]
+fn chained_collations_extend_the_candidate_pending_availability() {
+	let activated_hash = Hash::repeat_byte(1);
+	let para_id = ParaId::from(16);
+	let included_head = HeadData(vec![1]);
+	let pending_head = HeadData(vec![2]);
+
+	let to_collator_protocol = Arc::new(Mutex::new(Vec::new()));
+	let inner_to_collator_protocol = to_collator_protocol.clone();
+	let requested_assumptions = Arc::new(Mutex::new(Vec::new()));
+	let inner_requested_assumptions = requested_assumptions.clone();
+
+	let overseer = |mut handle: TestSubsystemContextHandle<CollationGenerationMessage>| async move {
+		loop {
+			match handle.try_recv().await {
+				None => break,
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::AvailabilityCores(tx),
+				))) => {
+					tx.send(Ok(vec![
+						CoreState::Scheduled(scheduled_core_for(para_id)),
+						CoreState::Occupied(OccupiedCore {
+							next_up_on_available: Some(scheduled_core_for(para_id)),
+							occupied_since: 0,
+							time_out_at: 10,
+							next_up_on_time_out: None,
+							availability: Default::default(),
+							group_responsible: GroupIndex(1),
+							candidate_hash: Default::default(),
+							candidate_descriptor: dummy_candidate_descriptor(dummy_hash()),
+						}),
+					]))
+					.unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::PersistedValidationData(_para_id, assumption, tx),
+				))) => {
+					inner_requested_assumptions.lock().await.push(assumption);
+					let mut validation_data = test_validation_data();
+					validation_data.parent_head = match assumption {
+						OccupiedCoreAssumption::Included => pending_head.clone(),
+						_ => included_head.clone(),
+					};
+					tx.send(Ok(Some(validation_data))).unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::Validators(tx),
+				))) => {
+					tx.send(Ok(vec![dummy_validator(); 3])).unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::ValidationCodeHash(_para_id, _assumption, tx),
+				))) => {
+					tx.send(Ok(Some(ValidationCode(vec![1, 2, 3]).hash()))).unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::StagingAsyncBackingParams(tx),
+				))) => {
+					tx.send(Ok(AsyncBackingParams {
+						max_candidate_depth: 3,
+						allowed_ancestry_len: 2,
+					}))
+					.unwrap();
+				},
+				Some(AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					_hash,
+					RuntimeApiRequest::Version(tx),
+				))) => {
+					tx.send(Ok(
+						RuntimeApiRequest::MULTIPLE_CANDIDATES_PER_PARA_RUNTIME_REQUIREMENT,
+					))
+					.unwrap();
+				},
+				Some(msg @ AllMessages::CollatorProtocol(_)) => {
+					inner_to_collator_protocol.lock().await.push(msg);
+				},
+				Some(msg) => {
+					panic!("didn't expect any other overseer requests; got {:?}", msg)
+				},
+			}
+		}
+	};
+
+	let config = Arc::new(CollationGenerationConfig {
+		key: CollatorPair::generate().0,
+		collator: Some(Box::new(|_: Hash, vd: &PersistedValidationData| {
+			let mut collation = test_collation();
+			collation.head_data = HeadData([&vd.parent_head.0[..], &[1]].concat());
+			async move { Some(CollationResult { collation, result_sender: None }) }.boxed()
+		})),
+		para_id,
+	});
+
+	subsystem_test_harness(overseer, |mut ctx| async move {
+		handle_new_activations(config, vec![activated_hash], &mut ctx, Metrics(None))
+			.await
+			.unwrap();
+	});
+
+	// Each core is asked for the validation data of its own assumption.
+	let requested_assumptions = Arc::try_unwrap(requested_assumptions).unwrap().into_inner();
+	assert_matches!(
+		&requested_assumptions[..],
+		[OccupiedCoreAssumption::Included, OccupiedCoreAssumption::Free]
+	);
+
+	let to_collator_protocol = Arc::try_unwrap(to_collator_protocol)
+		.expect("subsystem should have shut down by now")
+		.into_inner();
+
+	// The chain starts on top of the candidate pending availability on the occupied core.
+	let first_head = HeadData(vec![2, 1]);
+	let expected = [
+		(1, HeadData(vec![2]).hash(), first_head.hash()),
+		(0, first_head.hash(), HeadData(vec![2, 1, 1]).hash()),
+	];
+	assert_eq!(to_collator_protocol.len(), 2);
+	for (msg, (expected_core, expected_parent, expected_head)) in
+		to_collator_protocol.into_iter().zip(expected)
+	{
+		match msg {
+			AllMessages::CollatorProtocol(CollatorProtocolMessage::DistributeCollation(
+				CandidateReceipt { descriptor, .. },
+				parent_head_data_hash,
+				_pov,
+				_result_sender,
+				core_index,
+			)) => {
+				assert_eq!(core_index, Some(CoreIndex(expected_core)));
+				assert_eq!(parent_head_data_hash, expected_parent);
+				assert_eq!(descriptor.para_head, expected_head);
+			},
+			_ => panic!("received wrong message type"),
+		}
+	}
+}
+
 #[test]
 fn submit_collation_is_no_op_before_initialization() {
 	test_harness(|mut virtual_overseer| async move {
@@ -551,6 +904,7 @@ fn submit_collation_is_no_op_before_initialization() {
 					parent_head: vec![1, 2, 3].into(),
 					validation_code_hash: Hash::repeat_byte(1).into(),
 					result_sender: None,
+					core_index: None,
 				}),
 			})
 			.await;
@@ -587,6 +941,7 @@ fn submit_collation_leads_to_distribution() {
 					parent_head: vec![1, 2, 3].into(),
 					validation_code_hash,
 					result_sender: None,
+					core_index: None,
 				}),
 			})
 			.await;
@@ -96,6 +96,7 @@ use polkadot_node_subsystem::{
 use polkadot_node_subsystem_util::{
 	self as util,
 	backing_implicit_view::{FetchError as ImplicitViewFetchError, View as ImplicitView},
+	pov_cache::PoVCache,
 	request_from_runtime, request_session_index_for_child, request_validator_groups,
 	request_validators,
 	runtime::{prospective_parachains_mode, ProspectiveParachainsMode},
@@ -174,13 +175,14 @@ impl ValidatedCandidateCommand {
 /// The candidate backing subsystem.
 pub struct CandidateBackingSubsystem {
 	keystore: KeystorePtr,
+	pov_cache: PoVCache,
 	metrics: Metrics,
 }
 
 impl CandidateBackingSubsystem {
 	/// Create a new instance of the `CandidateBackingSubsystem`.
-	pub fn new(keystore: KeystorePtr, metrics: Metrics) -> Self {
-		Self { keystore, metrics }
+	pub fn new(keystore: KeystorePtr, pov_cache: PoVCache, metrics: Metrics) -> Self {
+		Self { keystore, pov_cache, metrics }
 	}
 }
 
@@ -191,7 +193,7 @@ where
 {
 	fn start(self, ctx: Context) -> SpawnedSubsystem {
 		let future = async move {
-			run(ctx, self.keystore, self.metrics)
+			run(ctx, self.keystore, self.pov_cache, self.metrics)
 				.await
 				.map_err(|e| SubsystemError::with_origin("candidate-backing", e))
 		}
@@ -275,12 +277,15 @@ struct State {
 	background_validation_tx: mpsc::Sender<(Hash, ValidatedCandidateCommand)>,
 	/// The handle to the keystore used for signing.
 	keystore: KeystorePtr,
+	/// The node-wide PoV cache, consulted before fetching PoVs from other validators.
+	pov_cache: PoVCache,
 }
 
 impl State {
 	fn new(
 		background_validation_tx: mpsc::Sender<(Hash, ValidatedCandidateCommand)>,
 		keystore: KeystorePtr,
+		pov_cache: PoVCache,
 	) -> Self {
 		State {
 			implicit_view: ImplicitView::default(),
@@ -289,6 +294,7 @@ impl State {
 			per_candidate: HashMap::new(),
 			background_validation_tx,
 			keystore,
+			pov_cache,
 		}
 	}
 }
@@ -297,10 +303,11 @@ impl State {
 async fn run<Context>(
 	mut ctx: Context,
 	keystore: KeystorePtr,
+	pov_cache: PoVCache,
 	metrics: Metrics,
 ) -> FatalResult<()> {
 	let (background_validation_tx, mut background_validation_rx) = mpsc::channel(16);
-	let mut state = State::new(background_validation_tx, keystore);
+	let mut state = State::new(background_validation_tx, keystore, pov_cache);
 
 	loop {
 		let res =
@@ -587,6 +594,7 @@ struct BackgroundValidationParams<S: overseer::CandidateBackingSenderTrait, F> {
 	relay_parent: Hash,
 	persisted_validation_data: PersistedValidationData,
 	pov: PoVData,
+	pov_cache: PoVCache,
 	n_validators: usize,
 	make_command: F,
 }
@@ -604,6 +612,7 @@ async fn validate_and_make_available(
 		relay_parent,
 		persisted_validation_data,
 		pov,
+		pov_cache,
 		n_validators,
 		make_command,
 	} = params;
@@ -626,6 +635,13 @@ async fn validate_and_make_available(
 		}
 	};
 
+	// Another subsystem may have obtained the PoV already.
+	let pov = match pov {
+		PoVData::FetchFromValidator { pov_hash, .. } =>
+			pov_cache.get(&pov_hash, "candidate-backing").map_or(pov, PoVData::Ready),
+		PoVData::Ready(_) => pov,
+	};
+
 	let pov = match pov {
 		PoVData::Ready(pov) => pov,
 		PoVData::FetchFromValidator { from_validator, candidate_hash, pov_hash } =>
@@ -684,11 +700,14 @@ async fn validate_and_make_available(
 			.await;
 
 			match erasure_valid {
-				Ok(()) => Ok(BackgroundValidationOutputs {
-					candidate,
-					commitments,
-					persisted_validation_data: validation_data,
-				}),
+				Ok(()) => {
+					pov_cache.insert(pov, Some(validation_data.clone()));
+					Ok(BackgroundValidationOutputs {
+						candidate,
+						commitments,
+						persisted_validation_data: validation_data,
+					})
+				},
 				Err(Error::StoreAvailableData(StoreAvailableDataError::InvalidErasureRoot)) => {
 					gum::debug!(
 						target: LOG_TARGET,
@@ -1421,6 +1440,7 @@ async fn handle_validated_candidate_command<Context>(
 									rp_state,
 									pvd,
 									&state.background_validation_tx,
+									&state.pov_cache,
 									attesting,
 								)
 								.await?;
@@ -1711,6 +1731,7 @@ async fn kick_off_validation_work<Context>(
 	rp_state: &mut PerRelayParentState,
 	persisted_validation_data: PersistedValidationData,
 	background_validation_tx: &mpsc::Sender<(Hash, ValidatedCandidateCommand)>,
+	pov_cache: &PoVCache,
 	attesting: AttestingData,
 ) -> Result<(), Error> {
 	let candidate_hash = attesting.candidate.hash();
@@ -1742,6 +1763,7 @@ async fn kick_off_validation_work<Context>(
 			relay_parent: rp_state.parent,
 			persisted_validation_data,
 			pov,
+			pov_cache: pov_cache.clone(),
 			n_validators: rp_state.table_context.validators.len(),
 			make_command: ValidatedCandidateCommand::Attest,
 		},
@@ -1846,6 +1868,7 @@ async fn maybe_validate_and_import<Context>(
 				rp_state,
 				pvd,
 				&state.background_validation_tx,
+				&state.pov_cache,
 				attesting,
 			)
 			.await?;
@@ -1863,6 +1886,7 @@ async fn validate_and_second<Context>(
 	candidate: &CandidateReceipt,
 	pov: Arc<PoV>,
 	background_validation_tx: &mpsc::Sender<(Hash, ValidatedCandidateCommand)>,
+	pov_cache: &PoVCache,
 ) -> Result<(), Error> {
 	let candidate_hash = candidate.hash();
 
@@ -1884,6 +1908,7 @@ async fn validate_and_second<Context>(
 			relay_parent: rp_state.parent,
 			persisted_validation_data,
 			pov: PoVData::Ready(pov),
+			pov_cache: pov_cache.clone(),
 			n_validators: rp_state.table_context.validators.len(),
 			make_command: ValidatedCandidateCommand::Second,
 		},
@@ -1960,6 +1985,7 @@ async fn handle_second_message<Context>(
 			&candidate,
 			pov,
 			&state.background_validation_tx,
+			&state.pov_cache,
 		)
 		.await?;
 	}
@@ -32,6 +32,7 @@ use polkadot_node_subsystem::{
 	ActivatedLeaf, ActiveLeavesUpdate, FromOrchestra, LeafStatus, OverseerSignal, TimeoutExt,
 };
 use polkadot_node_subsystem_test_helpers as test_helpers;
+use polkadot_node_subsystem_util::pov_cache::DEFAULT_POV_CACHE_SIZE;
 use polkadot_primitives::{
 	CandidateDescriptor, GroupRotationInfo, HeadData, PersistedValidationData, PvfExecTimeoutKind,
 	ScheduledCore, SessionIndex,
@@ -159,13 +160,21 @@ type VirtualOverseer = test_helpers::TestSubsystemContextHandle<CandidateBacking
 fn test_harness<T: Future<Output = VirtualOverseer>>(
 	keystore: KeystorePtr,
 	test: impl FnOnce(VirtualOverseer) -> T,
+) {
+	test_harness_with_pov_cache(keystore, PoVCache::new(0, Default::default()), test)
+}
+
+fn test_harness_with_pov_cache<T: Future<Output = VirtualOverseer>>(
+	keystore: KeystorePtr,
+	pov_cache: PoVCache,
+	test: impl FnOnce(VirtualOverseer) -> T,
 ) {
 	let pool = sp_core::testing::TaskExecutor::new();
 
 	let (context, virtual_overseer) = test_helpers::make_subsystem_context(pool.clone());
 
 	let subsystem = async move {
-		if let Err(e) = super::run(context, keystore, Metrics(None)).await {
+		if let Err(e) = super::run(context, keystore, pov_cache, Metrics(None)).await {
 			panic!("{:?}", e);
 		}
 	};
@@ -572,6 +581,126 @@ fn backing_works() {
 	});
 }
 
+// Test that PoVs obtained by other subsystems are not fetched again.
+#[test]
+fn backing_uses_cached_pov() {
+	let test_state = TestState::default();
+	let pov = PoV { block_data: BlockData(vec![1, 2, 3]) };
+	let pov_cache = PoVCache::new(DEFAULT_POV_CACHE_SIZE, Default::default());
+	pov_cache.insert(Arc::new(pov.clone()), None);
+
+	test_harness_with_pov_cache(
+		test_state.keystore.clone(),
+		pov_cache,
+		|mut virtual_overseer| async move {
+			test_startup(&mut virtual_overseer, &test_state).await;
+
+			let pvd = dummy_pvd();
+			let validation_code = ValidationCode(vec![1, 2, 3]);
+			let expected_head_data = test_state.head_data.get(&test_state.chain_ids[0]).unwrap();
+
+			let candidate_a = TestCandidateBuilder {
+				para_id: test_state.chain_ids[0],
+				relay_parent: test_state.relay_parent,
+				pov_hash: pov.hash(),
+				head_data: expected_head_data.clone(),
+				erasure_root: make_erasure_root(&test_state, pov.clone(), pvd.clone()),
+				validation_code: validation_code.0.clone(),
+				..Default::default()
+			}
+			.build();
+
+			let public2 = Keystore::sr25519_generate_new(
+				&*test_state.keystore,
+				ValidatorId::ID,
+				Some(&test_state.validators[2].to_seed()),
+			)
+			.expect("Insert key into keystore");
+
+			let signed_a = SignedFullStatementWithPVD::sign(
+				&test_state.keystore,
+				StatementWithPVD::Seconded(candidate_a.clone(), pvd.clone()),
+				&test_state.signing_context,
+				ValidatorIndex(2),
+				&public2.into(),
+			)
+			.ok()
+			.flatten()
+			.expect("should be signed");
+
+			let statement =
+				CandidateBackingMessage::Statement(test_state.relay_parent, signed_a.clone());
+
+			virtual_overseer.send(FromOrchestra::Communication { msg: statement }).await;
+
+			assert_matches!(
+				virtual_overseer.recv().await,
+				AllMessages::RuntimeApi(
+					RuntimeApiMessage::Request(_, RuntimeApiRequest::ValidationCodeByHash(hash, tx))
+				) if hash == validation_code.hash() => {
+					tx.send(Ok(Some(validation_code.clone()))).unwrap();
+				}
+			);
+
+			// The PoV is taken from the cache, so validation is requested right away.
+			assert_matches!(
+				virtual_overseer.recv().await,
+				AllMessages::CandidateValidation(
+					CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _pov, _, tx),
+				) if *_pov == pov => {
+					tx.send(Ok(
+						ValidationResult::Valid(CandidateCommitments {
+							head_data: expected_head_data.clone(),
+							upward_messages: Default::default(),
+							horizontal_messages: Default::default(),
+							new_validation_code: None,
+							processed_downward_messages: 0,
+							hrmp_watermark: 0,
+						}, test_state.validation_data.clone()),
+					)).unwrap();
+				}
+			);
+
+			assert_matches!(
+				virtual_overseer.recv().await,
+				AllMessages::AvailabilityStore(
+					AvailabilityStoreMessage::StoreAvailableData { candidate_hash, tx, .. }
+				) if candidate_hash == candidate_a.hash() => {
+					tx.send(Ok(())).unwrap();
+				}
+			);
+
+			assert_matches!(
+				virtual_overseer.recv().await,
+				AllMessages::StatementDistribution(
+					StatementDistributionMessage::Share(hash, _stmt)
+				) => {
+					assert_eq!(test_state.relay_parent, hash);
+				}
+			);
+
+			assert_matches!(
+				virtual_overseer.recv().await,
+				AllMessages::Provisioner(
+					ProvisionerMessage::ProvisionableData(
+						_,
+						ProvisionableData::BackedCandidate(candidate_receipt)
+					)
+				) => {
+					assert_eq!(candidate_receipt, candidate_a.to_plain());
+				}
+			);
+
+			virtual_overseer
+				.send(FromOrchestra::Signal(OverseerSignal::ActiveLeaves(
+					ActiveLeavesUpdate::stop_work(test_state.relay_parent),
+				)))
+				.await;
+			virtual_overseer
+		},
+	);
+}
+
 #[test]
 fn backing_works_while_validation_ongoing() {
 	let test_state = TestState::default();
@@ -65,6 +65,9 @@ const LOG_TARGET: &str = "parachain::provisioner";
 const PRIORITIZED_SELECTION_RUNTIME_VERSION_REQUIREMENT: u32 =
 	RuntimeApiRequest::DISPUTES_RUNTIME_REQUIREMENT;
 
+const MULTIPLE_CANDIDATES_PER_PARA_RUNTIME_VERSION_REQUIREMENT: u32 =
+	RuntimeApiRequest::MULTIPLE_CANDIDATES_PER_PARA_RUNTIME_REQUIREMENT;
+
 /// The provisioner subsystem.
 pub struct ProvisionerSubsystem {
 	metrics: Metrics,
@@ -435,11 +438,22 @@ async fn send_inherent_data(
 		"Selected bitfields"
 	);
 
+	// Chained candidates of a para on several cores are only accepted by runtimes which map a
+	// para to all of its cores, any other runtime would drop all the candidates of the block.
+	let chain_candidates = prospective_parachains_mode.is_enabled() &&
+		has_required_runtime(
+			from_job,
+			leaf.hash,
+			MULTIPLE_CANDIDATES_PER_PARA_RUNTIME_VERSION_REQUIREMENT,
+		)
+		.await;
+
 	let candidates = select_candidates(
 		&availability_cores,
 		&bitfields,
 		candidates,
 		prospective_parachains_mode,
+		chain_candidates,
 		leaf.hash,
 		from_job,
 	)
@@ -640,16 +654,23 @@ async fn select_candidate_hashes_from_tracked(
 /// Requests backable candidates from Prospective Parachains subsystem
 /// based on core states.
 ///
+/// With `chain_candidates`, a para assigned to several cores gets a chain of candidates,
+/// otherwise only its first core is served.
+///
 /// Should be called when prospective parachains are enabled.
 async fn request_backable_candidates(
 	availability_cores: &[CoreState],
 	bitfields: &[SignedAvailabilityBitfield],
+	chain_candidates: bool,
 	relay_parent: Hash,
 	sender: &mut impl overseer::ProvisionerSenderTrait,
 ) -> Result<Vec<(CandidateHash, Hash)>, Error> {
 	let block_number = get_block_number_under_construction(relay_parent, sender).await?;
 
 	let mut selected_candidates = Vec::with_capacity(availability_cores.len());
+	// The path leading to the candidates selected so far for each para. A para assigned to
+	// several cores gets a chain of candidates, each one a child of the previous one.
+	let mut para_paths: HashMap<ParaId, Vec<CandidateHash>> = HashMap::new();
 
 	for (core_idx, core) in availability_cores.iter().enumerate() {
 		let (para_id, required_path) = match core {
@@ -687,10 +708,26 @@ async fn request_backable_candidates(
 			CoreState::Free => continue,
 		};
 
-		let response = get_backable_candidate(relay_parent, para_id, required_path, sender).await?;
+		if !chain_candidates && para_paths.contains_key(&para_id) {
+			gum::trace!(
+				target: LOG_TARGET,
+				leaf_hash = ?relay_parent,
+				core = core_idx,
+				?para_id,
+				"Runtime does not accept several candidates of a para, skipping core",
+			);
+			continue
+		}
+
+		let required_path = para_paths.remove(&para_id).unwrap_or(required_path);
+		let response =
+			get_backable_candidate(relay_parent, para_id, required_path.clone(), sender).await?;
 
 		match response {
-			Some((hash, relay_parent)) => selected_candidates.push((hash, relay_parent)),
+			Some((hash, relay_parent)) => {
+				selected_candidates.push((hash, relay_parent));
+				para_paths.insert(para_id, required_path.into_iter().chain(Some(hash)).collect());
+			},
 			None => {
 				gum::debug!(
 					target: LOG_TARGET,
@@ -712,6 +749,7 @@ async fn select_candidates(
 	bitfields: &[SignedAvailabilityBitfield],
 	candidates: &[CandidateReceipt],
 	prospective_parachains_mode: ProspectiveParachainsMode,
+	chain_candidates: bool,
 	relay_parent: Hash,
 	sender: &mut impl overseer::ProvisionerSenderTrait,
 ) -> Result<Vec<BackedCandidate>, Error> {
@@ -721,7 +759,14 @@ async fn select_candidates(
 
 	let selected_candidates = match prospective_parachains_mode {
 		ProspectiveParachainsMode::Enabled { .. } =>
-			request_backable_candidates(availability_cores, bitfields, relay_parent, sender).await?,
+			request_backable_candidates(
+				availability_cores,
+				bitfields,
+				chain_candidates,
+				relay_parent,
+				sender,
+			)
+			.await?,
 		ProspectiveParachainsMode::Disabled =>
 			select_candidate_hashes_from_tracked(
 				availability_cores,
@@ -397,6 +397,7 @@ mod select_candidates {
 					&[],
 					&[],
 					prospective_parachains_mode,
+					false,
 					Default::default(),
 					&mut tx,
 				)
@@ -473,6 +474,7 @@ mod select_candidates {
 					&[],
 					&candidates,
 					prospective_parachains_mode,
+					false,
 					Default::default(),
 					&mut tx,
 				)
@@ -551,6 +553,7 @@ mod select_candidates {
 					&[],
 					&candidates,
 					prospective_parachains_mode,
+					false,
 					Default::default(),
 					&mut tx,
 				)
@@ -618,6 +621,7 @@ mod select_candidates {
 					&[],
 					&[],
 					prospective_parachains_mode,
+					false,
 					Default::default(),
 					&mut tx,
 				)
@@ -684,6 +688,7 @@ mod select_candidates {
 					&[],
 					&[],
 					prospective_parachains_mode,
+					false,
 					Default::default(),
 					&mut tx,
 				)
@@ -700,4 +705,105 @@ mod select_candidates {
 			},
 		)
 	}
+
+	#[test]
+	fn requests_chain_for_para_on_several_cores() {
+		use CoreState::Scheduled;
+
+		let cores = vec![
+			Scheduled(scheduled_core(1)),
+			Scheduled(scheduled_core(2)),
+			Scheduled(scheduled_core(1)),
+			Scheduled(scheduled_core(1)),
+		];
+		let candidate = |n: u8| (CandidateHash(Hash::repeat_byte(n)), Hash::repeat_byte(0));
+
+		let overseer = |mut receiver: mpsc::UnboundedReceiver<AllMessages>| async move {
+			// The para on several cores gets a chain, while the para on one core does not.
+			let mut expected = vec![
+				(ParaId::from(1), vec![], candidate(1)),
+				(ParaId::from(2), vec![], candidate(2)),
+				(ParaId::from(1), vec![candidate(1).0], candidate(3)),
+				(ParaId::from(1), vec![candidate(1).0, candidate(3).0], candidate(4)),
+			]
+			.into_iter();
+
+			while let Some(from_job) = receiver.next().await {
+				match from_job {
+					AllMessages::ChainApi(ChainApiMessage::BlockNumber(_relay_parent, tx)) =>
+						tx.send(Ok(Some(BLOCK_UNDER_PRODUCTION - 1))).unwrap(),
+					AllMessages::ProspectiveParachains(
+						ProspectiveParachainsMessage::GetBackableCandidate(
+							_relay_parent,
+							para_id,
+							required_path,
+							tx,
+						),
+					) => {
+						let (expected_para, expected_path, response) = expected.next().unwrap();
+						assert_eq!(para_id, expected_para);
+						assert_eq!(required_path, expected_path);
+						let _ = tx.send(Some(response));
+					},
+					_ => panic!("Unexpected message: {:?}", from_job),
+				}
+			}
+		};
+
+		test_harness(overseer, |mut tx: TestSubsystemSender| async move {
+			let selected =
+				request_backable_candidates(&cores, &[], true, Default::default(), &mut tx)
+					.await
+					.unwrap();
+			assert_eq!(selected, vec![candidate(1), candidate(2), candidate(3), candidate(4)]);
+		})
+	}
+
+	#[test]
+	fn requests_one_candidate_per_para_without_runtime_support() {
+		use CoreState::Scheduled;
+
+		let cores = vec![
+			Scheduled(scheduled_core(1)),
+			Scheduled(scheduled_core(2)),
+			Scheduled(scheduled_core(1)),
+		];
+		let candidate = |n: u8| (CandidateHash(Hash::repeat_byte(n)), Hash::repeat_byte(0));
+
+		let overseer = |mut receiver: mpsc::UnboundedReceiver<AllMessages>| async move {
+			// The second core of para 1 is not served.
+			let mut expected =
+				vec![(ParaId::from(1), candidate(1)), (ParaId::from(2), candidate(2))].into_iter();
+
+			while let Some(from_job) = receiver.next().await {
+				match from_job {
+					AllMessages::ChainApi(ChainApiMessage::BlockNumber(_relay_parent, tx)) =>
+						tx.send(Ok(Some(BLOCK_UNDER_PRODUCTION - 1))).unwrap(),
+					AllMessages::ProspectiveParachains(
+						ProspectiveParachainsMessage::GetBackableCandidate(
+							_relay_parent,
+							para_id,
+							required_path,
+							tx,
+						),
+					) => {
+						let (expected_para, response) = expected.next().unwrap();
+						assert_eq!(para_id, expected_para);
+						assert!(required_path.is_empty());
+						let _ = tx.send(Some(response));
+					},
+					_ => panic!("Unexpected message: {:?}", from_job),
+				}
+			}
+			assert!(expected.next().is_none());
+		};
+
+		test_harness(overseer, |mut tx: TestSubsystemSender| async move {
+			let selected =
+				request_backable_candidates(&cores, &[], false, Default::default(), &mut tx)
+					.await
+					.unwrap();
+			assert_eq!(selected, vec![candidate(1), candidate(2)]);
+		})
+	}
 }
@@ -21,7 +21,7 @@ use sp_consensus_babe::Epoch;
 
 use polkadot_primitives::{
 	vstaging, AuthorityDiscoveryId, BlockNumber, CandidateCommitments, CandidateEvent,
-	CandidateHash, CommittedCandidateReceipt, CoreState, DisputeState, ExecutorParams,
+	CandidateHash, CollatorId, CommittedCandidateReceipt, CoreState, DisputeState, ExecutorParams,
 	GroupRotationInfo, Hash, Id as ParaId, InboundDownwardMessage, InboundHrmpMessage,
 	OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes,
 	SessionIndex, SessionInfo, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
@@ -71,6 +71,7 @@ pub(crate) struct RequestResultCache {
 
 	staging_para_backing_state: LruCache<(Hash, ParaId), Option<vstaging::BackingState>>,
 	staging_async_backing_params: LruCache<Hash, vstaging::AsyncBackingParams>,
+	staging_authorized_collators: LruCache<(Hash, ParaId), Option<Vec<CollatorId>>>,
 }
 
 impl Default for RequestResultCache {
@@ -103,6 +104,7 @@ impl Default for RequestResultCache {
 
 			staging_para_backing_state: LruCache::new(DEFAULT_CACHE_CAP),
 			staging_async_backing_params: LruCache::new(DEFAULT_CACHE_CAP),
+			staging_authorized_collators: LruCache::new(DEFAULT_CACHE_CAP),
 		}
 	}
 }
@@ -466,6 +468,21 @@ impl RequestResultCache {
 	) {
 		self.staging_async_backing_params.put(key, value);
 	}
+
+	pub(crate) fn staging_authorized_collators(
+		&mut self,
+		key: (Hash, ParaId),
+	) -> Option<&Option<Vec<CollatorId>>> {
+		self.staging_authorized_collators.get(&key)
+	}
+
+	pub(crate) fn cache_staging_authorized_collators(
+		&mut self,
+		key: (Hash, ParaId),
+		value: Option<Vec<CollatorId>>,
+	) {
+		self.staging_authorized_collators.put(key, value);
+	}
 }
 
 pub(crate) enum RequestResult {
@@ -515,4 +532,5 @@ pub(crate) enum RequestResult {
 
 	StagingParaBackingState(Hash, ParaId, Option<vstaging::BackingState>),
 	StagingAsyncBackingParams(Hash, vstaging::AsyncBackingParams),
+	StagingAuthorizedCollators(Hash, ParaId, Option<Vec<CollatorId>>),
 }
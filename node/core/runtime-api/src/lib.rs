@@ -169,6 +169,9 @@ where
 				.cache_staging_para_backing_state((relay_parent, para_id), constraints),
 			StagingAsyncBackingParams(relay_parent, params) =>
 				self.requests_cache.cache_staging_async_backing_params(relay_parent, params),
+			StagingAuthorizedCollators(relay_parent, para_id, collators) => self
+				.requests_cache
+				.cache_staging_authorized_collators((relay_parent, para_id), collators),
 		}
 	}
 
@@ -301,6 +304,9 @@ where
 			Request::StagingAsyncBackingParams(sender) =>
 				query!(staging_async_backing_params(), sender)
 					.map(|sender| Request::StagingAsyncBackingParams(sender)),
+			Request::StagingAuthorizedCollators(para, sender) =>
+				query!(staging_authorized_collators(para), sender)
+					.map(|sender| Request::StagingAuthorizedCollators(para, sender)),
 		}
 	}
 
@@ -568,5 +574,13 @@ where
 				sender
 			)
 		},
+		Request::StagingAuthorizedCollators(para, sender) => {
+			query!(
+				StagingAuthorizedCollators,
+				staging_authorized_collators(para),
+				ver = Request::STAGING_AUTHORIZED_COLLATORS,
+				sender
+			)
+		},
 	}
 }
@@ -18,7 +18,7 @@ use futures::{future::Either, FutureExt, StreamExt, TryFutureExt};
 
 use sp_keystore::KeystorePtr;
 
-use polkadot_node_network_protocol::request_response::{v1, IncomingRequestReceiver};
+use polkadot_node_network_protocol::request_response::{v1, vstaging, IncomingRequestReceiver};
 use polkadot_node_subsystem::{
 	jaeger, messages::AvailabilityDistributionMessage, overseer, FromOrchestra, OverseerSignal,
 	SpawnedSubsystem, SubsystemError,
@@ -30,7 +30,7 @@ use std::collections::HashMap;
 mod error;
 use error::{log_error, FatalError, Result};
 
-use polkadot_node_subsystem_util::runtime::RuntimeInfo;
+use polkadot_node_subsystem_util::{pov_cache::PoVCache, runtime::RuntimeInfo};
 
 /// `Requester` taking care of requesting chunks for candidates pending availability.
 mod requester;
@@ -41,7 +41,7 @@ mod pov_requester;
 
 /// Responding to erasure chunk requests:
 mod responder;
-use responder::{run_chunk_receiver, run_pov_receiver};
+use responder::{run_chunk_batch_receiver, run_chunk_receiver, run_pov_receiver};
 
 mod metrics;
 /// Prometheus `Metrics` for availability distribution.
@@ -58,6 +58,8 @@ pub struct AvailabilityDistributionSubsystem {
 	runtime: RuntimeInfo,
 	/// Receivers to receive messages from.
 	recvs: IncomingRequestReceivers,
+	/// The node-wide PoV cache, consulted before fetching PoVs.
+	pov_cache: PoVCache,
 	/// Prometheus metrics.
 	metrics: Metrics,
 }
@@ -68,6 +70,8 @@ pub struct IncomingRequestReceivers {
 	pub pov_req_receiver: IncomingRequestReceiver<v1::PoVFetchingRequest>,
 	/// Receiver for incoming availability chunk requests.
 	pub chunk_req_receiver: IncomingRequestReceiver<v1::ChunkFetchingRequest>,
+	/// Receiver for incoming batched availability chunk requests.
+	pub chunk_req_vstaging_receiver: IncomingRequestReceiver<vstaging::ChunkFetchingRequest>,
 }
 
 #[overseer::subsystem(AvailabilityDistribution, error=SubsystemError, prefix=self::overseer)]
@@ -85,17 +89,26 @@ impl<Context> AvailabilityDistributionSubsystem {
 #[overseer::contextbounds(AvailabilityDistribution, prefix = self::overseer)]
 impl AvailabilityDistributionSubsystem {
 	/// Create a new instance of the availability distribution.
-	pub fn new(keystore: KeystorePtr, recvs: IncomingRequestReceivers, metrics: Metrics) -> Self {
+	pub fn new(
+		keystore: KeystorePtr,
+		recvs: IncomingRequestReceivers,
+		pov_cache: PoVCache,
+		metrics: Metrics,
+	) -> Self {
 		let runtime = RuntimeInfo::new(Some(keystore));
-		Self { runtime, recvs, metrics }
+		Self { runtime, recvs, pov_cache, metrics }
 	}
 
 	/// Start processing work as passed on from the Overseer.
 	async fn run<Context>(self, mut ctx: Context) -> std::result::Result<(), FatalError> {
-		let Self { mut runtime, recvs, metrics } = self;
+		let Self { mut runtime, recvs, pov_cache, metrics } = self;
 		let mut spans: HashMap<Hash, jaeger::PerLeafSpan> = HashMap::new();
 
-		let IncomingRequestReceivers { pov_req_receiver, chunk_req_receiver } = recvs;
+		let IncomingRequestReceivers {
+			pov_req_receiver,
+			chunk_req_receiver,
+			chunk_req_vstaging_receiver,
+		} = recvs;
 		let mut requester = Requester::new(metrics.clone()).fuse();
 		let mut warn_freq = gum::Freq::new();
 
@@ -109,7 +122,14 @@ impl AvailabilityDistributionSubsystem {
 
 			ctx.spawn(
 				"chunk-receiver",
-				run_chunk_receiver(sender, chunk_req_receiver, metrics.clone()).boxed(),
+				run_chunk_receiver(sender.clone(), chunk_req_receiver, metrics.clone()).boxed(),
+			)
+			.map_err(FatalError::SpawnTask)?;
+
+			ctx.spawn(
+				"chunk-batch-receiver",
+				run_chunk_batch_receiver(sender, chunk_req_vstaging_receiver, metrics.clone())
+					.boxed(),
 			)
 			.map_err(FatalError::SpawnTask)?;
 		}
@@ -185,6 +205,7 @@ impl AvailabilityDistributionSubsystem {
 							candidate_hash,
 							pov_hash,
 							tx,
+							pov_cache.clone(),
 							metrics.clone(),
 							&span,
 						)
@@ -17,6 +17,7 @@
 //! PoV requester takes care of requesting PoVs from validators of a backing group.
 
 use futures::{channel::oneshot, future::BoxFuture, FutureExt};
+use std::sync::Arc;
 
 use polkadot_node_network_protocol::request_response::{
 	outgoing::{RequestError, Requests},
@@ -29,7 +30,7 @@ use polkadot_node_subsystem::{
 	messages::{IfDisconnected, NetworkBridgeTxMessage},
 	overseer,
 };
-use polkadot_node_subsystem_util::runtime::RuntimeInfo;
+use polkadot_node_subsystem_util::{pov_cache::PoVCache, runtime::RuntimeInfo};
 use polkadot_primitives::{
 	AuthorityDiscoveryId, CandidateHash, Hash, Id as ParaId, ValidatorIndex,
 };
@@ -51,6 +52,7 @@ pub async fn fetch_pov<Context>(
 	candidate_hash: CandidateHash,
 	pov_hash: Hash,
 	tx: oneshot::Sender<PoV>,
+	pov_cache: PoVCache,
 	metrics: Metrics,
 	span: &jaeger::Span,
 ) -> Result<()> {
@@ -64,6 +66,10 @@ pub async fn fetch_pov<Context>(
 		.with_string_tag("pov-hash", format!("{:?}", pov_hash))
 		.with_stage(jaeger::Stage::AvailabilityDistribution);
 
+	if let Some(pov) = pov_cache.get(&pov_hash, "availability-distribution") {
+		return tx.send((*pov).clone()).map_err(|_| Error::SendResponse)
+	}
+
 	let info = &runtime.get_session_info(ctx.sender(), parent).await?.session_info;
 	let authority_id = info
 		.discovery_keys
@@ -84,8 +90,16 @@ pub async fn fetch_pov<Context>(
 
 	ctx.spawn(
 		"pov-fetcher",
-		fetch_pov_job(para_id, pov_hash, authority_id, pending_response.boxed(), tx, metrics)
-			.boxed(),
+		fetch_pov_job(
+			para_id,
+			pov_hash,
+			authority_id,
+			pending_response.boxed(),
+			tx,
+			pov_cache,
+			metrics,
+		)
+		.boxed(),
 	)
 	.map_err(|e| FatalError::SpawnTask(e))?;
 	Ok(())
@@ -98,9 +112,10 @@ async fn fetch_pov_job(
 	authority_id: AuthorityDiscoveryId,
 	pending_response: BoxFuture<'static, std::result::Result<PoVFetchingResponse, RequestError>>,
 	tx: oneshot::Sender<PoV>,
+	pov_cache: PoVCache,
 	metrics: Metrics,
 ) {
-	if let Err(err) = do_fetch_pov(pov_hash, pending_response, tx, metrics).await {
+	if let Err(err) = do_fetch_pov(pov_hash, pending_response, tx, pov_cache, metrics).await {
 		gum::warn!(target: LOG_TARGET, ?err, ?para_id, ?pov_hash, ?authority_id, "fetch_pov_job");
 	}
 }
@@ -110,6 +125,7 @@ async fn do_fetch_pov(
 	pov_hash: Hash,
 	pending_response: BoxFuture<'static, std::result::Result<PoVFetchingResponse, RequestError>>,
 	tx: oneshot::Sender<PoV>,
+	pov_cache: PoVCache,
 	metrics: Metrics,
 ) -> Result<()> {
 	let response = pending_response.await.map_err(Error::FetchPoV);
@@ -126,6 +142,7 @@ async fn do_fetch_pov(
 	};
 	if pov.hash() == pov_hash {
 		metrics.on_fetched_pov(SUCCEEDED);
+		pov_cache.insert(Arc::new(pov.clone()), None);
 		tx.send(pov).map_err(|_| Error::SendResponse)
 	} else {
 		metrics.on_fetched_pov(FAILED);
@@ -146,6 +163,7 @@ mod tests {
 		AllMessages, AvailabilityDistributionMessage, RuntimeApiMessage, RuntimeApiRequest,
 	};
 	use polkadot_node_subsystem_test_helpers as test_helpers;
+	use polkadot_node_subsystem_util::pov_cache::DEFAULT_POV_CACHE_SIZE;
 	use polkadot_primitives::{CandidateHash, Hash, ValidatorIndex};
 	use test_helpers::mock::make_ferdie_keystore;
 
@@ -156,17 +174,55 @@ mod tests {
 	fn rejects_invalid_pov() {
 		sp_tracing::try_init_simple();
 		let pov = PoV { block_data: BlockData(vec![1, 2, 3, 4, 5, 6]) };
-		test_run(Hash::default(), pov);
+		let pov_cache = PoVCache::new(DEFAULT_POV_CACHE_SIZE, Default::default());
+		test_run(Hash::default(), pov.clone(), pov_cache.clone());
+		assert!(pov_cache.get(&pov.hash(), "test").is_none());
 	}
 
 	#[test]
 	fn accepts_valid_pov() {
 		sp_tracing::try_init_simple();
 		let pov = PoV { block_data: BlockData(vec![1, 2, 3, 4, 5, 6]) };
-		test_run(pov.hash(), pov);
+		let pov_cache = PoVCache::new(DEFAULT_POV_CACHE_SIZE, Default::default());
+		test_run(pov.hash(), pov.clone(), pov_cache.clone());
+		assert_eq!(pov_cache.get(&pov.hash(), "test").as_deref(), Some(&pov));
+	}
+
+	#[test]
+	fn serves_cached_pov() {
+		sp_tracing::try_init_simple();
+		let pov = PoV { block_data: BlockData(vec![1, 2, 3, 4, 5, 6]) };
+		let pov_cache = PoVCache::new(DEFAULT_POV_CACHE_SIZE, Default::default());
+		pov_cache.insert(Arc::new(pov.clone()), None);
+
+		let pool = TaskExecutor::new();
+		let (mut context, _virtual_overseer) = test_helpers::make_subsystem_context::<
+			AvailabilityDistributionMessage,
+			TaskExecutor,
+		>(pool.clone());
+		let keystore = make_ferdie_keystore();
+		let mut runtime = polkadot_node_subsystem_util::runtime::RuntimeInfo::new(Some(keystore));
+
+		// No session info or network request is needed.
+		let (tx, rx) = oneshot::channel();
+		executor::block_on(fetch_pov(
+			&mut context,
+			&mut runtime,
+			Hash::default(),
+			ValidatorIndex(0),
+			ParaId::default(),
+			CandidateHash::default(),
+			pov.hash(),
+			tx,
+			pov_cache,
+			Metrics::new_dummy(),
+			&jaeger::Span::Disabled,
+		))
+		.expect("Should succeed");
+		assert_eq!(executor::block_on(rx), Ok(pov));
 	}
 
-	fn test_run(pov_hash: Hash, pov: PoV) {
+	fn test_run(pov_hash: Hash, pov: PoV, pov_cache: PoVCache) {
 		let pool = TaskExecutor::new();
 		let (mut context, mut virtual_overseer) = test_helpers::make_subsystem_context::<
 			AvailabilityDistributionMessage,
@@ -186,6 +242,7 @@ mod tests {
 				CandidateHash::default(),
 				pov_hash,
 				tx,
+				pov_cache,
 				Metrics::new_dummy(),
 				&jaeger::Span::Disabled,
 			)
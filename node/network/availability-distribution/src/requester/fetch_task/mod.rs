@@ -24,15 +24,11 @@ use futures::{
 
 use polkadot_erasure_coding::branch_hash;
 use polkadot_node_network_protocol::request_response::{
-	outgoing::{OutgoingRequest, Recipient, RequestError, Requests},
+	outgoing::{OutgoingRequest, Recipient, RequestError},
 	v1::{ChunkFetchingRequest, ChunkFetchingResponse},
 };
 use polkadot_node_primitives::ErasureChunk;
-use polkadot_node_subsystem::{
-	jaeger,
-	messages::{AvailabilityStoreMessage, IfDisconnected, NetworkBridgeTxMessage},
-	overseer,
-};
+use polkadot_node_subsystem::{jaeger, messages::AvailabilityStoreMessage, overseer};
 use polkadot_primitives::{
 	AuthorityDiscoveryId, BlakeTwo256, CandidateHash, GroupIndex, Hash, HashT, OccupiedCore,
 	SessionIndex,
@@ -86,6 +82,9 @@ pub enum FromFetchTask {
 	/// Message to other subsystem.
 	Message(overseer::AvailabilityDistributionOutgoingMessages),
 
+	/// Chunk request to send, batched with the requests of other tasks to the same validator.
+	Request(OutgoingRequest<ChunkFetchingRequest>),
+
 	/// Concluded with result.
 	///
 	/// In case of `None` everything was fine, in case of `Some`, some validators in the group
@@ -364,16 +363,9 @@ impl RunningTask {
 
 		let (full_request, response_recv) =
 			OutgoingRequest::new(Recipient::Authority(validator.clone()), self.request);
-		let requests = Requests::ChunkFetchingV1(full_request);
 
 		self.sender
-			.send(FromFetchTask::Message(
-				NetworkBridgeTxMessage::SendRequests(
-					vec![requests],
-					IfDisconnected::ImmediateError,
-				)
-				.into(),
-			))
+			.send(FromFetchTask::Request(full_request))
 			.await
 			.map_err(|_| TaskError::ShuttingDown)?;
 
@@ -217,6 +217,7 @@ impl TestRun {
 					FromFetchTask::Concluded(_) => break,
 					FromFetchTask::Failed(_) => break,
 					FromFetchTask::Message(msg) => end_ok = self.handle_message(msg).await,
+					FromFetchTask::Request(req) => end_ok = self.handle_request(req),
 				}
 			}
 			if !end_ok {
@@ -233,30 +234,6 @@ impl TestRun {
 	) -> bool {
 		let msg = AllMessages::from(msg);
 		match msg {
-			AllMessages::NetworkBridgeTx(NetworkBridgeTxMessage::SendRequests(
-				reqs,
-				IfDisconnected::ImmediateError,
-			)) => {
-				let mut valid_responses = 0;
-				for req in reqs {
-					let req = match req {
-						Requests::ChunkFetchingV1(req) => req,
-						_ => panic!("Unexpected request"),
-					};
-					let response =
-						self.chunk_responses.get(&req.peer).ok_or(network::RequestFailure::Refused);
-
-					if let Ok(ChunkFetchingResponse::Chunk(resp)) = &response {
-						if self.valid_chunks.contains(&resp.chunk) {
-							valid_responses += 1;
-						}
-					}
-					req.pending_response
-						.send(response.map(Encode::encode))
-						.expect("Sending response should succeed");
-				}
-				return (valid_responses == 0) && self.valid_chunks.is_empty()
-			},
 			AllMessages::AvailabilityStore(AvailabilityStoreMessage::StoreChunk {
 				chunk,
 				tx,
@@ -272,6 +249,19 @@ impl TestRun {
 			},
 		}
 	}
+
+	/// Returns true, if after answering the given request it would be OK for the stream to end.
+	fn handle_request(&self, req: OutgoingRequest<ChunkFetchingRequest>) -> bool {
+		let response = self.chunk_responses.get(&req.peer).ok_or(network::RequestFailure::Refused);
+		let valid_response = matches!(
+			&response,
+			Ok(ChunkFetchingResponse::Chunk(resp)) if self.valid_chunks.contains(&resp.chunk)
+		);
+		req.pending_response
+			.send(response.map(Encode::encode))
+			.expect("Sending response should succeed");
+		!valid_response && self.valid_chunks.is_empty()
+	}
 }
 
 /// Get a `RunningTask` filled with dummy values.
@@ -32,9 +32,12 @@ use futures::{
 	Stream,
 };
 
+use polkadot_node_network_protocol::request_response::batching::{
+	ChunkRequest, ChunkRequestBatcher,
+};
 use polkadot_node_subsystem::{
 	jaeger,
-	messages::{ChainApiMessage, RuntimeApiMessage},
+	messages::{ChainApiMessage, IfDisconnected, NetworkBridgeTxMessage, RuntimeApiMessage},
 	overseer, ActivatedLeaf, ActiveLeavesUpdate, LeafStatus,
 };
 use polkadot_node_subsystem_util::runtime::{get_occupied_cores, RuntimeInfo};
@@ -75,6 +78,12 @@ pub struct Requester {
 	/// Receive messages from `FetchTask`.
 	rx: mpsc::Receiver<FromFetchTask>,
 
+	/// Chunk requests of `FetchTask`s received since the last requests were sent.
+	requests: Vec<ChunkRequest>,
+
+	/// Batches the chunk requests of concurrent `FetchTask`s to the same validator.
+	batcher: ChunkRequestBatcher,
+
 	/// Prometheus Metrics
 	metrics: Metrics,
 }
@@ -90,7 +99,15 @@ impl Requester {
 	/// by advancing the stream.
 	pub fn new(metrics: Metrics) -> Self {
 		let (tx, rx) = mpsc::channel(1);
-		Requester { fetches: HashMap::new(), session_cache: SessionCache::new(), tx, rx, metrics }
+		Requester {
+			fetches: HashMap::new(),
+			session_cache: SessionCache::new(),
+			tx,
+			rx,
+			requests: Vec::new(),
+			batcher: ChunkRequestBatcher::new(),
+			metrics,
+		}
 	}
 
 	/// Update heads that need availability distribution.
@@ -261,6 +278,12 @@ impl Stream for Requester {
 		loop {
 			match Pin::new(&mut self.rx).poll_next(ctx) {
 				Poll::Ready(Some(FromFetchTask::Message(m))) => return Poll::Ready(Some(m)),
+				Poll::Ready(Some(FromFetchTask::Request(request))) => {
+					// Keep collecting, so that requests of tasks which are ready at the same time
+					// are batched.
+					self.requests.push(request);
+					continue
+				},
 				Poll::Ready(Some(FromFetchTask::Concluded(Some(bad_boys)))) => {
 					self.session_cache.report_bad_log(bad_boys);
 					continue
@@ -271,7 +294,25 @@ impl Stream for Requester {
 					self.fetches.remove(&candidate_hash);
 				},
 				Poll::Ready(None) => return Poll::Ready(None),
-				Poll::Pending => return Poll::Pending,
+				Poll::Pending => {
+					let requests = if !self.requests.is_empty() {
+						let requests = std::mem::take(&mut self.requests);
+						self.batcher.batch(requests)
+					} else {
+						// Requests to validators not supporting batched requests are sent again.
+						match Pin::new(&mut self.batcher).poll_next(ctx) {
+							Poll::Ready(Some(requests)) => requests,
+							Poll::Ready(None) | Poll::Pending => return Poll::Pending,
+						}
+					};
+					return Poll::Ready(Some(
+						NetworkBridgeTxMessage::SendRequests(
+							requests,
+							IfDisconnected::ImmediateError,
+						)
+						.into(),
+					))
+				},
 			}
 		}
 	}
@@ -21,8 +21,11 @@ use std::sync::Arc;
 use futures::channel::oneshot;
 
 use fatality::Nested;
+use parity_scale_codec::{Compact, Encode};
 use polkadot_node_network_protocol::{
-	request_response::{v1, IncomingRequest, IncomingRequestReceiver},
+	request_response::{
+		v1, vstaging, IncomingRequest, IncomingRequestReceiver, CHUNK_RESPONSE_SIZE,
+	},
 	UnifiedReputationChange as Rep,
 };
 use polkadot_node_primitives::{AvailableData, ErasureChunk};
@@ -97,6 +100,38 @@ pub async fn run_chunk_receiver<Sender>(
 	}
 }
 
+/// Receiver task to be forked as a separate task to handle batched chunk requests.
+pub async fn run_chunk_batch_receiver<Sender>(
+	mut sender: Sender,
+	mut receiver: IncomingRequestReceiver<vstaging::ChunkFetchingRequest>,
+	metrics: Metrics,
+) where
+	Sender: SubsystemSender<AvailabilityStoreMessage>,
+{
+	loop {
+		match receiver.recv(|| vec![COST_INVALID_REQUEST]).await.into_nested() {
+			Ok(Ok(msg)) => {
+				answer_chunk_batch_request_log(&mut sender, msg, &metrics).await;
+			},
+			Err(fatal) => {
+				gum::debug!(
+					target: LOG_TARGET,
+					error = ?fatal,
+					"Shutting down chunk batch receiver."
+				);
+				return
+			},
+			Ok(Err(jfyi)) => {
+				gum::debug!(
+					target: LOG_TARGET,
+					error = ?jfyi,
+					"Error decoding incoming chunk batch request."
+				);
+			},
+		}
+	}
+}
+
 /// Variant of `answer_pov_request` that does Prometheus metric and logging on errors.
 ///
 /// Any errors of `answer_pov_request` will simply be logged.
@@ -146,6 +181,33 @@ where
 	}
 }
 
+/// Variant of `answer_chunk_batch_request` that does Prometheus metric and logging on errors.
+///
+/// Any errors of `answer_chunk_batch_request` will simply be logged.
+pub async fn answer_chunk_batch_request_log<Sender>(
+	sender: &mut Sender,
+	req: IncomingRequest<vstaging::ChunkFetchingRequest>,
+	metrics: &Metrics,
+) where
+	Sender: SubsystemSender<AvailabilityStoreMessage>,
+{
+	let res = answer_chunk_batch_request(sender, req).await;
+	match res {
+		Ok(results) =>
+			for result in results {
+				metrics.on_served_chunk(if result { SUCCEEDED } else { NOT_FOUND })
+			},
+		Err(err) => {
+			gum::warn!(
+				target: LOG_TARGET,
+				err= ?err,
+				"Serving chunk batch failed with error"
+			);
+			metrics.on_served_chunk(FAILED);
+		},
+	}
+}
+
 /// Answer an incoming PoV fetch request by querying the av store.
 ///
 /// Returns: `Ok(true)` if chunk was found and served.
@@ -213,6 +275,53 @@ where
 	Ok(result)
 }
 
+/// Answer an incoming batched chunk request by querying the av store.
+///
+/// Chunks are answered in the requested order, for as long as they fit into the response.
+///
+/// Returns: For each answered chunk, whether it was found and served.
+pub async fn answer_chunk_batch_request<Sender>(
+	sender: &mut Sender,
+	req: IncomingRequest<vstaging::ChunkFetchingRequest>,
+) -> Result<Vec<bool>>
+where
+	Sender: SubsystemSender<AvailabilityStoreMessage>,
+{
+	let max_size = CHUNK_RESPONSE_SIZE as usize -
+		Compact(vstaging::MAX_CHUNKS_PER_REQUEST as u32).encoded_size();
+
+	let mut results = Vec::new();
+	let mut chunks = Vec::new();
+	let mut size = 0;
+	for chunk_req in req.payload.chunks.iter().take(vstaging::MAX_CHUNKS_PER_REQUEST) {
+		let chunk = query_chunk(sender, chunk_req.candidate_hash, chunk_req.index).await?;
+		let found = chunk.is_some();
+		let response = match chunk {
+			None => v1::ChunkFetchingResponse::NoSuchChunk,
+			Some(chunk) => v1::ChunkFetchingResponse::Chunk(chunk.into()),
+		};
+
+		size += response.encoded_size();
+		if size > max_size {
+			break
+		}
+		results.push(found);
+		chunks.push(response);
+	}
+
+	gum::trace!(
+		target: LOG_TARGET,
+		requested = req.payload.chunks.len(),
+		served = chunks.len(),
+		peer = ?req.peer,
+		"Serving chunk batch",
+	);
+
+	req.send_response(vstaging::ChunkFetchingResponse { chunks })
+		.map_err(|_| JfyiError::SendResponse)?;
+	Ok(results)
+}
+
 /// Query chunk from the availability store.
 async fn query_chunk<Sender>(
 	sender: &mut Sender,
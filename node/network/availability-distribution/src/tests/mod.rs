@@ -16,10 +16,17 @@
 
 use std::collections::HashSet;
 
-use futures::{executor, future, Future};
-
-use polkadot_node_network_protocol::request_response::{IncomingRequest, ReqProtocolNames};
-use polkadot_primitives::{CoreState, Hash};
+use assert_matches::assert_matches;
+use futures::{channel::oneshot, executor, future, Future};
+use parity_scale_codec::Decode;
+
+use polkadot_node_network_protocol::request_response::{
+	IncomingRequest, ReqProtocolNames, CHUNK_RESPONSE_SIZE,
+};
+use polkadot_node_primitives::{BlockData, ErasureChunk, PoV};
+use polkadot_node_subsystem::messages::{AllMessages, AvailabilityStoreMessage};
+use polkadot_primitives::{CandidateHash, CoreState, Hash, ValidatorIndex};
+use sc_network::PeerId;
 use sp_keystore::KeystorePtr;
 
 use polkadot_node_subsystem_test_helpers as test_helpers;
@@ -47,14 +54,27 @@ fn test_harness<T: Future<Output = ()>>(
 	let (pov_req_receiver, pov_req_cfg) = IncomingRequest::get_config_receiver(&req_protocol_names);
 	let (chunk_req_receiver, chunk_req_cfg) =
 		IncomingRequest::get_config_receiver(&req_protocol_names);
+	let (chunk_req_vstaging_receiver, chunk_req_vstaging_cfg) =
+		IncomingRequest::get_config_receiver(&req_protocol_names);
 	let subsystem = AvailabilityDistributionSubsystem::new(
 		keystore,
-		IncomingRequestReceivers { pov_req_receiver, chunk_req_receiver },
+		IncomingRequestReceivers {
+			pov_req_receiver,
+			chunk_req_receiver,
+			chunk_req_vstaging_receiver,
+		},
+		PoVCache::new(0, Default::default()),
 		Default::default(),
 	);
 	let subsystem = subsystem.run(context);
 
-	let test_fut = test_fx(TestHarness { virtual_overseer, pov_req_cfg, chunk_req_cfg, pool });
+	let test_fut = test_fx(TestHarness {
+		virtual_overseer,
+		pov_req_cfg,
+		chunk_req_cfg,
+		chunk_req_vstaging_cfg,
+		pool,
+	});
 
 	futures::pin_mut!(test_fut);
 	futures::pin_mut!(subsystem);
@@ -123,3 +143,83 @@ fn check_fetch_retry() {
 	}
 	test_harness(state.keystore.clone(), move |harness| state.run(harness));
 }
+
+/// Answer a batched request for the given chunks, as stored in the availability store.
+///
+/// Returns what `answer_chunk_batch_request` reports as served and the response sent.
+fn answer_chunk_batch(
+	chunks: Vec<Option<ErasureChunk>>,
+) -> (Vec<bool>, vstaging::ChunkFetchingResponse) {
+	let (mut sender, mut av_store) = test_helpers::sender_receiver();
+	let payload = vstaging::ChunkFetchingRequest {
+		chunks: (0..chunks.len())
+			.map(|index| v1::ChunkFetchingRequest {
+				candidate_hash: CandidateHash::default(),
+				index: ValidatorIndex(index as u32),
+			})
+			.collect(),
+	};
+	let (tx, rx) = oneshot::channel();
+	let req = IncomingRequest::new(PeerId::random(), payload, tx);
+
+	let answer = async move { responder::answer_chunk_batch_request(&mut sender, req).await };
+	let serve = async move {
+		while let Some(msg) = av_store.next().await {
+			assert_matches!(
+				msg,
+				AllMessages::AvailabilityStore(AvailabilityStoreMessage::QueryChunk(_, index, tx)) => {
+					tx.send(chunks[index.0 as usize].clone()).unwrap();
+				}
+			);
+		}
+	};
+	let (served, ()) = executor::block_on(future::join(answer, serve));
+
+	let response = executor::block_on(rx).unwrap().result.unwrap();
+	(served.unwrap(), vstaging::ChunkFetchingResponse::decode(&mut &response[..]).unwrap())
+}
+
+fn erasure_chunk(index: u32, size: usize) -> ErasureChunk {
+	let pov = PoV { block_data: BlockData(vec![45, 46, 47]) };
+	let (_, mut chunk) = mock::get_valid_chunk_data(pov);
+	chunk.index = ValidatorIndex(index);
+	chunk.chunk = vec![index as u8; size];
+	chunk
+}
+
+/// Batched chunk requests are answered in the requested order.
+#[test]
+fn chunk_batch_request_is_answered_in_order() {
+	let chunks = vec![Some(erasure_chunk(0, 10)), None, Some(erasure_chunk(2, 10))];
+
+	let (served, response) = answer_chunk_batch(chunks.clone());
+
+	assert_eq!(served, vec![true, false, true]);
+	assert_eq!(response.chunks.len(), 3);
+	for (chunk, response) in chunks.into_iter().zip(response.chunks) {
+		match (chunk, response) {
+			(Some(chunk), v1::ChunkFetchingResponse::Chunk(response)) => {
+				assert_eq!(response.chunk, chunk.chunk);
+				assert_eq!(response.proof, chunk.proof);
+			},
+			(None, v1::ChunkFetchingResponse::NoSuchChunk) => {},
+			(chunk, response) => panic!("Unexpected response {:?} for {:?}", response, chunk),
+		}
+	}
+}
+
+/// Only as many chunks are answered as fit into the response size limit.
+#[test]
+fn chunk_batch_request_is_truncated_at_the_response_size_limit() {
+	let size = CHUNK_RESPONSE_SIZE as usize / 3;
+	let chunks = (0..3).map(|index| Some(erasure_chunk(index, size))).collect();
+
+	let (served, response) = answer_chunk_batch(chunks);
+
+	assert_eq!(served, vec![true, true]);
+	assert_eq!(response.chunks.len(), 2);
+	assert!(response.chunks.iter().enumerate().all(|(index, response)| matches!(
+		response,
+		v1::ChunkFetchingResponse::Chunk(response) if response.chunk == vec![index as u8; size]
+	)));
+}
@@ -28,6 +28,7 @@ use futures::{
 	FutureExt, SinkExt, StreamExt,
 };
 use futures_timer::Delay;
+use parity_scale_codec::{Decode, Encode};
 
 use sc_network as network;
 use sc_network::{config as netconfig, config::RequestResponseConfig, IfDisconnected};
@@ -36,7 +37,7 @@ use sp_keystore::KeystorePtr;
 
 use polkadot_node_network_protocol::{
 	jaeger,
-	request_response::{v1, IncomingRequest, OutgoingRequest, Requests},
+	request_response::{IncomingRequest, IsRequest, OutgoingRequest, Requests},
 };
 use polkadot_node_primitives::ErasureChunk;
 use polkadot_node_subsystem::{
@@ -61,6 +62,7 @@ pub struct TestHarness {
 	pub virtual_overseer: VirtualOverseer,
 	pub pov_req_cfg: RequestResponseConfig,
 	pub chunk_req_cfg: RequestResponseConfig,
+	pub chunk_req_vstaging_cfg: RequestResponseConfig,
 	pub pool: TaskExecutor,
 }
 
@@ -220,15 +222,18 @@ impl TestState {
 				)) => {
 					for req in reqs {
 						// Forward requests:
-						let in_req = to_incoming_req(&harness.pool, req);
-						harness
-							.chunk_req_cfg
-							.inbound_queue
-							.as_mut()
-							.unwrap()
-							.send(in_req.into_raw())
-							.await
-							.unwrap();
+						let (cfg, in_req) = match req {
+							Requests::ChunkFetchingV1(req) => (
+								&mut harness.chunk_req_cfg,
+								to_incoming_req(&harness.pool, req).into_raw(),
+							),
+							Requests::ChunkFetchingVStaging(req) => (
+								&mut harness.chunk_req_vstaging_cfg,
+								to_incoming_req(&harness.pool, req).into_raw(),
+							),
+							_ => panic!("Unexpected request!"),
+						};
+						cfg.inbound_queue.as_mut().unwrap().send(in_req).await.unwrap();
 					}
 				},
 				AllMessages::AvailabilityStore(AvailabilityStoreMessage::QueryChunk(
@@ -309,34 +314,34 @@ async fn overseer_recv(rx: &mut mpsc::UnboundedReceiver<AllMessages>) -> AllMess
 	rx.next().await.expect("Test subsystem no longer live")
 }
 
-fn to_incoming_req(
+fn to_incoming_req<Req>(
 	executor: &TaskExecutor,
-	outgoing: Requests,
-) -> IncomingRequest<v1::ChunkFetchingRequest> {
-	match outgoing {
-		Requests::ChunkFetchingV1(OutgoingRequest { payload, pending_response, .. }) => {
-			let (tx, rx): (oneshot::Sender<netconfig::OutgoingResponse>, oneshot::Receiver<_>) =
-				oneshot::channel();
-			executor.spawn(
-				"message-forwarding",
-				None,
-				async {
-					let response = rx.await;
-					let payload = response.expect("Unexpected canceled request").result;
-					pending_response
-						.send(payload.map_err(|_| network::RequestFailure::Refused))
-						.expect("Sending response is expected to work");
-				}
-				.boxed(),
-			);
+	outgoing: OutgoingRequest<Req>,
+) -> IncomingRequest<Req>
+where
+	Req: IsRequest + Decode + Encode,
+	Req::Response: Encode,
+{
+	let OutgoingRequest { payload, pending_response, .. } = outgoing;
+	let (tx, rx): (oneshot::Sender<netconfig::OutgoingResponse>, oneshot::Receiver<_>) =
+		oneshot::channel();
+	executor.spawn(
+		"message-forwarding",
+		None,
+		async {
+			let response = rx.await;
+			let payload = response.expect("Unexpected canceled request").result;
+			pending_response
+				.send(payload.map_err(|_| network::RequestFailure::Refused))
+				.expect("Sending response is expected to work");
+		}
+		.boxed(),
+	);
 
-			IncomingRequest::new(
-				// We don't really care:
-				network::PeerId::random(),
-				payload,
-				tx,
-			)
-		},
-		_ => panic!("Unexpected request!"),
-	}
+	IncomingRequest::new(
+		// We don't really care:
+		network::PeerId::random(),
+		payload,
+		tx,
+	)
 }
@@ -46,8 +46,10 @@ use polkadot_erasure_coding::{
 use polkadot_node_network_protocol::request_response::CHUNK_REQUEST_TIMEOUT;
 use polkadot_node_network_protocol::{
 	request_response::{
-		self as req_res, outgoing::RequestError, v1 as request_v1, IncomingRequestReceiver,
-		OutgoingRequest, Recipient, Requests,
+		self as req_res,
+		batching::{ChunkRequest, ChunkRequestBatcher},
+		outgoing::RequestError,
+		v1 as request_v1, IncomingRequestReceiver, OutgoingRequest, Recipient, Requests,
 	},
 	IfDisconnected, UnifiedReputationChange as Rep,
 };
@@ -59,7 +61,7 @@ use polkadot_node_subsystem::{
 	overseer, ActiveLeavesUpdate, FromOrchestra, OverseerSignal, SpawnedSubsystem, SubsystemError,
 	SubsystemResult,
 };
-use polkadot_node_subsystem_util::request_session_info;
+use polkadot_node_subsystem_util::{pov_cache::PoVCache, request_session_info};
 use polkadot_primitives::{
 	AuthorityDiscoveryId, BlakeTwo256, BlockNumber, CandidateHash, CandidateReceipt, GroupIndex,
 	Hash, HashT, IndexedVec, SessionIndex, SessionInfo, ValidatorId, ValidatorIndex,
@@ -148,6 +150,8 @@ pub struct AvailabilityRecoverySubsystem {
 	recovery_strategy: RecoveryStrategy,
 	/// Receiver for available data requests.
 	req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
+	/// The node-wide PoV cache.
+	pov_cache: PoVCache,
 	/// Metrics for this subsystem.
 	metrics: Metrics,
 }
@@ -194,6 +198,18 @@ struct RecoveryParams {
 	/// The root of the erasure encoding of the para block.
 	erasure_root: Hash,
 
+	/// The hash of the candidate's PoV.
+	pov_hash: Hash,
+
+	/// The hash of the candidate's persisted validation data.
+	validation_data_hash: Hash,
+
+	/// The node-wide PoV cache.
+	pov_cache: PoVCache,
+
+	/// Channel to the subsystem for sending chunk requests, which batches them per validator.
+	chunk_request_tx: futures::channel::mpsc::Sender<Vec<ChunkRequest>>,
+
 	/// Metrics to report
 	metrics: Metrics,
 
@@ -391,13 +407,7 @@ impl RequestChunksFromValidators {
 		)
 	}
 
-	async fn launch_parallel_requests<Sender>(
-		&mut self,
-		params: &RecoveryParams,
-		sender: &mut Sender,
-	) where
-		Sender: overseer::AvailabilityRecoverySenderTrait,
-	{
+	async fn launch_parallel_requests(&mut self, params: &RecoveryParams) {
 		let num_requests = self.get_desired_request_count(params.threshold);
 		let candidate_hash = &params.candidate_hash;
 		let already_requesting_count = self.requesting_chunks.len();
@@ -432,7 +442,7 @@ impl RequestChunksFromValidators {
 				};
 
 				let (req, res) = OutgoingRequest::new(Recipient::Authority(validator), raw_request);
-				requests.push(Requests::ChunkFetchingV1(req));
+				requests.push(req);
 
 				params.metrics.on_chunk_request_issued();
 				let timer = params.metrics.time_chunk_request();
@@ -451,12 +461,13 @@ impl RequestChunksFromValidators {
 			}
 		}
 
-		sender
-			.send_message(NetworkBridgeTxMessage::SendRequests(
-				requests,
-				IfDisconnected::TryConnect,
-			))
-			.await;
+		if params.chunk_request_tx.clone().send(requests).await.is_err() {
+			gum::debug!(
+				target: LOG_TARGET,
+				?candidate_hash,
+				"Failed to send chunk requests, subsystem is shutting down",
+			);
+		}
 	}
 
 	/// Wait for a sufficient amount of chunks to reconstruct according to the provided `params`.
@@ -620,7 +631,7 @@ impl RequestChunksFromValidators {
 				return Err(RecoveryError::Unavailable)
 			}
 
-			self.launch_parallel_requests(params, sender).await;
+			self.launch_parallel_requests(params).await;
 			self.wait_for_chunks(params).await;
 
 			// If received_chunks has more than threshold entries, attempt to recover the data.
@@ -808,9 +819,37 @@ where
 			}
 		}
 
+		// Then see if the data was obtained by another subsystem. It is only used if it matches
+		// the erasure root of the candidate.
+		if let Some(data) = self.params.pov_cache.get_available_data(
+			&self.params.pov_hash,
+			&self.params.validation_data_hash,
+			"availability-recovery",
+		) {
+			let (reencode_tx, reencode_rx) = channel();
+			self.erasure_task_tx
+				.send(ErasureTask::Reencode(
+					self.params.validators.len(),
+					self.params.erasure_root,
+					data,
+					reencode_tx,
+				))
+				.await
+				.map_err(|_| RecoveryError::ChannelClosed)?;
+
+			match reencode_rx.await.map_err(|_| RecoveryError::ChannelClosed)? {
+				Some(data) => return Ok(data),
+				None => gum::debug!(
+					target: LOG_TARGET,
+					candidate_hash = ?self.params.candidate_hash,
+					"Cached data doesn't match the erasure root",
+				),
+			}
+		}
+
 		self.params.metrics.on_recovery_started();
 
-		loop {
+		let result = loop {
 			// These only fail if we cannot reach the underlying subsystem, which case there is
 			// nothing meaningful we can do.
 			match self.source {
@@ -830,7 +869,15 @@ where
 				Source::RequestChunks(ref mut from_all) =>
 					break from_all.run(&self.params, &mut self.sender).await,
 			}
+		};
+
+		if let Ok(data) = &result {
+			self.params
+				.pov_cache
+				.insert(data.pov.clone(), Some(data.validation_data.clone()));
 		}
+
+		result
 	}
 }
 
@@ -978,9 +1025,11 @@ async fn launch_recovery_task<Context>(
 	receipt: CandidateReceipt,
 	mut backing_group: Option<GroupIndex>,
 	response_sender: oneshot::Sender<Result<AvailableData, RecoveryError>>,
+	pov_cache: &PoVCache,
 	metrics: &Metrics,
 	recovery_strategy: &RecoveryStrategy,
 	erasure_task_tx: futures::channel::mpsc::Sender<ErasureTask>,
+	chunk_request_tx: futures::channel::mpsc::Sender<Vec<ChunkRequest>>,
 ) -> error::Result<()> {
 	let candidate_hash = receipt.hash();
 	let params = RecoveryParams {
@@ -989,6 +1038,10 @@ async fn launch_recovery_task<Context>(
 		threshold: recovery_threshold(session_info.validators.len())?,
 		candidate_hash,
 		erasure_root: receipt.descriptor.erasure_root,
+		pov_hash: receipt.descriptor.pov_hash,
+		validation_data_hash: receipt.descriptor.persisted_validation_data_hash,
+		pov_cache: pov_cache.clone(),
+		chunk_request_tx,
 		metrics: metrics.clone(),
 		bypass_availability_store: recovery_strategy == &RecoveryStrategy::BypassAvailabilityStore,
 	};
@@ -1064,9 +1117,11 @@ async fn handle_recover<Context>(
 	session_index: SessionIndex,
 	backing_group: Option<GroupIndex>,
 	response_sender: oneshot::Sender<Result<AvailableData, RecoveryError>>,
+	pov_cache: &PoVCache,
 	metrics: &Metrics,
 	recovery_strategy: &RecoveryStrategy,
 	erasure_task_tx: futures::channel::mpsc::Sender<ErasureTask>,
+	chunk_request_tx: futures::channel::mpsc::Sender<Vec<ChunkRequest>>,
 ) -> error::Result<()> {
 	let candidate_hash = receipt.hash();
 
@@ -1109,9 +1164,11 @@ async fn handle_recover<Context>(
 				receipt,
 				backing_group,
 				response_sender,
+				pov_cache,
 				metrics,
 				recovery_strategy,
 				erasure_task_tx,
+				chunk_request_tx,
 			)
 			.await,
 		None => {
@@ -1156,48 +1213,68 @@ impl AvailabilityRecoverySubsystem {
 	/// `AvailabilityStoreSubsystem` subsystem.
 	pub fn with_availability_store_skip(
 		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
+		pov_cache: PoVCache,
 		metrics: Metrics,
 	) -> Self {
-		Self { recovery_strategy: RecoveryStrategy::BypassAvailabilityStore, req_receiver, metrics }
+		Self {
+			recovery_strategy: RecoveryStrategy::BypassAvailabilityStore,
+			req_receiver,
+			pov_cache,
+			metrics,
+		}
 	}
 
 	/// Create a new instance of `AvailabilityRecoverySubsystem` which starts with a fast path to
 	/// request data from backers.
 	pub fn with_fast_path(
 		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
+		pov_cache: PoVCache,
 		metrics: Metrics,
 	) -> Self {
-		Self { recovery_strategy: RecoveryStrategy::BackersFirstAlways, req_receiver, metrics }
+		Self {
+			recovery_strategy: RecoveryStrategy::BackersFirstAlways,
+			req_receiver,
+			pov_cache,
+			metrics,
+		}
 	}
 
 	/// Create a new instance of `AvailabilityRecoverySubsystem` which requests only chunks
 	pub fn with_chunks_only(
 		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
+		pov_cache: PoVCache,
 		metrics: Metrics,
 	) -> Self {
-		Self { recovery_strategy: RecoveryStrategy::ChunksAlways, req_receiver, metrics }
+		Self { recovery_strategy: RecoveryStrategy::ChunksAlways, req_receiver, pov_cache, metrics }
 	}
 
 	/// Create a new instance of `AvailabilityRecoverySubsystem` which requests chunks if PoV is
 	/// above a threshold.
 	pub fn with_chunks_if_pov_large(
 		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
+		pov_cache: PoVCache,
 		metrics: Metrics,
 	) -> Self {
 		Self {
 			recovery_strategy: RecoveryStrategy::BackersFirstIfSizeLower(SMALL_POV_LIMIT),
 			req_receiver,
+			pov_cache,
 			metrics,
 		}
 	}
 
 	async fn run<Context>(self, mut ctx: Context) -> SubsystemResult<()> {
 		let mut state = State::default();
-		let Self { recovery_strategy, mut req_receiver, metrics } = self;
+		let Self { recovery_strategy, mut req_receiver, pov_cache, metrics } = self;
 
 		let (erasure_task_tx, erasure_task_rx) = futures::channel::mpsc::channel(16);
 		let mut erasure_task_rx = erasure_task_rx.fuse();
 
+		// Chunk requests of all recovery tasks are sent by the main loop, which batches requests
+		// to the same validator.
+		let (chunk_request_tx, mut chunk_request_rx) = futures::channel::mpsc::channel(16);
+		let mut chunk_batcher = ChunkRequestBatcher::new();
+
 		// `ThreadPoolBuilder` spawns the tasks using `spawn_blocking`. For each worker there will
 		// be a `mpsc` channel created. Each of these workers take the `Receiver` and poll it in an
 		// infinite loop. All of the sender ends of the channel are sent as a vec which we then use
@@ -1257,6 +1334,37 @@ impl AvailabilityRecoverySubsystem {
 						}
 					}
 				}
+				chunk_requests = chunk_request_rx.next() => {
+					match chunk_requests {
+						Some(mut requests) => {
+							// Take all requests which are ready, so that requests of concurrent
+							// recovery tasks to the same validator are batched.
+							while let Ok(Some(more)) = chunk_request_rx.try_next() {
+								requests.extend(more);
+							}
+
+							ctx.send_message(NetworkBridgeTxMessage::SendRequests(
+								chunk_batcher.batch(requests),
+								IfDisconnected::TryConnect,
+							)).await;
+						},
+						None => {
+							gum::debug!(
+								target: LOG_TARGET,
+								"Chunk request channel closed",
+							);
+
+							return Err(SubsystemError::with_origin("availability-recovery", RecoveryError::ChannelClosed))
+						}
+					}
+				}
+				requests = chunk_batcher.select_next_some() => {
+					// The validator doesn't support batched requests.
+					ctx.send_message(NetworkBridgeTxMessage::SendRequests(
+						requests,
+						IfDisconnected::TryConnect,
+					)).await;
+				}
 				v = ctx.recv().fuse() => {
 					match v? {
 						FromOrchestra::Signal(signal) => if handle_signal(
@@ -1280,9 +1388,11 @@ impl AvailabilityRecoverySubsystem {
 										session_index,
 										maybe_backing_group.filter(|_| recovery_strategy.needs_backing_group()),
 										response_sender,
+										&pov_cache,
 										&metrics,
 										&recovery_strategy,
 										erasure_task_tx.clone(),
+										chunk_request_tx.clone(),
 									).await {
 										gum::warn!(
 											target: LOG_TARGET,
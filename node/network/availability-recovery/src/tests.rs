@@ -35,7 +35,7 @@ use polkadot_node_subsystem::{
 	ActivatedLeaf, LeafStatus,
 };
 use polkadot_node_subsystem_test_helpers::{make_subsystem_context, TestSubsystemContextHandle};
-use polkadot_node_subsystem_util::TimeoutExt;
+use polkadot_node_subsystem_util::{pov_cache::DEFAULT_POV_CACHE_SIZE, TimeoutExt};
 use polkadot_primitives::{
 	AuthorityDiscoveryId, Hash, HeadData, IndexedVec, PersistedValidationData, ValidatorId,
 };
@@ -60,8 +60,11 @@ fn test_harness_fast_path<T: Future<Output = (VirtualOverseer, RequestResponseCo
 
 	let (collation_req_receiver, req_cfg) =
 		IncomingRequest::get_config_receiver(&ReqProtocolNames::new(&GENESIS_HASH, None));
-	let subsystem =
-		AvailabilityRecoverySubsystem::with_fast_path(collation_req_receiver, Metrics::new_dummy());
+	let subsystem = AvailabilityRecoverySubsystem::with_fast_path(
+		collation_req_receiver,
+		PoVCache::new(0, Default::default()),
+		Metrics::new_dummy(),
+	);
 	let subsystem = async {
 		subsystem.run(context).await.unwrap();
 	};
@@ -83,6 +86,15 @@ fn test_harness_fast_path<T: Future<Output = (VirtualOverseer, RequestResponseCo
 
 fn test_harness_chunks_only<T: Future<Output = (VirtualOverseer, RequestResponseConfig)>>(
 	test: impl FnOnce(VirtualOverseer, RequestResponseConfig) -> T,
+) {
+	test_harness_chunks_only_with_pov_cache(PoVCache::new(0, Default::default()), test)
+}
+
+fn test_harness_chunks_only_with_pov_cache<
+	T: Future<Output = (VirtualOverseer, RequestResponseConfig)>,
+>(
+	pov_cache: PoVCache,
+	test: impl FnOnce(VirtualOverseer, RequestResponseConfig) -> T,
 ) {
 	let _ = env_logger::builder()
 		.is_test(true)
@@ -97,6 +109,7 @@ fn test_harness_chunks_only<T: Future<Output = (VirtualOverseer, RequestResponse
 		IncomingRequest::get_config_receiver(&ReqProtocolNames::new(&GENESIS_HASH, None));
 	let subsystem = AvailabilityRecoverySubsystem::with_chunks_only(
 		collation_req_receiver,
+		pov_cache,
 		Metrics::new_dummy(),
 	);
 	let subsystem = subsystem.run(context);
@@ -135,6 +148,7 @@ fn test_harness_chunks_if_pov_large<
 		IncomingRequest::get_config_receiver(&ReqProtocolNames::new(&GENESIS_HASH, None));
 	let subsystem = AvailabilityRecoverySubsystem::with_chunks_if_pov_large(
 		collation_req_receiver,
+		PoVCache::new(0, Default::default()),
 		Metrics::new_dummy(),
 	);
 	let subsystem = subsystem.run(context);
@@ -1471,6 +1485,56 @@ fn returns_early_if_we_have_the_data() {
 	});
 }
 
+#[test]
+fn returns_early_if_the_data_is_cached() {
+	let mut test_state = TestState::default();
+	test_state.candidate.descriptor.pov_hash = test_state.available_data.pov.hash();
+	test_state.candidate.descriptor.persisted_validation_data_hash =
+		test_state.persisted_validation_data.hash();
+
+	let pov_cache = PoVCache::new(DEFAULT_POV_CACHE_SIZE, Default::default());
+	pov_cache.insert(
+		test_state.available_data.pov.clone(),
+		Some(test_state.persisted_validation_data.clone()),
+	);
+
+	test_harness_chunks_only_with_pov_cache(
+		pov_cache,
+		|mut virtual_overseer, req_cfg| async move {
+			overseer_signal(
+				&mut virtual_overseer,
+				OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+					hash: test_state.current,
+					number: 1,
+					status: LeafStatus::Fresh,
+					span: Arc::new(jaeger::Span::Disabled),
+				})),
+			)
+			.await;
+
+			let (tx, rx) = oneshot::channel();
+
+			overseer_send(
+				&mut virtual_overseer,
+				AvailabilityRecoveryMessage::RecoverAvailableData(
+					test_state.candidate.clone(),
+					test_state.session_index,
+					None,
+					tx,
+				),
+			)
+			.await;
+
+			test_state.test_runtime_api(&mut virtual_overseer).await;
+			test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+
+			// No chunks are requested.
+			assert_eq!(rx.await.unwrap().unwrap(), test_state.available_data);
+			(virtual_overseer, req_cfg)
+		},
+	);
+}
+
 #[test]
 fn does_not_query_local_validator() {
 	let test_state = TestState::default();
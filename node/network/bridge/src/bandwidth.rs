@@ -0,0 +1,690 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Per-peer bandwidth accounting and rate limiting.
+//!
+//! Every notification sent to or received from a connected peer is accounted to that peer and
+//! the peer-set it was exchanged on, every request and response to that peer and the request
+//! protocol. Only peers connected on one of the parachain peer-sets are accounted, their traffic
+//! is forgotten once they disconnect from all of them.
+//!
+//! Incoming notifications on a peer-set with a configured [`RateLimit`] are additionally run
+//! through a token bucket per peer: notifications exceeding the bucket are dropped and the peer
+//! gets reported, and peers exceeding it repeatedly get disconnected from the peer-set.
+
+use std::{
+	collections::HashMap,
+	fmt,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use futures::{
+	channel::oneshot, future::BoxFuture, select, stream::FuturesUnordered, FutureExt, StreamExt,
+};
+use parking_lot::Mutex;
+
+use polkadot_node_network_protocol::{
+	peer_set::{PeerSet, MAX_NOTIFICATION_SIZE},
+	request_response::{
+		network, Protocol, ReqProtocolNames, RequestResponseConfig, ResponseSender,
+	},
+	PeerId,
+};
+use strum::IntoEnumIterator;
+
+const LOG_TARGET: &'static str = "parachain::network-bridge-bandwidth";
+
+/// The number of rate limit violations after which a peer gets disconnected from the peer-set.
+///
+/// Violations are forgotten once the peer lets its token bucket fill up again.
+const MAX_RATE_LIMIT_VIOLATIONS: u32 = 3;
+
+/// How often to update the top talkers metric.
+const TOP_TALKERS_METRIC_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The number of peers reported by the top talkers metric.
+const TOP_TALKERS_METRIC_COUNT: usize = 10;
+
+/// A per-peer limit on the rate of incoming notification bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+	/// The sustained number of bytes per second a peer may send.
+	pub bytes_per_second: u64,
+	/// The number of bytes a peer may send in a single burst.
+	pub burst: u64,
+}
+
+impl RateLimit {
+	/// A rate limit of `bytes_per_second`, with a burst of a second worth of bytes, but at least
+	/// a notification of the maximum size.
+	pub fn per_second(bytes_per_second: u64) -> Self {
+		Self { bytes_per_second, burst: bytes_per_second.max(MAX_NOTIFICATION_SIZE) }
+	}
+}
+
+/// Configuration of the bandwidth limits.
+#[derive(Debug, Clone, Default)]
+pub struct BandwidthConfig {
+	/// The limit on incoming bytes per peer on the collation peer-set, if any.
+	pub collation_rate_limit: Option<RateLimit>,
+}
+
+impl BandwidthConfig {
+	fn rate_limit(&self, peer_set: PeerSet) -> Option<RateLimit> {
+		match peer_set {
+			PeerSet::Validation => None,
+			PeerSet::Collation => self.collation_rate_limit,
+		}
+	}
+}
+
+/// The protocol traffic is exchanged on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrafficProtocol {
+	/// Notifications on a peer-set.
+	Notifications(PeerSet),
+	/// Requests and their responses on a request protocol.
+	Requests(Protocol),
+}
+
+impl fmt::Display for TrafficProtocol {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			TrafficProtocol::Notifications(peer_set) => f.write_str(peer_set.get_label()),
+			TrafficProtocol::Requests(protocol) => write!(f, "{:?}", protocol),
+		}
+	}
+}
+
+/// The traffic exchanged with a peer on a protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerBandwidth {
+	/// The peer.
+	pub peer: PeerId,
+	/// The protocol the traffic was exchanged on.
+	pub protocol: TrafficProtocol,
+	/// The number of bytes received from the peer.
+	pub bytes_received: u64,
+	/// The number of bytes sent to the peer.
+	pub bytes_sent: u64,
+}
+
+impl PeerBandwidth {
+	fn total(&self) -> u64 {
+		self.bytes_received.saturating_add(self.bytes_sent)
+	}
+}
+
+/// The outcome of accounting incoming bytes against the rate limit of a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RateLimitOutcome {
+	/// The peer is within its rate limit, or has none.
+	Within,
+	/// The peer exceeded its rate limit, the notifications should be dropped.
+	Exceeded,
+	/// The peer exceeded its rate limit too often and should be disconnected.
+	Disconnect,
+}
+
+struct TokenBucket {
+	limit: RateLimit,
+	tokens: u64,
+	last_refill: Instant,
+}
+
+impl TokenBucket {
+	fn new(limit: RateLimit, now: Instant) -> Self {
+		Self { limit, tokens: limit.burst, last_refill: now }
+	}
+
+	fn refill(&mut self, now: Instant) {
+		let elapsed = now.saturating_duration_since(self.last_refill);
+		let refill = self.limit.bytes_per_second as u128 * elapsed.as_nanos() / 1_000_000_000;
+		// Only move the refill time forward on progress, so fractions of a byte accumulate.
+		if refill > 0 {
+			self.tokens =
+				(self.tokens as u128).saturating_add(refill).min(self.limit.burst as u128) as u64;
+			self.last_refill = now;
+		}
+	}
+
+	fn is_full(&self) -> bool {
+		self.tokens == self.limit.burst
+	}
+
+	/// Take `bytes` out of the bucket. If there are not enough tokens, the bucket is drained.
+	fn try_consume(&mut self, bytes: u64) -> bool {
+		match self.tokens.checked_sub(bytes) {
+			Some(left) => {
+				self.tokens = left;
+				true
+			},
+			None => {
+				self.tokens = 0;
+				false
+			},
+		}
+	}
+}
+
+struct Connection {
+	bucket: Option<TokenBucket>,
+	violations: u32,
+}
+
+#[derive(Default)]
+struct Traffic {
+	bytes_received: u64,
+	bytes_sent: u64,
+}
+
+struct Inner {
+	config: BandwidthConfig,
+	connections: HashMap<(PeerId, PeerSet), Connection>,
+	traffic: HashMap<(PeerId, TrafficProtocol), Traffic>,
+	last_top_talkers_update: Option<Instant>,
+}
+
+impl Inner {
+	fn is_connected(&self, peer: PeerId) -> bool {
+		PeerSet::iter().any(|peer_set| self.connections.contains_key(&(peer, peer_set)))
+	}
+
+	/// Account traffic with a peer, if it is connected on any peer-set.
+	fn account(
+		&mut self,
+		peer: PeerId,
+		protocol: TrafficProtocol,
+		bytes_received: usize,
+		bytes_sent: usize,
+	) {
+		if !self.is_connected(peer) {
+			return
+		}
+
+		let traffic = self.traffic.entry((peer, protocol)).or_default();
+		traffic.bytes_received = traffic.bytes_received.saturating_add(bytes_received as u64);
+		traffic.bytes_sent = traffic.bytes_sent.saturating_add(bytes_sent as u64);
+	}
+}
+
+/// Bandwidth accounting of the peers connected on the parachain peer-sets.
+///
+/// Clones share the accounting, so a clone handed out before the network bridge starts observes
+/// its traffic.
+#[derive(Clone)]
+pub struct BandwidthStats(Arc<Mutex<Inner>>);
+
+impl Default for BandwidthStats {
+	fn default() -> Self {
+		Self::new(BandwidthConfig::default())
+	}
+}
+
+impl BandwidthStats {
+	/// Create new bandwidth accounting, enforcing the limits of `config`.
+	pub fn new(config: BandwidthConfig) -> Self {
+		Self(Arc::new(Mutex::new(Inner {
+			config,
+			connections: HashMap::new(),
+			traffic: HashMap::new(),
+			last_top_talkers_update: None,
+		})))
+	}
+
+	/// The `count` connected peers which exchanged the most bytes with us on a protocol, in
+	/// descending order.
+	pub fn top_talkers(&self, count: usize) -> Vec<PeerBandwidth> {
+		let inner = self.0.lock();
+		let mut talkers = inner
+			.traffic
+			.iter()
+			.map(|((peer, protocol), traffic)| PeerBandwidth {
+				peer: *peer,
+				protocol: *protocol,
+				bytes_received: traffic.bytes_received,
+				bytes_sent: traffic.bytes_sent,
+			})
+			.collect::<Vec<_>>();
+
+		talkers.sort_by(|a, b| b.total().cmp(&a.total()));
+		talkers.truncate(count);
+		talkers
+	}
+
+	/// The top talkers for the metric, if it is due to be updated.
+	pub(crate) fn top_talkers_if_due(&self) -> Option<Vec<PeerBandwidth>> {
+		let now = Instant::now();
+		{
+			let mut inner = self.0.lock();
+			match inner.last_top_talkers_update {
+				Some(last) if now.saturating_duration_since(last) < TOP_TALKERS_METRIC_INTERVAL =>
+					return None,
+				_ => inner.last_top_talkers_update = Some(now),
+			}
+		}
+
+		Some(self.top_talkers(TOP_TALKERS_METRIC_COUNT))
+	}
+
+	pub(crate) fn on_peer_connected(&self, peer: PeerId, peer_set: PeerSet) {
+		self.on_peer_connected_at(peer, peer_set, Instant::now())
+	}
+
+	fn on_peer_connected_at(&self, peer: PeerId, peer_set: PeerSet, now: Instant) {
+		let mut inner = self.0.lock();
+		let bucket = inner.config.rate_limit(peer_set).map(|limit| TokenBucket::new(limit, now));
+		inner.connections.insert((peer, peer_set), Connection { bucket, violations: 0 });
+		inner
+			.traffic
+			.insert((peer, TrafficProtocol::Notifications(peer_set)), Traffic::default());
+	}
+
+	pub(crate) fn on_peer_disconnected(&self, peer: PeerId, peer_set: PeerSet) {
+		let mut inner = self.0.lock();
+		inner.connections.remove(&(peer, peer_set));
+		inner.traffic.remove(&(peer, TrafficProtocol::Notifications(peer_set)));
+		if !inner.is_connected(peer) {
+			inner.traffic.retain(|(p, _), _| *p != peer);
+		}
+	}
+
+	/// Account bytes received from a peer, checking them against the peer's rate limit.
+	pub(crate) fn on_received(
+		&self,
+		peer: PeerId,
+		peer_set: PeerSet,
+		bytes: usize,
+	) -> RateLimitOutcome {
+		self.on_received_at(peer, peer_set, bytes, Instant::now())
+	}
+
+	fn on_received_at(
+		&self,
+		peer: PeerId,
+		peer_set: PeerSet,
+		bytes: usize,
+		now: Instant,
+	) -> RateLimitOutcome {
+		let mut inner = self.0.lock();
+		if let Some(traffic) =
+			inner.traffic.get_mut(&(peer, TrafficProtocol::Notifications(peer_set)))
+		{
+			traffic.bytes_received = traffic.bytes_received.saturating_add(bytes as u64);
+		}
+
+		let connection = match inner.connections.get_mut(&(peer, peer_set)) {
+			Some(connection) => connection,
+			None => return RateLimitOutcome::Within,
+		};
+
+		let bucket = match connection.bucket.as_mut() {
+			Some(bucket) => bucket,
+			None => return RateLimitOutcome::Within,
+		};
+
+		bucket.refill(now);
+		if bucket.is_full() {
+			connection.violations = 0;
+		}
+
+		if bucket.try_consume(bytes as u64) {
+			return RateLimitOutcome::Within
+		}
+
+		connection.violations += 1;
+		if connection.violations >= MAX_RATE_LIMIT_VIOLATIONS {
+			RateLimitOutcome::Disconnect
+		} else {
+			RateLimitOutcome::Exceeded
+		}
+	}
+
+	/// Account a notification of `bytes` sent to each of the `peers`.
+	pub(crate) fn on_sent(&self, peers: &[PeerId], peer_set: PeerSet, bytes: usize) {
+		let mut inner = self.0.lock();
+		for peer in peers {
+			if let Some(traffic) =
+				inner.traffic.get_mut(&(*peer, TrafficProtocol::Notifications(peer_set)))
+			{
+				traffic.bytes_sent = traffic.bytes_sent.saturating_add(bytes as u64);
+			}
+		}
+	}
+
+	/// Account request or response bytes received from a peer.
+	pub(crate) fn on_request_bytes_received(&self, peer: PeerId, protocol: Protocol, bytes: usize) {
+		self.0.lock().account(peer, TrafficProtocol::Requests(protocol), bytes, 0)
+	}
+
+	/// Account request or response bytes sent to a peer.
+	pub(crate) fn on_request_bytes_sent(&self, peer: PeerId, protocol: Protocol, bytes: usize) {
+		self.0.lock().account(peer, TrafficProtocol::Requests(protocol), 0, bytes)
+	}
+
+	/// Account the response to an outgoing request to `peer`.
+	///
+	/// Returns the sender to hand to the network instead of `pending_response` and a future
+	/// accounting the response before forwarding it to `pending_response`, which needs to be
+	/// polled for the response to arrive.
+	pub(crate) fn observe_response(
+		&self,
+		peer: PeerId,
+		protocol: Protocol,
+		pending_response: ResponseSender,
+	) -> (ResponseSender, BoxFuture<'static, ()>) {
+		let (tx, rx) = oneshot::channel();
+		let bandwidth = self.clone();
+		let forward = async move {
+			// If the network dropped the sender, dropping `pending_response` tells the requester.
+			if let Ok(response) = rx.await {
+				if let Ok(bytes) = &response {
+					bandwidth.on_request_bytes_received(peer, protocol, bytes.len());
+				}
+				let _ = pending_response.send(response);
+			}
+		};
+		(tx, forward.boxed())
+	}
+
+	/// Account the incoming requests of the parachain request protocols in `configs` and their
+	/// responses.
+	///
+	/// The inbound queues of the configurations of protocols known to `req_protocol_names` are
+	/// replaced. The returned future forwards the incoming requests to the original queues and
+	/// needs to be spawned for them to reach their handlers. Like the network does, requests are
+	/// dropped if the original queue is full.
+	pub fn observe_incoming_requests(
+		&self,
+		req_protocol_names: &ReqProtocolNames,
+		configs: Vec<RequestResponseConfig>,
+	) -> (Vec<RequestResponseConfig>, BoxFuture<'static, ()>) {
+		let mut forwarders = Vec::new();
+		let configs = configs
+			.into_iter()
+			.map(|mut config| {
+				let protocol = req_protocol_names.try_get_protocol(&config.name);
+				match (protocol, config.inbound_queue.take()) {
+					(Some(protocol), Some(inbound_queue)) => {
+						let (tx, rx) = match inbound_queue.capacity() {
+							Some(capacity) => async_channel::bounded(capacity),
+							None => async_channel::unbounded(),
+						};
+						config.inbound_queue = Some(tx);
+						forwarders.push(self.clone().forward_requests(protocol, rx, inbound_queue));
+					},
+					(_, inbound_queue) => config.inbound_queue = inbound_queue,
+				}
+				config
+			})
+			.collect();
+
+		(configs, futures::future::join_all(forwarders).map(|_| ()).boxed())
+	}
+
+	async fn forward_requests(
+		self,
+		protocol: Protocol,
+		mut incoming: async_channel::Receiver<network::IncomingRequest>,
+		inbound_queue: async_channel::Sender<network::IncomingRequest>,
+	) {
+		let mut responses = FuturesUnordered::new();
+		loop {
+			select! {
+				req = incoming.next().fuse() => {
+					let network::IncomingRequest { peer, payload, pending_response } = match req {
+						Some(req) => req,
+						None => return,
+					};
+					self.on_request_bytes_received(peer, protocol, payload.len());
+
+					let (tx, rx) = oneshot::channel::<network::OutgoingResponse>();
+					let req = network::IncomingRequest { peer, payload, pending_response: tx };
+					if let Err(err) = inbound_queue.try_send(req) {
+						gum::debug!(
+							target: LOG_TARGET,
+							?peer,
+							?protocol,
+							?err,
+							"Dropping incoming request",
+						);
+						continue
+					}
+
+					let bandwidth = self.clone();
+					responses.push(async move {
+						if let Ok(response) = rx.await {
+							if let Ok(bytes) = &response.result {
+								bandwidth.on_request_bytes_sent(peer, protocol, bytes.len());
+							}
+							let _ = pending_response.send(response);
+						}
+					});
+				},
+				() = responses.select_next_some() => {},
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use parity_scale_codec::Encode;
+	use polkadot_node_network_protocol::request_response::{v1, IncomingRequest};
+	use polkadot_primitives::{CandidateHash, Hash, ValidatorIndex};
+
+	#[test]
+	fn top_talkers_are_sorted_by_total_traffic() {
+		let stats = BandwidthStats::default();
+		let (peer_a, peer_b) = (PeerId::random(), PeerId::random());
+
+		stats.on_peer_connected(peer_a, PeerSet::Validation);
+		stats.on_peer_connected(peer_b, PeerSet::Validation);
+		stats.on_peer_connected(peer_b, PeerSet::Collation);
+
+		stats.on_received(peer_a, PeerSet::Validation, 100);
+		stats.on_sent(&[peer_a, peer_b], PeerSet::Validation, 50);
+		stats.on_received(peer_b, PeerSet::Collation, 500);
+		// Not connected, not accounted.
+		stats.on_sent(&[PeerId::random()], PeerSet::Validation, 1000);
+
+		assert_eq!(
+			stats.top_talkers(2),
+			vec![
+				PeerBandwidth {
+					peer: peer_b,
+					protocol: TrafficProtocol::Notifications(PeerSet::Collation),
+					bytes_received: 500,
+					bytes_sent: 0,
+				},
+				PeerBandwidth {
+					peer: peer_a,
+					protocol: TrafficProtocol::Notifications(PeerSet::Validation),
+					bytes_received: 100,
+					bytes_sent: 50,
+				},
+			],
+		);
+
+		stats.on_peer_disconnected(peer_b, PeerSet::Collation);
+		assert_eq!(stats.top_talkers(10).len(), 2);
+	}
+
+	#[test]
+	fn request_traffic_is_accounted_per_protocol() {
+		let stats = BandwidthStats::default();
+		let peer = PeerId::random();
+
+		stats.on_peer_connected(peer, PeerSet::Validation);
+		stats.on_peer_connected(peer, PeerSet::Collation);
+
+		stats.on_request_bytes_sent(peer, Protocol::ChunkFetchingV1, 40);
+		stats.on_request_bytes_received(peer, Protocol::ChunkFetchingV1, 1000);
+		stats.on_request_bytes_received(peer, Protocol::PoVFetchingV1, 300);
+		// Not connected, not accounted.
+		stats.on_request_bytes_received(PeerId::random(), Protocol::PoVFetchingV1, 5000);
+
+		assert_eq!(
+			stats.top_talkers(2),
+			vec![
+				PeerBandwidth {
+					peer,
+					protocol: TrafficProtocol::Requests(Protocol::ChunkFetchingV1),
+					bytes_received: 1000,
+					bytes_sent: 40,
+				},
+				PeerBandwidth {
+					peer,
+					protocol: TrafficProtocol::Requests(Protocol::PoVFetchingV1),
+					bytes_received: 300,
+					bytes_sent: 0,
+				},
+			],
+		);
+
+		// Request traffic is kept as long as the peer is connected on any peer-set.
+		stats.on_peer_disconnected(peer, PeerSet::Collation);
+		assert_eq!(stats.top_talkers(10).len(), 3);
+
+		stats.on_peer_disconnected(peer, PeerSet::Validation);
+		assert!(stats.top_talkers(10).is_empty());
+	}
+
+	#[test]
+	fn incoming_requests_and_responses_are_accounted() {
+		let stats = BandwidthStats::default();
+		let peer = PeerId::random();
+		stats.on_peer_connected(peer, PeerSet::Validation);
+
+		let req_protocol_names = ReqProtocolNames::new(&Hash::repeat_byte(0), None);
+		let (mut receiver, config) =
+			IncomingRequest::<v1::ChunkFetchingRequest>::get_config_receiver(&req_protocol_names);
+		let (mut configs, forward) =
+			stats.observe_incoming_requests(&req_protocol_names, vec![config]);
+		let inbound_queue = configs.pop().unwrap().inbound_queue.unwrap();
+
+		let request = v1::ChunkFetchingRequest {
+			candidate_hash: CandidateHash(Hash::repeat_byte(1)),
+			index: ValidatorIndex(0),
+		};
+		let response = v1::ChunkFetchingResponse::NoSuchChunk;
+		let (request_len, response_len) = (request.encode().len(), response.encode().len());
+		let test = async move {
+			let (pending_response, response_rx) = oneshot::channel();
+			inbound_queue
+				.send(network::IncomingRequest {
+					peer,
+					payload: request.encode(),
+					pending_response,
+				})
+				.await
+				.unwrap();
+
+			let incoming = receiver.recv(|| vec![]).await.unwrap();
+			assert_eq!(incoming.peer, peer);
+			assert_eq!(incoming.payload.encode(), request.encode());
+			incoming.send_response(response.clone()).unwrap();
+
+			let outgoing = response_rx.await.unwrap();
+			assert_eq!(outgoing.result, Ok(response.encode()));
+		};
+		futures::executor::block_on(async move {
+			select! {
+				() = test.fuse() => {},
+				() = forward.fuse() => panic!("Forwarding stopped"),
+			}
+		});
+
+		assert_eq!(
+			stats.top_talkers(1),
+			vec![PeerBandwidth {
+				peer,
+				protocol: TrafficProtocol::Requests(Protocol::ChunkFetchingV1),
+				bytes_received: request_len as u64,
+				bytes_sent: response_len as u64,
+			}],
+		);
+	}
+
+	#[test]
+	fn rate_limit_violations_lead_to_disconnect() {
+		let stats = BandwidthStats::new(BandwidthConfig {
+			collation_rate_limit: Some(RateLimit { bytes_per_second: 1000, burst: 2000 }),
+		});
+		let peer = PeerId::random();
+		let start = Instant::now();
+		stats.on_peer_connected_at(peer, PeerSet::Collation, start);
+		stats.on_peer_connected_at(peer, PeerSet::Validation, start);
+
+		// The validation peer-set is not limited.
+		assert_eq!(
+			stats.on_received_at(peer, PeerSet::Validation, 10_000, start),
+			RateLimitOutcome::Within,
+		);
+
+		// The burst is available right away.
+		assert_eq!(
+			stats.on_received_at(peer, PeerSet::Collation, 2000, start),
+			RateLimitOutcome::Within,
+		);
+		assert_eq!(
+			stats.on_received_at(peer, PeerSet::Collation, 1, start),
+			RateLimitOutcome::Exceeded,
+		);
+
+		// Half a second refills 500 bytes.
+		let later = start + Duration::from_millis(500);
+		assert_eq!(
+			stats.on_received_at(peer, PeerSet::Collation, 500, later),
+			RateLimitOutcome::Within,
+		);
+		assert_eq!(
+			stats.on_received_at(peer, PeerSet::Collation, 500, later),
+			RateLimitOutcome::Exceeded,
+		);
+		assert_eq!(
+			stats.on_received_at(peer, PeerSet::Collation, 500, later),
+			RateLimitOutcome::Disconnect,
+		);
+	}
+
+	#[test]
+	fn rate_limit_violations_are_forgotten_once_the_bucket_is_full() {
+		let stats = BandwidthStats::new(BandwidthConfig {
+			collation_rate_limit: Some(RateLimit { bytes_per_second: 1000, burst: 1000 }),
+		});
+		let peer = PeerId::random();
+		let start = Instant::now();
+		stats.on_peer_connected_at(peer, PeerSet::Collation, start);
+
+		for _ in 0..MAX_RATE_LIMIT_VIOLATIONS - 1 {
+			assert_eq!(
+				stats.on_received_at(peer, PeerSet::Collation, 1001, start),
+				RateLimitOutcome::Exceeded,
+			);
+		}
+
+		// A second of silence fills up the bucket again.
+		let later = start + Duration::from_secs(1);
+		assert_eq!(
+			stats.on_received_at(peer, PeerSet::Collation, 1001, later),
+			RateLimitOutcome::Exceeded,
+		);
+	}
+}
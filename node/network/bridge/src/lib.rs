@@ -50,6 +50,11 @@ use self::network::Network;
 mod metrics;
 pub use self::metrics::Metrics;
 
+mod bandwidth;
+pub use self::bandwidth::{
+	BandwidthConfig, BandwidthStats, PeerBandwidth, RateLimit, TrafficProtocol,
+};
+
 mod errors;
 pub(crate) use self::errors::Error;
 
@@ -69,6 +74,7 @@ pub(crate) const UNCONNECTED_PEERSET_COST: Rep =
 	Rep::CostMinor("Message sent to un-connected peer-set");
 pub(crate) const MALFORMED_VIEW_COST: Rep = Rep::CostMajor("Malformed view");
 pub(crate) const EMPTY_VIEW_COST: Rep = Rep::CostMajor("Peer sent us an empty view");
+pub(crate) const RATE_LIMIT_COST: Rep = Rep::CostMinor("Peer exceeded its bandwidth limit");
 
 /// Messages from and to the network.
 ///
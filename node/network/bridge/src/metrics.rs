@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::{PeerSet, ProtocolVersion};
+use super::{PeerBandwidth, PeerSet, ProtocolVersion};
 use polkadot_node_metrics::metrics::{self, prometheus};
 
 /// Metrics for the network bridge.
@@ -108,6 +108,25 @@ impl Metrics {
 			metrics.report_events.inc()
 		}
 	}
+
+	pub fn note_top_talkers(&self, talkers: &[PeerBandwidth]) {
+		if let Some(metrics) = self.0.as_ref() {
+			// Peers drop out of the top talkers, so start from scratch.
+			metrics.top_talkers_bytes.reset();
+			for talker in talkers {
+				let peer = talker.peer.to_base58();
+				let protocol = talker.protocol.to_string();
+				metrics
+					.top_talkers_bytes
+					.with_label_values(&[&peer, &protocol, "received"])
+					.set(talker.bytes_received);
+				metrics
+					.top_talkers_bytes
+					.with_label_values(&[&peer, &protocol, "sent"])
+					.set(talker.bytes_sent);
+			}
+		}
+	}
 }
 
 #[derive(Clone)]
@@ -123,6 +142,8 @@ pub(crate) struct MetricsInner {
 
 	bytes_received: prometheus::CounterVec<prometheus::U64>,
 	bytes_sent: prometheus::CounterVec<prometheus::U64>,
+
+	top_talkers_bytes: prometheus::GaugeVec<prometheus::U64>,
 }
 
 impl metrics::Metrics for Metrics {
@@ -217,6 +238,16 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			top_talkers_bytes: prometheus::register(
+				prometheus::GaugeVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_peer_top_talkers_bytes",
+						"The bytes exchanged with the connected peers exchanging the most bytes on a parachain protocol",
+					),
+					&["peer", "protocol", "direction"]
+				)?,
+				registry,
+			)?,
 		};
 
 		Ok(Metrics(Some(metrics)))
@@ -17,7 +17,7 @@
 use std::{collections::HashSet, sync::Arc};
 
 use async_trait::async_trait;
-use futures::{prelude::*, stream::BoxStream};
+use futures::{future::BoxFuture, prelude::*, stream::BoxStream};
 
 use parity_scale_codec::Encode;
 
@@ -34,7 +34,7 @@ use polkadot_node_network_protocol::{
 };
 use polkadot_primitives::{AuthorityDiscoveryId, Block, Hash};
 
-use crate::validator_discovery::AuthorityDiscovery;
+use crate::{validator_discovery::AuthorityDiscovery, BandwidthStats};
 
 // network bridge network abstraction log target
 const LOG_TARGET: &'static str = "parachain::network-bridge-net";
@@ -52,6 +52,7 @@ pub(crate) fn send_message<M>(
 	protocol_names: &PeerSetProtocolNames,
 	message: M,
 	metrics: &super::Metrics,
+	bandwidth: &super::BandwidthStats,
 ) where
 	M: Encode + Clone,
 {
@@ -61,6 +62,7 @@ pub(crate) fn send_message<M>(
 	let message = {
 		let encoded = message.encode();
 		metrics.on_notification_sent(peer_set, version, encoded.len(), peers.len());
+		bandwidth.on_sent(&peers, peer_set, encoded.len());
 		encoded
 	};
 
@@ -107,13 +109,17 @@ pub trait Network: Clone + Send + 'static {
 	) -> Result<(), String>;
 
 	/// Send a request to a remote peer.
+	///
+	/// The request and its response are accounted in `bandwidth`. The returned future, if any,
+	/// delivers the accounted response to the requester and needs to be polled to completion.
 	async fn start_request<AD: AuthorityDiscovery>(
 		&self,
 		authority_discovery: &mut AD,
 		req: Requests,
 		req_protocol_names: &ReqProtocolNames,
 		if_disconnected: IfDisconnected,
-	);
+		bandwidth: &BandwidthStats,
+	) -> Option<BoxFuture<'static, ()>>;
 
 	/// Report a given peer as either beneficial (+) or costly (-) according to the given scalar.
 	fn report_peer(&self, who: PeerId, rep: ReputationChange);
@@ -165,7 +171,8 @@ impl Network for Arc<NetworkService<Block, Hash>> {
 		req: Requests,
 		req_protocol_names: &ReqProtocolNames,
 		if_disconnected: IfDisconnected,
-	) {
+		bandwidth: &BandwidthStats,
+	) -> Option<BoxFuture<'static, ()>> {
 		let (protocol, OutgoingRequest { peer, payload, pending_response }) = req.encode_request();
 
 		let peer_id = match peer {
@@ -208,7 +215,7 @@ impl Network for Arc<NetworkService<Block, Hash>> {
 					},
 					Ok(_) => {},
 				}
-				return
+				return None
 			},
 			Some(peer_id) => peer_id,
 		};
@@ -221,6 +228,10 @@ impl Network for Arc<NetworkService<Block, Hash>> {
 			"Starting request",
 		);
 
+		bandwidth.on_request_bytes_sent(peer_id, protocol, payload.len());
+		let (pending_response, forward_response) =
+			bandwidth.observe_response(peer_id, protocol, pending_response);
+
 		NetworkService::start_request(
 			self,
 			peer_id,
@@ -229,6 +240,8 @@ impl Network for Arc<NetworkService<Block, Hash>> {
 			pending_response,
 			if_disconnected,
 		);
+
+		Some(forward_response)
 	}
 }
 
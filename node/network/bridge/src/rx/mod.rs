@@ -68,7 +68,7 @@ use crate::network::{send_message, Network};
 
 use crate::network::get_peer_id_by_authority_id;
 
-use super::metrics::Metrics;
+use super::{bandwidth::RateLimitOutcome, metrics::Metrics};
 
 #[cfg(test)]
 mod tests;
@@ -84,6 +84,7 @@ pub struct NetworkBridgeRx<N, AD> {
 	sync_oracle: Box<dyn SyncOracle + Send>,
 	shared: Shared,
 	metrics: Metrics,
+	bandwidth: BandwidthStats,
 	peerset_protocol_names: PeerSetProtocolNames,
 }
 
@@ -93,11 +94,15 @@ impl<N, AD> NetworkBridgeRx<N, AD> {
 	///
 	/// This assumes that the network service has had the notifications protocol for the network
 	/// bridge already registered. See [`peers_sets_info`](peers_sets_info).
+	///
+	/// Traffic of connected peers is accounted in `bandwidth`, which also holds the rate limits
+	/// to enforce on incoming notifications.
 	pub fn new(
 		network_service: N,
 		authority_discovery_service: AD,
 		sync_oracle: Box<dyn SyncOracle + Send>,
 		metrics: Metrics,
+		bandwidth: BandwidthStats,
 		peerset_protocol_names: PeerSetProtocolNames,
 	) -> Self {
 		let shared = Shared::default();
@@ -107,6 +112,7 @@ impl<N, AD> NetworkBridgeRx<N, AD> {
 			sync_oracle,
 			shared,
 			metrics,
+			bandwidth,
 			peerset_protocol_names,
 		}
 	}
@@ -138,6 +144,7 @@ async fn handle_network_messages<AD>(
 	network_stream: BoxStream<'static, NetworkEvent>,
 	mut authority_discovery_service: AD,
 	metrics: Metrics,
+	bandwidth: BandwidthStats,
 	shared: Shared,
 	peerset_protocol_names: PeerSetProtocolNames,
 ) -> Result<(), Error>
@@ -224,6 +231,7 @@ where
 
 					metrics.on_peer_connected(peer_set, version);
 					metrics.note_peer_count(peer_set, version, peer_map.len());
+					bandwidth.on_peer_connected(peer, peer_set);
 
 					shared.local_view.clone().unwrap_or(View::default())
 				};
@@ -260,6 +268,7 @@ where
 									local_view,
 								),
 								&metrics,
+								&bandwidth,
 							),
 							ValidationVersion::VStaging => send_message(
 								&mut network_service,
@@ -271,6 +280,7 @@ where
 									local_view,
 								),
 								&metrics,
+								&bandwidth,
 							),
 						}
 					},
@@ -302,6 +312,7 @@ where
 									local_view,
 								),
 								&metrics,
+								&bandwidth,
 							),
 							CollationVersion::VStaging => send_message(
 								&mut network_service,
@@ -313,6 +324,7 @@ where
 									local_view,
 								),
 								&metrics,
+								&bandwidth,
 							),
 						}
 					},
@@ -339,6 +351,9 @@ where
 					};
 
 					let w = peer_map.remove(&peer).is_some();
+					if w {
+						bandwidth.on_peer_disconnected(peer, peer_set);
+					}
 
 					metrics.on_peer_disconnected(peer_set, version);
 					metrics.note_peer_count(peer_set, version, peer_map.len());
@@ -398,7 +413,7 @@ where
 					})
 					.collect();
 
-				let v_messages = match v_messages {
+				let mut v_messages = match v_messages {
 					Err(rep) => {
 						gum::debug!(target: LOG_TARGET, action = "ReportPeer");
 						network_service.report_peer(remote, rep.into());
@@ -429,7 +444,7 @@ where
 					})
 					.collect();
 
-				let c_messages = match c_messages {
+				let mut c_messages = match c_messages {
 					Err(rep) => {
 						gum::debug!(target: LOG_TARGET, action = "ReportPeer");
 						network_service.report_peer(remote, rep.into());
@@ -439,6 +454,41 @@ where
 					Ok(v) => v,
 				};
 
+				for (peer_set, messages) in
+					[(PeerSet::Validation, &mut v_messages), (PeerSet::Collation, &mut c_messages)]
+				{
+					if messages.is_empty() {
+						continue
+					}
+
+					let bytes = messages.iter().map(|m| m.len()).sum();
+					let disconnect = match bandwidth.on_received(remote, peer_set, bytes) {
+						RateLimitOutcome::Within => continue,
+						RateLimitOutcome::Exceeded => false,
+						RateLimitOutcome::Disconnect => true,
+					};
+
+					gum::debug!(
+						target: LOG_TARGET,
+						action = "ReportPeer",
+						peer = ?remote,
+						?peer_set,
+						disconnect,
+						"Peer exceeded its rate limit",
+					);
+					messages.clear();
+					network_service.report_peer(remote, RATE_LIMIT_COST.into());
+					if disconnect {
+						// [`NetworkService`] keeps track of the protocols by their main name.
+						let protocol = peerset_protocol_names.get_main_name(peer_set);
+						network_service.disconnect_peer(remote, protocol);
+					}
+				}
+
+				if let Some(talkers) = bandwidth.top_talkers_if_due() {
+					metrics.note_top_talkers(&talkers);
+				}
+
 				if v_messages.is_empty() && c_messages.is_empty() {
 					continue
 				}
@@ -567,6 +617,7 @@ async fn run_incoming_orchestra_signals<Context, N, AD>(
 	shared: Shared,
 	sync_oracle: Box<dyn SyncOracle + Send>,
 	metrics: Metrics,
+	bandwidth: BandwidthStats,
 	peerset_protocol_names: PeerSetProtocolNames,
 ) -> Result<(), Error>
 where
@@ -587,6 +638,7 @@ where
 						local_index,
 						canonical_shuffling,
 						shuffled_indices,
+						fallback_routes,
 					},
 			} => {
 				gum::debug!(
@@ -594,6 +646,7 @@ where
 					action = "NewGossipTopology",
 					?session,
 					?local_index,
+					?fallback_routes,
 					"Gossip topology has changed",
 				);
 
@@ -604,7 +657,8 @@ where
 				dispatch_validation_event_to_all_unbounded(
 					NetworkBridgeEvent::NewGossipTopology(NewGossipTopology {
 						session,
-						topology: SessionGridTopology::new(shuffled_indices, topology_peers),
+						topology: SessionGridTopology::new(shuffled_indices, topology_peers)
+							.with_fallback_routes(fallback_routes),
 						local_index,
 					}),
 					ctx.sender(),
@@ -668,6 +722,7 @@ where
 							&shared,
 							finalized_number,
 							&metrics,
+							&bandwidth,
 							&peerset_protocol_names,
 						);
 					}
@@ -712,6 +767,7 @@ where
 		network_service,
 		authority_discovery_service,
 		metrics,
+		bandwidth,
 		sync_oracle,
 		shared,
 		peerset_protocol_names,
@@ -723,6 +779,7 @@ where
 		network_stream,
 		authority_discovery_service.clone(),
 		metrics.clone(),
+		bandwidth.clone(),
 		shared.clone(),
 		peerset_protocol_names.clone(),
 	)
@@ -738,6 +795,7 @@ where
 		shared,
 		sync_oracle,
 		metrics,
+		bandwidth,
 		peerset_protocol_names,
 	);
 
@@ -765,6 +823,7 @@ fn update_our_view<Net, Context>(
 	shared: &Shared,
 	finalized_number: BlockNumber,
 	metrics: &Metrics,
+	bandwidth: &BandwidthStats,
 	peerset_protocol_names: &PeerSetProtocolNames,
 ) where
 	Net: Network,
@@ -822,6 +881,7 @@ fn update_our_view<Net, Context>(
 		peerset_protocol_names,
 		WireMessage::ViewUpdate(new_view.clone()),
 		metrics,
+		bandwidth,
 	);
 
 	send_collation_message_v1(
@@ -830,6 +890,7 @@ fn update_our_view<Net, Context>(
 		peerset_protocol_names,
 		WireMessage::ViewUpdate(new_view.clone()),
 		metrics,
+		bandwidth,
 	);
 
 	send_validation_message_vstaging(
@@ -838,6 +899,7 @@ fn update_our_view<Net, Context>(
 		peerset_protocol_names,
 		WireMessage::ViewUpdate(new_view.clone()),
 		metrics,
+		bandwidth,
 	);
 
 	send_collation_message_vstaging(
@@ -846,6 +908,7 @@ fn update_our_view<Net, Context>(
 		peerset_protocol_names,
 		WireMessage::ViewUpdate(new_view),
 		metrics,
+		bandwidth,
 	);
 
 	let our_view = OurView::new(
@@ -923,6 +986,7 @@ fn send_validation_message_v1(
 	peerset_protocol_names: &PeerSetProtocolNames,
 	message: WireMessage<protocol_v1::ValidationProtocol>,
 	metrics: &Metrics,
+	bandwidth: &BandwidthStats,
 ) {
 	send_message(
 		net,
@@ -932,6 +996,7 @@ fn send_validation_message_v1(
 		peerset_protocol_names,
 		message,
 		metrics,
+		bandwidth,
 	);
 }
 
@@ -941,6 +1006,7 @@ fn send_collation_message_v1(
 	peerset_protocol_names: &PeerSetProtocolNames,
 	message: WireMessage<protocol_v1::CollationProtocol>,
 	metrics: &Metrics,
+	bandwidth: &BandwidthStats,
 ) {
 	send_message(
 		net,
@@ -950,6 +1016,7 @@ fn send_collation_message_v1(
 		peerset_protocol_names,
 		message,
 		metrics,
+		bandwidth,
 	);
 }
 
@@ -959,6 +1026,7 @@ fn send_validation_message_vstaging(
 	protocol_names: &PeerSetProtocolNames,
 	message: WireMessage<protocol_vstaging::ValidationProtocol>,
 	metrics: &Metrics,
+	bandwidth: &BandwidthStats,
 ) {
 	send_message(
 		net,
@@ -968,6 +1036,7 @@ fn send_validation_message_vstaging(
 		protocol_names,
 		message,
 		metrics,
+		bandwidth,
 	);
 }
 
@@ -977,6 +1046,7 @@ fn send_collation_message_vstaging(
 	protocol_names: &PeerSetProtocolNames,
 	message: WireMessage<protocol_vstaging::CollationProtocol>,
 	metrics: &Metrics,
+	bandwidth: &BandwidthStats,
 ) {
 	send_message(
 		net,
@@ -986,6 +1056,7 @@ fn send_collation_message_vstaging(
 		protocol_names,
 		message,
 		metrics,
+		bandwidth,
 	);
 }
 
@@ -15,7 +15,7 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::*;
-use futures::{channel::oneshot, executor, stream::BoxStream};
+use futures::{channel::oneshot, executor, future::BoxFuture, stream::BoxStream};
 use polkadot_node_network_protocol::{self as net_protocol, OurView};
 use polkadot_node_subsystem::{messages::NetworkBridgeEvent, ActivatedLeaf};
 
@@ -132,7 +132,9 @@ impl Network for TestNetwork {
 		_: Requests,
 		_: &ReqProtocolNames,
 		_: IfDisconnected,
-	) {
+		_: &BandwidthStats,
+	) -> Option<BoxFuture<'static, ()>> {
+		None
 	}
 
 	fn report_peer(&self, who: PeerId, rep: ReputationChange) {
@@ -329,6 +331,14 @@ async fn await_peer_connections(
 fn test_harness<T: Future<Output = VirtualOverseer>>(
 	sync_oracle: Box<dyn SyncOracle + Send>,
 	test: impl FnOnce(TestHarness) -> T,
+) {
+	test_harness_with_bandwidth(sync_oracle, BandwidthStats::default(), test)
+}
+
+fn test_harness_with_bandwidth<T: Future<Output = VirtualOverseer>>(
+	sync_oracle: Box<dyn SyncOracle + Send>,
+	bandwidth: BandwidthStats,
+	test: impl FnOnce(TestHarness) -> T,
 ) {
 	let genesis_hash = Hash::repeat_byte(0xff);
 	let fork_id = None;
@@ -345,6 +355,7 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 		network_service: network,
 		authority_discovery_service: discovery,
 		metrics: Metrics(None),
+		bandwidth,
 		sync_oracle,
 		shared: shared.clone(),
 		peerset_protocol_names,
@@ -1022,6 +1033,90 @@ fn relays_collation_protocol_messages() {
 	});
 }
 
+#[test]
+fn collation_peers_exceeding_rate_limit_get_disconnected() {
+	let collator_protocol_message = protocol_v1::CollatorProtocolMessage::Declare(
+		Sr25519Keyring::Alice.public().into(),
+		Default::default(),
+		sp_core::crypto::UncheckedFrom::unchecked_from([1u8; 64]),
+	);
+	let message_v1 = WireMessage::ProtocolMessage(
+		protocol_v1::CollationProtocol::CollatorProtocol(collator_protocol_message.clone()),
+	)
+	.encode();
+
+	// Enough for a single message, slowly refilled.
+	let bandwidth = BandwidthStats::new(BandwidthConfig {
+		collation_rate_limit: Some(RateLimit {
+			bytes_per_second: 1,
+			burst: message_v1.len() as u64,
+		}),
+	});
+
+	test_harness_with_bandwidth(done_syncing_oracle(), bandwidth, |test_harness| async move {
+		let TestHarness { mut network_handle, mut virtual_overseer, shared } = test_harness;
+
+		let peer = PeerId::random();
+
+		network_handle
+			.connect_peer(peer, ValidationVersion::V1, PeerSet::Collation, ObservedRole::Full)
+			.await;
+
+		await_peer_connections(&shared, 0, 1).await;
+
+		assert_sends_collation_event_to_all(
+			NetworkBridgeEvent::PeerConnected(
+				peer,
+				ObservedRole::Full,
+				ValidationVersion::V1.into(),
+				None,
+			),
+			&mut virtual_overseer,
+		)
+		.await;
+
+		assert_sends_collation_event_to_all(
+			NetworkBridgeEvent::PeerViewChange(peer, View::default()),
+			&mut virtual_overseer,
+		)
+		.await;
+
+		// The first message is within the limit.
+		network_handle.peer_message(peer, PeerSet::Collation, message_v1.clone()).await;
+
+		assert_matches!(
+			virtual_overseer.recv().await,
+			AllMessages::CollatorProtocol(
+				CollatorProtocolMessage::NetworkBridgeUpdate(
+					NetworkBridgeEvent::PeerMessage(p, Versioned::V1(m))
+				)
+			) => {
+				assert_eq!(p, peer);
+				assert_eq!(m, collator_protocol_message);
+			}
+		);
+
+		// The following ones exceed it, until the peer gets disconnected.
+		for _ in 0..3 {
+			network_handle.peer_message(peer, PeerSet::Collation, message_v1.clone()).await;
+		}
+
+		// Our view, three reports and the disconnect.
+		let actions = network_handle.next_network_actions(5).await;
+		let reports = actions
+			.iter()
+			.filter(|a| **a == NetworkAction::ReputationChange(peer, RATE_LIMIT_COST.into()))
+			.count();
+		assert_eq!(reports, 3);
+		assert_network_actions_contains(
+			&actions,
+			&NetworkAction::DisconnectPeer(peer, PeerSet::Collation),
+		);
+
+		virtual_overseer
+	});
+}
+
 #[test]
 fn different_views_on_different_peer_sets() {
 	test_harness(done_syncing_oracle(), |test_harness| async move {
@@ -17,9 +17,11 @@
 //! The Network Bridge Subsystem - handles _outgoing_ messages, from subsystem to the network.
 use super::*;
 
+use futures::{future::BoxFuture, stream::FuturesUnordered};
+
 use polkadot_node_network_protocol::{
 	peer_set::{CollationVersion, PeerSet, PeerSetProtocolNames, ValidationVersion},
-	request_response::ReqProtocolNames,
+	request_response::{compression::PoVCompression, ReqProtocolNames, Requests},
 	v1 as protocol_v1, vstaging as protocol_vstaging, PeerId, Versioned,
 };
 
@@ -33,7 +35,7 @@ use polkadot_node_subsystem::{
 ///
 /// To be passed to [`FullNetworkConfiguration::add_notification_protocol`]().
 pub use polkadot_node_network_protocol::peer_set::{peer_sets_info, IsAuthority};
-use sc_network::ReputationChange;
+use sc_network::{IfDisconnected, ReputationChange};
 
 use crate::validator_discovery;
 
@@ -56,8 +58,10 @@ pub struct NetworkBridgeTx<N, AD> {
 	network_service: N,
 	authority_discovery_service: AD,
 	metrics: Metrics,
+	bandwidth: BandwidthStats,
 	req_protocol_names: ReqProtocolNames,
 	peerset_protocol_names: PeerSetProtocolNames,
+	compression: Option<PoVCompression>,
 }
 
 impl<N, AD> NetworkBridgeTx<N, AD> {
@@ -66,19 +70,30 @@ impl<N, AD> NetworkBridgeTx<N, AD> {
 	///
 	/// This assumes that the network service has had the notifications protocol for the network
 	/// bridge already registered. See [`peers_sets_info`](peers_sets_info).
+	///
+	/// Notifications sent to connected peers are accounted in `bandwidth`, which is to be shared
+	/// with the receiving side.
+	///
+	/// If `compression` is given, requests carrying PoVs are sent on the versions of their
+	/// protocols with compressed responses, falling back to the uncompressed versions for peers
+	/// not supporting them.
 	pub fn new(
 		network_service: N,
 		authority_discovery_service: AD,
 		metrics: Metrics,
+		bandwidth: BandwidthStats,
 		req_protocol_names: ReqProtocolNames,
 		peerset_protocol_names: PeerSetProtocolNames,
+		compression: Option<PoVCompression>,
 	) -> Self {
 		Self {
 			network_service,
 			authority_discovery_service,
 			metrics,
+			bandwidth,
 			req_protocol_names,
 			peerset_protocol_names,
+			compression,
 		}
 	}
 }
@@ -103,8 +118,10 @@ async fn handle_subsystem_messages<Context, N, AD>(
 	mut network_service: N,
 	mut authority_discovery_service: AD,
 	metrics: Metrics,
+	bandwidth: BandwidthStats,
 	req_protocol_names: ReqProtocolNames,
 	peerset_protocol_names: PeerSetProtocolNames,
+	compression: Option<PoVCompression>,
 ) -> Result<(), Error>
 where
 	N: Network,
@@ -112,39 +129,50 @@ where
 {
 	let mut validator_discovery =
 		validator_discovery::Service::<N, AD>::new(peerset_protocol_names.clone());
+	// Responses to our requests, delivered to the requesters once accounted.
+	let mut pending_responses = FuturesUnordered::new();
 
 	loop {
-		match ctx.recv().fuse().await? {
-			FromOrchestra::Signal(OverseerSignal::Conclude) => return Ok(()),
-			FromOrchestra::Signal(_) => { /* handled by incoming */ },
-			FromOrchestra::Communication { msg } => {
-				(network_service, authority_discovery_service) =
-					handle_incoming_subsystem_communication(
-						&mut ctx,
-						network_service,
-						&mut validator_discovery,
-						authority_discovery_service.clone(),
-						msg,
-						&metrics,
-						&req_protocol_names,
-						&peerset_protocol_names,
-					)
-					.await;
+		futures::select! {
+			msg = ctx.recv().fuse() => match msg? {
+				FromOrchestra::Signal(OverseerSignal::Conclude) => return Ok(()),
+				FromOrchestra::Signal(_) => { /* handled by incoming */ },
+				FromOrchestra::Communication { msg } => {
+					(network_service, authority_discovery_service) =
+						handle_incoming_subsystem_communication(
+							&mut ctx,
+							network_service,
+							&mut validator_discovery,
+							authority_discovery_service.clone(),
+							msg,
+							&metrics,
+							&bandwidth,
+							&req_protocol_names,
+							&peerset_protocol_names,
+							compression.as_ref(),
+							&mut pending_responses,
+						)
+						.await;
+				},
 			},
+			() = pending_responses.select_next_some() => {},
 		}
 	}
 }
 
 #[overseer::contextbounds(NetworkBridgeTx, prefix = self::overseer)]
 async fn handle_incoming_subsystem_communication<Context, N, AD>(
-	_ctx: &mut Context,
+	ctx: &mut Context,
 	mut network_service: N,
 	validator_discovery: &mut validator_discovery::Service<N, AD>,
 	mut authority_discovery_service: AD,
 	msg: NetworkBridgeTxMessage,
 	metrics: &Metrics,
+	bandwidth: &BandwidthStats,
 	req_protocol_names: &ReqProtocolNames,
 	peerset_protocol_names: &PeerSetProtocolNames,
+	compression: Option<&PoVCompression>,
+	pending_responses: &mut FuturesUnordered<BoxFuture<'static, ()>>,
 ) -> (N, AD)
 where
 	N: Network,
@@ -196,6 +224,7 @@ where
 					peerset_protocol_names,
 					WireMessage::ProtocolMessage(msg),
 					&metrics,
+					bandwidth,
 				),
 				Versioned::VStaging(msg) => send_validation_message_vstaging(
 					&mut network_service,
@@ -203,6 +232,7 @@ where
 					peerset_protocol_names,
 					WireMessage::ProtocolMessage(msg),
 					&metrics,
+					bandwidth,
 				),
 			}
 		},
@@ -221,6 +251,7 @@ where
 						peerset_protocol_names,
 						WireMessage::ProtocolMessage(msg),
 						&metrics,
+						bandwidth,
 					),
 					Versioned::VStaging(msg) => send_validation_message_vstaging(
 						&mut network_service,
@@ -228,6 +259,7 @@ where
 						peerset_protocol_names,
 						WireMessage::ProtocolMessage(msg),
 						&metrics,
+						bandwidth,
 					),
 				}
 			}
@@ -246,6 +278,7 @@ where
 					peerset_protocol_names,
 					WireMessage::ProtocolMessage(msg),
 					&metrics,
+					bandwidth,
 				),
 				Versioned::VStaging(msg) => send_collation_message_vstaging(
 					&mut network_service,
@@ -253,6 +286,7 @@ where
 					peerset_protocol_names,
 					WireMessage::ProtocolMessage(msg),
 					&metrics,
+					bandwidth,
 				),
 			}
 		},
@@ -271,6 +305,7 @@ where
 						peerset_protocol_names,
 						WireMessage::ProtocolMessage(msg),
 						&metrics,
+						bandwidth,
 					),
 					Versioned::VStaging(msg) => send_collation_message_vstaging(
 						&mut network_service,
@@ -278,6 +313,7 @@ where
 						peerset_protocol_names,
 						WireMessage::ProtocolMessage(msg),
 						&metrics,
+						bandwidth,
 					),
 				}
 			}
@@ -290,14 +326,34 @@ where
 			);
 
 			for req in reqs {
-				network_service
+				let req = match compression {
+					Some(compression) => {
+						let (req, fallback) = req.into_compressed(compression);
+						if let Some(fallback) = fallback {
+							spawn_fallback(
+								ctx,
+								fallback,
+								network_service.clone(),
+								authority_discovery_service.clone(),
+								req_protocol_names.clone(),
+								if_disconnected,
+								bandwidth.clone(),
+							);
+						}
+						req
+					},
+					None => req,
+				};
+				let pending_response = network_service
 					.start_request(
 						&mut authority_discovery_service,
 						req,
 						req_protocol_names,
 						if_disconnected,
+						bandwidth,
 					)
 					.await;
+				pending_responses.extend(pending_response);
 			}
 		},
 		NetworkBridgeTxMessage::ConnectToValidators { validator_ids, peer_set, failed } => {
@@ -357,8 +413,10 @@ where
 		network_service,
 		authority_discovery_service,
 		metrics,
+		bandwidth,
 		req_protocol_names,
 		peerset_protocol_names,
+		compression,
 	} = bridge;
 
 	handle_subsystem_messages(
@@ -366,20 +424,59 @@ where
 		network_service,
 		authority_discovery_service,
 		metrics,
+		bandwidth,
 		req_protocol_names,
 		peerset_protocol_names,
+		compression,
 	)
 	.await?;
 
 	Ok(())
 }
 
+/// Resend a request uncompressed once `fallback` finds the peer doesn't support the compressed
+/// version of its protocol.
+#[overseer::contextbounds(NetworkBridgeTx, prefix = self::overseer)]
+fn spawn_fallback<Context, N, AD>(
+	ctx: &mut Context,
+	fallback: BoxFuture<'static, Option<Requests>>,
+	network_service: N,
+	mut authority_discovery_service: AD,
+	req_protocol_names: ReqProtocolNames,
+	if_disconnected: IfDisconnected,
+	bandwidth: BandwidthStats,
+) where
+	N: Network,
+	AD: validator_discovery::AuthorityDiscovery,
+{
+	let task = async move {
+		if let Some(req) = fallback.await {
+			let pending_response = network_service
+				.start_request(
+					&mut authority_discovery_service,
+					req,
+					&req_protocol_names,
+					if_disconnected,
+					&bandwidth,
+				)
+				.await;
+			if let Some(pending_response) = pending_response {
+				pending_response.await;
+			}
+		}
+	};
+	if let Err(err) = ctx.spawn("network-bridge-tx-compression-fallback", task.boxed()) {
+		gum::warn!(target: LOG_TARGET, ?err, "Failed to spawn compressed request fallback");
+	}
+}
+
 fn send_validation_message_v1(
 	net: &mut impl Network,
 	peers: Vec<PeerId>,
 	protocol_names: &PeerSetProtocolNames,
 	message: WireMessage<protocol_v1::ValidationProtocol>,
 	metrics: &Metrics,
+	bandwidth: &BandwidthStats,
 ) {
 	send_message(
 		net,
@@ -389,6 +486,7 @@ fn send_validation_message_v1(
 		protocol_names,
 		message,
 		metrics,
+		bandwidth,
 	);
 }
 
@@ -398,6 +496,7 @@ fn send_collation_message_v1(
 	protocol_names: &PeerSetProtocolNames,
 	message: WireMessage<protocol_v1::CollationProtocol>,
 	metrics: &Metrics,
+	bandwidth: &BandwidthStats,
 ) {
 	send_message(
 		net,
@@ -407,6 +506,7 @@ fn send_collation_message_v1(
 		protocol_names,
 		message,
 		metrics,
+		bandwidth,
 	);
 }
 
@@ -416,6 +516,7 @@ fn send_validation_message_vstaging(
 	protocol_names: &PeerSetProtocolNames,
 	message: WireMessage<protocol_vstaging::ValidationProtocol>,
 	metrics: &Metrics,
+	bandwidth: &BandwidthStats,
 ) {
 	send_message(
 		net,
@@ -425,6 +526,7 @@ fn send_validation_message_vstaging(
 		protocol_names,
 		message,
 		metrics,
+		bandwidth,
 	);
 }
 
@@ -434,6 +536,7 @@ fn send_collation_message_vstaging(
 	protocol_names: &PeerSetProtocolNames,
 	message: WireMessage<protocol_vstaging::CollationProtocol>,
 	metrics: &Metrics,
+	bandwidth: &BandwidthStats,
 ) {
 	send_message(
 		net,
@@ -443,5 +546,6 @@ fn send_collation_message_vstaging(
 		protocol_names,
 		message,
 		metrics,
+		bandwidth,
 	);
 }
@@ -119,7 +119,9 @@ impl Network for TestNetwork {
 		_: Requests,
 		_: &ReqProtocolNames,
 		_: IfDisconnected,
-	) {
+		_: &BandwidthStats,
+	) -> Option<BoxFuture<'static, ()>> {
+		None
 	}
 
 	fn report_peer(&self, who: PeerId, rep: ReputationChange) {
@@ -217,8 +219,10 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(test: impl FnOnce(TestHarne
 		network,
 		discovery,
 		Metrics(None),
+		BandwidthStats::default(),
 		req_protocol_names,
 		peerset_protocol_names,
+		None,
 	);
 
 	let network_bridge_out_fut = run_network_out(bridge_out, context)
@@ -166,10 +166,10 @@ fn extract_peer_ids(multiaddr: impl Iterator<Item = Multiaddr>) -> HashSet<PeerI
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::network::Network;
+	use crate::{network::Network, BandwidthStats};
 
 	use async_trait::async_trait;
-	use futures::stream::BoxStream;
+	use futures::{future::BoxFuture, stream::BoxStream};
 	use polkadot_node_network_protocol::{
 		request_response::{outgoing::Requests, ReqProtocolNames},
 		PeerId,
@@ -252,7 +252,9 @@ mod tests {
 			_: Requests,
 			_: &ReqProtocolNames,
 			_: IfDisconnected,
-		) {
+			_: &BandwidthStats,
+		) -> Option<BoxFuture<'static, ()>> {
+			None
 		}
 
 		fn report_peer(&self, _: PeerId, _: ReputationChange) {
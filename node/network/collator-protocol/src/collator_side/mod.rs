@@ -190,9 +190,9 @@ struct PeerData {
 
 struct PerRelayParent {
 	prospective_parachains_mode: ProspectiveParachainsMode,
-	/// Validators group responsible for backing candidates built
-	/// on top of this relay parent.
-	validator_group: ValidatorGroup,
+	/// Validators groups responsible for backing candidates built
+	/// on top of this relay parent, per core of our para.
+	validator_groups: HashMap<CoreIndex, ValidatorGroup>,
 	/// Distributed collations.
 	collations: HashMap<CandidateHash, Collation>,
 }
@@ -201,7 +201,7 @@ impl PerRelayParent {
 	fn new(mode: ProspectiveParachainsMode) -> Self {
 		Self {
 			prospective_parachains_mode: mode,
-			validator_group: ValidatorGroup::default(),
+			validator_groups: HashMap::new(),
 			collations: HashMap::new(),
 		}
 	}
@@ -319,6 +319,8 @@ impl State {
 /// Distribute a collation.
 ///
 /// Figure out the core our para is assigned to and the relevant validators.
+/// If the para is assigned to several cores, `core_index` picks the one the collation is built
+/// for, each core being backed by a different group.
 /// Issue a connection request to these validators.
 /// If the para is not scheduled or next up on any core, at the relay-parent,
 /// or the relay-parent isn't in the active-leaves set, we ignore the message
@@ -334,6 +336,7 @@ async fn distribute_collation<Context>(
 	parent_head_data_hash: Hash,
 	pov: PoV,
 	result_sender: Option<oneshot::Sender<CollationSecondedSignal>>,
+	core_index: Option<CoreIndex>,
 ) -> Result<()> {
 	let candidate_relay_parent = receipt.descriptor.relay_parent;
 	let candidate_hash = receipt.hash();
@@ -380,21 +383,27 @@ async fn distribute_collation<Context>(
 		return Ok(())
 	}
 
-	// Determine which core the para collated-on is assigned to.
+	// Determine which cores the para collated-on is assigned to.
 	// If it is not scheduled then ignore the message.
-	let (our_core, num_cores) =
-		match determine_core(ctx.sender(), id, candidate_relay_parent, relay_parent_mode).await? {
-			Some(core) => core,
-			None => {
-				gum::warn!(
-					target: LOG_TARGET,
-					para_id = %id,
-					"looks like no core is assigned to {} at {}", id, candidate_relay_parent,
-				);
+	let (our_cores, num_cores) =
+		determine_cores(ctx.sender(), id, candidate_relay_parent, relay_parent_mode).await?;
+	let our_core = match core_index {
+		None => our_cores.first().copied(),
+		Some(core_index) => our_cores.contains(&core_index).then_some(core_index),
+	};
+	let our_core = match our_core {
+		Some(core) => core,
+		None => {
+			gum::warn!(
+				target: LOG_TARGET,
+				para_id = %id,
+				?core_index,
+				"looks like no core is assigned to {} at {}", id, candidate_relay_parent,
+			);
 
-				return Ok(())
-			},
-		};
+			return Ok(())
+		},
+	};
 
 	// Determine the group on that core.
 	//
@@ -437,7 +446,8 @@ async fn distribute_collation<Context>(
 		"Accepted collation, connecting to validators."
 	);
 
-	let validators_at_relay_parent = &mut per_relay_parent.validator_group.validators;
+	let validators_at_relay_parent =
+		&mut per_relay_parent.validator_groups.entry(our_core).or_default().validators;
 	if validators_at_relay_parent.is_empty() {
 		*validators_at_relay_parent = validators;
 	}
@@ -451,7 +461,13 @@ async fn distribute_collation<Context>(
 
 	per_relay_parent.collations.insert(
 		candidate_hash,
-		Collation { receipt, parent_head_data_hash, pov, status: CollationStatus::Created },
+		Collation {
+			receipt,
+			parent_head_data_hash,
+			pov,
+			core_index: our_core,
+			status: CollationStatus::Created,
+		},
 	);
 
 	// If prospective parachains are disabled, a leaf should be known to peer.
@@ -492,15 +508,16 @@ async fn distribute_collation<Context>(
 	Ok(())
 }
 
-/// Get the Id of the Core that is assigned to the para being collated on if any
+/// Get the Ids of the Cores that are assigned to the para being collated on, in ascending order,
 /// and the total number of cores.
-async fn determine_core(
+async fn determine_cores(
 	sender: &mut impl overseer::SubsystemSender<RuntimeApiMessage>,
 	para_id: ParaId,
 	relay_parent: Hash,
 	relay_parent_mode: ProspectiveParachainsMode,
-) -> Result<Option<(CoreIndex, usize)>> {
+) -> Result<(Vec<CoreIndex>, usize)> {
 	let cores = get_availability_cores(sender, relay_parent).await?;
+	let mut our_cores = Vec::new();
 
 	for (idx, core) in cores.iter().enumerate() {
 		let core_para_id = match core {
@@ -517,11 +534,11 @@ async fn determine_core(
 		};
 
 		if core_para_id == Some(para_id) {
-			return Ok(Some(((idx as u32).into(), cores.len())))
+			our_cores.push((idx as u32).into());
 		}
 	}
 
-	Ok(None)
+	Ok((our_cores, cores.len()))
 }
 
 /// Validators of a particular group index.
@@ -682,10 +699,12 @@ async fn advertise_collation<Context>(
 			}
 		}
 
-		let should_advertise =
-			per_relay_parent
-				.validator_group
-				.should_advertise_to(candidate_hash, peer_ids, &peer);
+		let validator_group = match per_relay_parent.validator_groups.get_mut(&collation.core_index)
+		{
+			Some(validator_group) => validator_group,
+			None => continue,
+		};
+		let should_advertise = validator_group.should_advertise_to(candidate_hash, peer_ids, &peer);
 
 		if !should_advertise {
 			gum::debug!(
@@ -729,9 +748,7 @@ async fn advertise_collation<Context>(
 		))
 		.await;
 
-		per_relay_parent
-			.validator_group
-			.advertised_to_peer(candidate_hash, &peer_ids, peer);
+		validator_group.advertised_to_peer(candidate_hash, &peer_ids, peer);
 
 		advertisement_timeouts.push(ResetInterestTimeout::new(
 			*candidate_hash,
@@ -757,7 +774,7 @@ async fn process_msg<Context>(
 		CollateOn(id) => {
 			state.collating_on = Some(id);
 		},
-		DistributeCollation(receipt, parent_head_data_hash, pov, result_sender) => {
+		DistributeCollation(receipt, parent_head_data_hash, pov, result_sender, core_index) => {
 			let _span1 = state
 				.span_per_relay_parent
 				.get(&receipt.descriptor.relay_parent)
@@ -786,6 +803,7 @@ async fn process_msg<Context>(
 						parent_head_data_hash,
 						pov,
 						result_sender,
+						core_index,
 					)
 					.await?;
 				},
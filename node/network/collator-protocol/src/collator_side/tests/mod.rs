@@ -361,6 +361,7 @@ async fn distribute_collation_with_receipt(
 			parent_head_data_hash,
 			pov.clone(),
 			None,
+			None,
 		),
 	)
 	.await;
@@ -277,6 +277,7 @@ fn distribute_collation_from_implicit_view() {
 					parent_head_data_hash,
 					pov.clone(),
 					None,
+					None,
 				),
 			)
 			.await;
@@ -357,6 +358,7 @@ fn distribute_collation_up_to_limit() {
 					parent_head_data_hash,
 					pov.clone(),
 					None,
+					None,
 				),
 			)
 			.await;
@@ -573,3 +575,126 @@ fn advertise_core_occupied() {
 		},
 	)
 }
+
+/// Test that collations for different cores of the para are advertised to the groups of their
+/// cores.
+#[test]
+fn distribute_collations_to_groups_of_their_cores() {
+	let head_a = Hash::from_low_u64_be(128);
+	let head_a_num: u32 = 64;
+
+	let mut test_state = TestState::default();
+	test_state.availability_cores = vec![
+		CoreState::Scheduled(ScheduledCore { para_id: test_state.para_id, collator: None }),
+		CoreState::Scheduled(ScheduledCore { para_id: test_state.para_id, collator: None }),
+	];
+
+	let group_authority_ids = |core: u32| -> HashSet<AuthorityDiscoveryId> {
+		let group = test_state.group_rotation_info.group_for_core(CoreIndex(core), 2);
+		test_state
+			.session_info
+			.validator_groups
+			.get(group)
+			.unwrap()
+			.iter()
+			.map(|i| test_state.session_info.discovery_keys[i.0 as usize].clone())
+			.collect()
+	};
+	let core_0_validators = group_authority_ids(0);
+	let core_1_validators = group_authority_ids(1);
+	assert!(core_0_validators.is_disjoint(&core_1_validators));
+
+	let local_peer_id = test_state.local_peer_id;
+	let collator_pair = test_state.collator_pair.clone();
+
+	test_harness(
+		local_peer_id,
+		collator_pair,
+		ReputationAggregator::new(|_| true),
+		|mut test_harness| async move {
+			let virtual_overseer = &mut test_harness.virtual_overseer;
+
+			overseer_send(virtual_overseer, CollatorProtocolMessage::CollateOn(test_state.para_id))
+				.await;
+			update_view(virtual_overseer, &test_state, vec![(head_a, head_a_num)], 1).await;
+
+			let mut expected_validators = HashSet::new();
+			for (core, validators) in [(1, &core_1_validators), (0, &core_0_validators)] {
+				let pov = PoV { block_data: BlockData(vec![core as u8]) };
+				let candidate = TestCandidateBuilder {
+					para_id: test_state.para_id,
+					relay_parent: head_a,
+					pov_hash: pov.hash(),
+					..Default::default()
+				}
+				.build();
+
+				overseer_send(
+					virtual_overseer,
+					CollatorProtocolMessage::DistributeCollation(
+						candidate,
+						Hash::repeat_byte(core as u8),
+						pov,
+						None,
+						Some(CoreIndex(core)),
+					),
+				)
+				.await;
+
+				assert_matches!(
+					overseer_recv(virtual_overseer).await,
+					AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+						relay_parent,
+						RuntimeApiRequest::AvailabilityCores(tx)
+					)) => {
+						assert_eq!(relay_parent, head_a);
+						tx.send(Ok(test_state.availability_cores.clone())).unwrap();
+					}
+				);
+
+				// Session info might be cached.
+				loop {
+					match overseer_recv(virtual_overseer).await {
+						AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+							_,
+							RuntimeApiRequest::SessionIndexForChild(tx),
+						)) => {
+							tx.send(Ok(test_state.current_session_index())).unwrap();
+						},
+						AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+							_,
+							RuntimeApiRequest::SessionInfo(_, tx),
+						)) => {
+							tx.send(Ok(Some(test_state.session_info.clone()))).unwrap();
+						},
+						AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+							_,
+							RuntimeApiRequest::ValidatorGroups(tx),
+						)) => {
+							tx.send(Ok((
+								test_state.session_info.validator_groups.to_vec(),
+								test_state.group_rotation_info.clone(),
+							)))
+							.unwrap();
+							break
+						},
+						other => panic!("Unexpected message received: {:?}", other),
+					}
+				}
+
+				// The group of each core is connected to, in addition to the previous ones.
+				expected_validators.extend(validators.iter().cloned());
+				assert_matches!(
+					overseer_recv(virtual_overseer).await,
+					AllMessages::NetworkBridgeTx(
+						NetworkBridgeTxMessage::ConnectToValidators { validator_ids, .. }
+					) => {
+						assert_eq!(validator_ids.into_iter().collect::<HashSet<_>>(), expected_validators);
+					}
+				);
+			}
+
+			test_harness
+		},
+	);
+}
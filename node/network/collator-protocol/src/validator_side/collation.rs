@@ -27,7 +27,7 @@
 //!    ┌──────────────────────────────────────────┐
 //!    └─▶Advertised ─▶ Pending ─▶ Fetched ─▶ Validated
 
-use std::{collections::VecDeque, future::Future, pin::Pin, task::Poll};
+use std::{cmp::Reverse, collections::VecDeque, future::Future, pin::Pin, task::Poll};
 
 use futures::{future::BoxFuture, FutureExt};
 use polkadot_node_network_protocol::{
@@ -46,6 +46,8 @@ use tokio_util::sync::CancellationToken;
 
 use crate::{error::SecondingError, LOG_TARGET};
 
+use super::collator_scores::CollatorScores;
+
 /// Candidate supplied with a para head it's built on top of.
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
 pub struct ProspectiveCandidate {
@@ -236,11 +238,13 @@ impl Collations {
 	/// This will reset the status back to `Waiting` using [`CollationStatus::back_to_waiting`].
 	///
 	/// Returns `Some(_)` if there is any collation to fetch, the `status` is not `Seconded` and
-	/// the passed in `finished_one` is the currently `waiting_collation`.
+	/// the passed in `finished_one` is the currently `waiting_collation`. The collation is picked
+	/// with [`Collations::pop_next_in_queue`].
 	pub(super) fn get_next_collation_to_fetch(
 		&mut self,
 		finished_one: &(CollatorId, Option<CandidateHash>),
 		relay_parent_mode: ProspectiveParachainsMode,
+		scores: &CollatorScores,
 	) -> Option<(PendingCollation, CollatorId)> {
 		// If finished one does not match waiting_collation, then we already dequeued another fetch
 		// to replace it.
@@ -267,13 +271,42 @@ impl Collations {
 				if !self.is_seconded_limit_reached(relay_parent_mode) {
 					None
 				} else {
-					self.waiting_queue.pop_front()
+					self.pop_next_in_queue(scores)
 				},
 			CollationStatus::WaitingOnValidation | CollationStatus::Fetching =>
 				unreachable!("We have reset the status above!"),
 		}
 	}
 
+	/// Removes the advertisement to fetch next from the `waiting_queue`.
+	///
+	/// Advertisements of collators with the highest score are preferred, the earliest one among
+	/// equal scores. Collators that reached the limit of concurrent fetches are skipped.
+	pub(super) fn pop_next_in_queue(
+		&mut self,
+		scores: &CollatorScores,
+	) -> Option<(PendingCollation, CollatorId)> {
+		let index = self
+			.waiting_queue
+			.iter()
+			.enumerate()
+			.filter(|(_, (_, collator_id))| scores.can_fetch_from(collator_id))
+			.min_by_key(|(_, (_, collator_id))| Reverse(scores.score(collator_id)))
+			.map(|(index, _)| index)?;
+		self.waiting_queue.remove(index)
+	}
+
+	/// Whether a queued advertisement can be fetched right away, without waiting for an ongoing
+	/// fetch or validation to conclude.
+	pub(super) fn is_ready_to_fetch(&self, relay_parent_mode: ProspectiveParachainsMode) -> bool {
+		let idle = match self.status {
+			CollationStatus::Waiting => true,
+			CollationStatus::Seconded => relay_parent_mode.is_enabled(),
+			CollationStatus::Fetching | CollationStatus::WaitingOnValidation => false,
+		};
+		idle && self.is_seconded_limit_reached(relay_parent_mode)
+	}
+
 	/// Checks the limit of seconded candidates for a given para.
 	pub(super) fn is_seconded_limit_reached(
 		&self,
@@ -0,0 +1,333 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Scores of collators, based on the outcome of the collations fetched from them.
+//!
+//! Scores are used to prioritize fetching advertisements of collators with a good track record
+//! and are persisted in the database, so that they survive restarts. Every collator is scored
+//! individually for each para it collates for, see [`ScoreKey`]. Collator keys are free to
+//! replace, so a collator must not gain from declaring with a new key: a collator without a
+//! record of its own starts slightly below zero, behind every collator which didn't do worse than
+//! break even. The start doesn't depend on the scores of other collators, so that collators of a
+//! para can't drag down the start of honest newcomers by misbehaving on purpose.
+//!
+//! Independently of scores, the number of fetches a single collator can have in flight is
+//! limited, so that a spamming collator can't crowd out honest collators of the same para.
+
+use std::collections::HashMap;
+
+use parity_scale_codec::{Decode, Encode};
+
+use polkadot_node_subsystem_util::database::{DBTransaction, Database};
+use polkadot_primitives::{CollatorId, Id as ParaId};
+
+use crate::LOG_TARGET;
+
+/// Score gained for each collation that got seconded.
+const VALID_COLLATION_SCORE: i64 = 1;
+/// Score lost for each collation request that timed out.
+const TIMED_OUT_COLLATION_COST: i64 = 2;
+/// Score lost for each collation that was invalid or malformed.
+const INVALID_COLLATION_COST: i64 = 8;
+
+/// Score of a collator before its first recorded outcome.
+const UNKNOWN_COLLATOR_SCORE: i64 = -1;
+
+/// Number of outcomes after which the record of a collator is halved, so that old outcomes
+/// weigh less than recent ones.
+const OUTCOMES_WINDOW: u32 = 64;
+
+/// Maximum number of records to keep scores for. The record updated least recently is evicted
+/// when exceeded.
+const MAX_TRACKED_COLLATORS: usize = 1024;
+
+/// The maximum number of collation fetches from a single collator at the same time.
+pub const MAX_CONCURRENT_FETCHES_PER_COLLATOR: usize = 2;
+
+/// The database key of the persisted scores.
+const PERSISTED_SCORES_KEY: &[u8] = b"CollatorProtocolScores";
+
+/// What a score is tracked for: a collator collating for a para.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct ScoreKey {
+	/// The para collated for.
+	pub para_id: ParaId,
+	/// The collator.
+	pub collator_id: CollatorId,
+}
+
+impl ScoreKey {
+	/// The key of a collator declared for `para_id`.
+	pub fn new(para_id: ParaId, collator_id: CollatorId) -> Self {
+		Self { para_id, collator_id }
+	}
+}
+
+/// The outcomes of the collations of a collator.
+#[derive(Debug, Clone, Copy, Encode, Decode)]
+struct CollatorRecord {
+	/// The score the collator had before its first recorded outcome.
+	initial: i64,
+	/// Number of collations that got seconded.
+	valid: u32,
+	/// Number of collations that were invalid or malformed.
+	invalid: u32,
+	/// Number of collation requests that timed out.
+	timed_out: u32,
+	/// The value of [`CollatorScores::clock`] when the record was last updated.
+	last_updated: u64,
+}
+
+impl CollatorRecord {
+	fn new(initial: i64) -> Self {
+		Self { initial, valid: 0, invalid: 0, timed_out: 0, last_updated: 0 }
+	}
+
+	fn score(&self) -> i64 {
+		self.initial + self.valid as i64 * VALID_COLLATION_SCORE -
+			self.timed_out as i64 * TIMED_OUT_COLLATION_COST -
+			self.invalid as i64 * INVALID_COLLATION_COST
+	}
+
+	fn update(&mut self, now: u64, f: impl FnOnce(&mut Self)) {
+		f(self);
+		if self.valid + self.invalid + self.timed_out > OUTCOMES_WINDOW {
+			self.initial /= 2;
+			self.valid /= 2;
+			self.invalid /= 2;
+			self.timed_out /= 2;
+		}
+		self.last_updated = now;
+	}
+}
+
+/// The records as stored in the database.
+#[derive(Encode, Decode)]
+struct StoredScores(Vec<(ScoreKey, CollatorRecord)>);
+
+/// Scores and ongoing fetches of collators.
+#[derive(Default)]
+pub struct CollatorScores {
+	/// Outcomes of past collations per score key.
+	records: HashMap<ScoreKey, CollatorRecord>,
+	/// The score keys of the declared collators.
+	keys: HashMap<CollatorId, ScoreKey>,
+	/// Number of ongoing collation fetches per collator.
+	fetching: HashMap<CollatorId, usize>,
+	/// Incremented on every update, orders records by recency.
+	clock: u64,
+	/// Whether records changed since they were last persisted.
+	dirty: bool,
+}
+
+impl CollatorScores {
+	/// Load the scores persisted in `column` of the database.
+	pub fn load(db: &dyn Database, column: u32) -> Self {
+		let stored = match db.get(column, PERSISTED_SCORES_KEY) {
+			Ok(Some(raw)) => match StoredScores::decode(&mut &raw[..]) {
+				Ok(stored) => stored,
+				Err(err) => {
+					gum::warn!(target: LOG_TARGET, ?err, "Failed to decode persisted collator scores");
+					return Self::default()
+				},
+			},
+			Ok(None) => return Self::default(),
+			Err(err) => {
+				gum::warn!(target: LOG_TARGET, ?err, "Failed to load persisted collator scores");
+				return Self::default()
+			},
+		};
+
+		let clock = stored.0.iter().map(|(_, record)| record.last_updated).max().unwrap_or(0);
+		Self { records: stored.0.into_iter().collect(), clock, ..Default::default() }
+	}
+
+	/// Persist the scores in `column` of the database, if they changed since the last time.
+	pub fn persist(&mut self, db: &dyn Database, column: u32) {
+		if !self.dirty {
+			return
+		}
+
+		let stored =
+			StoredScores(self.records.iter().map(|(key, record)| (key.clone(), *record)).collect());
+		let mut tx = DBTransaction::new();
+		tx.put_vec(column, PERSISTED_SCORES_KEY, stored.encode());
+		match db.write(tx) {
+			Ok(()) => self.dirty = false,
+			Err(err) => {
+				gum::warn!(target: LOG_TARGET, ?err, "Failed to persist collator scores");
+			},
+		}
+	}
+
+	/// Note that a collator declared itself, with the key its outcomes are recorded under.
+	pub fn note_declared(&mut self, collator_id: CollatorId, key: ScoreKey) {
+		self.keys.insert(collator_id, key);
+	}
+
+	/// Note that a collator disconnected. Its outcomes are no longer recorded.
+	pub fn note_disconnected(&mut self, collator_id: &CollatorId) {
+		self.keys.remove(collator_id);
+	}
+
+	/// The score of a collator. Collators which did not declare themselves have a score of zero.
+	pub fn score(&self, collator_id: &CollatorId) -> i64 {
+		match self.keys.get(collator_id) {
+			Some(key) => self.score_of(key),
+			None => 0,
+		}
+	}
+
+	fn score_of(&self, key: &ScoreKey) -> i64 {
+		match self.records.get(key) {
+			Some(record) => record.score(),
+			None => UNKNOWN_COLLATOR_SCORE,
+		}
+	}
+
+	/// Note that a collation of the collator got seconded.
+	pub fn note_valid(&mut self, collator_id: &CollatorId) {
+		self.update(collator_id, |record| record.valid += 1);
+	}
+
+	/// Note that a collation of the collator was invalid or malformed.
+	pub fn note_invalid(&mut self, collator_id: &CollatorId) {
+		self.update(collator_id, |record| record.invalid += 1);
+	}
+
+	/// Note that a collation request to the collator timed out.
+	pub fn note_timed_out(&mut self, collator_id: &CollatorId) {
+		self.update(collator_id, |record| record.timed_out += 1);
+	}
+
+	/// Whether another collation may be fetched from the collator right now.
+	pub fn can_fetch_from(&self, collator_id: &CollatorId) -> bool {
+		self.fetching.get(collator_id).copied().unwrap_or(0) < MAX_CONCURRENT_FETCHES_PER_COLLATOR
+	}
+
+	/// Note that a collation fetch from the collator started.
+	pub fn note_fetch_started(&mut self, collator_id: &CollatorId) {
+		*self.fetching.entry(collator_id.clone()).or_default() += 1;
+	}
+
+	/// Note that a collation fetch from the collator concluded, for whatever reason.
+	pub fn note_fetch_concluded(&mut self, collator_id: &CollatorId) {
+		if let Some(fetching) = self.fetching.get_mut(collator_id) {
+			*fetching -= 1;
+			if *fetching == 0 {
+				self.fetching.remove(collator_id);
+			}
+		}
+	}
+
+	fn update(&mut self, collator_id: &CollatorId, f: impl FnOnce(&mut CollatorRecord)) {
+		let key = match self.keys.get(collator_id) {
+			Some(key) => key.clone(),
+			None => return,
+		};
+
+		self.clock += 1;
+		self.dirty = true;
+		self.records
+			.entry(key)
+			.or_insert_with(|| CollatorRecord::new(UNKNOWN_COLLATOR_SCORE))
+			.update(self.clock, f);
+
+		if self.records.len() > MAX_TRACKED_COLLATORS {
+			let oldest = self
+				.records
+				.iter()
+				.min_by_key(|(_, record)| record.last_updated)
+				.map(|(key, _)| key.clone());
+			if let Some(oldest) = oldest {
+				self.records.remove(&oldest);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter;
+	use sp_keyring::Sr25519Keyring;
+	use std::sync::Arc;
+
+	fn make_db() -> Arc<dyn Database> {
+		Arc::new(DbAdapter::new(kvdb_memorydb::create(1), &[]))
+	}
+
+	#[test]
+	fn scores_survive_restarts() {
+		let db = make_db();
+		let para_id = ParaId::from(1);
+		let collator: CollatorId = Sr25519Keyring::Alice.public().into();
+
+		let mut scores = CollatorScores::load(&*db, 0);
+		scores.note_declared(collator.clone(), ScoreKey::new(para_id, collator.clone()));
+		scores.note_invalid(&collator);
+		scores.persist(&*db, 0);
+
+		// A restart, the collator declares itself again.
+		let mut scores = CollatorScores::load(&*db, 0);
+		assert_eq!(scores.score(&collator), 0);
+		scores.note_declared(collator.clone(), ScoreKey::new(para_id, collator.clone()));
+		assert_eq!(scores.score(&collator), UNKNOWN_COLLATOR_SCORE - INVALID_COLLATION_COST);
+	}
+
+	#[test]
+	fn collators_are_scored_individually() {
+		let para_id = ParaId::from(1);
+		let good_collator: CollatorId = Sr25519Keyring::Alice.public().into();
+		let bad_collator: CollatorId = Sr25519Keyring::Bob.public().into();
+
+		let mut scores = CollatorScores::default();
+		scores.note_declared(good_collator.clone(), ScoreKey::new(para_id, good_collator.clone()));
+		scores.note_declared(bad_collator.clone(), ScoreKey::new(para_id, bad_collator.clone()));
+		scores.note_valid(&good_collator);
+		scores.note_invalid(&bad_collator);
+
+		assert_eq!(scores.score(&good_collator), UNKNOWN_COLLATOR_SCORE + VALID_COLLATION_SCORE);
+		assert_eq!(scores.score(&bad_collator), UNKNOWN_COLLATOR_SCORE - INVALID_COLLATION_COST);
+	}
+
+	#[test]
+	fn new_collator_keys_start_below_zero() {
+		let para_id = ParaId::from(1);
+		let good_collator: CollatorId = Sr25519Keyring::Alice.public().into();
+		let bad_collator: CollatorId = Sr25519Keyring::Bob.public().into();
+		let new_collator: CollatorId = Sr25519Keyring::Charlie.public().into();
+
+		let mut scores = CollatorScores::default();
+		scores.note_declared(good_collator.clone(), ScoreKey::new(para_id, good_collator.clone()));
+		scores.note_valid(&good_collator);
+		scores.note_valid(&good_collator);
+		scores.note_declared(bad_collator.clone(), ScoreKey::new(para_id, bad_collator.clone()));
+		scores.note_invalid(&bad_collator);
+		scores.note_invalid(&bad_collator);
+
+		// A new key ranks behind collators with a good track record ...
+		scores.note_declared(new_collator.clone(), ScoreKey::new(para_id, new_collator.clone()));
+		assert_eq!(scores.score(&new_collator), UNKNOWN_COLLATOR_SCORE);
+		assert!(scores.score(&new_collator) < 0);
+		assert!(scores.score(&new_collator) < scores.score(&good_collator));
+		// ... and doesn't inherit the scores of misbehaving collators of its para.
+		assert!(scores.score(&new_collator) > scores.score(&bad_collator));
+
+		scores.note_valid(&new_collator);
+		assert_eq!(scores.score(&new_collator), UNKNOWN_COLLATOR_SCORE + VALID_COLLATION_SCORE);
+	}
+}
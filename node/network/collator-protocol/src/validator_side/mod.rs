@@ -23,6 +23,7 @@ use std::{
 	convert::TryInto,
 	future::Future,
 	iter::FromIterator,
+	sync::Arc,
 	time::{Duration, Instant},
 };
 use tokio_util::sync::CancellationToken;
@@ -48,9 +49,11 @@ use polkadot_node_subsystem::{
 		ProspectiveValidationDataRequest,
 	},
 	overseer, CollatorProtocolSenderTrait, FromOrchestra, OverseerSignal, PerLeafSpan,
+	RuntimeApiError,
 };
 use polkadot_node_subsystem_util::{
 	backing_implicit_view::View as ImplicitView,
+	database::Database,
 	reputation::{ReputationAggregator, REPUTATION_CHANGE_INTERVAL},
 	runtime::{prospective_parachains_mode, ProspectiveParachainsMode},
 };
@@ -64,6 +67,7 @@ use crate::error::{Error, FetchError, Result, SecondingError};
 use super::{modify_reputation, tick_stream, LOG_TARGET};
 
 mod collation;
+mod collator_scores;
 mod metrics;
 
 use collation::{
@@ -71,6 +75,7 @@ use collation::{
 	CollationFetchRequest, CollationStatus, Collations, FetchedCollation, PendingCollation,
 	PendingCollationFetch, ProspectiveCandidate,
 };
+use collator_scores::{CollatorScores, ScoreKey};
 
 #[cfg(test)]
 mod tests;
@@ -86,6 +91,8 @@ const COST_INVALID_SIGNATURE: Rep = Rep::Malicious("Invalid network message sign
 const COST_REPORT_BAD: Rep = Rep::Malicious("A collator was reported by another subsystem");
 const COST_WRONG_PARA: Rep = Rep::Malicious("A collator provided a collation for the wrong para");
 const COST_UNNEEDED_COLLATOR: Rep = Rep::CostMinor("An unneeded collator connected");
+const COST_UNAUTHORIZED_COLLATOR: Rep =
+	Rep::CostMinor("A collator not in the allow-list of the para connected");
 const BENEFIT_NOTIFY_GOOD: Rep =
 	Rep::BenefitMinor("A collator was noted good by another subsystem");
 
@@ -347,6 +354,18 @@ impl PeerData {
 struct GroupAssignments {
 	/// Current assignment.
 	current: Option<ParaId>,
+	/// The collators allowed to collate for the current assignment, `None` if any collator is.
+	authorized_collators: Option<Vec<CollatorId>>,
+}
+
+impl GroupAssignments {
+	/// Whether `collator_id` may collate for `para_id` according to this assignment.
+	fn is_authorized(&self, para_id: ParaId, collator_id: &CollatorId) -> bool {
+		self.current == Some(para_id) &&
+			self.authorized_collators
+				.as_ref()
+				.map_or(true, |collators| collators.contains(collator_id))
+	}
 }
 
 struct PerRelayParent {
@@ -359,7 +378,7 @@ impl PerRelayParent {
 	fn new(mode: ProspectiveParachainsMode) -> Self {
 		Self {
 			prospective_parachains_mode: mode,
-			assignment: GroupAssignments { current: None },
+			assignment: GroupAssignments { current: None, authorized_collators: None },
 			collations: Collations::default(),
 		}
 	}
@@ -428,6 +447,9 @@ struct State {
 
 	/// Aggregated reputation change
 	reputation: ReputationAggregator,
+
+	/// Scores and ongoing fetches per collator, used to prioritize and limit fetches.
+	collator_scores: CollatorScores,
 }
 
 fn is_relay_parent_in_implicit_view(
@@ -495,6 +517,24 @@ where
 		},
 	};
 
+	let authorized_collators = match para_now {
+		Some(para_id) => match polkadot_node_subsystem_util::request_staging_authorized_collators(
+			relay_parent,
+			para_id,
+			sender,
+		)
+		.await
+		.await
+		.map_err(Error::CancelledAuthorizedCollators)?
+		{
+			Ok(collators) => collators,
+			// Without allow-lists in the runtime, any collator may collate.
+			Err(RuntimeApiError::NotSupported { .. }) => None,
+			Err(err) => return Err(err.into()),
+		},
+		None => None,
+	};
+
 	// This code won't work well, if at all for on-demand parachains. For on-demand we'll
 	// have to be aware of which core the on-demand claim is going to be multiplexed
 	// onto. The on-demand claim will also have a known collator, and we should always
@@ -516,7 +556,7 @@ where
 		}
 	}
 
-	*group_assignment = GroupAssignments { current: para_now };
+	*group_assignment = GroupAssignments { current: para_now, authorized_collators };
 
 	Ok(())
 }
@@ -553,6 +593,18 @@ fn collator_peer_id(
 		.find_map(|(peer, data)| data.collator_id().filter(|c| c == &collator_id).map(|_| *peer))
 }
 
+// Whether the collator is allowed to collate for the para at any of the relay parents
+// we're assigned to the para at.
+fn is_collator_authorized(
+	per_relay_parent: &HashMap<Hash, PerRelayParent>,
+	para_id: ParaId,
+	collator_id: &CollatorId,
+) -> bool {
+	per_relay_parent
+		.values()
+		.any(|per_relay_parent| per_relay_parent.assignment.is_authorized(para_id, collator_id))
+}
+
 async fn disconnect_peer(sender: &mut impl overseer::CollatorProtocolSenderTrait, peer_id: PeerId) {
 	sender
 		.send_message(NetworkBridgeTxMessage::DisconnectPeer(peer_id, PeerSet::Collation))
@@ -726,6 +778,7 @@ async fn request_collation(
 	state
 		.collation_requests_cancel_handles
 		.insert(pending_collation, cancellation_token);
+	state.collator_scores.note_fetch_started(&collator_id);
 
 	gum::debug!(
 		target: LOG_TARGET,
@@ -836,6 +889,26 @@ async fn process_incoming_peer_message<Context>(
 			}
 
 			if state.current_assignments.contains_key(&para_id) {
+				if !is_collator_authorized(&state.per_relay_parent, para_id, &collator_id) {
+					gum::debug!(
+						target: LOG_TARGET,
+						peer_id = ?origin,
+						?collator_id,
+						?para_id,
+						"Declared as collator not in the allow-list of the para",
+					);
+
+					modify_reputation(
+						&mut state.reputation,
+						ctx.sender(),
+						origin,
+						COST_UNAUTHORIZED_COLLATOR,
+					)
+					.await;
+					disconnect_peer(ctx.sender(), origin).await;
+					return
+				}
+
 				gum::debug!(
 					target: LOG_TARGET,
 					peer_id = ?origin,
@@ -844,6 +917,8 @@ async fn process_incoming_peer_message<Context>(
 					"Declared as collator for current para",
 				);
 
+				let score_key = ScoreKey::new(para_id, collator_id.clone());
+				state.collator_scores.note_declared(collator_id.clone(), score_key);
 				peer_data.set_collating(collator_id, para_id);
 			} else {
 				gum::debug!(
@@ -1210,6 +1285,18 @@ where
 		PendingCollation::new(relay_parent, para_id, &peer_id, prospective_candidate);
 
 	match collations.status {
+		_ if collations.is_ready_to_fetch(relay_parent_mode) &&
+			!state.collator_scores.can_fetch_from(&collator_id) =>
+		{
+			gum::trace!(
+				target: LOG_TARGET,
+				peer_id = ?peer_id,
+				%para_id,
+				?relay_parent,
+				"Collator reached the limit of concurrent fetches, added collation to the pending list"
+			);
+			collations.waiting_queue.push_back((pending_collation, collator_id));
+		},
 		CollationStatus::Fetching | CollationStatus::WaitingOnValidation => {
 			gum::trace!(
 				target: LOG_TARGET,
@@ -1373,6 +1460,18 @@ where
 					"Disconnecting peer on view change (not current parachain id)"
 				);
 				disconnect_peer(sender, *peer_id).await;
+			} else if let Some(collator_id) = peer_data
+				.collator_id()
+				.filter(|id| !is_collator_authorized(&state.per_relay_parent, para_id, id))
+			{
+				gum::trace!(
+					target: LOG_TARGET,
+					?peer_id,
+					?collator_id,
+					?para_id,
+					"Disconnecting peer on view change (no longer in the allow-list)"
+				);
+				disconnect_peer(sender, *peer_id).await;
 			}
 		}
 	}
@@ -1414,7 +1513,11 @@ async fn handle_network_msg<Context>(
 			state.metrics.note_collator_peer_count(state.peer_data.len());
 		},
 		PeerDisconnected(peer_id) => {
-			state.peer_data.remove(&peer_id);
+			if let Some(collator_id) =
+				state.peer_data.remove(&peer_id).as_ref().and_then(PeerData::collator_id)
+			{
+				state.collator_scores.note_disconnected(collator_id);
+			}
 			state.metrics.note_collator_peer_count(state.peer_data.len());
 		},
 		NewGossipTopology { .. } => {
@@ -1464,6 +1567,7 @@ async fn process_msg<Context>(
 			);
 		},
 		ReportCollator(id) => {
+			state.collator_scores.note_invalid(&id);
 			report_collator(&mut state.reputation, ctx.sender(), &state.peer_data, id).await;
 		},
 		NetworkBridgeUpdate(event) => {
@@ -1494,6 +1598,7 @@ async fn process_msg<Context>(
 			{
 				let PendingCollation { relay_parent, peer_id, prospective_candidate, .. } =
 					pending_collation;
+				state.collator_scores.note_valid(&collator_id);
 				note_good_collation(
 					&mut state.reputation,
 					ctx.sender(),
@@ -1558,6 +1663,7 @@ async fn process_msg<Context>(
 				Entry::Vacant(_) => return,
 			};
 
+			state.collator_scores.note_invalid(&id);
 			report_collator(&mut state.reputation, ctx.sender(), &state.peer_data, id.clone())
 				.await;
 
@@ -1573,12 +1679,16 @@ pub(crate) async fn run<Context>(
 	keystore: KeystorePtr,
 	eviction_policy: crate::CollatorEvictionPolicy,
 	metrics: Metrics,
+	db: Arc<dyn Database>,
+	col_collator_scores: u32,
 ) -> std::result::Result<(), crate::error::FatalError> {
 	run_inner(
 		ctx,
 		keystore,
 		eviction_policy,
 		metrics,
+		db,
+		col_collator_scores,
 		ReputationAggregator::default(),
 		REPUTATION_CHANGE_INTERVAL,
 	)
@@ -1591,13 +1701,16 @@ async fn run_inner<Context>(
 	keystore: KeystorePtr,
 	eviction_policy: crate::CollatorEvictionPolicy,
 	metrics: Metrics,
+	db: Arc<dyn Database>,
+	col_collator_scores: u32,
 	reputation: ReputationAggregator,
 	reputation_interval: Duration,
 ) -> std::result::Result<(), crate::error::FatalError> {
 	let new_reputation_delay = || futures_timer::Delay::new(reputation_interval).fuse();
 	let mut reputation_delay = new_reputation_delay();
 
-	let mut state = State { metrics, reputation, ..Default::default() };
+	let collator_scores = CollatorScores::load(&*db, col_collator_scores);
+	let mut state = State { metrics, reputation, collator_scores, ..Default::default() };
 
 	let next_inactivity_stream = tick_stream(ACTIVITY_POLL);
 	futures::pin_mut!(next_inactivity_stream);
@@ -1622,21 +1735,30 @@ async fn run_inner<Context>(
 							&mut state,
 						).await;
 					}
-					Ok(FromOrchestra::Signal(OverseerSignal::Conclude)) | Err(_) => break,
+					Ok(FromOrchestra::Signal(OverseerSignal::Conclude)) | Err(_) => {
+						state.collator_scores.persist(&*db, col_collator_scores);
+						break
+					},
 					Ok(FromOrchestra::Signal(_)) => continue,
 				}
 			}
 			_ = next_inactivity_stream.next() => {
 				disconnect_inactive_peers(ctx.sender(), &eviction_policy, &state.peer_data).await;
+				state.collator_scores.persist(&*db, col_collator_scores);
 			}
 
 			resp = state.collation_requests.select_next_some() => {
-				let res = match handle_collation_fetch_response(
+				let res = handle_collation_fetch_response(
 					&mut state,
 					resp,
 					&mut network_error_freq,
 					&mut canceled_freq,
-				).await {
+				).await;
+				// The concluded fetch might allow fetching from a collator that reached the limit
+				// of concurrent fetches.
+				fetch_queued_collations(ctx.sender(), &mut state).await;
+
+				let res = match res {
 					Err(Some((peer_id, rep))) => {
 						modify_reputation(&mut state.reputation, ctx.sender(), peer_id, rep).await;
 						continue
@@ -1660,6 +1782,7 @@ async fn run_inner<Context>(
 
 					if err.is_malicious() {
 						// Report malicious peer.
+						state.collator_scores.note_invalid(&collator_id);
 						modify_reputation(&mut state.reputation, ctx.sender(), pending_collation.peer_id, COST_REPORT_BAD).await;
 					}
 					let maybe_candidate_hash =
@@ -1704,11 +1827,14 @@ async fn dequeue_next_collation_and_fetch<Context>(
 	// The collator we tried to fetch from last, optionally which candidate.
 	previous_fetch: (CollatorId, Option<CandidateHash>),
 ) {
-	while let Some((next, id)) = state.per_relay_parent.get_mut(&relay_parent).and_then(|state| {
-		state
-			.collations
-			.get_next_collation_to_fetch(&previous_fetch, state.prospective_parachains_mode)
-	}) {
+	while let Some((next, id)) =
+		state.per_relay_parent.get_mut(&relay_parent).and_then(|rp_state| {
+			rp_state.collations.get_next_collation_to_fetch(
+				&previous_fetch,
+				rp_state.prospective_parachains_mode,
+				&state.collator_scores,
+			)
+		}) {
 		gum::debug!(
 			target: LOG_TARGET,
 			?relay_parent,
@@ -1730,6 +1856,46 @@ async fn dequeue_next_collation_and_fetch<Context>(
 	}
 }
 
+/// Fetch queued advertisements of relay parents without an ongoing fetch or validation.
+///
+/// Advertisements are queued without starting a fetch if their collator has too many fetches in
+/// flight already, so they are picked up once any fetch concludes.
+async fn fetch_queued_collations<Sender>(sender: &mut Sender, state: &mut State)
+where
+	Sender: CollatorProtocolSenderTrait,
+{
+	let relay_parents: Vec<_> = state
+		.per_relay_parent
+		.iter()
+		.filter(|(_, rp_state)| {
+			!rp_state.collations.waiting_queue.is_empty() &&
+				rp_state.collations.is_ready_to_fetch(rp_state.prospective_parachains_mode)
+		})
+		.map(|(relay_parent, _)| *relay_parent)
+		.collect();
+
+	for relay_parent in relay_parents {
+		while let Some((next, id)) = state
+			.per_relay_parent
+			.get_mut(&relay_parent)
+			.and_then(|rp_state| rp_state.collations.pop_next_in_queue(&state.collator_scores))
+		{
+			if let Err(err) = fetch_collation(sender, state, next, id).await {
+				gum::debug!(
+					target: LOG_TARGET,
+					relay_parent = ?next.relay_parent,
+					para_id = ?next.para_id,
+					peer_id = ?next.peer_id,
+					error = %err,
+					"Failed to request a queued collation, dequeueing next one",
+				);
+			} else {
+				break
+			}
+		}
+	}
+}
+
 async fn request_persisted_validation_data<Sender>(
 	sender: &mut Sender,
 	relay_parent: Hash,
@@ -1879,6 +2045,7 @@ async fn handle_collation_fetch_response(
 	let (CollationEvent { collator_id, pending_collation }, response) = response;
 	// Remove the cancellation handle, as the future already completed.
 	state.collation_requests_cancel_handles.remove(&pending_collation);
+	state.collator_scores.note_fetch_concluded(&collator_id);
 
 	let response = match response {
 		Err(CollationFetchError::Cancelled) => {
@@ -1913,6 +2080,7 @@ async fn handle_collation_fetch_response(
 				err = ?err,
 				"Collator provided response that could not be decoded"
 			);
+			state.collator_scores.note_invalid(&collator_id);
 			Err(Some((pending_collation.peer_id, COST_CORRUPTED_MESSAGE)))
 		},
 		Err(err) if err.is_timed_out() => {
@@ -1925,6 +2093,8 @@ async fn handle_collation_fetch_response(
 			);
 			// For now we don't want to change reputation on timeout, to mitigate issues like
 			// this: https://github.com/paritytech/polkadot/issues/4617
+			// Only the collator's score is lowered, to prefer other collators for later fetches.
+			state.collator_scores.note_timed_out(&collator_id);
 			Err(None)
 		},
 		Err(RequestError::NetworkError(err)) => {
@@ -1967,6 +2137,7 @@ async fn handle_collation_fetch_response(
 				peer_id = ?pending_collation.peer_id,
 				"Got wrong para ID for requested collation."
 			);
+			state.collator_scores.note_invalid(&collator_id);
 
 			Err(Some((pending_collation.peer_id, COST_WRONG_PARA)))
 		},
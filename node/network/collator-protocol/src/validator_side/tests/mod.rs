@@ -43,6 +43,10 @@ use polkadot_primitives_test_helpers::{
 	dummy_candidate_descriptor, dummy_candidate_receipt_bad_sig, dummy_hash,
 };
 
+use polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter;
+
+use super::collator_scores::MAX_CONCURRENT_FETCHES_PER_COLLATOR;
+
 mod prospective_parachains;
 
 const ACTIVITY_TIMEOUT: Duration = Duration::from_millis(500);
@@ -70,6 +74,7 @@ struct TestState {
 	validator_groups: Vec<Vec<ValidatorIndex>>,
 	group_rotation_info: GroupRotationInfo,
 	cores: Vec<CoreState>,
+	authorized_collators: Option<Vec<CollatorId>>,
 }
 
 impl Default for TestState {
@@ -127,6 +132,24 @@ impl Default for TestState {
 			validator_groups,
 			group_rotation_info,
 			cores,
+			authorized_collators: None,
+		}
+	}
+}
+
+impl TestState {
+	/// The para our group is assigned to, given the group rotation. We're always in the first
+	/// group.
+	fn assigned_para(
+		&self,
+		group_rotation_info: &GroupRotationInfo,
+		async_backing: bool,
+	) -> Option<ParaId> {
+		let core = group_rotation_info.core_for_group(GroupIndex(0), self.cores.len());
+		match &self.cores[core.0 as usize] {
+			CoreState::Occupied(core) if async_backing => Some(core.para_id()),
+			CoreState::Scheduled(core) => Some(core.para_id),
+			CoreState::Occupied(_) | CoreState::Free => None,
 		}
 	}
 }
@@ -168,6 +191,8 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 			undeclared: DECLARE_TIMEOUT,
 		},
 		Metrics::default(),
+		Arc::new(DbAdapter::new(kvdb_memorydb::create(1), &[])),
+		0,
 		reputation,
 		REPUTATION_CHANGE_TEST_INTERVAL,
 	);
@@ -261,6 +286,27 @@ async fn respond_to_core_info_queries(
 			let _ = tx.send(Ok(test_state.cores.clone()));
 		}
 	);
+
+	if let Some(para_id) = test_state.assigned_para(&test_state.group_rotation_info, false) {
+		respond_to_authorized_collators_query(virtual_overseer, test_state, para_id).await;
+	}
+}
+
+async fn respond_to_authorized_collators_query(
+	virtual_overseer: &mut VirtualOverseer,
+	test_state: &TestState,
+	expected_para_id: ParaId,
+) {
+	assert_matches!(
+		overseer_recv(virtual_overseer).await,
+		AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+			_,
+			RuntimeApiRequest::StagingAuthorizedCollators(para_id, tx),
+		)) => {
+			assert_eq!(para_id, expected_para_id);
+			let _ = tx.send(Ok(test_state.authorized_collators.clone()));
+		}
+	);
 }
 
 /// Assert that the next message is a `CandidateBacking(Second())`.
@@ -1045,6 +1091,172 @@ fn fetch_next_collation_on_invalid_collation() {
 	});
 }
 
+/// Tests that queued advertisements of collators with a better score are fetched first.
+#[test]
+fn prefers_collations_of_collators_with_better_score() {
+	let test_state = TestState::default();
+
+	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
+		let TestHarness { mut virtual_overseer, .. } = test_harness;
+
+		overseer_send(
+			&mut virtual_overseer,
+			CollatorProtocolMessage::NetworkBridgeUpdate(NetworkBridgeEvent::OurViewChange(
+				our_view![test_state.relay_parent],
+			)),
+		)
+		.await;
+
+		assert_async_backing_params_request(&mut virtual_overseer, test_state.relay_parent).await;
+		respond_to_core_info_queries(&mut virtual_overseer, &test_state).await;
+
+		let peer_b = PeerId::random();
+		let peer_c = PeerId::random();
+		let peer_d = PeerId::random();
+
+		for (peer, collator) in [peer_b, peer_c, peer_d].into_iter().zip(&test_state.collators) {
+			connect_and_declare_collator(
+				&mut virtual_overseer,
+				peer,
+				collator.clone(),
+				test_state.chain_ids[0],
+				CollationVersion::V1,
+			)
+			.await;
+		}
+
+		// The collators of `peer_c` and `peer_d` are reported, `peer_c` more often, lowering
+		// their scores.
+		for (collator, peer) in [(1, peer_c), (1, peer_c), (2, peer_d)] {
+			overseer_send(
+				&mut virtual_overseer,
+				CollatorProtocolMessage::ReportCollator(test_state.collators[collator].public()),
+			)
+			.await;
+
+			assert_matches!(
+				overseer_recv(&mut virtual_overseer).await,
+				AllMessages::NetworkBridgeTx(
+					NetworkBridgeTxMessage::ReportPeer(ReportPeerMessage::Single(reported, rep)),
+				) => {
+					assert_eq!(reported, peer);
+					assert_eq!(rep.value, COST_REPORT_BAD.cost_or_benefit());
+				}
+			);
+		}
+
+		advertise_collation(&mut virtual_overseer, peer_b, test_state.relay_parent, None).await;
+		let _response_channel = assert_fetch_collation_request(
+			&mut virtual_overseer,
+			test_state.relay_parent,
+			test_state.chain_ids[0],
+			None,
+		)
+		.await;
+
+		// Both get queued, `peer_c` advertised first.
+		advertise_collation(&mut virtual_overseer, peer_c, test_state.relay_parent, None).await;
+		advertise_collation(&mut virtual_overseer, peer_d, test_state.relay_parent, None).await;
+
+		// The collation of `peer_d` is fetched once the first fetch takes too long.
+		Delay::new(MAX_UNSHARED_DOWNLOAD_TIME + Duration::from_millis(50)).await;
+
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::NetworkBridgeTx(NetworkBridgeTxMessage::SendRequests(reqs, _)) => {
+				assert_matches!(
+					&reqs[..],
+					[Requests::CollationFetchingV1(req)] => {
+						assert_eq!(req.peer, Recipient::Peer(peer_d));
+					}
+				);
+			}
+		);
+
+		virtual_overseer
+	});
+}
+
+/// Tests that a collator can't have more than [`MAX_CONCURRENT_FETCHES_PER_COLLATOR`] collation
+/// fetches at the same time.
+#[test]
+fn limits_concurrent_fetches_per_collator() {
+	let test_state = TestState::default();
+
+	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
+		let TestHarness { mut virtual_overseer, .. } = test_harness;
+
+		let relay_parents =
+			vec![Hash::repeat_byte(0x01), Hash::repeat_byte(0x02), Hash::repeat_byte(0x03)];
+		assert_eq!(relay_parents.len(), MAX_CONCURRENT_FETCHES_PER_COLLATOR + 1);
+
+		let our_view = our_view![relay_parents[0], relay_parents[1], relay_parents[2]];
+
+		overseer_send(
+			&mut virtual_overseer,
+			CollatorProtocolMessage::NetworkBridgeUpdate(NetworkBridgeEvent::OurViewChange(
+				our_view.clone(),
+			)),
+		)
+		.await;
+
+		for hash in our_view.iter() {
+			assert_async_backing_params_request(&mut virtual_overseer, *hash).await;
+			respond_to_core_info_queries(&mut virtual_overseer, &test_state).await;
+		}
+
+		let peer_b = PeerId::random();
+
+		connect_and_declare_collator(
+			&mut virtual_overseer,
+			peer_b,
+			test_state.collators[0].clone(),
+			test_state.chain_ids[0],
+			CollationVersion::V1,
+		)
+		.await;
+
+		for relay_parent in &relay_parents {
+			advertise_collation(&mut virtual_overseer, peer_b, *relay_parent, None).await;
+		}
+
+		let response_channel = assert_fetch_collation_request(
+			&mut virtual_overseer,
+			relay_parents[0],
+			test_state.chain_ids[0],
+			None,
+		)
+		.await;
+		let _response_channel = assert_fetch_collation_request(
+			&mut virtual_overseer,
+			relay_parents[1],
+			test_state.chain_ids[0],
+			None,
+		)
+		.await;
+
+		assert!(
+			overseer_recv_with_timeout(&mut virtual_overseer, Duration::from_millis(30))
+				.await
+				.is_none(),
+			"The collator reached the limit of concurrent fetches",
+		);
+
+		// The queued collation is fetched once one of the fetches concludes.
+		drop(response_channel);
+
+		assert_fetch_collation_request(
+			&mut virtual_overseer,
+			relay_parents[2],
+			test_state.chain_ids[0],
+			None,
+		)
+		.await;
+
+		virtual_overseer
+	});
+}
+
 #[test]
 fn inactive_disconnected() {
 	let test_state = TestState::default();
@@ -1276,6 +1488,72 @@ fn disconnect_if_wrong_declare() {
 	})
 }
 
+#[test]
+fn refuse_declarations_of_unauthorized_collators() {
+	let mut test_state = TestState::default();
+	test_state.authorized_collators = Some(vec![test_state.collators[0].public()]);
+
+	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
+		let TestHarness { mut virtual_overseer, .. } = test_harness;
+
+		overseer_send(
+			&mut virtual_overseer,
+			CollatorProtocolMessage::NetworkBridgeUpdate(NetworkBridgeEvent::OurViewChange(
+				our_view![test_state.relay_parent],
+			)),
+		)
+		.await;
+
+		assert_async_backing_params_request(&mut virtual_overseer, test_state.relay_parent).await;
+		respond_to_core_info_queries(&mut virtual_overseer, &test_state).await;
+
+		let peer_b = PeerId::random();
+		let peer_c = PeerId::random();
+
+		connect_and_declare_collator(
+			&mut virtual_overseer,
+			peer_b,
+			test_state.collators[1].clone(),
+			test_state.chain_ids[0],
+			CollationVersion::V1,
+		)
+		.await;
+
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::NetworkBridgeTx(NetworkBridgeTxMessage::ReportPeer(
+				ReportPeerMessage::Single(peer, rep),
+			)) => {
+				assert_eq!(peer, peer_b);
+				assert_eq!(rep.value, COST_UNAUTHORIZED_COLLATOR.cost_or_benefit());
+			}
+		);
+		assert_collator_disconnect(&mut virtual_overseer, peer_b).await;
+
+		// Collators in the allow-list are accepted and can advertise.
+		connect_and_declare_collator(
+			&mut virtual_overseer,
+			peer_c,
+			test_state.collators[0].clone(),
+			test_state.chain_ids[0],
+			CollationVersion::V1,
+		)
+		.await;
+
+		advertise_collation(&mut virtual_overseer, peer_c, test_state.relay_parent, None).await;
+
+		assert_fetch_collation_request(
+			&mut virtual_overseer,
+			test_state.relay_parent,
+			test_state.chain_ids[0],
+			None,
+		)
+		.await;
+
+		virtual_overseer
+	})
+}
+
 #[test]
 fn delay_reputation_change() {
 	let test_state = TestState::default();
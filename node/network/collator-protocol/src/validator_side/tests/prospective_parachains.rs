@@ -71,6 +71,12 @@ async fn assert_assign_incoming(
 			tx.send(Ok(test_state.cores.clone())).unwrap();
 		}
 	);
+
+	let mut group_rotation_info = test_state.group_rotation_info.clone();
+	group_rotation_info.now = number;
+	if let Some(para_id) = test_state.assigned_para(&group_rotation_info, true) {
+		respond_to_authorized_collators_query(virtual_overseer, test_state, para_id).await;
+	}
 }
 
 /// Handle a view update.
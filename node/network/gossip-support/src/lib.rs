@@ -23,6 +23,11 @@
 //! to be an order of sqrt of the validators. Our neighbors
 //! in this graph will be forwarded to the network bridge with
 //! the `NetworkBridgeRxMessage::NewGossipTopology` message.
+//!
+//! Throughout the session, it keeps track of which of our grid neighbors
+//! we are connected to. If too many of them are unreachable, the topology
+//! is sent again with fallback routes to the validators those neighbors
+//! would have forwarded our messages to.
 
 use std::{
 	collections::{HashMap, HashSet},
@@ -40,8 +45,10 @@ use sp_application_crypto::{AppCrypto, ByteArray};
 use sp_keystore::{Keystore, KeystorePtr};
 
 use polkadot_node_network_protocol::{
-	authority_discovery::AuthorityDiscovery, peer_set::PeerSet, GossipSupportNetworkMessage,
-	PeerId, Versioned,
+	authority_discovery::AuthorityDiscovery,
+	grid_topology::{FallbackRoutes, SessionGridTopology, TopologyPeerInfo},
+	peer_set::PeerSet,
+	GossipSupportNetworkMessage, PeerId, Versioned,
 };
 use polkadot_node_subsystem::{
 	messages::{
@@ -77,6 +84,17 @@ const LOW_CONNECTIVITY_WARN_DELAY: Duration = Duration::from_secs(600);
 /// If connectivity is lower than this in percent, issue warning in logs.
 const LOW_CONNECTIVITY_WARN_THRESHOLD: usize = 90;
 
+/// How often we check whether our grid neighbors are reachable.
+const GRID_LIVENESS_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Time after sending the gossip topology of a session before we consider
+/// unreachable grid neighbors, to give the connections time to be established.
+const GRID_LIVENESS_GRACE_PERIOD: Duration = Duration::from_secs(120);
+
+/// If more than this percentage of our grid neighbors is unreachable, we add fallback routes
+/// to the gossip topology.
+const MAX_UNREACHABLE_GRID_NEIGHBORS: usize = 25;
+
 /// The Gossip Support subsystem.
 pub struct GossipSupport<AD> {
 	keystore: KeystorePtr,
@@ -108,6 +126,9 @@ pub struct GossipSupport<AD> {
 	/// Authority discovery service.
 	authority_discovery: AD,
 
+	/// The gossip topology of the current session, if we are an authority in it.
+	current_topology: Option<GossipTopology>,
+
 	/// Subsystem metrics.
 	metrics: Metrics,
 }
@@ -132,6 +153,7 @@ where
 			connected_authorities: HashMap::new(),
 			connected_authorities_by_peer_id: HashMap::new(),
 			authority_discovery,
+			current_topology: None,
 			metrics,
 		}
 	}
@@ -141,6 +163,7 @@ where
 			Delay::new(LOW_CONNECTIVITY_WARN_DELAY)
 		}
 		let mut next_connectivity_check = get_connectivity_check_delay().fuse();
+		let mut next_grid_liveness_check = Delay::new(GRID_LIVENESS_CHECK_INTERVAL).fuse();
 		loop {
 			let message = select!(
 				_ = next_connectivity_check => {
@@ -148,6 +171,11 @@ where
 					next_connectivity_check = get_connectivity_check_delay().fuse();
 					continue
 				}
+				_ = next_grid_liveness_check => {
+					self.check_grid_liveness(ctx.sender()).await;
+					next_grid_liveness_check = Delay::new(GRID_LIVENESS_CHECK_INTERVAL).fuse();
+					continue
+				}
 				result = ctx.recv().fuse() =>
 					match result {
 						Ok(message) => message,
@@ -259,10 +287,13 @@ where
 				}
 
 				if is_new_session {
+					self.current_topology = None;
+					self.metrics.on_fallback_routes(0);
+
 					// Gossip topology is only relevant for authorities in the current session.
 					let our_index = self.get_key_index_and_update_metrics(&session_info)?;
 
-					update_gossip_topology(
+					let topology = update_gossip_topology(
 						sender,
 						our_index,
 						session_info.discovery_keys.clone(),
@@ -270,6 +301,7 @@ where
 						session_index,
 					)
 					.await?;
+					self.current_topology = Some(topology);
 
 					self.update_authority_ids(sender, session_info.discovery_keys).await;
 				}
@@ -345,6 +377,9 @@ where
 				);
 			}
 		}
+		// Remember the addresses of the authorities we connect to, so we can reconnect to them
+		// right away after a restart.
+		self.authority_discovery.persist_addresses(resolved.clone()).await;
 		self.resolved_authorities = resolved;
 		gum::debug!(target: LOG_TARGET, %num, "Issuing a connection request");
 
@@ -458,6 +493,35 @@ where
 		}
 	}
 
+	/// Check whether our grid neighbors are reachable and send the gossip topology again if its
+	/// fallback routes need to change.
+	async fn check_grid_liveness(&mut self, sender: &mut impl overseer::GossipSupportSenderTrait) {
+		let topology = match self.current_topology.as_mut() {
+			Some(topology) if topology.sent_at.elapsed() >= GRID_LIVENESS_GRACE_PERIOD => topology,
+			_ => return,
+		};
+
+		let connected_authorities = &self.connected_authorities;
+		let fallback_routes =
+			topology.compute_fallback_routes(|a| connected_authorities.contains_key(a));
+		if fallback_routes == topology.fallback_routes {
+			return
+		}
+
+		gum::debug!(
+			target: LOG_TARGET,
+			session_index = topology.session_index,
+			row = fallback_routes.row.len(),
+			column = fallback_routes.column.len(),
+			"Reachability of grid neighbors changed, updating fallback routes",
+		);
+		self.metrics
+			.on_fallback_routes(fallback_routes.row.len() + fallback_routes.column.len());
+
+		topology.fallback_routes = fallback_routes;
+		send_gossip_topology(sender, topology).await;
+	}
+
 	/// Check connectivity and report on it in logs.
 	fn check_connectivity(&mut self) {
 		let absolute_connected = self.connected_authorities.len();
@@ -536,6 +600,96 @@ fn remove_all_controlled(
 	to_remove.len()
 }
 
+/// The gossip topology of a session, as sent to the network bridge.
+struct GossipTopology {
+	session_index: SessionIndex,
+	/// The index of our keys in `authorities`.
+	our_index: usize,
+	/// The discovery keys of the session.
+	authorities: Vec<AuthorityDiscoveryId>,
+	canonical_shuffling: Vec<(AuthorityDiscoveryId, ValidatorIndex)>,
+	shuffled_indices: Vec<usize>,
+	/// The fallback routes the topology was last sent with.
+	fallback_routes: FallbackRoutes,
+	/// When the topology was first sent.
+	sent_at: Instant,
+}
+
+impl GossipTopology {
+	/// Compute the fallback routes for our unreachable grid neighbors.
+	///
+	/// There are none as long as at most `MAX_UNREACHABLE_GRID_NEIGHBORS` percent of our grid
+	/// neighbors are unreachable. Otherwise, the reachable validators an unreachable neighbor
+	/// would have forwarded our messages to are added as routes: its column for a row neighbor
+	/// and its row for a column neighbor.
+	fn compute_fallback_routes(
+		&self,
+		is_connected: impl Fn(&AuthorityDiscoveryId) -> bool,
+	) -> FallbackRoutes {
+		let grid = SessionGridTopology::new(
+			self.shuffled_indices.clone(),
+			self.canonical_shuffling
+				.iter()
+				.map(|(a, v)| TopologyPeerInfo {
+					peer_ids: Vec::new(),
+					validator_index: *v,
+					discovery_id: a.clone(),
+				})
+				.collect(),
+		);
+		let our_index = ValidatorIndex(self.our_index as _);
+		let ours = match grid.compute_grid_neighbors_for(our_index) {
+			Some(ours) => ours,
+			None => return FallbackRoutes::default(),
+		};
+		let is_reachable = |v: &ValidatorIndex| {
+			self.authorities.get(v.0 as usize).map_or(false, |a| is_connected(a))
+		};
+
+		let neighbors = ours.validator_indices_x.len() + ours.validator_indices_y.len();
+		let unreachable = ours
+			.validator_indices_x
+			.iter()
+			.chain(ours.validator_indices_y.iter())
+			.filter(|v| !is_reachable(*v))
+			.count();
+		if 100 * unreachable <= MAX_UNREACHABLE_GRID_NEIGHBORS * neighbors {
+			return FallbackRoutes::default()
+		}
+
+		let routes_for = |neighbors: &HashSet<ValidatorIndex>, row: bool| {
+			let mut routes: Vec<_> =
+				neighbors
+					.iter()
+					.filter(|v| !is_reachable(*v))
+					.filter_map(|v| grid.compute_grid_neighbors_for(*v))
+					.flat_map(|theirs| {
+						if row {
+							theirs.validator_indices_y
+						} else {
+							theirs.validator_indices_x
+						}
+					})
+					.filter(|v| {
+						// We send to our own grid neighbors anyway.
+						*v != our_index &&
+							!ours.validator_indices_x.contains(v) &&
+							!ours.validator_indices_y.contains(v) &&
+							is_reachable(v)
+					})
+					.collect();
+			routes.sort();
+			routes.dedup();
+			routes
+		};
+
+		FallbackRoutes {
+			row: routes_for(&ours.validator_indices_x, true),
+			column: routes_for(&ours.validator_indices_y, false),
+		}
+	}
+}
+
 /// We partition the list of all sorted `authorities` into `sqrt(len)` groups of `sqrt(len)` size
 /// and form a matrix where each validator is connected to all validators in its row and column.
 /// This is similar to `[web3]` research proposed topology, except for the groups are not parachain
@@ -550,7 +704,7 @@ async fn update_gossip_topology(
 	authorities: Vec<AuthorityDiscoveryId>,
 	relay_parent: Hash,
 	session_index: SessionIndex,
-) -> Result<(), util::Error> {
+) -> Result<GossipTopology, util::Error> {
 	// retrieve BABE randomness
 	let random_seed = {
 		let (tx, rx) = oneshot::channel();
@@ -590,16 +744,33 @@ async fn update_gossip_topology(
 		(shuffled_indices, canonical_shuffling)
 	};
 
+	let topology = GossipTopology {
+		session_index,
+		our_index,
+		authorities,
+		canonical_shuffling,
+		shuffled_indices,
+		fallback_routes: FallbackRoutes::default(),
+		sent_at: Instant::now(),
+	};
+	send_gossip_topology(sender, &topology).await;
+
+	Ok(topology)
+}
+
+async fn send_gossip_topology(
+	sender: &mut impl overseer::GossipSupportSenderTrait,
+	topology: &GossipTopology,
+) {
 	sender
 		.send_message(NetworkBridgeRxMessage::NewGossipTopology {
-			session: session_index,
-			local_index: Some(ValidatorIndex(our_index as _)),
-			canonical_shuffling,
-			shuffled_indices,
+			session: topology.session_index,
+			local_index: Some(ValidatorIndex(topology.our_index as _)),
+			canonical_shuffling: topology.canonical_shuffling.clone(),
+			shuffled_indices: topology.shuffled_indices.clone(),
+			fallback_routes: topology.fallback_routes.clone(),
 		})
 		.await;
-
-	Ok(())
 }
 
 #[overseer::subsystem(GossipSupport, error = SubsystemError, prefix = self::overseer)]
@@ -32,6 +32,8 @@ struct MetricsInner {
 	is_authority: Gauge<U64>,
 	/// Tracks authority status for parachain approval checking.
 	is_parachain_validator: Gauge<U64>,
+	/// Number of fallback routes in the current gossip topology.
+	fallback_routes: Gauge<U64>,
 }
 
 impl Metrics {
@@ -68,6 +70,13 @@ impl Metrics {
 			metrics.is_parachain_validator.set(0);
 		}
 	}
+
+	/// Set the number of fallback routes in the current gossip topology.
+	pub fn on_fallback_routes(&self, routes: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.fallback_routes.set(routes as u64);
+		}
+	}
 }
 
 impl metrics::Metrics for Metrics {
@@ -85,6 +94,13 @@ impl metrics::Metrics for Metrics {
 				Updates at session boundary.")?,
 				registry,
 			)?,
+			fallback_routes: prometheus::register(
+				Gauge::new(
+					"polkadot_parachain_gossip_topology_fallback_routes",
+					"Number of validators added to the gossip topology as routes around unreachable grid neighbors.",
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
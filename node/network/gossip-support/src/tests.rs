@@ -266,9 +266,11 @@ async fn test_neighbors(overseer: &mut VirtualOverseer, expected_session: Sessio
 			local_index,
 			canonical_shuffling,
 			shuffled_indices,
+			fallback_routes,
 		}) => {
 			assert_eq!(expected_session, got_session);
 			assert_eq!(local_index, Some(ValidatorIndex(6)));
+			assert!(fallback_routes.is_empty());
 			assert_eq!(shuffled_indices, EXPECTED_SHUFFLING.clone());
 
 			let grid_topology = SessionGridTopology::new(
@@ -296,6 +298,59 @@ async fn test_neighbors(overseer: &mut VirtualOverseer, expected_session: Sessio
 	);
 }
 
+// A topology of 9 validators in which validator `i` is at position `i` of the shuffling:
+//
+// [0 1 2]
+// [3 4 5]
+// [6 7 8]
+fn make_gossip_topology() -> GossipTopology {
+	let authorities: Vec<AuthorityDiscoveryId> =
+		(0..9).map(|_| AuthorityDiscoveryPair::generate().0.public()).collect();
+	GossipTopology {
+		session_index: 1,
+		our_index: 0,
+		canonical_shuffling: authorities
+			.iter()
+			.enumerate()
+			.map(|(i, a)| (a.clone(), ValidatorIndex(i as _)))
+			.collect(),
+		authorities,
+		shuffled_indices: (0..9).collect(),
+		fallback_routes: FallbackRoutes::default(),
+		sent_at: Instant::now(),
+	}
+}
+
+#[test]
+fn fallback_routes_for_unreachable_grid_neighbors() {
+	let topology = make_gossip_topology();
+	let unreachable_validators = |unreachable: &[usize]| {
+		let unreachable: HashSet<_> =
+			unreachable.iter().map(|i| topology.authorities[*i].clone()).collect();
+		topology.compute_fallback_routes(|a| !unreachable.contains(a))
+	};
+	let validators =
+		|indices: &[u32]| indices.iter().copied().map(ValidatorIndex).collect::<Vec<_>>();
+
+	// All neighbors are reachable.
+	assert_eq!(unreachable_validators(&[]), FallbackRoutes::default());
+	// A single unreachable neighbor out of four is tolerated.
+	assert_eq!(unreachable_validators(&[1]), FallbackRoutes::default());
+	// Unreachable non-neighbors don't matter.
+	assert_eq!(unreachable_validators(&[4, 5, 7]), FallbackRoutes::default());
+
+	// The column of row neighbor 1 and the row of column neighbor 3 are routed to directly.
+	assert_eq!(
+		unreachable_validators(&[1, 3]),
+		FallbackRoutes { row: validators(&[4, 7]), column: validators(&[4, 5]) },
+	);
+	// Only reachable validators are added as routes.
+	assert_eq!(
+		unreachable_validators(&[1, 3, 7]),
+		FallbackRoutes { row: validators(&[4]), column: validators(&[4, 5]) },
+	);
+}
+
 #[test]
 fn issues_a_connection_request_on_new_session() {
 	let hash = Hash::repeat_byte(0xAA);
@@ -0,0 +1,240 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Serving of parachain head proofs to light clients.
+//!
+//! Full nodes answer [`ParaHeadProofRequest`]s with the head of a parachain at a finalized relay
+//! chain block, the storage proof of it and, if requested and stored, the GRANDPA justification of
+//! the block. Requesters check responses with `polkadot-para-head-proof-verifier`.
+
+#![deny(unused_crate_dependencies)]
+#![warn(missing_docs)]
+
+use std::{iter, sync::Arc};
+
+use fatality::Nested;
+use sc_client_api::{BlockBackend, ProofProvider};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+use polkadot_node_network_protocol::{
+	request_response::{
+		incoming::OutgoingResponse,
+		v1::{ParaHeadProofRequest, ParaHeadProofResponse},
+		IncomingRequestReceiver,
+	},
+	UnifiedReputationChange as Rep,
+};
+use polkadot_para_head_proof_verifier::{ParaHeadProof, JUSTIFICATION_ENGINE_ID};
+use polkadot_primitives::{well_known_keys, Hash, Header};
+
+const LOG_TARGET: &str = "parachain::para-head-proof-server";
+
+const COST_INVALID_REQUEST: Rep = Rep::CostMajor("Peer sent unparsable request");
+
+/// Answer incoming parachain head proof requests from the state of `client`.
+///
+/// Runs until the request channel is closed. Reading proofs blocks on the database, so this
+/// should be spawned as a blocking task.
+pub async fn respond<Block, Client>(
+	mut receiver: IncomingRequestReceiver<ParaHeadProofRequest>,
+	client: Arc<Client>,
+) where
+	Block: BlockT<Hash = Hash, Header = Header>,
+	Client: HeaderBackend<Block> + BlockBackend<Block> + ProofProvider<Block>,
+{
+	loop {
+		let req = match receiver.recv(|| vec![COST_INVALID_REQUEST]).await.into_nested() {
+			Ok(Ok(v)) => v,
+			Err(fatal) => {
+				gum::debug!(target: LOG_TARGET, error = ?fatal, "Shutting down request responder");
+				return
+			},
+			Ok(Err(jfyi)) => {
+				gum::debug!(target: LOG_TARGET, error = ?jfyi, "Decoding request failed");
+				continue
+			},
+		};
+
+		let response = match para_head_proof(&*client, &req.payload) {
+			Ok(response) => response,
+			Err(err) => {
+				gum::debug!(
+					target: LOG_TARGET,
+					?err,
+					para_id = ?req.payload.para_id,
+					relay_block_hash = ?req.payload.relay_block_hash,
+					"Failed to create parachain head proof",
+				);
+				let response = OutgoingResponse {
+					result: Err(()),
+					reputation_changes: Vec::new(),
+					sent_feedback: None,
+				};
+				if let Err(_) = req.send_outgoing_response(response) {
+					gum::debug!(target: LOG_TARGET, "Sending response failed");
+				}
+				continue
+			},
+		};
+
+		if let Err(_) = req.send_response(response) {
+			gum::debug!(target: LOG_TARGET, "Sending response failed");
+		}
+	}
+}
+
+/// Create the response to `request` from the state of `client`.
+fn para_head_proof<Block, Client>(
+	client: &Client,
+	request: &ParaHeadProofRequest,
+) -> sp_blockchain::Result<ParaHeadProofResponse>
+where
+	Block: BlockT<Hash = Hash, Header = Header>,
+	Client: HeaderBackend<Block> + BlockBackend<Block> + ProofProvider<Block>,
+{
+	let hash = request.relay_block_hash;
+	let relay_header = match client.header(hash)? {
+		Some(header) => header,
+		None => return Ok(ParaHeadProofResponse::UnknownBlock),
+	};
+
+	// Only finalized blocks can be checked by light clients following finality, and forks of
+	// them may get pruned while the proof is being checked.
+	let finalized = relay_header.number <= client.info().finalized_number &&
+		client.hash(relay_header.number)? == Some(hash);
+	if !finalized {
+		return Ok(ParaHeadProofResponse::NotFinalized)
+	}
+
+	let key = well_known_keys::para_head(request.para_id);
+	let storage_proof = client
+		.read_proof(hash, &mut iter::once(key.as_slice()))?
+		.into_iter_nodes()
+		.collect();
+
+	let justification = if request.with_justification {
+		client
+			.justifications(hash)?
+			.and_then(|justifications| justifications.into_justification(JUSTIFICATION_ENGINE_ID))
+	} else {
+		None
+	};
+
+	Ok(ParaHeadProofResponse::HeadProof(ParaHeadProof {
+		relay_header,
+		storage_proof,
+		justification,
+	}))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+	use parity_scale_codec::Encode;
+	use polkadot_para_head_proof_verifier::{verify_para_head_proof, Error};
+	use polkadot_primitives::{HeadData, Id as ParaId};
+	use polkadot_test_client::{
+		Client, ClientBlockImportExt, DefaultTestClientBuilderExt, InitPolkadotBlockBuilder,
+		TestClientBuilder, TestClientBuilderExt,
+	};
+	use sp_consensus::BlockOrigin;
+
+	fn request(relay_block_hash: Hash) -> ParaHeadProofRequest {
+		ParaHeadProofRequest {
+			para_id: ParaId::new(100),
+			relay_block_hash,
+			with_justification: true,
+		}
+	}
+
+	/// A client with the head of parachain 100 set to `head` at genesis.
+	fn client_with_head(head: &HeadData) -> Client {
+		TestClientBuilder::new()
+			.add_extra_storage(well_known_keys::para_head(ParaId::new(100)), head.encode())
+			.build()
+	}
+
+	#[test]
+	fn proofs_of_finalized_blocks_verify() {
+		let head = HeadData(vec![7; 64]);
+		let client = client_with_head(&head);
+		let genesis_hash = client.info().genesis_hash;
+
+		let proof = assert_matches!(
+			para_head_proof(&client, &request(genesis_hash)).unwrap(),
+			ParaHeadProofResponse::HeadProof(proof) => proof
+		);
+		assert!(proof.justification.is_none());
+		assert_eq!(verify_para_head_proof(ParaId::new(100), genesis_hash, &proof), Ok(Some(head)));
+		// The proof shows the absence of heads of other parachains just as well.
+		assert_eq!(verify_para_head_proof(ParaId::new(200), genesis_hash, &proof), Ok(None));
+	}
+
+	#[test]
+	fn tampered_proofs_are_rejected() {
+		let head = HeadData(vec![7; 64]);
+		let client = client_with_head(&head);
+		let genesis_hash = client.info().genesis_hash;
+
+		let proof = assert_matches!(
+			para_head_proof(&client, &request(genesis_hash)).unwrap(),
+			ParaHeadProofResponse::HeadProof(proof) => proof
+		);
+
+		let mut tampered = proof.clone();
+		for node in &mut tampered.storage_proof {
+			*node.last_mut().unwrap() ^= 1;
+		}
+		assert_eq!(
+			verify_para_head_proof(ParaId::new(100), genesis_hash, &tampered),
+			Err(Error::InvalidStorageProof),
+		);
+
+		let mut tampered = proof.clone();
+		tampered.storage_proof.pop();
+		assert_eq!(
+			verify_para_head_proof(ParaId::new(100), genesis_hash, &tampered),
+			Err(Error::InvalidStorageProof),
+		);
+
+		// A state root committing to a different head doesn't match the block anymore.
+		let mut tampered = proof;
+		tampered.relay_header.state_root = Hash::repeat_byte(1);
+		assert_eq!(
+			verify_para_head_proof(ParaId::new(100), genesis_hash, &tampered),
+			Err(Error::HeaderMismatch),
+		);
+	}
+
+	#[test]
+	fn unknown_and_unfinalized_blocks_are_not_served() {
+		let mut client = TestClientBuilder::new().build();
+		let block = client.init_polkadot_block_builder().build().unwrap().block;
+		let hash = block.hash();
+		futures::executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+
+		assert_matches!(
+			para_head_proof(&client, &request(hash)).unwrap(),
+			ParaHeadProofResponse::NotFinalized
+		);
+		assert_matches!(
+			para_head_proof(&client, &request(Hash::repeat_byte(1))).unwrap(),
+			ParaHeadProofResponse::UnknownBlock
+		);
+	}
+}
@@ -16,7 +16,10 @@
 
 //! Authority discovery service interfacing.
 
-use std::{collections::HashSet, fmt::Debug};
+use std::{
+	collections::{HashMap, HashSet},
+	fmt::Debug,
+};
 
 use async_trait::async_trait;
 
@@ -40,6 +43,15 @@ pub trait AuthorityDiscovery: Send + Debug + 'static {
 		&mut self,
 		peer_id: PeerId,
 	) -> Option<HashSet<AuthorityDiscoveryId>>;
+	/// Remember the given addresses across restarts, replacing the previously remembered ones.
+	///
+	/// They are used for authorities the local address cache doesn't know about (yet).
+	/// Does nothing by default.
+	async fn persist_addresses(
+		&mut self,
+		_addresses: HashMap<AuthorityDiscoveryId, HashSet<Multiaddr>>,
+	) {
+	}
 }
 
 #[async_trait]
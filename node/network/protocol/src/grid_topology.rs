@@ -31,6 +31,11 @@
 //! an adversary doesn't know which peers a validator will send to.
 //! This is combined with the property that the adversary doesn't know which validators will elect
 //! to check a block.
+//!
+//! When grid neighbors of the local validator are unreachable, the messages they would have
+//! forwarded are lost to their part of the grid. The grid itself must stay the same for all
+//! validators, so instead the local validator may be given [`FallbackRoutes`]: reachable
+//! validators which additionally receive the messages meant for the unreachable neighbors.
 
 use crate::PeerId;
 use polkadot_primitives::{AuthorityDiscoveryId, SessionIndex, ValidatorIndex};
@@ -64,6 +69,25 @@ pub struct TopologyPeerInfo {
 	pub discovery_id: AuthorityDiscoveryId,
 }
 
+/// Additional routes of the local validator, standing in for unreachable grid neighbors.
+///
+/// These only add peers to the local grid neighbors, the validator indices of the neighbors and
+/// thereby the routing of received messages are unaffected.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct FallbackRoutes {
+	/// Validators additionally receiving the messages sent to the row neighbors.
+	pub row: Vec<ValidatorIndex>,
+	/// Validators additionally receiving the messages sent to the column neighbors.
+	pub column: Vec<ValidatorIndex>,
+}
+
+impl FallbackRoutes {
+	/// Whether there are no fallback routes.
+	pub fn is_empty(&self) -> bool {
+		self.row.is_empty() && self.column.is_empty()
+	}
+}
+
 /// Topology representation for a session.
 #[derive(Default, Clone, Debug, PartialEq)]
 pub struct SessionGridTopology {
@@ -73,12 +97,55 @@ pub struct SessionGridTopology {
 	shuffled_indices: Vec<usize>,
 	/// The canonical shuffling of validators for the session.
 	canonical_shuffling: Vec<TopologyPeerInfo>,
+	/// The fallback routes of the local validator.
+	fallback_routes: FallbackRoutes,
 }
 
 impl SessionGridTopology {
 	/// Create a new session grid topology.
 	pub fn new(shuffled_indices: Vec<usize>, canonical_shuffling: Vec<TopologyPeerInfo>) -> Self {
-		SessionGridTopology { shuffled_indices, canonical_shuffling }
+		SessionGridTopology {
+			shuffled_indices,
+			canonical_shuffling,
+			fallback_routes: Default::default(),
+		}
+	}
+
+	/// Set the fallback routes of the local validator.
+	pub fn with_fallback_routes(mut self, fallback_routes: FallbackRoutes) -> Self {
+		self.fallback_routes = fallback_routes;
+		self
+	}
+
+	/// The fallback routes of the local validator.
+	pub fn fallback_routes(&self) -> &FallbackRoutes {
+		&self.fallback_routes
+	}
+
+	/// Produces the outgoing routing logic for the local validator, which includes the peers of
+	/// its fallback routes.
+	///
+	/// Returns `None` if the validator index is out of bounds.
+	pub fn compute_local_grid_neighbors(&self, local: ValidatorIndex) -> Option<GridNeighbors> {
+		let mut grid_subset = self.compute_grid_neighbors_for(local)?;
+
+		let routes = [
+			(&self.fallback_routes.row, &mut grid_subset.peers_x),
+			(&self.fallback_routes.column, &mut grid_subset.peers_y),
+		];
+		for (validators, peers) in routes {
+			for v in validators {
+				let n = self
+					.shuffled_indices
+					.get(v.0 as usize)
+					.and_then(|i| self.canonical_shuffling.get(*i));
+				if let Some(n) = n {
+					peers.extend(n.peer_ids.iter().copied());
+				}
+			}
+		}
+
+		Some(grid_subset)
 	}
 
 	/// Produces the outgoing routing logic for a particular peer.
@@ -302,7 +369,9 @@ impl SessionGridTopologies {
 		}
 	}
 
-	/// Insert a new topology, no-op if already present.
+	/// Insert a new topology, replacing the one of the same session if already present.
+	///
+	/// Topologies are replaced mid-session when the fallback routes of the local validator change.
 	pub fn insert_topology(
 		&mut self,
 		session: SessionIndex,
@@ -310,13 +379,11 @@ impl SessionGridTopologies {
 		local_index: Option<ValidatorIndex>,
 	) {
 		let entry = self.inner.entry(session).or_insert((None, 0));
-		if entry.0.is_none() {
-			let local_neighbors = local_index
-				.and_then(|l| topology.compute_grid_neighbors_for(l))
-				.unwrap_or_else(GridNeighbors::empty);
+		let local_neighbors = local_index
+			.and_then(|l| topology.compute_local_grid_neighbors(l))
+			.unwrap_or_else(GridNeighbors::empty);
 
-			entry.0 = Some(SessionGridTopologyEntry { topology, local_neighbors });
-		}
+		entry.0 = Some(SessionGridTopologyEntry { topology, local_neighbors });
 	}
 }
 
@@ -344,10 +411,7 @@ impl Default for SessionBoundGridTopologyStorage {
 				// as the default instead of the lower bound.
 				session_index: SessionIndex::max_value(),
 				entry: SessionGridTopologyEntry {
-					topology: SessionGridTopology {
-						shuffled_indices: Vec::new(),
-						canonical_shuffling: Vec::new(),
-					},
+					topology: SessionGridTopology::default(),
 					local_neighbors: GridNeighbors::empty(),
 				},
 			},
@@ -379,7 +443,9 @@ impl SessionBoundGridTopologyStorage {
 		None
 	}
 
-	/// Update the current topology preserving the previous one
+	/// Update the current topology preserving the previous one.
+	///
+	/// An update for the session of the current topology replaces it, keeping the previous one.
 	pub fn update_topology(
 		&mut self,
 		session_index: SessionIndex,
@@ -387,16 +453,19 @@ impl SessionBoundGridTopologyStorage {
 		local_index: Option<ValidatorIndex>,
 	) {
 		let local_neighbors = local_index
-			.and_then(|l| topology.compute_grid_neighbors_for(l))
+			.and_then(|l| topology.compute_local_grid_neighbors(l))
 			.unwrap_or_else(GridNeighbors::empty);
 
-		let old_current = std::mem::replace(
-			&mut self.current_topology,
-			GridTopologySessionBound {
-				entry: SessionGridTopologyEntry { topology, local_neighbors },
-				session_index,
-			},
-		);
+		let new_current = GridTopologySessionBound {
+			entry: SessionGridTopologyEntry { topology, local_neighbors },
+			session_index,
+		};
+		if self.current_topology.session_index == session_index {
+			self.current_topology = new_current;
+			return
+		}
+
+		let old_current = std::mem::replace(&mut self.current_topology, new_current);
 		self.prev_topology.replace(old_current);
 	}
 
@@ -486,6 +555,7 @@ mod tests {
 	use super::*;
 	use rand::SeedableRng;
 	use rand_chacha::ChaCha12Rng;
+	use sp_keyring::Sr25519Keyring;
 
 	fn dummy_rng() -> ChaCha12Rng {
 		rand_chacha::ChaCha12Rng::seed_from_u64(12345)
@@ -552,6 +622,57 @@ mod tests {
 		assert_eq!(run_random_routing(&mut random_routing, &mut rng, 10, 100), 10);
 	}
 
+	fn topology_peer_info(validator_index: u32) -> TopologyPeerInfo {
+		TopologyPeerInfo {
+			peer_ids: vec![PeerId::random()],
+			validator_index: ValidatorIndex(validator_index),
+			discovery_id: Sr25519Keyring::Alice.public().into(),
+		}
+	}
+
+	// A topology of 9 validators in which validator `i` is at position `i` of the shuffling.
+	fn identity_topology() -> SessionGridTopology {
+		SessionGridTopology::new((0..9).collect(), (0..9).map(topology_peer_info).collect())
+	}
+
+	#[test]
+	fn fallback_routes_add_local_peers() {
+		let topology = identity_topology();
+		let peer_of = |v: usize| topology.canonical_shuffling[v].peer_ids[0];
+		let fallback_routes =
+			FallbackRoutes { row: vec![ValidatorIndex(4)], column: vec![ValidatorIndex(8)] };
+		let topology = topology.clone().with_fallback_routes(fallback_routes);
+
+		let canonical = topology.compute_grid_neighbors_for(ValidatorIndex(0)).unwrap();
+		let local = topology.compute_local_grid_neighbors(ValidatorIndex(0)).unwrap();
+
+		assert!(!canonical.peers_x.contains(&peer_of(4)));
+		assert!(local.peers_x.contains(&peer_of(4)));
+		assert!(local.peers_y.contains(&peer_of(8)));
+		assert_eq!(local.len(), canonical.len() + 2);
+		assert_eq!(local.validator_indices_x, canonical.validator_indices_x);
+		assert_eq!(local.validator_indices_y, canonical.validator_indices_y);
+	}
+
+	#[test]
+	fn update_of_the_current_session_replaces_topology() {
+		let mut storage = SessionBoundGridTopologyStorage::default();
+		let topology = identity_topology();
+		storage.update_topology(1, topology.clone(), Some(ValidatorIndex(0)));
+		storage.update_topology(2, topology.clone(), Some(ValidatorIndex(0)));
+
+		let fallback_routes = FallbackRoutes { row: vec![ValidatorIndex(4)], column: Vec::new() };
+		storage.update_topology(
+			2,
+			topology.with_fallback_routes(fallback_routes.clone()),
+			Some(ValidatorIndex(0)),
+		);
+
+		assert_eq!(storage.get_topology(2).unwrap().get().fallback_routes(), &fallback_routes);
+		assert!(storage.get_topology(1).unwrap().get().fallback_routes().is_empty());
+		assert_eq!(storage.get_current_topology().local_grid_neighbors().len(), 5);
+	}
+
 	#[test]
 	fn test_matrix_neighbors() {
 		for (our_index, len, expected_row, expected_column) in vec![
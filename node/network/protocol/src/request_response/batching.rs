@@ -0,0 +1,331 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Batching of chunk requests to the same validator.
+//!
+//! Subsystems fetching chunks collect the chunk requests of their tasks, and send all requests to
+//! the same validator that are ready at the same time as a single `ChunkFetchingVStaging` request.
+//! The responses are handed back to the original requests, so the tasks are unaware of the
+//! batching. Validators which don't support batched requests get `ChunkFetchingV1` requests.
+
+use std::{
+	collections::HashMap,
+	num::NonZeroUsize,
+	pin::Pin,
+	task::{Context, Poll},
+};
+
+use futures::{
+	future::BoxFuture,
+	stream::{FusedStream, FuturesUnordered},
+	FutureExt, Stream, StreamExt,
+};
+use lru::LruCache;
+use parity_scale_codec::Encode;
+
+use sc_network::{OutboundFailure, RequestFailure};
+
+use super::{
+	outgoing::RequestError, v1 as request_v1, vstaging as request_vstaging, OutgoingRequest,
+	OutgoingResult, Recipient, Requests,
+};
+
+const LOG_TARGET: &str = "parachain::chunk-batching";
+
+/// How many validators not supporting batched chunk requests we remember.
+const LEGACY_PEERS_SIZE: NonZeroUsize = match NonZeroUsize::new(1024) {
+	Some(cap) => cap,
+	None => panic!("Legacy peers cache size must be non-zero."),
+};
+
+/// A request for a single chunk.
+pub type ChunkRequest = OutgoingRequest<request_v1::ChunkFetchingRequest>;
+
+/// Batches chunk requests to the same validator.
+///
+/// As a stream, it yields the requests which need to be sent again as single chunk requests,
+/// because the validator didn't support the batched request.
+pub struct ChunkRequestBatcher {
+	/// Validators known not to support batched chunk requests.
+	legacy_peers: LruCache<Recipient, ()>,
+	/// Batched requests awaiting their response.
+	///
+	/// They resolve to the validator and the original requests, if the validator doesn't
+	/// support batched requests.
+	pending: FuturesUnordered<BoxFuture<'static, Option<(Recipient, Vec<ChunkRequest>)>>>,
+}
+
+impl ChunkRequestBatcher {
+	/// Create a new batcher.
+	pub fn new() -> Self {
+		Self { legacy_peers: LruCache::new(LEGACY_PEERS_SIZE), pending: FuturesUnordered::new() }
+	}
+
+	/// Turn chunk requests into the requests to send, batching requests to the same validator.
+	///
+	/// Requests are sent as single chunk requests if they are the only one to their validator.
+	pub fn batch(&mut self, requests: Vec<ChunkRequest>) -> Vec<Requests> {
+		// Keep the order of the requests, apart from the grouping.
+		let mut by_peer: Vec<(Recipient, Vec<ChunkRequest>)> = Vec::new();
+		let mut peer_indices = HashMap::new();
+		for request in requests {
+			let index = *peer_indices.entry(request.peer.clone()).or_insert_with(|| {
+				by_peer.push((request.peer.clone(), Vec::new()));
+				by_peer.len() - 1
+			});
+			by_peer[index].1.push(request);
+		}
+
+		let mut to_send = Vec::new();
+		for (peer, mut requests) in by_peer {
+			if self.legacy_peers.contains(&peer) {
+				to_send.extend(requests.into_iter().map(Requests::ChunkFetchingV1));
+				continue
+			}
+
+			while !requests.is_empty() {
+				let rest = requests
+					.split_off(requests.len().min(request_vstaging::MAX_CHUNKS_PER_REQUEST));
+				let batch = std::mem::replace(&mut requests, rest);
+				if batch.len() == 1 {
+					to_send.extend(batch.into_iter().map(Requests::ChunkFetchingV1));
+					continue
+				}
+
+				let payload = request_vstaging::ChunkFetchingRequest {
+					chunks: batch.iter().map(|request| request.payload).collect(),
+				};
+				let (request, response) = OutgoingRequest::new(peer.clone(), payload);
+				to_send.push(Requests::ChunkFetchingVStaging(request));
+
+				let peer = peer.clone();
+				self.pending.push(
+					async move {
+						let unsupported = dispatch_responses(batch, response.await)?;
+						Some((peer, unsupported))
+					}
+					.boxed(),
+				);
+			}
+		}
+
+		to_send
+	}
+}
+
+impl Default for ChunkRequestBatcher {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Stream for ChunkRequestBatcher {
+	type Item = Vec<Requests>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+		loop {
+			match futures::ready!(self.pending.poll_next_unpin(cx)) {
+				Some(Some((peer, requests))) => {
+					gum::debug!(
+						target: LOG_TARGET,
+						?peer,
+						"Validator doesn't support batched chunk requests",
+					);
+					self.legacy_peers.put(peer, ());
+					return Poll::Ready(Some(
+						requests.into_iter().map(Requests::ChunkFetchingV1).collect(),
+					))
+				},
+				Some(None) => continue,
+				None => return Poll::Ready(None),
+			}
+		}
+	}
+}
+
+impl FusedStream for ChunkRequestBatcher {
+	fn is_terminated(&self) -> bool {
+		self.pending.is_terminated()
+	}
+}
+
+/// Hand the response to a batched request to the original requests.
+///
+/// Returns the original requests if the validator doesn't support batched requests.
+fn dispatch_responses(
+	batch: Vec<ChunkRequest>,
+	response: OutgoingResult<request_vstaging::ChunkFetchingResponse>,
+) -> Option<Vec<ChunkRequest>> {
+	match response {
+		Ok(response) => {
+			// Requests without a response are dropped, which cancels them, so recovery tasks
+			// request those chunks again.
+			for (request, chunk) in batch.into_iter().zip(response.chunks) {
+				let _ = request.pending_response.send(Ok(chunk.encode()));
+			}
+			None
+		},
+		Err(RequestError::NetworkError(RequestFailure::Network(
+			OutboundFailure::UnsupportedProtocols,
+		))) => Some(batch),
+		Err(RequestError::NetworkError(failure)) => {
+			for request in batch {
+				let _ = request.pending_response.send(Err(copy_request_failure(&failure)));
+			}
+			None
+		},
+		Err(err) => {
+			// There is no network failure to forward, the requests are dropped, which cancels
+			// them.
+			gum::trace!(target: LOG_TARGET, ?err, "Batched chunk request failed");
+			None
+		},
+	}
+}
+
+/// `RequestFailure` is not `Clone`, but the failure of a batch is the failure of each request in
+/// it.
+fn copy_request_failure(failure: &RequestFailure) -> RequestFailure {
+	match failure {
+		RequestFailure::NotConnected => RequestFailure::NotConnected,
+		RequestFailure::UnknownProtocol => RequestFailure::UnknownProtocol,
+		RequestFailure::Refused => RequestFailure::Refused,
+		RequestFailure::Obsolete => RequestFailure::Obsolete,
+		RequestFailure::Network(failure) => RequestFailure::Network(failure.clone()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+	use futures::{executor, future};
+	use polkadot_primitives::{AuthorityDiscoveryId, CandidateHash, ValidatorIndex};
+	use sp_keyring::Sr25519Keyring;
+
+	fn recipient(keyring: Sr25519Keyring) -> Recipient {
+		let authority: AuthorityDiscoveryId = keyring.public().into();
+		Recipient::Authority(authority)
+	}
+
+	fn chunk_request(
+		keyring: Sr25519Keyring,
+		index: u32,
+	) -> (
+		ChunkRequest,
+		impl future::Future<Output = OutgoingResult<request_v1::ChunkFetchingResponse>>,
+	) {
+		OutgoingRequest::new(
+			recipient(keyring),
+			request_v1::ChunkFetchingRequest {
+				candidate_hash: CandidateHash::default(),
+				index: ValidatorIndex(index),
+			},
+		)
+	}
+
+	fn no_such_chunk_response(chunks: usize) -> Vec<u8> {
+		request_vstaging::ChunkFetchingResponse {
+			chunks: vec![request_v1::ChunkFetchingResponse::NoSuchChunk; chunks],
+		}
+		.encode()
+	}
+
+	#[test]
+	fn batches_requests_to_the_same_validator() {
+		let mut batcher = ChunkRequestBatcher::new();
+		let (alice_0, alice_0_response) = chunk_request(Sr25519Keyring::Alice, 0);
+		let (bob, _bob_response) = chunk_request(Sr25519Keyring::Bob, 1);
+		let (alice_2, alice_2_response) = chunk_request(Sr25519Keyring::Alice, 2);
+
+		let mut to_send = batcher.batch(vec![alice_0, bob, alice_2]);
+		assert_eq!(to_send.len(), 2);
+		assert_matches!(to_send.pop().unwrap(), Requests::ChunkFetchingV1(request) => {
+			assert_eq!(request.peer, recipient(Sr25519Keyring::Bob));
+		});
+		assert_matches!(to_send.pop().unwrap(), Requests::ChunkFetchingVStaging(request) => {
+			assert_eq!(request.peer, recipient(Sr25519Keyring::Alice));
+			let indices: Vec<_> = request.payload.chunks.iter().map(|chunk| chunk.index).collect();
+			assert_eq!(indices, vec![ValidatorIndex(0), ValidatorIndex(2)]);
+
+			// Only the first chunk is answered.
+			request.pending_response.send(Ok(no_such_chunk_response(1))).unwrap();
+		});
+
+		assert!(executor::block_on(batcher.next()).is_none());
+		assert_matches!(
+			executor::block_on(alice_0_response),
+			Ok(request_v1::ChunkFetchingResponse::NoSuchChunk)
+		);
+		assert_matches!(executor::block_on(alice_2_response), Err(RequestError::Canceled(_)));
+	}
+
+	#[test]
+	fn forwards_network_failures_to_every_request() {
+		let mut batcher = ChunkRequestBatcher::new();
+		let (alice_0, alice_0_response) = chunk_request(Sr25519Keyring::Alice, 0);
+		let (alice_1, alice_1_response) = chunk_request(Sr25519Keyring::Alice, 1);
+
+		let mut to_send = batcher.batch(vec![alice_0, alice_1]);
+		assert_matches!(to_send.pop().unwrap(), Requests::ChunkFetchingVStaging(request) => {
+			request.pending_response.send(Err(RequestFailure::Refused)).unwrap();
+		});
+
+		assert!(executor::block_on(batcher.next()).is_none());
+		for response in [alice_0_response, alice_1_response] {
+			assert_matches!(
+				executor::block_on(response),
+				Err(RequestError::NetworkError(RequestFailure::Refused))
+			);
+		}
+	}
+
+	#[test]
+	fn falls_back_to_single_requests() {
+		let mut batcher = ChunkRequestBatcher::new();
+		let (alice_0, alice_0_response) = chunk_request(Sr25519Keyring::Alice, 0);
+		let (alice_1, _alice_1_response) = chunk_request(Sr25519Keyring::Alice, 1);
+
+		let mut to_send = batcher.batch(vec![alice_0, alice_1]);
+		assert_matches!(to_send.pop().unwrap(), Requests::ChunkFetchingVStaging(request) => {
+			request
+				.pending_response
+				.send(Err(RequestFailure::Network(OutboundFailure::UnsupportedProtocols)))
+				.unwrap();
+		});
+
+		let mut fallback = executor::block_on(batcher.next()).unwrap();
+		assert_eq!(fallback.len(), 2);
+		assert_matches!(fallback.remove(0), Requests::ChunkFetchingV1(request) => {
+			assert_eq!(request.payload.index, ValidatorIndex(0));
+			request
+				.pending_response
+				.send(Ok(request_v1::ChunkFetchingResponse::NoSuchChunk.encode()))
+				.unwrap();
+		});
+		assert_matches!(
+			executor::block_on(alice_0_response),
+			Ok(request_v1::ChunkFetchingResponse::NoSuchChunk)
+		);
+
+		// The validator is not sent batched requests anymore.
+		let (alice_2, _) = chunk_request(Sr25519Keyring::Alice, 2);
+		let (alice_3, _) = chunk_request(Sr25519Keyring::Alice, 3);
+		let to_send = batcher.batch(vec![alice_2, alice_3]);
+		assert_eq!(to_send.len(), 2);
+		assert!(to_send.iter().all(|request| matches!(request, Requests::ChunkFetchingV1(_))));
+	}
+}
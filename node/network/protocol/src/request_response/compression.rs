@@ -0,0 +1,445 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Compressed responses for protocols carrying PoVs.
+//!
+//! Each of these protocols has a version with compressed responses, see
+//! [`Protocol::compressed_version`]. Its requests are a [`CompressedRequest`]: the request of the
+//! uncompressed version followed by a [`CompressionOffer`] listing the zstd dictionaries known to
+//! the requester, the one for the para of the request first. Its responses are a
+//! [`CompressedResponse`], compressed with an offered dictionary known to the responder or with
+//! plain zstd, unless that doesn't make them smaller.
+//!
+//! Requesters try the compressed version first and fall back to the uncompressed one for peers
+//! not supporting it, see [`Requests::into_compressed`](super::Requests::into_compressed).
+//! Responders serve both versions with the same [`IncomingRequestReceiver`], see
+//! [`IncomingRequest::get_config_receivers`](super::IncomingRequest::get_config_receivers), so
+//! subsystems are unaware of compression.
+//!
+//! [`IncomingRequestReceiver`]: super::IncomingRequestReceiver
+
+use std::sync::Arc;
+
+use parity_scale_codec::{Decode, Encode, Error as DecodingError};
+
+use polkadot_node_primitives::compression::{
+	self, PoVDictionaries, PoVDictionary, DEFAULT_COMPRESSION_LEVEL,
+};
+use polkadot_primitives::{Hash, Id as ParaId};
+use substrate_prometheus_endpoint::{
+	self as prometheus, CounterVec, Opts, PrometheusError, Registry, U64,
+};
+
+use super::{IsRequest, Protocol, POV_RESPONSE_SIZE};
+
+const LOG_TARGET: &str = "parachain::compression";
+
+/// The maximum number of dictionaries offered in a request, to stay well within the maximum
+/// request size.
+pub const MAX_OFFERED_DICTIONARIES: usize = 16;
+
+/// The dictionaries known to a requester, in order of preference.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode)]
+pub struct CompressionOffer {
+	/// The hashes of the dictionaries.
+	pub dictionaries: Vec<Hash>,
+}
+
+/// A request on the compressed version of a protocol.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct CompressedRequest<Req> {
+	/// The request of the uncompressed version of the protocol.
+	pub request: Req,
+	/// How the response may be compressed.
+	pub offer: CompressionOffer,
+}
+
+/// A response on the compressed version of a protocol.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum CompressedResponse {
+	/// The encoded response, as compressing it would not have made it smaller.
+	#[codec(index = 0)]
+	Plain(Vec<u8>),
+	/// The encoded response compressed with zstd.
+	#[codec(index = 1)]
+	Zstd {
+		/// The hash of the dictionary used, if any.
+		dictionary: Option<Hash>,
+		/// The compressed encoded response.
+		data: Vec<u8>,
+	},
+}
+
+/// Compression of responses carrying PoVs, with the dictionaries known to this node.
+///
+/// Cheap to clone, all clones share the same dictionaries and metrics.
+#[derive(Clone, Default)]
+pub struct PoVCompression {
+	dictionaries: Arc<PoVDictionaries>,
+	metrics: Metrics,
+}
+
+impl std::fmt::Debug for PoVCompression {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("PoVCompression")
+			.field("dictionaries", &self.dictionaries)
+			.finish()
+	}
+}
+
+impl PoVCompression {
+	/// Compress with `dictionaries`, reporting to `registry` if any.
+	pub fn new(
+		dictionaries: PoVDictionaries,
+		registry: Option<&Registry>,
+	) -> Result<Self, PrometheusError> {
+		let metrics = match registry {
+			Some(registry) => Metrics::register(registry)?,
+			None => Metrics::default(),
+		};
+		Ok(Self { dictionaries: Arc::new(dictionaries), metrics })
+	}
+
+	/// The offer to send with a request for data of `para`, if known.
+	pub fn offer(&self, para: Option<ParaId>) -> CompressionOffer {
+		let preferred = para
+			.and_then(|para| self.dictionaries.for_para(para, None))
+			.map(PoVDictionary::hash);
+		let dictionaries = preferred
+			.into_iter()
+			.chain(self.dictionaries.hashes().copied().filter(|hash| Some(*hash) != preferred))
+			.take(MAX_OFFERED_DICTIONARIES)
+			.collect();
+		CompressionOffer { dictionaries }
+	}
+
+	/// Compress `response` to a request of type `Req` according to `offer`.
+	///
+	/// The dictionary registered for the candidate in the response is used if offered, otherwise
+	/// the first offered dictionary known to this node.
+	pub fn compress_response<Req: IsRequest>(
+		&self,
+		offer: &CompressionOffer,
+		response: &Req::Response,
+	) -> CompressedResponse
+	where
+		Req::Response: Encode,
+	{
+		let encoded = response.encode();
+		let offered = |dictionary: &&PoVDictionary| offer.dictionaries.contains(&dictionary.hash());
+		let dictionary = Req::response_compression_hint(response)
+			.and_then(|(para, code_hash)| self.dictionaries.for_para(para, Some(code_hash)))
+			.filter(offered)
+			.or_else(|| offer.dictionaries.iter().find_map(|hash| self.dictionaries.get(hash)));
+
+		let data = match compression::compress(&encoded, DEFAULT_COMPRESSION_LEVEL, dictionary) {
+			Ok(data) => data,
+			Err(err) => {
+				gum::debug!(
+					target: LOG_TARGET,
+					?err,
+					protocol = ?Req::PROTOCOL,
+					"Failed to compress response",
+				);
+				return CompressedResponse::Plain(encoded)
+			},
+		};
+		if data.len() >= encoded.len() {
+			return CompressedResponse::Plain(encoded)
+		}
+
+		self.metrics.on_compressed(Req::PROTOCOL, "sent", encoded.len(), data.len());
+		CompressedResponse::Zstd { dictionary: dictionary.map(PoVDictionary::hash), data }
+	}
+
+	/// Decompress the encoded [`CompressedResponse`] `raw`, received on the compressed version of
+	/// `protocol`, to the encoded response of the uncompressed version.
+	pub fn decompress_response(
+		&self,
+		protocol: Protocol,
+		raw: &[u8],
+	) -> Result<Vec<u8>, DecodingError> {
+		let (dictionary, data) = match CompressedResponse::decode(&mut &raw[..])? {
+			CompressedResponse::Plain(encoded) => return Ok(encoded),
+			CompressedResponse::Zstd { dictionary, data } => (dictionary, data),
+		};
+		let dictionary = match dictionary {
+			Some(hash) => Some(
+				self.dictionaries
+					.get(&hash)
+					.ok_or("Response compressed with unknown dictionary")?,
+			),
+			None => None,
+		};
+		let decompressed = compression::decompress(&data, POV_RESPONSE_SIZE as usize, dictionary)
+			.map_err(|err| {
+			gum::debug!(target: LOG_TARGET, ?err, ?protocol, "Failed to decompress response");
+			DecodingError::from("Response could not be decompressed")
+		})?;
+
+		self.metrics.on_compressed(protocol, "received", decompressed.len(), data.len());
+		Ok(decompressed)
+	}
+}
+
+#[derive(Clone)]
+struct MetricsInner {
+	bytes_saved: CounterVec<U64>,
+	compressed_responses: CounterVec<U64>,
+}
+
+/// Compression metrics.
+#[derive(Clone, Default)]
+struct Metrics(Option<MetricsInner>);
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		let metrics = MetricsInner {
+			bytes_saved: prometheus::register(
+				CounterVec::new(
+					Opts::new(
+						"polkadot_parachain_pov_compression_bytes_saved_total",
+						"Number of bytes saved by compressing responses carrying PoVs",
+					),
+					&["protocol", "direction"],
+				)?,
+				registry,
+			)?,
+			compressed_responses: prometheus::register(
+				CounterVec::new(
+					Opts::new(
+						"polkadot_parachain_pov_compression_responses_total",
+						"Number of compressed responses carrying PoVs",
+					),
+					&["protocol", "direction"],
+				)?,
+				registry,
+			)?,
+		};
+		Ok(Metrics(Some(metrics)))
+	}
+
+	fn on_compressed(&self, protocol: Protocol, direction: &str, plain: usize, compressed: usize) {
+		if let Some(metrics) = &self.0 {
+			let labels = [protocol_label(protocol), direction];
+			metrics
+				.bytes_saved
+				.with_label_values(&labels)
+				.inc_by(plain.saturating_sub(compressed) as u64);
+			metrics.compressed_responses.with_label_values(&labels).inc();
+		}
+	}
+}
+
+fn protocol_label(protocol: Protocol) -> &'static str {
+	match protocol {
+		Protocol::CollationFetchingV1 => "collation_fetching_v1",
+		Protocol::CollationFetchingVStaging => "collation_fetching_vstaging",
+		Protocol::PoVFetchingV1 => "pov_fetching_v1",
+		Protocol::AvailableDataFetchingV1 => "available_data_fetching_v1",
+		_ => "other",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use assert_matches::assert_matches;
+	use futures::{channel::oneshot, executor, Future};
+	use sc_network::{config as netconfig, OutboundFailure, PeerId, RequestFailure};
+
+	use crate::request_response::{
+		v1::{PoVFetchingRequest, PoVFetchingResponse},
+		IncomingRequest, IncomingRequestReceiver, OutgoingRequest, OutgoingResult, Recipient,
+		ReqProtocolNames, Requests,
+	};
+	use polkadot_node_primitives::{BlockData, PoV};
+	use polkadot_primitives::CandidateHash;
+
+	fn response() -> PoVFetchingResponse {
+		PoVFetchingResponse::PoV(PoV { block_data: BlockData(b"parachain block".repeat(100)) })
+	}
+
+	fn dictionary(byte: u8) -> PoVDictionary {
+		PoVDictionary::new(vec![byte; 100])
+	}
+
+	#[test]
+	fn offers_are_deterministic_and_prefer_the_para() {
+		let mut dictionaries = PoVDictionaries::default();
+		for para in 1..=MAX_OFFERED_DICTIONARIES as u8 + 4 {
+			dictionaries.register_for_para(ParaId::from(para as u32), dictionary(para));
+		}
+		let compression = PoVCompression::new(dictionaries, None).unwrap();
+
+		let mut sorted = compression.offer(None).dictionaries;
+		assert_eq!(sorted.len(), MAX_OFFERED_DICTIONARIES);
+		sorted.sort();
+		assert_eq!(compression.offer(None).dictionaries, sorted);
+
+		let offer = compression.offer(Some(ParaId::from(20)));
+		assert_eq!(offer.dictionaries[0], dictionary(20).hash());
+		assert_eq!(offer.dictionaries.len(), MAX_OFFERED_DICTIONARIES);
+		assert_eq!(offer, compression.offer(Some(ParaId::from(20))));
+	}
+
+	#[test]
+	fn responses_are_compressed_with_an_offered_dictionary() {
+		let mut dictionaries = PoVDictionaries::default();
+		dictionaries.register_for_para(ParaId::from(1), PoVDictionary::new(response().encode()));
+		let responder = PoVCompression::new(dictionaries.clone(), None).unwrap();
+		let requester = PoVCompression::new(dictionaries, None).unwrap();
+
+		let offer = requester.offer(Some(ParaId::from(1)));
+		let compressed = responder.compress_response::<PoVFetchingRequest>(&offer, &response());
+		assert_matches!(&compressed, CompressedResponse::Zstd { dictionary: Some(_), .. });
+		assert_eq!(
+			requester
+				.decompress_response(Protocol::PoVFetchingV1, &compressed.encode())
+				.unwrap(),
+			response().encode(),
+		);
+
+		// A requester without the dictionary gets a response compressed without it.
+		let compressed = responder
+			.compress_response::<PoVFetchingRequest>(&CompressionOffer::default(), &response());
+		assert_matches!(&compressed, CompressedResponse::Zstd { dictionary: None, .. });
+		assert_eq!(
+			PoVCompression::default()
+				.decompress_response(Protocol::PoVFetchingV1, &compressed.encode())
+				.unwrap(),
+			response().encode(),
+		);
+	}
+
+	#[test]
+	fn small_responses_are_not_compressed() {
+		let compression = PoVCompression::default();
+		let compressed = compression.compress_response::<PoVFetchingRequest>(
+			&CompressionOffer::default(),
+			&PoVFetchingResponse::NoSuchPoV,
+		);
+		assert_eq!(compressed, CompressedResponse::Plain(PoVFetchingResponse::NoSuchPoV.encode()));
+		assert_eq!(
+			compression
+				.decompress_response(Protocol::PoVFetchingV1, &compressed.encode())
+				.unwrap(),
+			PoVFetchingResponse::NoSuchPoV.encode(),
+		);
+	}
+
+	/// A responder serving `PoVFetchingV1`, and its compressed version if `compression` is given.
+	struct Responder {
+		receiver: IncomingRequestReceiver<PoVFetchingRequest>,
+		inbound: Vec<async_channel::Sender<netconfig::IncomingRequest>>,
+	}
+
+	impl Responder {
+		fn new(compression: Option<&PoVCompression>) -> Self {
+			let names = ReqProtocolNames::new(&Hash::zero(), None);
+			let (receiver, cfgs) = IncomingRequest::get_config_receivers(&names, compression);
+			let inbound = cfgs.into_iter().map(|cfg| cfg.inbound_queue.unwrap()).collect();
+			Self { receiver, inbound }
+		}
+
+		/// Serve the request on `protocol` as a network would deliver it.
+		fn serve(&mut self, protocol: Protocol, request: Requests) {
+			let (actual, request) = request.encode_request();
+			assert_eq!(actual, protocol);
+			let inbound = match protocol {
+				Protocol::PoVFetchingV1 => &self.inbound[0],
+				Protocol::CompressedPoVFetchingV1 => &self.inbound[1],
+				_ => panic!("Unexpected protocol {:?}", protocol),
+			};
+			let (tx, rx) = oneshot::channel();
+			inbound
+				.try_send(netconfig::IncomingRequest {
+					peer: PeerId::random(),
+					payload: request.payload,
+					pending_response: tx,
+				})
+				.unwrap();
+
+			let incoming = executor::block_on(self.receiver.recv(|| vec![])).unwrap();
+			assert_eq!(incoming.payload.candidate_hash, CandidateHash(Hash::repeat_byte(1)));
+			incoming.send_response(response()).unwrap();
+
+			let response = executor::block_on(rx).unwrap().result.unwrap();
+			request.pending_response.send(Ok(response)).unwrap();
+		}
+	}
+
+	fn request() -> (Requests, impl Future<Output = OutgoingResult<PoVFetchingResponse>>) {
+		let (request, response) = OutgoingRequest::new(
+			Recipient::Peer(PeerId::random()),
+			PoVFetchingRequest { candidate_hash: CandidateHash(Hash::repeat_byte(1)) },
+		);
+		(Requests::PoVFetchingV1(request), response)
+	}
+
+	#[test]
+	fn compressed_request_response_roundtrip_works() {
+		let mut dictionaries = PoVDictionaries::default();
+		dictionaries.register_for_para(ParaId::from(1), PoVDictionary::new(response().encode()));
+		let compression = PoVCompression::new(dictionaries, None).unwrap();
+		let mut responder = Responder::new(Some(&compression));
+
+		let (request, response_fut) = request();
+		let (request, fallback) = request.into_compressed(&compression);
+		responder.serve(Protocol::CompressedPoVFetchingV1, request);
+
+		assert_matches!(executor::block_on(fallback.unwrap()), None);
+		assert_matches!(
+			executor::block_on(response_fut),
+			Ok(PoVFetchingResponse::PoV(pov)) if pov.block_data.0 == b"parachain block".repeat(100)
+		);
+	}
+
+	#[test]
+	fn requests_fall_back_to_peers_without_compression() {
+		let compression = PoVCompression::default();
+		let mut responder = Responder::new(None);
+
+		let (request, response_fut) = request();
+		let (request, fallback) = request.into_compressed(&compression);
+		let (protocol, request) = request.encode_request();
+		assert_eq!(protocol, Protocol::CompressedPoVFetchingV1);
+		request
+			.pending_response
+			.send(Err(RequestFailure::Network(OutboundFailure::UnsupportedProtocols)))
+			.unwrap();
+
+		let request = executor::block_on(fallback.unwrap()).unwrap();
+		responder.serve(Protocol::PoVFetchingV1, request);
+		assert_matches!(executor::block_on(response_fut), Ok(PoVFetchingResponse::PoV(_)));
+	}
+
+	#[test]
+	fn requests_not_carrying_povs_are_not_compressed() {
+		let (request, _) = OutgoingRequest::new(
+			Recipient::Peer(PeerId::random()),
+			crate::request_response::v1::ChunkFetchingRequest {
+				candidate_hash: CandidateHash(Hash::repeat_byte(1)),
+				index: 0.into(),
+			},
+		);
+		let (request, fallback) =
+			Requests::ChunkFetchingV1(request).into_compressed(&PoVCompression::default());
+		assert_eq!(request.get_protocol(), Protocol::ChunkFetchingV1);
+		assert!(fallback.is_none());
+	}
+}
@@ -16,13 +16,16 @@
 
 use std::marker::PhantomData;
 
-use futures::{channel::oneshot, StreamExt};
+use futures::{channel::oneshot, select, StreamExt};
 
 use parity_scale_codec::{Decode, Encode};
 
 use sc_network::{config as netconfig, config::RequestResponseConfig, PeerId};
 
-use super::{IsRequest, ReqProtocolNames};
+use super::{
+	compression::{CompressedRequest, CompressionOffer, PoVCompression},
+	IsRequest, ReqProtocolNames,
+};
 use crate::UnifiedReputationChange;
 
 mod error;
@@ -56,7 +59,28 @@ where
 		req_protocol_names: &ReqProtocolNames,
 	) -> (IncomingRequestReceiver<Req>, RequestResponseConfig) {
 		let (raw, cfg) = Req::PROTOCOL.get_config(req_protocol_names);
-		(IncomingRequestReceiver { raw, phantom: PhantomData {} }, cfg)
+		(IncomingRequestReceiver { raw, compressed: None, phantom: PhantomData {} }, cfg)
+	}
+
+	/// Like [`Self::get_config_receiver`], but if `compression` is given and the protocol has a
+	/// version with compressed responses, the returned receiver serves that version as well.
+	///
+	/// All returned configs need to be registered with substrate networking.
+	pub fn get_config_receivers(
+		req_protocol_names: &ReqProtocolNames,
+		compression: Option<&PoVCompression>,
+	) -> (IncomingRequestReceiver<Req>, Vec<RequestResponseConfig>) {
+		let (raw, cfg) = Req::PROTOCOL.get_config(req_protocol_names);
+		let mut cfgs = vec![cfg];
+		let compressed = match (compression, Req::PROTOCOL.compressed_version()) {
+			(Some(compression), Some(protocol)) => {
+				let (raw, cfg) = protocol.get_config(req_protocol_names);
+				cfgs.push(cfg);
+				Some((raw, compression.clone()))
+			},
+			_ => None,
+		};
+		(IncomingRequestReceiver { raw, compressed, phantom: PhantomData {} }, cfgs)
 	}
 
 	/// Create new `IncomingRequest`.
@@ -68,7 +92,11 @@ where
 		Self {
 			peer,
 			payload,
-			pending_response: OutgoingResponseSender { pending_response, phantom: PhantomData {} },
+			pending_response: OutgoingResponseSender {
+				pending_response,
+				compression: None,
+				phantom: PhantomData {},
+			},
 		}
 	}
 
@@ -80,13 +108,20 @@ where
 	/// Params:
 	/// 		- The raw request to decode
 	/// 		- Reputation changes to apply for the peer in case decoding fails.
+	/// 		- The compression of the response, if received on the compressed protocol version.
 	fn try_from_raw(
 		raw: sc_network::config::IncomingRequest,
 		reputation_changes: Vec<UnifiedReputationChange>,
+		compression: Option<PoVCompression>,
 	) -> std::result::Result<Self, JfyiError> {
 		let sc_network::config::IncomingRequest { payload, peer, pending_response } = raw;
-		let payload = match Req::decode(&mut payload.as_ref()) {
-			Ok(payload) => payload,
+		let decoded = match compression {
+			Some(compression) => CompressedRequest::<Req>::decode(&mut payload.as_ref())
+				.map(|CompressedRequest { request, offer }| (request, Some((compression, offer)))),
+			None => Req::decode(&mut payload.as_ref()).map(|request| (request, None)),
+		};
+		let (payload, compression) = match decoded {
+			Ok(decoded) => decoded,
 			Err(err) => {
 				let reputation_changes = reputation_changes.into_iter().map(|r| r.into()).collect();
 				let response = sc_network::config::OutgoingResponse {
@@ -101,16 +136,22 @@ where
 				return Err(JfyiError::DecodingError(peer, err))
 			},
 		};
-		Ok(Self::new(peer, payload, pending_response))
+		let mut request = Self::new(peer, payload, pending_response);
+		request.pending_response.compression = compression;
+		Ok(request)
 	}
 
 	/// Convert into raw untyped substrate `IncomingRequest`.
 	///
 	/// This is mostly useful for testing.
 	pub fn into_raw(self) -> sc_network::config::IncomingRequest {
+		let payload = match self.pending_response.compression {
+			Some((_, offer)) => CompressedRequest { request: self.payload, offer }.encode(),
+			None => self.payload.encode(),
+		};
 		sc_network::config::IncomingRequest {
 			peer: self.peer,
-			payload: self.payload.encode(),
+			payload,
 			pending_response: self.pending_response.pending_response,
 		}
 	}
@@ -137,6 +178,9 @@ where
 #[derive(Debug)]
 pub struct OutgoingResponseSender<Req> {
 	pending_response: oneshot::Sender<netconfig::OutgoingResponse>,
+	/// How to compress the response, if the request was received on the version of the protocol
+	/// with compressed responses.
+	compression: Option<(PoVCompression, CompressionOffer)>,
 	phantom: PhantomData<Req>,
 }
 
@@ -152,9 +196,10 @@ where
 	/// `netconfig::OutgoingResponse` exposes a way of modifying the peer's reputation. If needed we
 	/// can change this function to expose this feature as well.
 	pub fn send_response(self, resp: Req::Response) -> std::result::Result<(), Req::Response> {
+		let encoded = Self::encode_response(&self.compression, &resp);
 		self.pending_response
 			.send(netconfig::OutgoingResponse {
-				result: Ok(resp.encode()),
+				result: Ok(encoded),
 				reputation_changes: Vec::new(),
 				sent_feedback: None,
 			})
@@ -173,13 +218,25 @@ where
 		let OutgoingResponse { result, reputation_changes, sent_feedback } = resp;
 
 		let response = netconfig::OutgoingResponse {
-			result: result.map(|v| v.encode()),
+			result: result.map(|v| Self::encode_response(&self.compression, &v)),
 			reputation_changes: reputation_changes.into_iter().map(|c| c.into()).collect(),
 			sent_feedback,
 		};
 
 		self.pending_response.send(response).map_err(|_| ())
 	}
+
+	/// Encode `resp`, compressed if the request was received on the compressed protocol version.
+	fn encode_response(
+		compression: &Option<(PoVCompression, CompressionOffer)>,
+		resp: &Req::Response,
+	) -> Vec<u8> {
+		match compression {
+			Some((compression, offer)) =>
+				compression.compress_response::<Req>(offer, resp).encode(),
+			None => resp.encode(),
+		}
+	}
 }
 
 /// Typed variant of [`netconfig::OutgoingResponse`].
@@ -205,6 +262,8 @@ pub struct OutgoingResponse<Response> {
 /// Takes care of decoding and handling of invalid encoded requests.
 pub struct IncomingRequestReceiver<Req> {
 	raw: async_channel::Receiver<netconfig::IncomingRequest>,
+	/// Requests received on the version of the protocol with compressed responses, if served.
+	compressed: Option<(async_channel::Receiver<netconfig::IncomingRequest>, PoVCompression)>,
 	phantom: PhantomData<Req>,
 }
 
@@ -221,9 +280,17 @@ where
 	where
 		F: FnOnce() -> Vec<UnifiedReputationChange>,
 	{
-		let req = match self.raw.next().await {
+		let (raw, compression) = match &mut self.compressed {
+			Some((compressed, compression)) => select! {
+				raw = self.raw.next() => (raw, None),
+				raw = compressed.next() => (raw, Some(compression.clone())),
+			},
+			None => (self.raw.next().await, None),
+		};
+		let req = match raw {
 			None => return Err(FatalError::RequestChannelExhausted.into()),
-			Some(raw) => IncomingRequest::<Req>::try_from_raw(raw, reputation_changes())?,
+			Some(raw) =>
+				IncomingRequest::<Req>::try_from_raw(raw, reputation_changes(), compression)?,
 		};
 		Ok(req)
 	}
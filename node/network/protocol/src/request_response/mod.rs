@@ -34,11 +34,17 @@
 
 use std::{collections::HashMap, time::Duration, u64};
 
-use polkadot_primitives::{MAX_CODE_SIZE, MAX_POV_SIZE};
+use polkadot_primitives::{
+	Id as ParaId, ValidationCodeHash, MAX_CODE_SIZE, MAX_HEAD_DATA_SIZE, MAX_POV_SIZE,
+};
 use strum::{EnumIter, IntoEnumIterator};
 
 pub use sc_network::{config as network, config::RequestResponseConfig, ProtocolName};
 
+/// Batching of chunk requests to the same validator.
+pub mod batching;
+/// Negotiation of compressed responses carrying PoVs.
+pub mod compression;
 /// Everything related to handling of incoming requests.
 pub mod incoming;
 /// Everything related to handling of outgoing requests.
@@ -63,6 +69,8 @@ pub mod vstaging;
 pub enum Protocol {
 	/// Protocol for chunk fetching, used by availability distribution and availability recovery.
 	ChunkFetchingV1,
+	/// Protocol for fetching many chunks with a single request, used by availability recovery.
+	ChunkFetchingVStaging,
 	/// Protocol for fetching collations from collators.
 	CollationFetchingV1,
 	/// Protocol for fetching collations from collators when async backing is enabled.
@@ -79,6 +87,19 @@ pub enum Protocol {
 	/// Protocol for requesting candidates with attestations in statement distribution
 	/// when async backing is enabled.
 	AttestedCandidateVStaging,
+
+	/// Protocol for fetching the head of a parachain at a relay chain block, together with a
+	/// proof of it. Served by full nodes, mostly to light clients.
+	ParaHeadProofV1,
+
+	/// `CollationFetchingV1` with compressed responses, see [`compression`].
+	CompressedCollationFetchingV1,
+	/// `CollationFetchingVStaging` with compressed responses, see [`compression`].
+	CompressedCollationFetchingVStaging,
+	/// `PoVFetchingV1` with compressed responses, see [`compression`].
+	CompressedPoVFetchingV1,
+	/// `AvailableDataFetchingV1` with compressed responses, see [`compression`].
+	CompressedAvailableDataFetchingV1,
 }
 
 /// Minimum bandwidth we expect for validators - 500Mbit/s is the recommendation, so approximately
@@ -90,7 +111,6 @@ const MIN_BANDWIDTH_BYTES: u64 = 50 * 1024 * 1024;
 /// When decreasing this value, take into account that the very first request might need to open a
 /// connection, which can be slow. If this causes problems, we should ensure connectivity via peer
 /// sets.
-#[allow(dead_code)]
 const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
 
 /// Request timeout where we can assume the connection is already open (e.g. we have peers in a
@@ -142,6 +162,11 @@ pub const MAX_PARALLEL_ATTESTED_CANDIDATE_REQUESTS: u32 = 5;
 /// limit might have more severe effects.
 const POV_RESPONSE_SIZE: u64 = MAX_POV_SIZE as u64 + 10_000;
 
+/// Maximum response size for `ChunkFetchingV1` and `ChunkFetchingVStaging`.
+///
+/// Responders to `ChunkFetchingVStaging` only answer as many of the requested chunks as fit.
+pub const CHUNK_RESPONSE_SIZE: u64 = POV_RESPONSE_SIZE * 3;
+
 /// Maximum response sizes for `StatementFetchingV1`.
 ///
 /// This is `MAX_CODE_SIZE` plus some additional space for protocol overhead.
@@ -153,6 +178,12 @@ const STATEMENT_RESPONSE_SIZE: u64 = MAX_CODE_SIZE as u64 + 10_000;
 /// additional backing statements.
 const ATTESTED_CANDIDATE_RESPONSE_SIZE: u64 = MAX_CODE_SIZE as u64 + 100_000;
 
+/// Maximum response size for `ParaHeadProofV1`.
+///
+/// The head data is part of the storage proof, the remaining trie nodes, the relay chain header
+/// and the justification fit comfortably into another megabyte.
+const PARA_HEAD_PROOF_RESPONSE_SIZE: u64 = MAX_HEAD_DATA_SIZE as u64 + 1024 * 1024;
+
 /// We can have relative large timeouts here, there is no value of hitting a
 /// timeout as we want to get statements through to each node in any case.
 pub const DISPUTE_REQUEST_TIMEOUT: Duration = Duration::from_secs(12);
@@ -194,38 +225,51 @@ impl Protocol {
 				name,
 				fallback_names,
 				max_request_size: 1_000,
-				max_response_size: POV_RESPONSE_SIZE as u64 * 3,
+				max_response_size: CHUNK_RESPONSE_SIZE,
 				// We are connected to all validators:
 				request_timeout: CHUNK_REQUEST_TIMEOUT,
 				inbound_queue: tx,
 			},
-			Protocol::CollationFetchingV1 | Protocol::CollationFetchingVStaging =>
-				RequestResponseConfig {
-					name,
-					fallback_names,
-					max_request_size: 1_000,
-					max_response_size: POV_RESPONSE_SIZE,
-					// Taken from initial implementation in collator protocol:
-					request_timeout: POV_REQUEST_TIMEOUT_CONNECTED,
-					inbound_queue: tx,
-				},
-			Protocol::PoVFetchingV1 => RequestResponseConfig {
+			Protocol::ChunkFetchingVStaging => RequestResponseConfig {
+				name,
+				fallback_names,
+				max_request_size: 10_000,
+				// Responders only answer as many chunks as fit, so the limit and the timeout can
+				// stay the same as for a single chunk.
+				max_response_size: CHUNK_RESPONSE_SIZE,
+				request_timeout: CHUNK_REQUEST_TIMEOUT,
+				inbound_queue: tx,
+			},
+			Protocol::CollationFetchingV1 |
+			Protocol::CollationFetchingVStaging |
+			Protocol::CompressedCollationFetchingV1 |
+			Protocol::CompressedCollationFetchingVStaging => RequestResponseConfig {
 				name,
 				fallback_names,
 				max_request_size: 1_000,
 				max_response_size: POV_RESPONSE_SIZE,
+				// Taken from initial implementation in collator protocol:
 				request_timeout: POV_REQUEST_TIMEOUT_CONNECTED,
 				inbound_queue: tx,
 			},
-			Protocol::AvailableDataFetchingV1 => RequestResponseConfig {
+			Protocol::PoVFetchingV1 | Protocol::CompressedPoVFetchingV1 => RequestResponseConfig {
 				name,
 				fallback_names,
 				max_request_size: 1_000,
-				// Available data size is dominated by the PoV size.
 				max_response_size: POV_RESPONSE_SIZE,
 				request_timeout: POV_REQUEST_TIMEOUT_CONNECTED,
 				inbound_queue: tx,
 			},
+			Protocol::AvailableDataFetchingV1 | Protocol::CompressedAvailableDataFetchingV1 =>
+				RequestResponseConfig {
+					name,
+					fallback_names,
+					max_request_size: 1_000,
+					// Available data size is dominated by the PoV size.
+					max_response_size: POV_RESPONSE_SIZE,
+					request_timeout: POV_REQUEST_TIMEOUT_CONNECTED,
+					inbound_queue: tx,
+				},
 			Protocol::StatementFetchingV1 => RequestResponseConfig {
 				name,
 				fallback_names,
@@ -262,6 +306,15 @@ impl Protocol {
 				request_timeout: ATTESTED_CANDIDATE_TIMEOUT,
 				inbound_queue: tx,
 			},
+			Protocol::ParaHeadProofV1 => RequestResponseConfig {
+				name,
+				fallback_names,
+				max_request_size: 1_000,
+				max_response_size: PARA_HEAD_PROOF_RESPONSE_SIZE,
+				// Requesters are usually not connected to us via any peer set.
+				request_timeout: DEFAULT_REQUEST_TIMEOUT,
+				inbound_queue: tx,
+			},
 		}
 	}
 
@@ -274,13 +327,19 @@ impl Protocol {
 			// assuming we can service requests relatively quickly, which would need to be measured
 			// as well.
 			Protocol::ChunkFetchingV1 => 100,
+			// Batches are sent during availability recovery of many candidates at once, which
+			// is as bursty as single chunk requests.
+			Protocol::ChunkFetchingVStaging => 100,
 			// 10 seems reasonable, considering group sizes of max 10 validators.
-			Protocol::CollationFetchingV1 | Protocol::CollationFetchingVStaging => 10,
+			Protocol::CollationFetchingV1 |
+			Protocol::CollationFetchingVStaging |
+			Protocol::CompressedCollationFetchingV1 |
+			Protocol::CompressedCollationFetchingVStaging => 10,
 			// 10 seems reasonable, considering group sizes of max 10 validators.
-			Protocol::PoVFetchingV1 => 10,
+			Protocol::PoVFetchingV1 | Protocol::CompressedPoVFetchingV1 => 10,
 			// Validators are constantly self-selecting to request available data which may lead
 			// to constant load and occasional burstiness.
-			Protocol::AvailableDataFetchingV1 => 100,
+			Protocol::AvailableDataFetchingV1 | Protocol::CompressedAvailableDataFetchingV1 => 100,
 			// Our queue size approximation is how many blocks of the size of
 			// a runtime we can transfer within a statements timeout, minus the requests we handle
 			// in parallel.
@@ -325,6 +384,30 @@ impl Protocol {
 				);
 				size as usize
 			},
+			// Requests come from untrusted light clients and are cheap to answer, a small queue
+			// makes us reject floods early instead of building up a backlog.
+			Protocol::ParaHeadProofV1 => 20,
+		}
+	}
+
+	/// The version of this protocol with compressed responses, if any, see [`compression`].
+	pub const fn compressed_version(self) -> Option<Protocol> {
+		match self {
+			Protocol::CollationFetchingV1 => Some(Protocol::CompressedCollationFetchingV1),
+			Protocol::CollationFetchingVStaging =>
+				Some(Protocol::CompressedCollationFetchingVStaging),
+			Protocol::PoVFetchingV1 => Some(Protocol::CompressedPoVFetchingV1),
+			Protocol::AvailableDataFetchingV1 => Some(Protocol::CompressedAvailableDataFetchingV1),
+			Protocol::ChunkFetchingV1 |
+			Protocol::ChunkFetchingVStaging |
+			Protocol::StatementFetchingV1 |
+			Protocol::DisputeSendingV1 |
+			Protocol::AttestedCandidateVStaging |
+			Protocol::ParaHeadProofV1 |
+			Protocol::CompressedCollationFetchingV1 |
+			Protocol::CompressedCollationFetchingVStaging |
+			Protocol::CompressedPoVFetchingV1 |
+			Protocol::CompressedAvailableDataFetchingV1 => None,
 		}
 	}
 
@@ -344,8 +427,14 @@ impl Protocol {
 			Protocol::DisputeSendingV1 => Some("/polkadot/send_dispute/1"),
 
 			// Introduced after legacy names became legacy.
+			Protocol::ChunkFetchingVStaging => None,
 			Protocol::AttestedCandidateVStaging => None,
 			Protocol::CollationFetchingVStaging => None,
+			Protocol::ParaHeadProofV1 => None,
+			Protocol::CompressedCollationFetchingV1 => None,
+			Protocol::CompressedCollationFetchingVStaging => None,
+			Protocol::CompressedPoVFetchingV1 => None,
+			Protocol::CompressedAvailableDataFetchingV1 => None,
 		}
 	}
 }
@@ -357,21 +446,39 @@ pub trait IsRequest {
 
 	/// What protocol this `Request` implements.
 	const PROTOCOL: Protocol;
+
+	/// The para whose data is requested, if known, to prefer its dictionary for compression.
+	fn compression_hint(&self) -> Option<ParaId> {
+		None
+	}
+
+	/// The para and validation code hash of the candidate in `response`, if any, to prefer their
+	/// dictionary for compression.
+	fn response_compression_hint(
+		_response: &Self::Response,
+	) -> Option<(ParaId, ValidationCodeHash)> {
+		None
+	}
 }
 
 /// Type for getting on the wire [`Protocol`] names using genesis hash & fork id.
+#[derive(Clone)]
 pub struct ReqProtocolNames {
 	names: HashMap<Protocol, ProtocolName>,
+	protocols: HashMap<ProtocolName, Protocol>,
 }
 
 impl ReqProtocolNames {
 	/// Construct [`ReqProtocolNames`] from `genesis_hash` and `fork_id`.
 	pub fn new<Hash: AsRef<[u8]>>(genesis_hash: Hash, fork_id: Option<&str>) -> Self {
 		let mut names = HashMap::new();
+		let mut protocols = HashMap::new();
 		for protocol in Protocol::iter() {
-			names.insert(protocol, Self::generate_name(protocol, &genesis_hash, fork_id));
+			let name = Self::generate_name(protocol, &genesis_hash, fork_id);
+			protocols.insert(name.clone(), protocol);
+			names.insert(protocol, name);
 		}
-		Self { names }
+		Self { names, protocols }
 	}
 
 	/// Get on the wire [`Protocol`] name.
@@ -382,6 +489,11 @@ impl ReqProtocolNames {
 			.clone()
 	}
 
+	/// Lookup the [`Protocol`] using its on the wire name.
+	pub fn try_get_protocol(&self, name: &ProtocolName) -> Option<Protocol> {
+		self.protocols.get(name).copied()
+	}
+
 	/// Protocol name of this protocol based on `genesis_hash` and `fork_id`.
 	fn generate_name<Hash: AsRef<[u8]>>(
 		protocol: Protocol,
@@ -402,8 +514,14 @@ impl ReqProtocolNames {
 			Protocol::StatementFetchingV1 => "/req_statement/1",
 			Protocol::DisputeSendingV1 => "/send_dispute/1",
 
+			Protocol::ChunkFetchingVStaging => "/req_chunk/2",
 			Protocol::CollationFetchingVStaging => "/req_collation/2",
 			Protocol::AttestedCandidateVStaging => "/req_attested_candidate/2",
+			Protocol::ParaHeadProofV1 => "/req_para_head_proof/1",
+			Protocol::CompressedCollationFetchingV1 => "/req_collation_compressed/1",
+			Protocol::CompressedCollationFetchingVStaging => "/req_collation_compressed/2",
+			Protocol::CompressedPoVFetchingV1 => "/req_pov_compressed/1",
+			Protocol::CompressedAvailableDataFetchingV1 => "/req_available_data_compressed/1",
 		};
 
 		format!("{}{}", prefix, short_name).into()
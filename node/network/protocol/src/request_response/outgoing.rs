@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use futures::{channel::oneshot, prelude::Future};
+use futures::{channel::oneshot, future::BoxFuture, prelude::Future, FutureExt};
 
 use parity_scale_codec::{Decode, Encode, Error as DecodingError};
 
@@ -23,7 +23,7 @@ use sc_network::PeerId;
 
 use polkadot_primitives::AuthorityDiscoveryId;
 
-use super::{v1, vstaging, IsRequest, Protocol};
+use super::{compression::PoVCompression, v1, vstaging, IsRequest, Protocol};
 
 /// All requests that can be sent to the network bridge via `NetworkBridgeTxMessage::SendRequest`.
 #[derive(Debug)]
@@ -41,11 +41,17 @@ pub enum Requests {
 	/// Requests for notifying about an ongoing dispute.
 	DisputeSendingV1(OutgoingRequest<v1::DisputeRequest>),
 
+	/// Request many availability chunks from a node at once.
+	ChunkFetchingVStaging(OutgoingRequest<vstaging::ChunkFetchingRequest>),
 	/// Request a candidate and attestations.
 	AttestedCandidateVStaging(OutgoingRequest<vstaging::AttestedCandidateRequest>),
 	/// Fetch a collation from a collator which previously announced it.
 	/// Compared to V1 it requires specifying which candidate is requested by its hash.
 	CollationFetchingVStaging(OutgoingRequest<vstaging::CollationFetchingRequest>),
+
+	/// A request already encoded for the version of its protocol with compressed responses, see
+	/// [`Requests::into_compressed`].
+	Compressed(Protocol, OutgoingRequest<Vec<u8>>),
 }
 
 impl Requests {
@@ -53,6 +59,7 @@ impl Requests {
 	pub fn get_protocol(&self) -> Protocol {
 		match self {
 			Self::ChunkFetchingV1(_) => Protocol::ChunkFetchingV1,
+			Self::ChunkFetchingVStaging(_) => Protocol::ChunkFetchingVStaging,
 			Self::CollationFetchingV1(_) => Protocol::CollationFetchingV1,
 			Self::CollationFetchingVStaging(_) => Protocol::CollationFetchingVStaging,
 			Self::PoVFetchingV1(_) => Protocol::PoVFetchingV1,
@@ -60,6 +67,7 @@ impl Requests {
 			Self::StatementFetchingV1(_) => Protocol::StatementFetchingV1,
 			Self::DisputeSendingV1(_) => Protocol::DisputeSendingV1,
 			Self::AttestedCandidateVStaging(_) => Protocol::AttestedCandidateVStaging,
+			Self::Compressed(protocol, _) => *protocol,
 		}
 	}
 
@@ -73,6 +81,7 @@ impl Requests {
 	pub fn encode_request(self) -> (Protocol, OutgoingRequest<Vec<u8>>) {
 		match self {
 			Self::ChunkFetchingV1(r) => r.encode_request(),
+			Self::ChunkFetchingVStaging(r) => r.encode_request(),
 			Self::CollationFetchingV1(r) => r.encode_request(),
 			Self::CollationFetchingVStaging(r) => r.encode_request(),
 			Self::PoVFetchingV1(r) => r.encode_request(),
@@ -80,8 +89,79 @@ impl Requests {
 			Self::StatementFetchingV1(r) => r.encode_request(),
 			Self::DisputeSendingV1(r) => r.encode_request(),
 			Self::AttestedCandidateVStaging(r) => r.encode_request(),
+			Self::Compressed(protocol, r) => (protocol, r),
 		}
 	}
+
+	/// Turn the request into one for the version of its protocol with compressed responses, if
+	/// there is one.
+	///
+	/// Returns the request to send instead, together with a future handing the decompressed
+	/// response to the original requester. If the peer doesn't support the compressed version,
+	/// the future resolves to the original request, to be sent again uncompressed.
+	pub fn into_compressed(
+		self,
+		compression: &PoVCompression,
+	) -> (Requests, Option<BoxFuture<'static, Option<Requests>>>) {
+		match self {
+			Self::CollationFetchingV1(r) =>
+				compressed(r, compression, Requests::CollationFetchingV1),
+			Self::CollationFetchingVStaging(r) =>
+				compressed(r, compression, Requests::CollationFetchingVStaging),
+			Self::PoVFetchingV1(r) => compressed(r, compression, Requests::PoVFetchingV1),
+			Self::AvailableDataFetchingV1(r) =>
+				compressed(r, compression, Requests::AvailableDataFetchingV1),
+			Self::ChunkFetchingV1(_) |
+			Self::ChunkFetchingVStaging(_) |
+			Self::StatementFetchingV1(_) |
+			Self::DisputeSendingV1(_) |
+			Self::AttestedCandidateVStaging(_) |
+			Self::Compressed(_, _) => (self, None),
+		}
+	}
+}
+
+/// See [`Requests::into_compressed`].
+fn compressed<Req>(
+	request: OutgoingRequest<Req>,
+	compression: &PoVCompression,
+	uncompressed: fn(OutgoingRequest<Req>) -> Requests,
+) -> (Requests, Option<BoxFuture<'static, Option<Requests>>>)
+where
+	Req: IsRequest + Encode + Send + 'static,
+{
+	let protocol = match Req::PROTOCOL.compressed_version() {
+		Some(protocol) => protocol,
+		None => return (uncompressed(request), None),
+	};
+	// Encoded as a `CompressedRequest`.
+	let mut payload = request.payload.encode();
+	compression.offer(request.payload.compression_hint()).encode_to(&mut payload);
+	let (tx, rx) = oneshot::channel();
+	let compressed = OutgoingRequest { peer: request.peer.clone(), payload, pending_response: tx };
+
+	let compression = compression.clone();
+	let fallback = async move {
+		let response = match rx.await {
+			Ok(Ok(raw)) =>
+				Ok(compression.decompress_response(protocol, &raw).unwrap_or_else(|_| {
+					// Fails to decode, so the requester treats it like any other invalid
+					// response.
+					Vec::new()
+				})),
+			Ok(Err(network::RequestFailure::Network(
+				network::OutboundFailure::UnsupportedProtocols,
+			))) => return Some(uncompressed(request)),
+			Ok(Err(err)) => Err(err),
+			// Dropping the original sender cancels the request for the requester as well.
+			Err(oneshot::Canceled) => return None,
+		};
+		let _ = request.pending_response.send(response);
+		None
+	}
+	.boxed();
+
+	(Requests::Compressed(protocol, compressed), Some(fallback))
 }
 
 /// Used by the network to send us a response to a request.
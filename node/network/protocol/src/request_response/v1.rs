@@ -21,8 +21,10 @@ use parity_scale_codec::{Decode, Encode};
 use polkadot_node_primitives::{
 	AvailableData, DisputeMessage, ErasureChunk, PoV, Proof, UncheckedDisputeMessage,
 };
+use polkadot_para_head_proof_verifier::ParaHeadProof;
 use polkadot_primitives::{
-	CandidateHash, CandidateReceipt, CommittedCandidateReceipt, Hash, Id as ParaId, ValidatorIndex,
+	CandidateHash, CandidateReceipt, CommittedCandidateReceipt, Hash, Id as ParaId,
+	ValidationCodeHash, ValidatorIndex,
 };
 
 use super::{IsRequest, Protocol};
@@ -108,6 +110,17 @@ pub enum CollationFetchingResponse {
 impl IsRequest for CollationFetchingRequest {
 	type Response = CollationFetchingResponse;
 	const PROTOCOL: Protocol = Protocol::CollationFetchingV1;
+
+	fn compression_hint(&self) -> Option<ParaId> {
+		Some(self.para_id)
+	}
+
+	fn response_compression_hint(
+		response: &Self::Response,
+	) -> Option<(ParaId, ValidationCodeHash)> {
+		let CollationFetchingResponse::Collation(receipt, _) = response;
+		Some((receipt.descriptor.para_id, receipt.descriptor.validation_code_hash))
+	}
 }
 
 /// Request the advertised collation at that relay-parent.
@@ -215,3 +228,36 @@ impl IsRequest for DisputeRequest {
 	type Response = DisputeResponse;
 	const PROTOCOL: Protocol = Protocol::DisputeSendingV1;
 }
+
+/// Request the head of a parachain at a relay chain block, along with a proof of it.
+#[derive(Debug, Copy, Clone, Encode, Decode)]
+pub struct ParaHeadProofRequest {
+	/// The parachain whose head is requested.
+	pub para_id: ParaId,
+	/// The relay chain block to read the head at.
+	pub relay_block_hash: Hash,
+	/// Whether to include the GRANDPA justification finalizing the relay chain block.
+	pub with_justification: bool,
+}
+
+/// Responses to `ParaHeadProofRequest`.
+#[derive(Debug, Clone, Encode, Decode)]
+pub enum ParaHeadProofResponse {
+	/// The proof of the parachain head.
+	///
+	/// The justification is missing if it was not requested, or if the block is finalized but
+	/// its justification was not stored.
+	#[codec(index = 0)]
+	HeadProof(ParaHeadProof),
+	/// The relay chain block is not known to the responder.
+	#[codec(index = 1)]
+	UnknownBlock,
+	/// The relay chain block is not finalized yet.
+	#[codec(index = 2)]
+	NotFinalized,
+}
+
+impl IsRequest for ParaHeadProofRequest {
+	type Response = ParaHeadProofResponse;
+	const PROTOCOL: Protocol = Protocol::ParaHeadProofV1;
+}
@@ -20,12 +20,40 @@ use parity_scale_codec::{Decode, Encode};
 
 use polkadot_primitives::vstaging::{
 	CandidateHash, CommittedCandidateReceipt, Hash, Id as ParaId, PersistedValidationData,
-	UncheckedSignedStatement,
+	UncheckedSignedStatement, ValidationCodeHash,
 };
 
-use super::{IsRequest, Protocol};
+use super::{v1, IsRequest, Protocol};
 use crate::vstaging::StatementFilter;
 
+/// The maximum number of chunks requested by a single [`ChunkFetchingRequest`].
+pub const MAX_CHUNKS_PER_REQUEST: usize = 64;
+
+/// Request many availability chunks at once.
+///
+/// At most [`MAX_CHUNKS_PER_REQUEST`] chunks are answered.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct ChunkFetchingRequest {
+	/// The requested chunks.
+	pub chunks: Vec<v1::ChunkFetchingRequest>,
+}
+
+/// Receive the requested erasure chunks.
+///
+/// There is a response for a prefix of the requested chunks, in the same order. It is shorter
+/// than the request if the remaining chunks didn't fit into the response size limit, in which
+/// case they need to be requested again.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct ChunkFetchingResponse {
+	/// A response for each of the answered chunks.
+	pub chunks: Vec<v1::ChunkFetchingResponse>,
+}
+
+impl IsRequest for ChunkFetchingRequest {
+	type Response = ChunkFetchingResponse;
+	const PROTOCOL: Protocol = Protocol::ChunkFetchingVStaging;
+}
+
 /// Request a candidate with statements.
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct AttestedCandidateRequest {
@@ -77,4 +105,15 @@ impl IsRequest for CollationFetchingRequest {
 	// The response is the same as for V1.
 	type Response = CollationFetchingResponse;
 	const PROTOCOL: Protocol = Protocol::CollationFetchingVStaging;
+
+	fn compression_hint(&self) -> Option<ParaId> {
+		Some(self.para_id)
+	}
+
+	fn response_compression_hint(
+		response: &Self::Response,
+	) -> Option<(ParaId, ValidationCodeHash)> {
+		let super::v1::CollationFetchingResponse::Collation(receipt, _) = response;
+		Some((receipt.descriptor.para_id, receipt.descriptor.validation_code_hash))
+	}
 }
@@ -867,6 +867,7 @@ fn test_network_bridge_rx_msg() -> NetworkBridgeRxMessage {
 		local_index: None,
 		canonical_shuffling: Vec::new(),
 		shuffled_indices: Vec::new(),
+		fallback_routes: Default::default(),
 	}
 }
 
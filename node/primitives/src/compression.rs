@@ -0,0 +1,207 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Compression of PoV-carrying data with zstd, optionally with trained dictionaries.
+//!
+//! Parachain PoVs tend to be highly repetitive, so a dictionary trained on past PoVs of a para
+//! compresses its new PoVs far better than zstd alone. Dictionaries are identified by the hash of
+//! their contents, so that peers can agree on which one to use without exchanging them, and are
+//! registered for a para, or for a validation code hash so that they follow code upgrades.
+//!
+//! Dictionaries are node-local: they are configured by the node operator and never fetched from
+//! the chain, so a dictionary is only used between peers which were both configured with it.
+
+use std::{collections::BTreeMap, io::Read, sync::Arc};
+
+use polkadot_primitives::{BlakeTwo256, Hash, HashT, Id as ParaId, ValidationCodeHash};
+
+/// The compression level used when none is configured, the same as `sp_maybe_compressed_blob`'s.
+pub const DEFAULT_COMPRESSION_LEVEL: i32 = 3;
+
+/// Errors which may occur while compressing or decompressing.
+#[derive(Debug, thiserror::Error)]
+pub enum CompressionError {
+	/// zstd failed to compress or decompress the data.
+	#[error("zstd failure: {0}")]
+	Zstd(#[from] std::io::Error),
+	/// The decompressed data would exceed the given limit.
+	#[error("Decompressed data exceeds the bomb limit")]
+	BombLimitExceeded,
+}
+
+/// A zstd dictionary, identified by the hash of its contents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoVDictionary {
+	hash: Hash,
+	data: Arc<Vec<u8>>,
+}
+
+impl PoVDictionary {
+	/// Wrap the contents of a dictionary, e.g. as trained on past PoVs of a para with
+	/// `zstd --train`. Contents which are not a trained dictionary are used as raw content.
+	pub fn new(data: Vec<u8>) -> Self {
+		Self { hash: BlakeTwo256::hash(&data), data: Arc::new(data) }
+	}
+
+	/// The hash identifying the dictionary.
+	pub fn hash(&self) -> Hash {
+		self.hash
+	}
+
+	/// The contents of the dictionary.
+	pub fn data(&self) -> &[u8] {
+		&self.data
+	}
+}
+
+/// Compress `data` at `level`, with `dictionary` if any.
+pub fn compress(
+	data: &[u8],
+	level: i32,
+	dictionary: Option<&PoVDictionary>,
+) -> Result<Vec<u8>, CompressionError> {
+	let mut compressor = match dictionary {
+		Some(dictionary) => zstd::bulk::Compressor::with_dictionary(level, dictionary.data())?,
+		None => zstd::bulk::Compressor::new(level)?,
+	};
+	Ok(compressor.compress(data)?)
+}
+
+/// Decompress `blob`, which was compressed with `dictionary` if any, failing if the decompressed
+/// data would exceed `bomb_limit` bytes.
+pub fn decompress(
+	blob: &[u8],
+	bomb_limit: usize,
+	dictionary: Option<&PoVDictionary>,
+) -> Result<Vec<u8>, CompressionError> {
+	let decoder = match dictionary {
+		Some(dictionary) => zstd::Decoder::with_dictionary(blob, dictionary.data())?,
+		None => zstd::Decoder::with_buffer(blob)?,
+	};
+	let mut decompressed = Vec::new();
+	// Read one more byte than the limit, to tell whether it is exceeded.
+	decoder.take(bomb_limit as u64 + 1).read_to_end(&mut decompressed)?;
+	if decompressed.len() > bomb_limit {
+		return Err(CompressionError::BombLimitExceeded)
+	}
+	Ok(decompressed)
+}
+
+/// The dictionaries known to this node, and which para they are for.
+#[derive(Clone, Debug, Default)]
+pub struct PoVDictionaries {
+	dictionaries: BTreeMap<Hash, PoVDictionary>,
+	by_para: BTreeMap<ParaId, Hash>,
+	by_code_hash: BTreeMap<ValidationCodeHash, Hash>,
+}
+
+impl PoVDictionaries {
+	/// Use `dictionary` for the PoVs of `para`, unless one is registered for their code hash.
+	pub fn register_for_para(&mut self, para: ParaId, dictionary: PoVDictionary) {
+		self.by_para.insert(para, dictionary.hash());
+		self.dictionaries.insert(dictionary.hash(), dictionary);
+	}
+
+	/// Use `dictionary` for the PoVs of candidates validated by the code of hash `code_hash`.
+	pub fn register_for_code_hash(
+		&mut self,
+		code_hash: ValidationCodeHash,
+		dictionary: PoVDictionary,
+	) {
+		self.by_code_hash.insert(code_hash, dictionary.hash());
+		self.dictionaries.insert(dictionary.hash(), dictionary);
+	}
+
+	/// The dictionary of hash `hash`, if known.
+	pub fn get(&self, hash: &Hash) -> Option<&PoVDictionary> {
+		self.dictionaries.get(hash)
+	}
+
+	/// The dictionary to use for the PoVs of `para`, validated by the code of hash `code_hash` if
+	/// known.
+	pub fn for_para(
+		&self,
+		para: ParaId,
+		code_hash: Option<ValidationCodeHash>,
+	) -> Option<&PoVDictionary> {
+		code_hash
+			.and_then(|code_hash| self.by_code_hash.get(&code_hash))
+			.or_else(|| self.by_para.get(&para))
+			.and_then(|hash| self.dictionaries.get(hash))
+	}
+
+	/// The hashes of all known dictionaries, in ascending order so that all nodes with the same
+	/// dictionaries prefer the same ones.
+	pub fn hashes(&self) -> impl Iterator<Item = &Hash> {
+		self.dictionaries.keys()
+	}
+
+	/// Whether no dictionaries are known.
+	pub fn is_empty(&self) -> bool {
+		self.dictionaries.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn samples() -> Vec<Vec<u8>> {
+		(0u32..200)
+			.map(|i| {
+				let mut sample = b"parachain block header and extrinsics, ".repeat(4);
+				sample.extend_from_slice(&i.to_le_bytes());
+				sample
+			})
+			.collect()
+	}
+
+	#[test]
+	fn dictionary_roundtrip_works() {
+		let mut samples = samples();
+		let data = samples.pop().unwrap();
+		let dictionary = PoVDictionary::new(samples.concat());
+
+		let with = compress(&data, DEFAULT_COMPRESSION_LEVEL, Some(&dictionary)).unwrap();
+		let without = compress(&data, DEFAULT_COMPRESSION_LEVEL, None).unwrap();
+		assert!(with.len() < without.len());
+		assert_eq!(decompress(&with, data.len(), Some(&dictionary)).unwrap(), data);
+		assert_eq!(decompress(&without, data.len(), None).unwrap(), data);
+		assert!(decompress(&with, data.len(), None).is_err());
+	}
+
+	#[test]
+	fn bomb_limit_is_enforced() {
+		let data = vec![0u8; 10_000];
+		let blob = compress(&data, DEFAULT_COMPRESSION_LEVEL, None).unwrap();
+		assert!(matches!(decompress(&blob, 1_000, None), Err(CompressionError::BombLimitExceeded)));
+	}
+
+	#[test]
+	fn code_hash_takes_precedence() {
+		let para = PoVDictionary::new(vec![1; 100]);
+		let code = PoVDictionary::new(vec![2; 100]);
+		let code_hash = ValidationCodeHash::from(Hash::repeat_byte(1));
+		let mut dictionaries = PoVDictionaries::default();
+		dictionaries.register_for_para(ParaId::from(1), para.clone());
+		dictionaries.register_for_code_hash(code_hash, code.clone());
+
+		assert_eq!(dictionaries.for_para(ParaId::from(1), None), Some(&para));
+		assert_eq!(dictionaries.for_para(ParaId::from(1), Some(code_hash)), Some(&code));
+		assert_eq!(dictionaries.for_para(ParaId::from(2), Some(code_hash)), Some(&code));
+		assert_eq!(dictionaries.for_para(ParaId::from(2), None), None);
+	}
+}
@@ -31,8 +31,8 @@ use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 use polkadot_primitives::{
 	BlakeTwo256, BlockNumber, CandidateCommitments, CandidateHash, CollatorPair,
-	CommittedCandidateReceipt, CompactStatement, EncodeAs, Hash, HashT, HeadData, Id as ParaId,
-	PersistedValidationData, SessionIndex, Signed, UncheckedSigned, ValidationCode,
+	CommittedCandidateReceipt, CompactStatement, CoreIndex, EncodeAs, Hash, HashT, HeadData,
+	Id as ParaId, PersistedValidationData, SessionIndex, Signed, UncheckedSigned, ValidationCode,
 	ValidationCodeHash, ValidatorIndex, MAX_CODE_SIZE, MAX_POV_SIZE,
 };
 pub use sp_consensus_babe::{
@@ -43,6 +43,10 @@ pub use polkadot_parachain::primitives::{BlockData, HorizontalMessages, UpwardMe
 
 pub mod approval;
 
+/// Compression of PoVs with trained dictionaries.
+#[cfg(not(target_os = "unknown"))]
+pub mod compression;
+
 /// Disputes related types.
 pub mod disputes;
 pub use disputes::{
@@ -521,6 +525,10 @@ pub struct SubmitCollationParams {
 	/// okay to just drop it. However, if it is called, it should be called with the signed
 	/// statement of a parachain validator seconding the collation.
 	pub result_sender: Option<futures::channel::oneshot::Sender<CollationSecondedSignal>>,
+	/// The core the collation is to be backed on, if the para is assigned to several.
+	///
+	/// If `None`, the first core the para is assigned to at the relay-parent is used.
+	pub core_index: Option<CoreIndex>,
 }
 
 /// This is the data we keep available for each candidate included in the relay chain.
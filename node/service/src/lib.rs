@@ -40,6 +40,7 @@ mod tests;
 use {
 	grandpa::{self, FinalityProofProvider as GrandpaFinalityProofProvider},
 	gum::info,
+	polkadot_network_bridge::{BandwidthConfig, BandwidthStats, RateLimit},
 	polkadot_node_core_approval_voting::{
 		self as approval_voting_subsystem, Config as ApprovalVotingConfig,
 	},
@@ -53,6 +54,7 @@ use {
 	polkadot_node_network_protocol::{
 		peer_set::PeerSetProtocolNames, request_response::ReqProtocolNames,
 	},
+	polkadot_node_subsystem_util::persisted_authority_discovery::PersistedAuthorityDiscovery,
 	sc_client_api::BlockBackend,
 	sc_transaction_pool_api::OffchainTransactionPoolFactory,
 	sp_core::traits::SpawnNamed,
@@ -62,6 +64,7 @@ use polkadot_node_subsystem_util::database::Database;
 
 #[cfg(feature = "full-node")]
 pub use {
+	polkadot_node_primitives::compression::{PoVDictionaries, PoVDictionary},
 	polkadot_overseer::{Handle, Overseer, OverseerConnector, OverseerHandle},
 	polkadot_primitives::runtime_api::ParachainHost,
 	relay_chain_selection::SelectRelayChain,
@@ -464,11 +467,32 @@ fn new_partial_basics(
 	Ok(Basics { task_manager, client, backend, keystore_container, telemetry })
 }
 
+/// Exposes the bandwidth accounting of the network bridge to the parachain networking RPC.
+#[cfg(feature = "full-node")]
+struct NetworkBandwidthRpc(BandwidthStats);
+
+#[cfg(feature = "full-node")]
+impl polkadot_rpc::parachain_network::NetworkBandwidth for NetworkBandwidthRpc {
+	fn top_talkers(&self, count: usize) -> Vec<polkadot_rpc::parachain_network::TopTalker> {
+		self.0
+			.top_talkers(count)
+			.into_iter()
+			.map(|talker| polkadot_rpc::parachain_network::TopTalker {
+				peer_id: talker.peer.to_base58(),
+				protocol: talker.protocol.to_string(),
+				bytes_received: talker.bytes_received,
+				bytes_sent: talker.bytes_sent,
+			})
+			.collect()
+	}
+}
+
 #[cfg(feature = "full-node")]
 fn new_partial<ChainSelection>(
 	config: &mut Configuration,
 	Basics { task_manager, backend, client, keystore_container, telemetry }: Basics,
 	select_chain: ChainSelection,
+	network_bandwidth: BandwidthStats,
 ) -> Result<
 	service::PartialComponents<
 		FullClient,
@@ -606,6 +630,7 @@ where
 					subscription_executor,
 				},
 				backend: backend.clone(),
+				network_bandwidth: Arc::new(NetworkBandwidthRpc(network_bandwidth.clone())),
 			};
 
 			polkadot_rpc::create_full(deps).map_err(Into::into)
@@ -643,6 +668,11 @@ pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	#[allow(dead_code)]
 	pub malus_finality_delay: Option<u32>,
 	pub hwbench: Option<sc_sysinfo::HwBench>,
+	/// The dictionaries to compress responses carrying PoVs with, if compressing them at all.
+	pub pov_compression: Option<PoVDictionaries>,
+	/// The limit on notification bytes per second a single peer may send on the collation
+	/// peer-set, if any.
+	pub collation_peer_rate_limit: Option<u64>,
 }
 
 #[cfg(feature = "full-node")]
@@ -729,9 +759,11 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 		overseer_message_channel_capacity_override,
 		malus_finality_delay: _malus_finality_delay,
 		hwbench,
+		pov_compression,
+		collation_peer_rate_limit,
 	}: NewFullParams<OverseerGenerator>,
 ) -> Result<NewFull, Error> {
-	use polkadot_node_network_protocol::request_response::IncomingRequest;
+	use polkadot_node_network_protocol::request_response::{self, IncomingRequest};
 	use sc_network_common::sync::warp::WarpSyncParams;
 
 	let is_offchain_indexing_enabled = config.offchain_worker.indexing_enabled;
@@ -765,6 +797,15 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	let pov_compression = pov_compression
+		.map(|dictionaries| {
+			request_response::compression::PoVCompression::new(
+				dictionaries,
+				prometheus_registry.as_ref(),
+			)
+		})
+		.transpose()?;
+
 	let overseer_connector = OverseerConnector::default();
 	let overseer_handle = Handle::new(overseer_connector.handle());
 
@@ -789,6 +830,10 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 		SelectRelayChain::new_longest_chain(basics.backend.clone())
 	};
 
+	let network_bandwidth = BandwidthStats::new(BandwidthConfig {
+		collation_rate_limit: collation_peer_rate_limit.map(RateLimit::per_second),
+	});
+
 	let service::PartialComponents::<_, _, SelectRelayChain<_>, _, _, _> {
 		client,
 		backend,
@@ -798,7 +843,12 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 		import_queue,
 		transaction_pool,
 		other: (rpc_extensions_builder, import_setup, rpc_setup, slot_duration, mut telemetry),
-	} = new_partial::<SelectRelayChain<_>>(&mut config, basics, select_chain)?;
+	} = new_partial::<SelectRelayChain<_>>(
+		&mut config,
+		basics,
+		select_chain,
+		network_bandwidth.clone(),
+	)?;
 
 	let shared_voter_state = rpc_setup;
 	let auth_disc_publish_non_global_ips = config.network.allow_non_globals_in_dht;
@@ -851,26 +901,47 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 
 	let req_protocol_names = ReqProtocolNames::new(&genesis_hash, config.chain_spec.fork_id());
 
-	let (pov_req_receiver, cfg) = IncomingRequest::get_config_receiver(&req_protocol_names);
-	net_config.add_request_response_protocol(cfg);
+	let mut req_cfgs = Vec::new();
+	// Requests carrying PoVs are also served with compressed responses, if enabled.
+	let (pov_req_receiver, cfgs) =
+		IncomingRequest::get_config_receivers(&req_protocol_names, pov_compression.as_ref());
+	req_cfgs.extend(cfgs);
 	let (chunk_req_receiver, cfg) = IncomingRequest::get_config_receiver(&req_protocol_names);
-	net_config.add_request_response_protocol(cfg);
-	let (collation_req_v1_receiver, cfg) =
-		IncomingRequest::get_config_receiver(&req_protocol_names);
-	net_config.add_request_response_protocol(cfg);
-	let (collation_req_vstaging_receiver, cfg) =
+	req_cfgs.push(cfg);
+	let (chunk_req_vstaging_receiver, cfg) =
 		IncomingRequest::get_config_receiver(&req_protocol_names);
-	net_config.add_request_response_protocol(cfg);
-	let (available_data_req_receiver, cfg) =
-		IncomingRequest::get_config_receiver(&req_protocol_names);
-	net_config.add_request_response_protocol(cfg);
+	req_cfgs.push(cfg);
+	let (collation_req_v1_receiver, cfgs) =
+		IncomingRequest::get_config_receivers(&req_protocol_names, pov_compression.as_ref());
+	req_cfgs.extend(cfgs);
+	let (collation_req_vstaging_receiver, cfgs) =
+		IncomingRequest::get_config_receivers(&req_protocol_names, pov_compression.as_ref());
+	req_cfgs.extend(cfgs);
+	let (available_data_req_receiver, cfgs) =
+		IncomingRequest::get_config_receivers(&req_protocol_names, pov_compression.as_ref());
+	req_cfgs.extend(cfgs);
 	let (statement_req_receiver, cfg) = IncomingRequest::get_config_receiver(&req_protocol_names);
-	net_config.add_request_response_protocol(cfg);
+	req_cfgs.push(cfg);
 	let (candidate_req_vstaging_receiver, cfg) =
 		IncomingRequest::get_config_receiver(&req_protocol_names);
-	net_config.add_request_response_protocol(cfg);
+	req_cfgs.push(cfg);
 	let (dispute_req_receiver, cfg) = IncomingRequest::get_config_receiver(&req_protocol_names);
-	net_config.add_request_response_protocol(cfg);
+	req_cfgs.push(cfg);
+	let (para_head_proof_req_receiver, cfg) =
+		IncomingRequest::get_config_receiver(&req_protocol_names);
+	req_cfgs.push(cfg);
+
+	// Incoming requests and their responses are accounted on their way to the handlers.
+	let (req_cfgs, req_accounting) =
+		network_bandwidth.observe_incoming_requests(&req_protocol_names, req_cfgs);
+	req_cfgs
+		.into_iter()
+		.for_each(|cfg| net_config.add_request_response_protocol(cfg));
+	task_manager.spawn_handle().spawn(
+		"parachain-request-accounting",
+		Some("networking"),
+		req_accounting,
+	);
 
 	let grandpa_hard_forks = if config.chain_spec.is_kusama() {
 		grandpa_support::kusama_hard_forks()
@@ -919,6 +990,12 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 		);
 	}
 
+	task_manager.spawn_handle().spawn_blocking(
+		"para-head-proof-server",
+		Some("networking"),
+		polkadot_para_head_proof_server::respond(para_head_proof_req_receiver, client.clone()),
+	);
+
 	let parachains_db = open_database(&config.database)?;
 
 	let approval_voting_config = ApprovalVotingConfig {
@@ -1035,7 +1112,11 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 				Some("authority-discovery"),
 				Box::pin(worker.run()),
 			);
-			Some(service)
+			Some(PersistedAuthorityDiscovery::new(
+				service,
+				parachains_db.clone(),
+				parachains_db::REAL_COLUMNS.col_authority_discovery_data,
+			))
 		} else {
 			None
 		};
@@ -1051,8 +1132,11 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 					network_service: network.clone(),
 					sync_service: sync_service.clone(),
 					authority_discovery_service,
+					network_bandwidth,
+					pov_compression,
 					pov_req_receiver,
 					chunk_req_receiver,
+					chunk_req_vstaging_receiver,
 					collation_req_v1_receiver,
 					collation_req_vstaging_receiver,
 					available_data_req_receiver,
@@ -1315,7 +1399,12 @@ macro_rules! chain_ops {
 		let chain_selection = LongestChain::new(basics.backend.clone());
 
 		let service::PartialComponents { client, backend, import_queue, task_manager, .. } =
-			new_partial::<LongestChain<_, Block>>(&mut config, basics, chain_selection)?;
+			new_partial::<LongestChain<_, Block>>(
+				&mut config,
+				basics,
+				chain_selection,
+				BandwidthStats::default(),
+			)?;
 		Ok((client, backend, import_queue, task_manager))
 	}};
 }
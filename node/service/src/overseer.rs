@@ -29,7 +29,8 @@ use polkadot_node_core_dispute_coordinator::Config as DisputeCoordinatorConfig;
 use polkadot_node_network_protocol::{
 	peer_set::PeerSetProtocolNames,
 	request_response::{
-		v1 as request_v1, vstaging as request_vstaging, IncomingRequestReceiver, ReqProtocolNames,
+		compression::PoVCompression, v1 as request_v1, vstaging as request_vstaging,
+		IncomingRequestReceiver, ReqProtocolNames,
 	},
 };
 #[cfg(any(feature = "malus", test))]
@@ -60,8 +61,8 @@ pub use polkadot_collator_protocol::{CollatorProtocolSubsystem, ProtocolSide};
 pub use polkadot_dispute_distribution::DisputeDistributionSubsystem;
 pub use polkadot_gossip_support::GossipSupport as GossipSupportSubsystem;
 pub use polkadot_network_bridge::{
-	Metrics as NetworkBridgeMetrics, NetworkBridgeRx as NetworkBridgeRxSubsystem,
-	NetworkBridgeTx as NetworkBridgeTxSubsystem,
+	BandwidthStats as NetworkBandwidthStats, Metrics as NetworkBridgeMetrics,
+	NetworkBridgeRx as NetworkBridgeRxSubsystem, NetworkBridgeTx as NetworkBridgeTxSubsystem,
 };
 pub use polkadot_node_collation_generation::CollationGenerationSubsystem;
 pub use polkadot_node_core_approval_voting::ApprovalVotingSubsystem;
@@ -76,7 +77,11 @@ pub use polkadot_node_core_prospective_parachains::ProspectiveParachainsSubsyste
 pub use polkadot_node_core_provisioner::ProvisionerSubsystem;
 pub use polkadot_node_core_pvf_checker::PvfCheckerSubsystem;
 pub use polkadot_node_core_runtime_api::RuntimeApiSubsystem;
-use polkadot_node_subsystem_util::rand::{self, SeedableRng};
+use polkadot_node_subsystem_util::{
+	persisted_authority_discovery::PersistedAuthorityDiscovery,
+	pov_cache::{PoVCache, DEFAULT_POV_CACHE_SIZE},
+	rand::{self, SeedableRng},
+};
 pub use polkadot_statement_distribution::StatementDistributionSubsystem;
 
 /// Arguments passed for overseer construction.
@@ -96,12 +101,20 @@ where
 	pub network_service: Arc<sc_network::NetworkService<Block, Hash>>,
 	/// Underlying syncing service implementation.
 	pub sync_service: Arc<sc_network_sync::SyncingService<Block>>,
-	/// Underlying authority discovery service.
-	pub authority_discovery_service: AuthorityDiscoveryService,
+	/// Underlying authority discovery service, falling back to the addresses persisted in the
+	/// parachains DB.
+	pub authority_discovery_service: PersistedAuthorityDiscovery<AuthorityDiscoveryService>,
+	/// Bandwidth accounting and limits of the network bridge, shared with the RPC.
+	pub network_bandwidth: NetworkBandwidthStats,
+	/// Compression of responses carrying PoVs, if enabled.
+	pub pov_compression: Option<PoVCompression>,
 	/// POV request receiver.
 	pub pov_req_receiver: IncomingRequestReceiver<request_v1::PoVFetchingRequest>,
 	/// Erasure chunks request receiver.
 	pub chunk_req_receiver: IncomingRequestReceiver<request_v1::ChunkFetchingRequest>,
+	/// Erasure chunks request receiver for network protocol vstaging.
+	pub chunk_req_vstaging_receiver:
+		IncomingRequestReceiver<request_vstaging::ChunkFetchingRequest>,
 	/// Collations request receiver for network protocol v1.
 	pub collation_req_v1_receiver: IncomingRequestReceiver<request_v1::CollationFetchingRequest>,
 	/// Collations request receiver for network protocol vstaging.
@@ -155,8 +168,11 @@ pub fn prepared_overseer_builder<Spawner, RuntimeClient>(
 		network_service,
 		sync_service,
 		authority_discovery_service,
+		network_bandwidth,
+		pov_compression,
 		pov_req_receiver,
 		chunk_req_receiver,
+		chunk_req_vstaging_receiver,
 		collation_req_v1_receiver,
 		collation_req_vstaging_receiver,
 		available_data_req_receiver,
@@ -194,20 +210,20 @@ pub fn prepared_overseer_builder<Spawner, RuntimeClient>(
 		AvailabilityStoreSubsystem,
 		NetworkBridgeRxSubsystem<
 			Arc<sc_network::NetworkService<Block, Hash>>,
-			AuthorityDiscoveryService,
+			PersistedAuthorityDiscovery<AuthorityDiscoveryService>,
 		>,
 		NetworkBridgeTxSubsystem<
 			Arc<sc_network::NetworkService<Block, Hash>>,
-			AuthorityDiscoveryService,
+			PersistedAuthorityDiscovery<AuthorityDiscoveryService>,
 		>,
 		ChainApiSubsystem<RuntimeClient>,
 		CollationGenerationSubsystem,
 		CollatorProtocolSubsystem,
 		ApprovalDistributionSubsystem,
 		ApprovalVotingSubsystem,
-		GossipSupportSubsystem<AuthorityDiscoveryService>,
+		GossipSupportSubsystem<PersistedAuthorityDiscovery<AuthorityDiscoveryService>>,
 		DisputeCoordinatorSubsystem,
-		DisputeDistributionSubsystem<AuthorityDiscoveryService>,
+		DisputeDistributionSubsystem<PersistedAuthorityDiscovery<AuthorityDiscoveryService>>,
 		ChainSelectionSubsystem,
 		ProspectiveParachainsSubsystem,
 	>,
@@ -226,6 +242,8 @@ where
 
 	let network_bridge_metrics: NetworkBridgeMetrics = Metrics::register(registry)?;
 
+	let pov_cache = PoVCache::new(DEFAULT_POV_CACHE_SIZE, Metrics::register(registry)?);
+
 	let runtime_api_client = Arc::new(DefaultSubsystemClient::new(
 		runtime_client.clone(),
 		offchain_transaction_pool_factory,
@@ -236,23 +254,32 @@ where
 			network_service.clone(),
 			authority_discovery_service.clone(),
 			network_bridge_metrics.clone(),
+			network_bandwidth.clone(),
 			req_protocol_names,
 			peerset_protocol_names.clone(),
+			pov_compression,
 		))
 		.network_bridge_rx(NetworkBridgeRxSubsystem::new(
 			network_service.clone(),
 			authority_discovery_service.clone(),
 			Box::new(sync_service.clone()),
 			network_bridge_metrics,
+			network_bandwidth,
 			peerset_protocol_names,
 		))
 		.availability_distribution(AvailabilityDistributionSubsystem::new(
 			keystore.clone(),
-			IncomingRequestReceivers { pov_req_receiver, chunk_req_receiver },
+			IncomingRequestReceivers {
+				pov_req_receiver,
+				chunk_req_receiver,
+				chunk_req_vstaging_receiver,
+			},
+			pov_cache.clone(),
 			Metrics::register(registry)?,
 		))
 		.availability_recovery(AvailabilityRecoverySubsystem::with_chunks_if_pov_large(
 			available_data_req_receiver,
+			pov_cache.clone(),
 			Metrics::register(registry)?,
 		))
 		.availability_store(AvailabilityStoreSubsystem::new(
@@ -268,6 +295,7 @@ where
 		))
 		.candidate_backing(CandidateBackingSubsystem::new(
 			keystore.clone(),
+			pov_cache,
 			Metrics::register(registry)?,
 		))
 		.candidate_validation(CandidateValidationSubsystem::with_config(
@@ -296,6 +324,9 @@ where
 					keystore: keystore.clone(),
 					eviction_policy: Default::default(),
 					metrics: Metrics::register(registry)?,
+					db: parachains_db.clone(),
+					col_collator_scores: crate::parachains_db::REAL_COLUMNS
+						.col_collator_scores_data,
 				},
 			};
 			CollatorProtocolSubsystem::new(side)
@@ -28,7 +28,7 @@ type Version = u32;
 const VERSION_FILE_NAME: &'static str = "parachain_db_version";
 
 /// Current db version.
-const CURRENT_VERSION: Version = 3;
+const CURRENT_VERSION: Version = 4;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -60,6 +60,8 @@ pub(crate) fn try_upgrade_db(db_path: &Path, db_kind: DatabaseKind) -> Result<()
 			Some(1) => migrate_from_version_1_to_2(db_path, db_kind)?,
 			// 2 -> 3 migration
 			Some(2) => migrate_from_version_2_to_3(db_path, db_kind)?,
+			// 3 -> 4 migration
+			Some(3) => migrate_from_version_3_to_4(db_path, db_kind)?,
 			// Already at current version, do nothing.
 			Some(CURRENT_VERSION) => (),
 			// This is an arbitrary future version, we don't handle it.
@@ -141,6 +143,18 @@ fn migrate_from_version_2_to_3(path: &Path, db_kind: DatabaseKind) -> Result<(),
 	})
 }
 
+fn migrate_from_version_3_to_4(path: &Path, db_kind: DatabaseKind) -> Result<(), Error> {
+	gum::info!(target: LOG_TARGET, "Migrating parachains db from version 3 to version 4 ...");
+	match db_kind {
+		DatabaseKind::ParityDB => paritydb_migrate_from_version_3_to_4(path),
+		DatabaseKind::RocksDB => rocksdb_migrate_from_version_3_to_4(path),
+	}
+	.and_then(|result| {
+		gum::info!(target: LOG_TARGET, "Migration complete! ");
+		Ok(result)
+	})
+}
+
 /// Migration from version 0 to version 1:
 /// * the number of columns has changed from 3 to 5;
 fn rocksdb_migrate_from_version_0_to_1(path: &Path) -> Result<(), Error> {
@@ -188,6 +202,23 @@ fn rocksdb_migrate_from_version_2_to_3(path: &Path) -> Result<(), Error> {
 	Ok(())
 }
 
+/// Migration from version 3 to version 4:
+/// * the number of columns has changed from 5 to 7;
+fn rocksdb_migrate_from_version_3_to_4(path: &Path) -> Result<(), Error> {
+	use kvdb_rocksdb::{Database, DatabaseConfig};
+
+	let db_path = path
+		.to_str()
+		.ok_or_else(|| super::other_io_error("Invalid database path".into()))?;
+	let db_cfg = DatabaseConfig::with_columns(super::columns::v3::NUM_COLUMNS);
+	let mut db = Database::open(&db_cfg, db_path)?;
+
+	db.add_column()?;
+	db.add_column()?;
+
+	Ok(())
+}
+
 // This currently clears columns which had their configs altered between versions.
 // The columns to be changed are constrained by the `allowed_columns` vector.
 fn paritydb_fix_columns(
@@ -249,7 +280,7 @@ fn paritydb_fix_columns(
 pub(crate) fn paritydb_version_1_config(path: &Path) -> parity_db::Options {
 	let mut options =
 		parity_db::Options::with_columns(&path, super::columns::v1::NUM_COLUMNS as u8);
-	for i in columns::v3::ORDERED_COL {
+	for i in columns::v4::ORDERED_COL {
 		options.columns[*i as usize].btree_index = true;
 	}
 
@@ -260,7 +291,7 @@ pub(crate) fn paritydb_version_1_config(path: &Path) -> parity_db::Options {
 pub(crate) fn paritydb_version_2_config(path: &Path) -> parity_db::Options {
 	let mut options =
 		parity_db::Options::with_columns(&path, super::columns::v2::NUM_COLUMNS as u8);
-	for i in columns::v3::ORDERED_COL {
+	for i in columns::v4::ORDERED_COL {
 		options.columns[*i as usize].btree_index = true;
 	}
 
@@ -271,7 +302,18 @@ pub(crate) fn paritydb_version_2_config(path: &Path) -> parity_db::Options {
 pub(crate) fn paritydb_version_3_config(path: &Path) -> parity_db::Options {
 	let mut options =
 		parity_db::Options::with_columns(&path, super::columns::v3::NUM_COLUMNS as u8);
-	for i in columns::v3::ORDERED_COL {
+	for i in columns::v4::ORDERED_COL {
+		options.columns[*i as usize].btree_index = true;
+	}
+
+	options
+}
+
+/// Database configuration for version 4.
+pub(crate) fn paritydb_version_4_config(path: &Path) -> parity_db::Options {
+	let mut options =
+		parity_db::Options::with_columns(&path, super::columns::v4::NUM_COLUMNS as u8);
+	for i in columns::v4::ORDERED_COL {
 		options.columns[*i as usize].btree_index = true;
 	}
 
@@ -288,7 +330,7 @@ fn paritydb_migrate_from_version_0_to_1(path: &Path) -> Result<(), Error> {
 	paritydb_fix_columns(
 		path,
 		paritydb_version_1_config(path),
-		vec![super::columns::v3::COL_DISPUTE_COORDINATOR_DATA],
+		vec![super::columns::v4::COL_DISPUTE_COORDINATOR_DATA],
 	)?;
 
 	Ok(())
@@ -314,10 +356,25 @@ fn paritydb_migrate_from_version_2_to_3(path: &Path) -> Result<(), Error> {
 	Ok(())
 }
 
+/// Migration from version 3 to version 4:
+/// - add a new column for persisting authority discovery addresses
+/// - add a new column for persisting collator scores
+fn paritydb_migrate_from_version_3_to_4(path: &Path) -> Result<(), Error> {
+	let mut options = paritydb_version_3_config(path);
+
+	// Adds the authority discovery and the collator scores columns.
+	for _ in 0..2 {
+		parity_db::Db::add_column(&mut options, Default::default())
+			.map_err(|e| other_io_error(format!("Error adding column {:?}", e)))?;
+	}
+
+	Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 	use super::{
-		columns::{v2::COL_SESSION_WINDOW_DATA, v3::*},
+		columns::{v2::COL_SESSION_WINDOW_DATA, v4::*},
 		*,
 	};
 
@@ -338,7 +395,7 @@ mod tests {
 		// We need to properly set db version for upgrade to work.
 		fs::write(version_file_path(db_dir.path()), "1").expect("Failed to write DB version");
 		{
-			let db = DbAdapter::new(db, columns::v3::ORDERED_COL);
+			let db = DbAdapter::new(db, columns::v4::ORDERED_COL);
 			db.write(DBTransaction {
 				ops: vec![DBOp::Insert {
 					col: COL_DISPUTE_COORDINATOR_DATA,
@@ -356,7 +413,7 @@ mod tests {
 
 		assert_eq!(db.num_columns(), super::columns::v2::NUM_COLUMNS);
 
-		let db = DbAdapter::new(db, columns::v3::ORDERED_COL);
+		let db = DbAdapter::new(db, columns::v4::ORDERED_COL);
 
 		assert_eq!(
 			db.get(COL_DISPUTE_COORDINATOR_DATA, b"1234").unwrap(),
@@ -402,4 +459,98 @@ mod tests {
 
 		assert_eq!(db.num_columns(), super::columns::v3::NUM_COLUMNS);
 	}
+
+	#[test]
+	fn test_rocksdb_migrate_3_to_4() {
+		use kvdb::{DBKey, DBOp};
+		use kvdb_rocksdb::{Database, DatabaseConfig};
+		use polkadot_node_subsystem_util::database::{
+			kvdb_impl::DbAdapter, DBTransaction, KeyValueDB,
+		};
+
+		let db_dir = tempfile::tempdir().unwrap();
+		let db_path = db_dir.path().to_str().unwrap();
+		let db_cfg = DatabaseConfig::with_columns(super::columns::v3::NUM_COLUMNS);
+		{
+			let db = Database::open(&db_cfg, db_path).unwrap();
+			assert_eq!(db.num_columns(), super::columns::v3::NUM_COLUMNS as u32);
+		}
+
+		// We need to properly set db version for upgrade to work.
+		fs::write(version_file_path(db_dir.path()), "3").expect("Failed to write DB version");
+
+		try_upgrade_db(&db_dir.path(), DatabaseKind::RocksDB).unwrap();
+
+		let db_cfg = DatabaseConfig::with_columns(super::columns::v4::NUM_COLUMNS);
+		let db = Database::open(&db_cfg, db_path).unwrap();
+
+		assert_eq!(db.num_columns(), super::columns::v4::NUM_COLUMNS);
+
+		let db = DbAdapter::new(db, columns::v4::ORDERED_COL);
+
+		// Test we can write the new columns.
+		for col in [COL_AUTHORITY_DISCOVERY_DATA, COL_COLLATOR_SCORES_DATA] {
+			db.write(DBTransaction {
+				ops: vec![DBOp::Insert {
+					col,
+					key: DBKey::from_slice(b"1337"),
+					value: b"0xdeadb00b".to_vec(),
+				}],
+			})
+			.unwrap();
+
+			assert_eq!(db.get(col, b"1337").unwrap(), Some("0xdeadb00b".as_bytes().to_vec()));
+		}
+	}
+
+	#[test]
+	fn test_paritydb_migrate_3_to_4() {
+		use kvdb::{DBKey, DBOp};
+		use polkadot_node_subsystem_util::database::{
+			paritydb_impl::DbAdapter, DBTransaction, KeyValueDB,
+		};
+
+		let db_dir = tempfile::tempdir().unwrap();
+		let path = db_dir.path();
+		{
+			let db = parity_db::Db::open_or_create(&paritydb_version_3_config(path)).unwrap();
+			let db = DbAdapter::new(db, columns::v4::ORDERED_COL);
+			db.write(DBTransaction {
+				ops: vec![DBOp::Insert {
+					col: COL_DISPUTE_COORDINATOR_DATA,
+					key: DBKey::from_slice(b"1234"),
+					value: b"0xdeadb00b".to_vec(),
+				}],
+			})
+			.unwrap();
+		}
+
+		// We need to properly set db version for upgrade to work.
+		fs::write(version_file_path(path), "3").expect("Failed to write DB version");
+
+		try_upgrade_db(path, DatabaseKind::ParityDB).unwrap();
+
+		let options = paritydb_version_4_config(path);
+		assert_eq!(options.columns.len(), super::columns::v4::NUM_COLUMNS as usize);
+		let db = DbAdapter::new(parity_db::Db::open(&options).unwrap(), columns::v4::ORDERED_COL);
+
+		assert_eq!(
+			db.get(COL_DISPUTE_COORDINATOR_DATA, b"1234").unwrap(),
+			Some("0xdeadb00b".as_bytes().to_vec())
+		);
+
+		// Test we can write the new columns.
+		for col in [COL_AUTHORITY_DISCOVERY_DATA, COL_COLLATOR_SCORES_DATA] {
+			db.write(DBTransaction {
+				ops: vec![DBOp::Insert {
+					col,
+					key: DBKey::from_slice(b"1337"),
+					value: b"0xdeadb00b".to_vec(),
+				}],
+			})
+			.unwrap();
+
+			assert_eq!(db.get(col, b"1337").unwrap(), Some("0xdeadb00b".as_bytes().to_vec()));
+		}
+	}
 }
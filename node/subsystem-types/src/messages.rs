@@ -29,7 +29,8 @@ use thiserror::Error;
 pub use sc_network::IfDisconnected;
 
 use polkadot_node_network_protocol::{
-	self as net_protocol, peer_set::PeerSet, request_response::Requests, PeerId,
+	self as net_protocol, grid_topology::FallbackRoutes, peer_set::PeerSet,
+	request_response::Requests, PeerId,
 };
 use polkadot_node_primitives::{
 	approval::{BlockApprovalMeta, IndirectAssignmentCert, IndirectSignedApprovalVote},
@@ -41,7 +42,7 @@ use polkadot_node_primitives::{
 use polkadot_primitives::{
 	slashing, vstaging as vstaging_primitives, AuthorityDiscoveryId, BackedCandidate, BlockNumber,
 	CandidateEvent, CandidateHash, CandidateIndex, CandidateReceipt, CollatorId,
-	CommittedCandidateReceipt, CoreState, DisputeState, ExecutorParams, GroupIndex,
+	CommittedCandidateReceipt, CoreIndex, CoreState, DisputeState, ExecutorParams, GroupIndex,
 	GroupRotationInfo, Hash, Header as BlockHeader, Id as ParaId, InboundDownwardMessage,
 	InboundHrmpMessage, MultiDisputeStatementSet, OccupiedCoreAssumption, PersistedValidationData,
 	PvfCheckStatement, PvfExecTimeoutKind, SessionIndex, SessionInfo, SignedAvailabilityBitfield,
@@ -189,7 +190,8 @@ pub enum CollatorProtocolMessage {
 	/// This should be sent before any `DistributeCollation` message.
 	CollateOn(ParaId),
 	/// Provide a collation to distribute to validators with an optional result sender.
-	/// The second argument is the parent head-data hash.
+	/// The second argument is the parent head-data hash. The last argument is the core the
+	/// collation is built for, among the ones the para is assigned to; if `None`, the first one.
 	///
 	/// The result sender should be informed when at least one parachain validator seconded the
 	/// collation. It is also completely okay to just drop the sender.
@@ -198,6 +200,7 @@ pub enum CollatorProtocolMessage {
 		Hash,
 		PoV,
 		Option<oneshot::Sender<CollationSecondedSignal>>,
+		Option<CoreIndex>,
 	),
 	/// Report a collator as having provided an invalid collation. This should lead to disconnect
 	/// and blacklist of the collator.
@@ -343,6 +346,11 @@ pub enum NetworkBridgeRxMessage {
 		/// The reverse mapping of `canonical_shuffling`: from validator index
 		/// to the index in `canonical_shuffling`
 		shuffled_indices: Vec<usize>,
+		/// Reachable validators standing in for our unreachable grid neighbors.
+		///
+		/// The topology of a session is sent again with updated fallback routes
+		/// when the liveness of our grid neighbors changes.
+		fallback_routes: FallbackRoutes,
 	},
 	/// Inform the distribution subsystems about `AuthorityDiscoveryId` key rotations.
 	UpdatedAuthorityIds {
@@ -699,6 +707,9 @@ pub enum RuntimeApiRequest {
 	///
 	/// If it's not supported by the Runtime, the async backing is said to be disabled.
 	StagingAsyncBackingParams(RuntimeApiSender<vstaging_primitives::AsyncBackingParams>),
+	/// Get the collators allowed to collate for the given para, `None` if any collator is.
+	/// This is a staging API that will not be available on production runtimes.
+	StagingAuthorizedCollators(ParaId, RuntimeApiSender<Option<Vec<CollatorId>>>),
 }
 
 impl RuntimeApiRequest {
@@ -723,6 +734,19 @@ impl RuntimeApiRequest {
 	///
 	/// 99 for now, should be adjusted to VSTAGING/actual runtime version once released.
 	pub const STAGING_BACKING_STATE: u32 = 99;
+
+	/// Minimum version for collator allow-lists.
+	///
+	/// 99 for now, should be adjusted to VSTAGING/actual runtime version once released.
+	pub const STAGING_AUTHORIZED_COLLATORS: u32 = 99;
+
+	/// Minimum version for building and backing several chained candidates of a para in one
+	/// block.
+	///
+	/// No runtime accepts more than one backed candidate per para yet, so this is above the
+	/// staging version until `paras_inherent` maps a para to several cores. Until then collators
+	/// build, and block authors include, one candidate per para.
+	pub const MULTIPLE_CANDIDATES_PER_PARA_RUNTIME_REQUIREMENT: u32 = 100;
 }
 
 /// A message to the Runtime API subsystem.
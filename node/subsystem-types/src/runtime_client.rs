@@ -17,7 +17,7 @@
 use async_trait::async_trait;
 use polkadot_primitives::{
 	runtime_api::ParachainHost, vstaging, Block, BlockNumber, CandidateCommitments, CandidateEvent,
-	CandidateHash, CommittedCandidateReceipt, CoreState, DisputeState, ExecutorParams,
+	CandidateHash, CollatorId, CommittedCandidateReceipt, CoreState, DisputeState, ExecutorParams,
 	GroupRotationInfo, Hash, Id, InboundDownwardMessage, InboundHrmpMessage,
 	OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes,
 	SessionIndex, SessionInfo, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
@@ -247,6 +247,17 @@ pub trait RuntimeApiSubsystemClient {
 		at: Hash,
 		para_id: Id,
 	) -> Result<Option<polkadot_primitives::vstaging::BackingState>, ApiError>;
+
+	// === Collator allow-lists API ===
+
+	/// Returns the collators allowed to collate for the given para, if the para restricts its
+	/// collators.
+	/// This is a staging method! Do not use on production runtimes!
+	async fn staging_authorized_collators(
+		&self,
+		at: Hash,
+		para_id: Id,
+	) -> Result<Option<Vec<CollatorId>>, ApiError>;
 }
 
 /// Default implementation of [`RuntimeApiSubsystemClient`] using the client.
@@ -488,4 +499,12 @@ where
 	) -> Result<polkadot_primitives::vstaging::AsyncBackingParams, ApiError> {
 		self.client.runtime_api().staging_async_backing_params(at)
 	}
+
+	async fn staging_authorized_collators(
+		&self,
+		at: Hash,
+		para_id: Id,
+	) -> Result<Option<Vec<CollatorId>>, ApiError> {
+		self.client.runtime_api().staging_authorized_collators(at, para_id)
+	}
 }
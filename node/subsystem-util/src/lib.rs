@@ -44,8 +44,8 @@ use parity_scale_codec::Encode;
 
 use polkadot_primitives::{
 	vstaging as vstaging_primitives, AuthorityDiscoveryId, CandidateEvent, CandidateHash,
-	CommittedCandidateReceipt, CoreState, EncodeAs, GroupIndex, GroupRotationInfo, Hash,
-	Id as ParaId, OccupiedCoreAssumption, PersistedValidationData, ScrapedOnChainVotes,
+	CollatorId, CommittedCandidateReceipt, CoreState, EncodeAs, GroupIndex, GroupRotationInfo,
+	Hash, Id as ParaId, OccupiedCoreAssumption, PersistedValidationData, ScrapedOnChainVotes,
 	SessionIndex, SessionInfo, Signed, SigningContext, ValidationCode, ValidationCodeHash,
 	ValidatorId, ValidatorIndex, ValidatorSignature,
 };
@@ -87,6 +87,12 @@ pub mod nesting_sender;
 
 pub mod reputation;
 
+/// A node-wide cache of PoVs, shared between subsystems.
+pub mod pov_cache;
+
+/// An authority discovery service remembering addresses across restarts.
+pub mod persisted_authority_discovery;
+
 mod determine_new_blocks;
 
 #[cfg(test)]
@@ -228,6 +234,7 @@ specialize_requests! {
 	fn request_submit_report_dispute_lost(dp: slashing::DisputeProof, okop: slashing::OpaqueKeyOwnershipProof) -> Option<()>; SubmitReportDisputeLost;
 
 	fn request_staging_async_backing_params() -> vstaging_primitives::AsyncBackingParams; StagingAsyncBackingParams;
+	fn request_staging_authorized_collators(para_id: ParaId) -> Option<Vec<CollatorId>>; StagingAuthorizedCollators;
 }
 
 /// Requests executor parameters from the runtime effective at given relay-parent. First obtains
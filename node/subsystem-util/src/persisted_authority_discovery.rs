@@ -0,0 +1,240 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! An authority discovery service remembering addresses across restarts.
+//!
+//! The address cache of authority discovery starts out empty on every restart, and filling it
+//! through DHT lookups takes minutes. In the meantime, a restarted validator can't connect to its
+//! peers. [`PersistedAuthorityDiscovery`] keeps the addresses handed to
+//! [`AuthorityDiscovery::persist_addresses`] in the database, and answers queries the address
+//! cache can't answer from them, starting right after a restart.
+
+use std::{
+	collections::{HashMap, HashSet},
+	fmt,
+	sync::Arc,
+};
+
+use async_trait::async_trait;
+use parity_scale_codec::{Decode, Encode};
+use parking_lot::Mutex;
+
+use polkadot_node_network_protocol::{authority_discovery::AuthorityDiscovery, PeerId};
+use polkadot_primitives::AuthorityDiscoveryId;
+use sc_network::{config::parse_addr, Multiaddr};
+
+use crate::database::{DBTransaction, Database};
+
+const LOG_TARGET: &str = "parachain::persisted-authority-discovery";
+
+/// The database key of the persisted addresses.
+const PERSISTED_ADDRESSES_KEY: &[u8] = b"PersistedAuthorityDiscoveryAddresses";
+
+/// The addresses as stored in the database, multiaddresses in their binary representation.
+#[derive(Encode, Decode)]
+struct StoredAddresses(Vec<(AuthorityDiscoveryId, Vec<Vec<u8>>)>);
+
+#[derive(Default)]
+struct Addresses {
+	by_authority: HashMap<AuthorityDiscoveryId, HashSet<Multiaddr>>,
+	by_peer_id: HashMap<PeerId, HashSet<AuthorityDiscoveryId>>,
+}
+
+impl Addresses {
+	fn new(by_authority: HashMap<AuthorityDiscoveryId, HashSet<Multiaddr>>) -> Self {
+		let mut by_peer_id: HashMap<PeerId, HashSet<AuthorityDiscoveryId>> = HashMap::new();
+		for (authority, addrs) in &by_authority {
+			for addr in addrs {
+				if let Ok((peer_id, _)) = parse_addr(addr.clone()) {
+					by_peer_id.entry(peer_id).or_default().insert(authority.clone());
+				}
+			}
+		}
+		Self { by_authority, by_peer_id }
+	}
+
+	fn load(db: &dyn Database, column: u32) -> std::io::Result<Self> {
+		let stored = match db.get(column, PERSISTED_ADDRESSES_KEY)? {
+			Some(raw) => StoredAddresses::decode(&mut &raw[..])
+				.map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?,
+			None => return Ok(Self::default()),
+		};
+
+		let by_authority = stored
+			.0
+			.into_iter()
+			.map(|(authority, addrs)| {
+				let addrs = addrs.into_iter().filter_map(|a| Multiaddr::try_from(a).ok()).collect();
+				(authority, addrs)
+			})
+			.collect();
+		Ok(Self::new(by_authority))
+	}
+
+	fn store(&self, db: &dyn Database, column: u32) -> std::io::Result<()> {
+		let stored = StoredAddresses(
+			self.by_authority
+				.iter()
+				.map(|(authority, addrs)| {
+					(authority.clone(), addrs.iter().map(|a| a.to_vec()).collect())
+				})
+				.collect(),
+		);
+
+		let mut tx = DBTransaction::new();
+		tx.put_vec(column, PERSISTED_ADDRESSES_KEY, stored.encode());
+		db.write(tx)
+	}
+}
+
+/// An [`AuthorityDiscovery`] falling back to addresses persisted in the database.
+///
+/// Clones share the persisted addresses.
+#[derive(Clone)]
+pub struct PersistedAuthorityDiscovery<AD> {
+	inner: AD,
+	persisted: Arc<Mutex<Addresses>>,
+	db: Arc<dyn Database>,
+	column: u32,
+}
+
+impl<AD> PersistedAuthorityDiscovery<AD> {
+	/// Wrap the given authority discovery service, loading the addresses persisted in `column`
+	/// of the database.
+	pub fn new(inner: AD, db: Arc<dyn Database>, column: u32) -> Self {
+		let persisted = Addresses::load(&*db, column).unwrap_or_else(|err| {
+			gum::warn!(target: LOG_TARGET, ?err, "Failed to load persisted authority addresses");
+			Addresses::default()
+		});
+		gum::debug!(
+			target: LOG_TARGET,
+			authorities = persisted.by_authority.len(),
+			"Loaded persisted authority addresses",
+		);
+
+		Self { inner, persisted: Arc::new(Mutex::new(persisted)), db, column }
+	}
+}
+
+impl<AD: fmt::Debug> fmt::Debug for PersistedAuthorityDiscovery<AD> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PersistedAuthorityDiscovery")
+			.field("inner", &self.inner)
+			.field("column", &self.column)
+			.finish()
+	}
+}
+
+#[async_trait]
+impl<AD> AuthorityDiscovery for PersistedAuthorityDiscovery<AD>
+where
+	AD: AuthorityDiscovery,
+{
+	async fn get_addresses_by_authority_id(
+		&mut self,
+		authority: AuthorityDiscoveryId,
+	) -> Option<HashSet<Multiaddr>> {
+		match self.inner.get_addresses_by_authority_id(authority.clone()).await {
+			Some(addrs) => Some(addrs),
+			None => self.persisted.lock().by_authority.get(&authority).cloned(),
+		}
+	}
+
+	async fn get_authority_ids_by_peer_id(
+		&mut self,
+		peer_id: PeerId,
+	) -> Option<HashSet<AuthorityDiscoveryId>> {
+		match self.inner.get_authority_ids_by_peer_id(peer_id).await {
+			Some(authorities) => Some(authorities),
+			None => self.persisted.lock().by_peer_id.get(&peer_id).cloned(),
+		}
+	}
+
+	async fn persist_addresses(
+		&mut self,
+		addresses: HashMap<AuthorityDiscoveryId, HashSet<Multiaddr>>,
+	) {
+		let mut persisted = self.persisted.lock();
+		if persisted.by_authority == addresses {
+			return
+		}
+
+		*persisted = Addresses::new(addresses);
+		if let Err(err) = persisted.store(&*self.db, self.column) {
+			gum::warn!(target: LOG_TARGET, ?err, "Failed to persist authority addresses");
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::database::kvdb_impl::DbAdapter;
+	use futures::executor::block_on;
+	use sc_network::multiaddr::Protocol;
+	use sp_core::sr25519;
+
+	#[derive(Debug, Default)]
+	struct EmptyAuthorityDiscovery;
+
+	#[async_trait]
+	impl AuthorityDiscovery for EmptyAuthorityDiscovery {
+		async fn get_addresses_by_authority_id(
+			&mut self,
+			_authority: AuthorityDiscoveryId,
+		) -> Option<HashSet<Multiaddr>> {
+			None
+		}
+
+		async fn get_authority_ids_by_peer_id(
+			&mut self,
+			_peer_id: PeerId,
+		) -> Option<HashSet<AuthorityDiscoveryId>> {
+			None
+		}
+	}
+
+	fn make_db() -> Arc<dyn Database> {
+		Arc::new(DbAdapter::new(kvdb_memorydb::create(1), &[]))
+	}
+
+	#[test]
+	fn persisted_addresses_survive_restarts() {
+		let db = make_db();
+		let authority: AuthorityDiscoveryId = sr25519::Public::from_raw([1; 32]).into();
+		let peer_id = PeerId::random();
+		let addr = Multiaddr::empty().with(Protocol::P2p(peer_id.into()));
+
+		let mut discovery =
+			PersistedAuthorityDiscovery::new(EmptyAuthorityDiscovery, db.clone(), 0);
+		assert!(block_on(discovery.get_addresses_by_authority_id(authority.clone())).is_none());
+		block_on(discovery.persist_addresses(HashMap::from([(
+			authority.clone(),
+			HashSet::from([addr.clone()]),
+		)])));
+
+		// A restart with an empty address cache.
+		let mut discovery = PersistedAuthorityDiscovery::new(EmptyAuthorityDiscovery, db, 0);
+		assert_eq!(
+			block_on(discovery.get_addresses_by_authority_id(authority.clone())),
+			Some(HashSet::from([addr])),
+		);
+		assert_eq!(
+			block_on(discovery.get_authority_ids_by_peer_id(peer_id)),
+			Some(HashSet::from([authority])),
+		);
+	}
+}
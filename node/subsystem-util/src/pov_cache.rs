@@ -0,0 +1,245 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A node-wide cache of PoVs, shared by the subsystems which obtain them.
+//!
+//! The same PoV is usually fetched during backing, then again by availability distribution, and
+//! recovered once more for approval checking. Subsystems consult this cache before going to the
+//! network or to the erasure decoder. Entries are keyed by the hash of the PoV, which is computed
+//! on insertion, so a hit is always the PoV a candidate commits to.
+
+use std::sync::Arc;
+
+use lru::LruCache;
+use parking_lot::Mutex;
+
+use polkadot_node_primitives::{AvailableData, PoV};
+use polkadot_primitives::{Hash, PersistedValidationData};
+
+use crate::metrics::{
+	self,
+	prometheus::{self, CounterVec, Opts, PrometheusError, Registry, U64},
+};
+
+/// The default maximum total size of the PoVs in the cache, in bytes.
+pub const DEFAULT_POV_CACHE_SIZE: usize = 64 * 1024 * 1024;
+
+struct Entry {
+	pov: Arc<PoV>,
+	/// The persisted validation data the PoV was validated against, if known.
+	validation_data: Option<PersistedValidationData>,
+	/// The size of the PoV in bytes.
+	size: usize,
+}
+
+struct Inner {
+	entries: LruCache<Hash, Entry>,
+	/// The total size of the cached PoVs in bytes.
+	size: usize,
+}
+
+/// A size-bounded cache of PoVs, keyed by their hash.
+///
+/// Clones share the same underlying cache.
+#[derive(Clone)]
+pub struct PoVCache {
+	inner: Arc<Mutex<Inner>>,
+	/// The maximum total size of the cached PoVs in bytes.
+	max_size: usize,
+	metrics: Metrics,
+}
+
+impl PoVCache {
+	/// Create a new cache holding PoVs of at most `max_size` bytes in total.
+	///
+	/// A `max_size` of zero disables caching.
+	pub fn new(max_size: usize, metrics: Metrics) -> Self {
+		let inner = Inner { entries: LruCache::unbounded(), size: 0 };
+		Self { inner: Arc::new(Mutex::new(inner)), max_size, metrics }
+	}
+
+	/// Get the PoV with the given hash, if cached.
+	///
+	/// `subsystem` is the name of the consulting subsystem, used for the hit-rate metrics.
+	pub fn get(&self, pov_hash: &Hash, subsystem: &'static str) -> Option<Arc<PoV>> {
+		let pov = self.inner.lock().entries.get(pov_hash).map(|entry| entry.pov.clone());
+		self.metrics.on_lookup(subsystem, pov.is_some());
+		pov
+	}
+
+	/// Get the available data made of the PoV with the given hash and the persisted validation
+	/// data with the given hash, if both are cached.
+	///
+	/// `subsystem` is the name of the consulting subsystem, used for the hit-rate metrics.
+	pub fn get_available_data(
+		&self,
+		pov_hash: &Hash,
+		validation_data_hash: &Hash,
+		subsystem: &'static str,
+	) -> Option<AvailableData> {
+		let data = self.inner.lock().entries.get(pov_hash).and_then(|entry| {
+			let validation_data = entry
+				.validation_data
+				.as_ref()
+				.filter(|validation_data| validation_data.hash() == *validation_data_hash)?;
+			Some(AvailableData { pov: entry.pov.clone(), validation_data: validation_data.clone() })
+		});
+		self.metrics.on_lookup(subsystem, data.is_some());
+		data
+	}
+
+	/// Insert a PoV, along with the persisted validation data it was validated against if known.
+	///
+	/// The least recently used PoVs are evicted to stay within the maximum size. PoVs larger
+	/// than the maximum size are not cached.
+	pub fn insert(&self, pov: Arc<PoV>, validation_data: Option<PersistedValidationData>) {
+		let size = pov.block_data.0.len();
+		if self.max_size == 0 || size > self.max_size {
+			return
+		}
+		let pov_hash = pov.hash();
+
+		let mut inner = self.inner.lock();
+		if let Some(entry) = inner.entries.get_mut(&pov_hash) {
+			if validation_data.is_some() {
+				entry.validation_data = validation_data;
+			}
+			return
+		}
+
+		inner.size += size;
+		inner.entries.put(pov_hash, Entry { pov, validation_data, size });
+		while inner.size > self.max_size {
+			match inner.entries.pop_lru() {
+				Some((_, evicted)) => inner.size -= evicted.size,
+				None => break,
+			}
+		}
+	}
+}
+
+#[derive(Clone)]
+struct MetricsInner {
+	hits: CounterVec<U64>,
+	misses: CounterVec<U64>,
+}
+
+/// PoV cache metrics.
+#[derive(Clone, Default)]
+pub struct Metrics(Option<MetricsInner>);
+
+impl Metrics {
+	fn on_lookup(&self, subsystem: &'static str, hit: bool) {
+		if let Some(metrics) = &self.0 {
+			let counter = if hit { &metrics.hits } else { &metrics.misses };
+			counter.with_label_values(&[subsystem]).inc();
+		}
+	}
+}
+
+impl metrics::Metrics for Metrics {
+	fn try_register(registry: &Registry) -> Result<Self, PrometheusError> {
+		let metrics = MetricsInner {
+			hits: prometheus::register(
+				CounterVec::new(
+					Opts::new(
+						"polkadot_parachain_pov_cache_hits_total",
+						"Number of PoV cache lookups which found the PoV.",
+					),
+					&["subsystem"],
+				)?,
+				registry,
+			)?,
+			misses: prometheus::register(
+				CounterVec::new(
+					Opts::new(
+						"polkadot_parachain_pov_cache_misses_total",
+						"Number of PoV cache lookups which didn't find the PoV.",
+					),
+					&["subsystem"],
+				)?,
+				registry,
+			)?,
+		};
+		Ok(Metrics(Some(metrics)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_node_primitives::BlockData;
+
+	fn pov(byte: u8, size: usize) -> Arc<PoV> {
+		Arc::new(PoV { block_data: BlockData(vec![byte; size]) })
+	}
+
+	fn validation_data() -> PersistedValidationData {
+		PersistedValidationData {
+			parent_head: vec![1, 2, 3].into(),
+			relay_parent_number: 1,
+			relay_parent_storage_root: Hash::repeat_byte(1),
+			max_pov_size: 1024,
+		}
+	}
+
+	#[test]
+	fn evicts_least_recently_used() {
+		let cache = PoVCache::new(30, Metrics::default());
+		let (a, b, c) = (pov(1, 10), pov(2, 10), pov(3, 15));
+
+		cache.insert(a.clone(), None);
+		cache.insert(b.clone(), None);
+		assert!(cache.get(&a.hash(), "test").is_some());
+
+		// `b` is the least recently used.
+		cache.insert(c.clone(), None);
+		assert!(cache.get(&b.hash(), "test").is_none());
+		assert!(cache.get(&a.hash(), "test").is_some());
+		assert!(cache.get(&c.hash(), "test").is_some());
+
+		// Too large to be cached.
+		let d = pov(4, 31);
+		cache.insert(d.clone(), None);
+		assert!(cache.get(&d.hash(), "test").is_none());
+		assert!(cache.get(&a.hash(), "test").is_some());
+	}
+
+	#[test]
+	fn available_data_needs_matching_validation_data() {
+		let cache = PoVCache::new(DEFAULT_POV_CACHE_SIZE, Metrics::default());
+		let pov = pov(1, 10);
+		let validation_data = validation_data();
+
+		cache.insert(pov.clone(), None);
+		assert!(cache.get_available_data(&pov.hash(), &validation_data.hash(), "test").is_none());
+
+		cache.insert(pov.clone(), Some(validation_data.clone()));
+		assert!(cache.get_available_data(&pov.hash(), &Hash::zero(), "test").is_none());
+		assert_eq!(
+			cache.get_available_data(&pov.hash(), &validation_data.hash(), "test"),
+			Some(AvailableData { pov, validation_data }),
+		);
+	}
+
+	#[test]
+	fn disabled_cache_is_empty() {
+		let cache = PoVCache::new(0, Metrics::default());
+		let pov = pov(1, 10);
+		cache.insert(pov.clone(), None);
+		assert!(cache.get(&pov.hash(), "test").is_none());
+	}
+}
@@ -47,18 +47,29 @@ pub type LongestChain = sc_consensus::LongestChain<FullBackend, Block>;
 
 /// Parameters of test-client builder with test-runtime.
 #[derive(Default)]
-pub struct GenesisParameters;
+pub struct GenesisParameters {
+	extra_storage: Storage,
+}
 
 impl substrate_test_client::GenesisInit for GenesisParameters {
 	fn genesis_storage(&self) -> Storage {
-		polkadot_test_service::chain_spec::polkadot_local_testnet_genesis()
+		let mut storage = polkadot_test_service::chain_spec::polkadot_local_testnet_genesis()
 			.build_storage()
-			.expect("Builds test runtime genesis storage")
+			.expect("Builds test runtime genesis storage");
+		storage.top.extend(self.extra_storage.top.clone());
+		storage
 	}
 }
 
 /// A `test-runtime` extensions to `TestClientBuilder`.
 pub trait TestClientBuilderExt: Sized {
+	/// Add an extra value into the genesis storage.
+	///
+	/// # Panics
+	///
+	/// Panics if the key is empty.
+	fn add_extra_storage<K: Into<Vec<u8>>, V: Into<Vec<u8>>>(self, key: K, value: V) -> Self;
+
 	/// Build the test client.
 	fn build(self) -> Client {
 		self.build_with_longest_chain().0
@@ -69,6 +80,13 @@ pub trait TestClientBuilderExt: Sized {
 }
 
 impl TestClientBuilderExt for TestClientBuilder {
+	fn add_extra_storage<K: Into<Vec<u8>>, V: Into<Vec<u8>>>(mut self, key: K, value: V) -> Self {
+		let key = key.into();
+		assert!(!key.is_empty());
+		self.genesis_init_mut().extra_storage.top.insert(key, value.into());
+		self
+	}
+
 	fn build_with_longest_chain(self) -> (Client, LongestChain) {
 		let executor = WasmExecutor::builder().build();
 		let executor = client::LocalCallExecutor::new(
@@ -91,6 +91,8 @@ pub fn new_full(
 			overseer_message_channel_capacity_override: None,
 			malus_finality_delay: None,
 			hwbench: None,
+			pov_compression: None,
+			collation_peer_rate_limit: None,
 		},
 	)
 }
@@ -76,6 +76,8 @@ fn main() -> Result<()> {
 						overseer_message_channel_capacity_override: None,
 						malus_finality_delay: None,
 						hwbench: None,
+						pov_compression: None,
+						collation_peer_rate_limit: None,
 					},
 				)
 				.map_err(|e| e.to_string())?;
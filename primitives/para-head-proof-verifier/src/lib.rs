@@ -0,0 +1,379 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Verification of parachain head proofs.
+//!
+//! Full nodes serve the head of a parachain at a given relay chain block, together with the
+//! storage proof of the `paras::Heads` entry and optionally the GRANDPA justification finalizing
+//! the block. This crate allows light clients, including ones running in a runtime, to check such
+//! a [`ParaHeadProof`] without trusting the node serving it.
+
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_consensus_grandpa::{
+	AuthorityId, AuthorityList, AuthoritySignature, RoundNumber, SetId, GRANDPA_ENGINE_ID,
+};
+use sp_runtime::{traits::Header as HeaderT, ConsensusEngineId, RuntimeAppPublic};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	prelude::*,
+	vec,
+};
+use sp_trie::{trie_types::TrieDBBuilder, MemoryDB, Trie, EMPTY_PREFIX};
+
+use polkadot_primitives::{
+	well_known_keys, BlakeTwo256, BlockNumber, Hash, HeadData, Header, Id as ParaId,
+};
+
+/// The head of a parachain at a relay chain block, along with everything needed to verify it.
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct ParaHeadProof {
+	/// The header of the relay chain block the head was read at.
+	pub relay_header: Header,
+	/// The trie nodes proving the `paras::Heads` entry of the parachain against the state root
+	/// of `relay_header`.
+	pub storage_proof: Vec<Vec<u8>>,
+	/// The encoded GRANDPA justification finalizing `relay_header`, if requested and available.
+	pub justification: Option<Vec<u8>>,
+}
+
+/// The engine id of the justifications in [`ParaHeadProof::justification`].
+pub const JUSTIFICATION_ENGINE_ID: ConsensusEngineId = GRANDPA_ENGINE_ID;
+
+/// Errors of the verification of a [`ParaHeadProof`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+	/// The relay chain header doesn't hash to the expected block hash.
+	HeaderMismatch,
+	/// The storage proof lacks nodes needed to read the head of the parachain.
+	InvalidStorageProof,
+	/// The stored value isn't valid head data.
+	InvalidHeadData,
+	/// A justification was required but the proof doesn't contain one.
+	MissingJustification,
+	/// The justification couldn't be decoded.
+	InvalidJustificationEncoding,
+	/// The justification finalizes a different block.
+	JustificationTargetMismatch,
+	/// A precommit was signed by a key outside of the authority set.
+	UnknownSigner,
+	/// A precommit signature is invalid.
+	InvalidSignature,
+	/// A precommit target doesn't descend from the finalized block, or the justification
+	/// contains ancestry headers which aren't needed.
+	InvalidAncestry,
+	/// The precommits don't carry a supermajority of the authority set weight.
+	NotEnoughWeight,
+}
+
+/// A GRANDPA justification, encoded the same way as the ones the relay chain nodes store.
+#[derive(Encode, Decode)]
+struct GrandpaJustification {
+	round: RoundNumber,
+	commit: grandpa::Commit<Hash, BlockNumber, AuthoritySignature, AuthorityId>,
+	votes_ancestries: Vec<Header>,
+}
+
+/// Verify the storage proof of a [`ParaHeadProof`] for the relay chain block `relay_block_hash`.
+///
+/// Yields the head of the parachain at that block, or `None` if the parachain had no head. This
+/// doesn't check that the block is finalized, see [`verify_finalized_para_head_proof`].
+pub fn verify_para_head_proof(
+	para_id: ParaId,
+	relay_block_hash: Hash,
+	proof: &ParaHeadProof,
+) -> Result<Option<HeadData>, Error> {
+	if proof.relay_header.hash() != relay_block_hash {
+		return Err(Error::HeaderMismatch)
+	}
+
+	let mut trie_storage: MemoryDB<BlakeTwo256> = MemoryDB::default();
+	for node in &proof.storage_proof {
+		(&mut trie_storage as &mut sp_trie::HashDB<_>).insert(EMPTY_PREFIX, node);
+	}
+
+	let state_root = proof.relay_header.state_root;
+	let trie = TrieDBBuilder::new(&trie_storage, &state_root).build();
+	match trie.get(&well_known_keys::para_head(para_id)) {
+		Ok(Some(raw)) =>
+			HeadData::decode(&mut &raw[..]).map(Some).map_err(|_| Error::InvalidHeadData),
+		Ok(None) => Ok(None),
+		Err(_) => Err(Error::InvalidStorageProof),
+	}
+}
+
+/// Verify a [`ParaHeadProof`] including its justification, against the GRANDPA authority set
+/// `set_id` with the given authorities.
+///
+/// Yields the head of the parachain at the finalized relay chain block `relay_block_hash`.
+pub fn verify_finalized_para_head_proof(
+	para_id: ParaId,
+	relay_block_hash: Hash,
+	proof: &ParaHeadProof,
+	set_id: SetId,
+	authorities: &AuthorityList,
+) -> Result<Option<HeadData>, Error> {
+	let head = verify_para_head_proof(para_id, relay_block_hash, proof)?;
+	let justification = proof.justification.as_ref().ok_or(Error::MissingJustification)?;
+	verify_justification(&proof.relay_header, justification, set_id, authorities)?;
+	Ok(head)
+}
+
+/// Verify that the encoded GRANDPA `justification` finalizes `relay_header` in the authority set
+/// `set_id` with the given authorities.
+pub fn verify_justification(
+	relay_header: &Header,
+	justification: &[u8],
+	set_id: SetId,
+	authorities: &AuthorityList,
+) -> Result<(), Error> {
+	let justification = GrandpaJustification::decode(&mut &justification[..])
+		.map_err(|_| Error::InvalidJustificationEncoding)?;
+	let commit = &justification.commit;
+	if commit.target_hash != relay_header.hash() || commit.target_number != relay_header.number {
+		return Err(Error::JustificationTargetMismatch)
+	}
+
+	let ancestry: BTreeMap<Hash, &Header> = justification
+		.votes_ancestries
+		.iter()
+		.map(|header| (header.hash(), header))
+		.collect();
+	let mut visited_ancestry = BTreeSet::new();
+	let mut counted = vec![false; authorities.len()];
+	let mut weight = 0u64;
+
+	for signed in &commit.precommits {
+		let position = authorities
+			.iter()
+			.position(|(id, _)| id == &signed.id)
+			.ok_or(Error::UnknownSigner)?;
+
+		let message = grandpa::Message::Precommit(signed.precommit.clone());
+		let payload =
+			sp_consensus_grandpa::localized_payload(justification.round, set_id, &message);
+		if !signed.id.verify(&payload, &signed.signature) {
+			return Err(Error::InvalidSignature)
+		}
+
+		// Walk from the precommit target back to the finalized block.
+		let mut current = signed.precommit.target_hash;
+		while current != commit.target_hash {
+			let header = ancestry.get(&current).ok_or(Error::InvalidAncestry)?;
+			if header.number <= commit.target_number {
+				return Err(Error::InvalidAncestry)
+			}
+			visited_ancestry.insert(current);
+			current = header.parent_hash;
+		}
+
+		// Equivocating authorities may have several precommits, they are only counted once.
+		if !counted[position] {
+			counted[position] = true;
+			weight = weight.saturating_add(authorities[position].1);
+		}
+	}
+
+	if visited_ancestry.len() != ancestry.len() {
+		return Err(Error::InvalidAncestry)
+	}
+
+	let total_weight = authorities.iter().fold(0u64, |total, (_, w)| total.saturating_add(*w));
+	let threshold = total_weight - total_weight.saturating_sub(1) / 3;
+	if weight < threshold {
+		return Err(Error::NotEnoughWeight)
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_keyring::Ed25519Keyring;
+	use sp_runtime::Digest;
+	use sp_trie::{
+		trie_types::TrieDBMutBuilderV0 as TrieDBMutBuilder, LayoutV0, Recorder, TrieMut,
+	};
+
+	const PARA_ID: ParaId = ParaId::new(2000);
+
+	fn head_data() -> HeadData {
+		HeadData(vec![1, 2, 3, 4])
+	}
+
+	fn relay_header(number: BlockNumber, parent_hash: Hash, state_root: Hash) -> Header {
+		Header::new(number, Default::default(), state_root, parent_hash, Digest::default())
+	}
+
+	/// A relay chain header committing to a state holding the head of `PARA_ID`, and the proof
+	/// of the head.
+	fn make_proof() -> ParaHeadProof {
+		let key = well_known_keys::para_head(PARA_ID);
+		let mut trie_storage: MemoryDB<BlakeTwo256> = MemoryDB::default();
+		let mut state_root = Hash::default();
+		{
+			let mut trie = TrieDBMutBuilder::new(&mut trie_storage, &mut state_root).build();
+			trie.insert(&key, &head_data().encode()).unwrap();
+			trie.insert(&well_known_keys::para_head(ParaId::new(2001)), &[5; 64]).unwrap();
+		}
+
+		let mut recorder = Recorder::<LayoutV0<BlakeTwo256>>::new();
+		{
+			let trie = TrieDBBuilder::new(&trie_storage, &state_root)
+				.with_recorder(&mut recorder)
+				.build();
+			trie.get(&key).unwrap().unwrap();
+		}
+		let storage_proof = recorder.drain().into_iter().map(|r| r.data).collect();
+
+		ParaHeadProof {
+			relay_header: relay_header(10, Hash::repeat_byte(9), state_root),
+			storage_proof,
+			justification: None,
+		}
+	}
+
+	fn authorities() -> AuthorityList {
+		[Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie]
+			.iter()
+			.map(|keyring| (keyring.public().into(), 1))
+			.collect()
+	}
+
+	fn make_justification(
+		target: &Header,
+		signers: &[Ed25519Keyring],
+		set_id: SetId,
+		votes_ancestries: Vec<Header>,
+		precommit_target: &Header,
+	) -> Vec<u8> {
+		let round = 1;
+		let precommit = grandpa::Precommit {
+			target_hash: precommit_target.hash(),
+			target_number: precommit_target.number,
+		};
+		let message = grandpa::Message::Precommit(precommit.clone());
+		let payload = sp_consensus_grandpa::localized_payload(round, set_id, &message);
+		let precommits = signers
+			.iter()
+			.map(|signer| grandpa::SignedPrecommit {
+				precommit: precommit.clone(),
+				signature: signer.sign(&payload).into(),
+				id: signer.public().into(),
+			})
+			.collect();
+
+		GrandpaJustification {
+			round,
+			commit: grandpa::Commit {
+				target_hash: target.hash(),
+				target_number: target.number,
+				precommits,
+			},
+			votes_ancestries,
+		}
+		.encode()
+	}
+
+	#[test]
+	fn valid_storage_proof_yields_head() {
+		let proof = make_proof();
+		let hash = proof.relay_header.hash();
+
+		assert_eq!(verify_para_head_proof(PARA_ID, hash, &proof), Ok(Some(head_data())));
+		assert_eq!(verify_para_head_proof(ParaId::new(3000), hash, &proof), Ok(None));
+	}
+
+	#[test]
+	fn invalid_storage_proofs_are_rejected() {
+		let proof = make_proof();
+
+		assert_eq!(
+			verify_para_head_proof(PARA_ID, Hash::repeat_byte(1), &proof),
+			Err(Error::HeaderMismatch),
+		);
+
+		let mut truncated = proof.clone();
+		truncated.storage_proof.pop();
+		assert_eq!(
+			verify_para_head_proof(PARA_ID, proof.relay_header.hash(), &truncated),
+			Err(Error::InvalidStorageProof),
+		);
+	}
+
+	#[test]
+	fn justifications_need_a_supermajority() {
+		let mut proof = make_proof();
+		let hash = proof.relay_header.hash();
+		let header = proof.relay_header.clone();
+		let all = [Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie];
+
+		assert_eq!(
+			verify_finalized_para_head_proof(PARA_ID, hash, &proof, 0, &authorities()),
+			Err(Error::MissingJustification),
+		);
+
+		proof.justification = Some(make_justification(&header, &all, 0, Vec::new(), &header));
+		assert_eq!(
+			verify_finalized_para_head_proof(PARA_ID, hash, &proof, 0, &authorities()),
+			Ok(Some(head_data())),
+		);
+		// Signatures are bound to the set id.
+		assert_eq!(
+			verify_finalized_para_head_proof(PARA_ID, hash, &proof, 1, &authorities()),
+			Err(Error::InvalidSignature),
+		);
+
+		let justification = make_justification(&header, &all[..2], 0, Vec::new(), &header);
+		assert_eq!(
+			verify_justification(&header, &justification, 0, &authorities()),
+			Err(Error::NotEnoughWeight),
+		);
+
+		let justification =
+			make_justification(&header, &[Ed25519Keyring::Dave], 0, Vec::new(), &header);
+		assert_eq!(
+			verify_justification(&header, &justification, 0, &authorities()),
+			Err(Error::UnknownSigner),
+		);
+	}
+
+	#[test]
+	fn precommits_for_descendants_need_ancestry() {
+		let header = make_proof().relay_header;
+		let child = relay_header(header.number + 1, header.hash(), Default::default());
+		let all = [Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie];
+
+		let justification = make_justification(&header, &all, 0, vec![child.clone()], &child);
+		assert_eq!(verify_justification(&header, &justification, 0, &authorities()), Ok(()));
+
+		let justification = make_justification(&header, &all, 0, Vec::new(), &child);
+		assert_eq!(
+			verify_justification(&header, &justification, 0, &authorities()),
+			Err(Error::InvalidAncestry),
+		);
+
+		// Unneeded ancestry headers are rejected.
+		let justification = make_justification(&header, &all, 0, vec![child], &header);
+		assert_eq!(
+			verify_justification(&header, &justification, 0, &authorities()),
+			Err(Error::InvalidAncestry),
+		);
+	}
+}
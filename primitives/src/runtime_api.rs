@@ -114,7 +114,7 @@
 //! separated from the stable primitives.
 
 use crate::{
-	vstaging, BlockNumber, CandidateCommitments, CandidateEvent, CandidateHash,
+	vstaging, BlockNumber, CandidateCommitments, CandidateEvent, CandidateHash, CollatorId,
 	CommittedCandidateReceipt, CoreState, DisputeState, ExecutorParams, GroupRotationInfo,
 	OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes,
 	SessionIndex, SessionInfo, ValidatorId, ValidatorIndex, ValidatorSignature,
@@ -250,5 +250,22 @@ sp_api::decl_runtime_apis! {
 		/// Returns candidate's acceptance limitations for asynchronous backing for a relay parent.
 		#[api_version(99)]
 		fn staging_async_backing_params() -> vstaging::AsyncBackingParams;
+
+		/***** Collator allow-lists *****/
+
+		/// Returns the collators allowed to collate for the given para, if the para restricts its
+		/// collators. `None` means that any collator may collate for the para.
+		/// This is a staging method! Do not use on production runtimes!
+		#[api_version(99)]
+		fn staging_authorized_collators(para_id: ppp::Id) -> Option<Vec<CollatorId>>;
+
+		/***** Escalating dispute slashes *****/
+
+		/// Returns a list of validators that lost a past session dispute and need to be slashed,
+		/// with the fraction of their exposure to slash.
+		/// This is a staging method! Do not use on production runtimes!
+		#[api_version(99)]
+		fn staging_unapplied_slashes_with_fraction(
+		) -> Vec<(SessionIndex, CandidateHash, vstaging::slashing::PendingSlashesWithFraction)>;
 	}
 }
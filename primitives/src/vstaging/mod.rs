@@ -24,6 +24,8 @@ use parity_scale_codec::{Decode, Encode};
 use primitives::RuntimeDebug;
 use scale_info::TypeInfo;
 
+pub mod slashing;
+
 /// Useful type alias for Para IDs.
 pub type ParaId = Id;
 
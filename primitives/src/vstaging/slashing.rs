@@ -0,0 +1,45 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Staging primitives types used for dispute slashing.
+
+pub use crate::v5::slashing::*;
+
+use crate::{ValidatorId, ValidatorIndex};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_arithmetic::Perbill;
+use sp_std::collections::btree_map::BTreeMap;
+
+/// Slashes that are waiting to be applied once we have validator key
+/// identification, together with the fraction they slash.
+#[derive(Encode, Decode, TypeInfo, Debug, Clone, PartialEq, Eq)]
+pub struct PendingSlashesWithFraction {
+	/// Indices and keys of the validators who lost a dispute and are pending
+	/// slashes.
+	pub keys: BTreeMap<ValidatorIndex, ValidatorId>,
+	/// The dispute outcome.
+	pub kind: SlashingOffenceKind,
+	/// The fraction of the offenders' exposure that will be slashed, as given by
+	/// the slash curve when the dispute concluded.
+	pub slash_fraction: Perbill,
+}
+
+impl From<PendingSlashesWithFraction> for PendingSlashes {
+	fn from(pending: PendingSlashesWithFraction) -> Self {
+		PendingSlashes { keys: pending.keys, kind: pending.kind }
+	}
+}
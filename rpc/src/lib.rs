@@ -36,6 +36,8 @@ use sp_consensus_babe::BabeApi;
 use sp_keystore::KeystorePtr;
 use txpool_api::TransactionPool;
 
+pub mod parachain_network;
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = RpcModule<()>;
 
@@ -91,11 +93,24 @@ pub struct FullDeps<C, P, SC, B> {
 	pub beefy: BeefyDeps,
 	/// Backend used by the node.
 	pub backend: Arc<B>,
+	/// Bandwidth accounting of the parachain protocols.
+	pub network_bandwidth: Arc<dyn parachain_network::NetworkBandwidth>,
 }
 
 /// Instantiate all RPC extensions.
 pub fn create_full<C, P, SC, B>(
-	FullDeps { client, pool, select_chain, chain_spec, deny_unsafe, babe, grandpa, beefy, backend } : FullDeps<C, P, SC, B>,
+	FullDeps {
+		client,
+		pool,
+		select_chain,
+		chain_spec,
+		deny_unsafe,
+		babe,
+		grandpa,
+		beefy,
+		backend,
+		network_bandwidth,
+	}: FullDeps<C, P, SC, B>,
 ) -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>
@@ -118,6 +133,7 @@ where
 	use frame_rpc_system::{System, SystemApiServer};
 	use mmr_rpc::{Mmr, MmrApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use parachain_network::{ParachainNetwork, ParachainNetworkApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
 	use sc_consensus_beefy_rpc::{Beefy, BeefyApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
@@ -173,5 +189,7 @@ where
 		.into_rpc(),
 	)?;
 
+	io.merge(ParachainNetwork::new(network_bandwidth, deny_unsafe).into_rpc())?;
+
 	Ok(io)
 }
@@ -0,0 +1,85 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC exposing the bandwidth accounting of the parachain protocols.
+
+use std::sync::Arc;
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sc_rpc::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+
+/// The number of peers returned by `parachainNetwork_topTalkers` if no count is given.
+const DEFAULT_TOP_TALKERS: usize = 10;
+
+/// The maximum number of peers returned by `parachainNetwork_topTalkers`.
+const MAX_TOP_TALKERS: usize = 1000;
+
+/// The traffic exchanged with a peer on a parachain protocol.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopTalker {
+	/// The base58 encoded peer id.
+	pub peer_id: String,
+	/// The protocol the traffic was exchanged on: a peer-set for notifications, a request
+	/// protocol for requests and their responses.
+	pub protocol: String,
+	/// The number of bytes received from the peer.
+	pub bytes_received: u64,
+	/// The number of bytes sent to the peer.
+	pub bytes_sent: u64,
+}
+
+/// The bandwidth accounting of the parachain protocols, as provided by the node.
+pub trait NetworkBandwidth: Send + Sync {
+	/// The `count` connected peers which exchanged the most bytes with this node on a parachain
+	/// protocol, in descending order.
+	fn top_talkers(&self, count: usize) -> Vec<TopTalker>;
+}
+
+/// Parachain networking RPC methods.
+#[rpc(server)]
+pub trait ParachainNetworkApi {
+	/// The connected peers which exchanged the most bytes with this node on a parachain protocol,
+	/// in descending order.
+	///
+	/// This method is unsafe.
+	#[method(name = "parachainNetwork_topTalkers")]
+	fn top_talkers(&self, count: Option<u32>) -> RpcResult<Vec<TopTalker>>;
+}
+
+/// Implements the [`ParachainNetworkApiServer`] RPC trait.
+pub struct ParachainNetwork {
+	bandwidth: Arc<dyn NetworkBandwidth>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl ParachainNetwork {
+	/// Create a new instance of the parachain networking RPC.
+	pub fn new(bandwidth: Arc<dyn NetworkBandwidth>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { bandwidth, deny_unsafe }
+	}
+}
+
+impl ParachainNetworkApiServer for ParachainNetwork {
+	fn top_talkers(&self, count: Option<u32>) -> RpcResult<Vec<TopTalker>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let count =
+			count.map_or(DEFAULT_TOP_TALKERS, |count| (count as usize).min(MAX_TOP_TALKERS));
+		Ok(self.bandwidth.top_talkers(count))
+	}
+}
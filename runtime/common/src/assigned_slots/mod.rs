@@ -756,6 +756,7 @@ mod tests {
 		type LeasePeriod = LeasePeriod;
 		type LeaseOffset = LeaseOffset;
 		type ForceOrigin = EnsureRoot<Self::AccountId>;
+		type Auctioneer = crate::mock::TestAuctioneer<Test, Balances>;
 		type WeightInfo = crate::slots::TestWeightInfo;
 	}
 
@@ -313,6 +313,23 @@ pub mod pallet {
 	}
 }
 
+impl<T: Config> crate::traits::SlotClaim for Pallet<T> {
+	fn has_slot_claim(id: ParaId) -> bool {
+		// At most `EndingPeriod` / `SampleLength` samples are stored for the ongoing auction.
+		Winning::<T>::iter_values()
+			.any(|winning| winning.iter().flatten().any(|(_, para, _)| *para == id))
+	}
+
+	fn note_slot_claims(claims: &mut sp_std::collections::btree_set::BTreeSet<ParaId>) -> u64 {
+		let mut reads = 0u64;
+		for winning in Winning::<T>::iter_values() {
+			reads.saturating_inc();
+			claims.extend(winning.iter().flatten().map(|(_, para, _)| *para));
+		}
+		reads
+	}
+}
+
 impl<T: Config> Auctioneer<BlockNumberFor<T>> for Pallet<T> {
 	type AccountId = T::AccountId;
 	type LeasePeriod = BlockNumberFor<T>;
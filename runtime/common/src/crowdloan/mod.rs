@@ -48,8 +48,14 @@
 //! Successful funds remain tracked (in the `Funds` storage item and the associated child trie) as
 //! long as the parachain remains active. Users can withdraw their funds once the slot is completed
 //! and funds are returned to the crowdloan account.
+//!
+//! If the fund was created with `create_transferable`, a contribution acts as a receipt which may
+//! be transferred to another account with `transfer_contribution`; refunds and withdrawals are then
+//! paid to the new holder. The contributions of a fund can be enumerated off-chain through the
+//! [`runtime_api::CrowdloanApi`].
 
 pub mod migration;
+pub mod runtime_api;
 
 use crate::{
 	slot_range::SlotRange,
@@ -75,7 +81,7 @@ use sp_runtime::{
 	traits::{
 		AccountIdConversion, CheckedAdd, Hash, IdentifyAccount, One, Saturating, Verify, Zero,
 	},
-	MultiSignature, MultiSigner, RuntimeDebug,
+	DispatchError, MultiSignature, MultiSigner, RuntimeDebug,
 };
 use sp_std::vec::Vec;
 
@@ -91,6 +97,7 @@ type FundIndex = u32;
 
 pub trait WeightInfo {
 	fn create() -> Weight;
+	fn create_transferable() -> Weight;
 	fn contribute() -> Weight;
 	fn withdraw() -> Weight;
 	fn refund(k: u32) -> Weight;
@@ -99,6 +106,7 @@ pub trait WeightInfo {
 	fn add_memo() -> Weight;
 	fn on_initialize(n: u32) -> Weight;
 	fn poke() -> Weight;
+	fn transfer_contribution() -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -106,6 +114,9 @@ impl WeightInfo for TestWeightInfo {
 	fn create() -> Weight {
 		Weight::zero()
 	}
+	fn create_transferable() -> Weight {
+		Weight::zero()
+	}
 	fn contribute() -> Weight {
 		Weight::zero()
 	}
@@ -130,6 +141,9 @@ impl WeightInfo for TestWeightInfo {
 	fn poke() -> Weight {
 		Weight::zero()
 	}
+	fn transfer_contribution() -> Weight {
+		Weight::zero()
+	}
 }
 
 #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
@@ -253,6 +267,10 @@ pub mod pallet {
 	#[pallet::getter(fn next_fund_index)]
 	pub(super) type NextFundIndex<T> = StorageValue<_, u32, ValueQuery>;
 
+	/// The funds whose contributions may be transferred, keyed by fund index.
+	#[pallet::storage]
+	pub(super) type TransferableFunds<T> = StorageMap<_, Twox64Concat, FundIndex, ()>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -277,6 +295,13 @@ pub mod pallet {
 		MemoUpdated { who: T::AccountId, para_id: ParaId, memo: Vec<u8> },
 		/// A parachain has been moved to `NewRaise`
 		AddedToNewRaise { para_id: ParaId },
+		/// A contribution has been transferred to another account.
+		ContributionTransferred {
+			from: T::AccountId,
+			to: T::AccountId,
+			para_id: ParaId,
+			amount: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -328,6 +353,10 @@ pub mod pallet {
 		VrfDelayInProgress,
 		/// A lease period has not started yet, due to an offset in the starting block.
 		NoLeasePeriod,
+		/// A contribution cannot be transferred to the account holding it.
+		SelfTransfer,
+		/// The contributions to this crowdloan cannot be transferred.
+		NotTransferable,
 	}
 
 	#[pallet::hooks]
@@ -383,68 +412,7 @@ pub mod pallet {
 			verifier: Option<MultiSigner>,
 		) -> DispatchResult {
 			let depositor = ensure_signed(origin)?;
-			let now = frame_system::Pallet::<T>::block_number();
-
-			ensure!(first_period <= last_period, Error::<T>::LastPeriodBeforeFirstPeriod);
-			let last_period_limit = first_period
-				.checked_add(&((SlotRange::LEASE_PERIODS_PER_SLOT as u32) - 1).into())
-				.ok_or(Error::<T>::FirstPeriodTooFarInFuture)?;
-			ensure!(last_period <= last_period_limit, Error::<T>::LastPeriodTooFarInFuture);
-			ensure!(end > now, Error::<T>::CannotEndInPast);
-
-			// Here we check the lease period on the ending block is at most the first block of the
-			// period after `first_period`. If it would be larger, there is no way we could win an
-			// active auction, thus it would make no sense to have a crowdloan this long.
-			let (lease_period_at_end, is_first_block) =
-				T::Auctioneer::lease_period_index(end).ok_or(Error::<T>::NoLeasePeriod)?;
-			let adjusted_lease_period_at_end = if is_first_block {
-				lease_period_at_end.saturating_sub(One::one())
-			} else {
-				lease_period_at_end
-			};
-			ensure!(adjusted_lease_period_at_end <= first_period, Error::<T>::EndTooFarInFuture);
-
-			// Can't start a crowdloan for a lease period that already passed.
-			if let Some((current_lease_period, _)) = T::Auctioneer::lease_period_index(now) {
-				ensure!(first_period >= current_lease_period, Error::<T>::FirstPeriodInPast);
-			}
-
-			// There should not be an existing fund.
-			ensure!(!Funds::<T>::contains_key(index), Error::<T>::FundNotEnded);
-
-			let manager = T::Registrar::manager_of(index).ok_or(Error::<T>::InvalidParaId)?;
-			ensure!(depositor == manager, Error::<T>::InvalidOrigin);
-			ensure!(T::Registrar::is_registered(index), Error::<T>::InvalidParaId);
-
-			let fund_index = Self::next_fund_index();
-			let new_fund_index = fund_index.checked_add(1).ok_or(Error::<T>::Overflow)?;
-
-			let deposit = T::SubmissionDeposit::get();
-
-			frame_system::Pallet::<T>::inc_providers(&Self::fund_account_id(fund_index));
-			CurrencyOf::<T>::reserve(&depositor, deposit)?;
-
-			Funds::<T>::insert(
-				index,
-				FundInfo {
-					depositor,
-					verifier,
-					deposit,
-					raised: Zero::zero(),
-					end,
-					cap,
-					last_contribution: LastContribution::Never,
-					first_period,
-					last_period,
-					fund_index,
-				},
-			);
-
-			NextFundIndex::<T>::put(new_fund_index);
-			// Add a lock to the para so that the configuration cannot be changed.
-			T::Registrar::apply_lock(index);
-
-			Self::deposit_event(Event::<T>::Created { para_id: index });
+			Self::do_create(depositor, index, cap, first_period, last_period, end, verifier)?;
 			Ok(())
 		}
 
@@ -581,6 +549,7 @@ pub mod pallet {
 			frame_system::Pallet::<T>::dec_providers(&Self::fund_account_id(fund.fund_index))?;
 			CurrencyOf::<T>::unreserve(&fund.depositor, fund.deposit);
 			Funds::<T>::remove(index);
+			TransferableFunds::<T>::remove(fund.fund_index);
 			Self::deposit_event(Event::<T>::Dissolved { para_id: index });
 			Ok(())
 		}
@@ -671,10 +640,157 @@ pub mod pallet {
 			let value = CurrencyOf::<T>::free_balance(&who);
 			Self::do_contribute(who, index, value, signature, AllowDeath)
 		}
+
+		/// Transfer the whole contribution of the origin to a crowdloan to another account.
+		///
+		/// Origin must be Signed, the crowdloan must have been created with `create_transferable`
+		/// and the user must have contributed to the crowdloan. If the recipient already
+		/// contributed, the contributions are merged and the recipient keeps its memo; otherwise
+		/// the recipient starts with an empty memo.
+		///
+		/// If the fund has a verifier, the transfer must be signed by the verifier over
+		/// `(index, who, to, balance)`, where `balance` is the contribution being transferred.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::transfer_contribution())]
+		pub fn transfer_contribution(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: ParaId,
+			to: T::AccountId,
+			signature: Option<MultiSignature>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who != to, Error::<T>::SelfTransfer);
+
+			let fund = Self::funds(index).ok_or(Error::<T>::InvalidParaId)?;
+			ensure!(
+				TransferableFunds::<T>::contains_key(fund.fund_index),
+				Error::<T>::NotTransferable
+			);
+			let (balance, _) = Self::contribution_get(fund.fund_index, &who);
+			ensure!(balance > Zero::zero(), Error::<T>::NoContributions);
+
+			if let Some(ref verifier) = fund.verifier {
+				let signature = signature.ok_or(Error::<T>::InvalidSignature)?;
+				let payload = (index, &who, &to, balance);
+				let valid = payload.using_encoded(|encoded| {
+					signature.verify(encoded, &verifier.clone().into_account())
+				});
+				ensure!(valid, Error::<T>::InvalidSignature);
+			}
+
+			let (to_balance, to_memo) = Self::contribution_get(fund.fund_index, &to);
+			let new_balance = to_balance.checked_add(&balance).ok_or(Error::<T>::Overflow)?;
+
+			Self::contribution_kill(fund.fund_index, &who);
+			Self::contribution_put(fund.fund_index, &to, &new_balance, &to_memo);
+
+			Self::deposit_event(Event::<T>::ContributionTransferred {
+				from: who,
+				to,
+				para_id: index,
+				amount: balance,
+			});
+			Ok(())
+		}
+
+		/// Create a new crowdloaning campaign like `create`, whose contributions may be transferred
+		/// to other accounts with `transfer_contribution`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::create_transferable())]
+		pub fn create_transferable(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: ParaId,
+			#[pallet::compact] cap: BalanceOf<T>,
+			#[pallet::compact] first_period: LeasePeriodOf<T>,
+			#[pallet::compact] last_period: LeasePeriodOf<T>,
+			#[pallet::compact] end: BlockNumberFor<T>,
+			verifier: Option<MultiSigner>,
+		) -> DispatchResult {
+			let depositor = ensure_signed(origin)?;
+			let fund_index =
+				Self::do_create(depositor, index, cap, first_period, last_period, end, verifier)?;
+			TransferableFunds::<T>::insert(fund_index, ());
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Create a new crowdloan for `index`, managed by `depositor`, and return its fund index.
+	fn do_create(
+		depositor: T::AccountId,
+		index: ParaId,
+		cap: BalanceOf<T>,
+		first_period: LeasePeriodOf<T>,
+		last_period: LeasePeriodOf<T>,
+		end: BlockNumberFor<T>,
+		verifier: Option<MultiSigner>,
+	) -> Result<FundIndex, DispatchError> {
+		let now = frame_system::Pallet::<T>::block_number();
+
+		ensure!(first_period <= last_period, Error::<T>::LastPeriodBeforeFirstPeriod);
+		let last_period_limit = first_period
+			.checked_add(&((SlotRange::LEASE_PERIODS_PER_SLOT as u32) - 1).into())
+			.ok_or(Error::<T>::FirstPeriodTooFarInFuture)?;
+		ensure!(last_period <= last_period_limit, Error::<T>::LastPeriodTooFarInFuture);
+		ensure!(end > now, Error::<T>::CannotEndInPast);
+
+		// Here we check the lease period on the ending block is at most the first block of the
+		// period after `first_period`. If it would be larger, there is no way we could win an
+		// active auction, thus it would make no sense to have a crowdloan this long.
+		let (lease_period_at_end, is_first_block) =
+			T::Auctioneer::lease_period_index(end).ok_or(Error::<T>::NoLeasePeriod)?;
+		let adjusted_lease_period_at_end = if is_first_block {
+			lease_period_at_end.saturating_sub(One::one())
+		} else {
+			lease_period_at_end
+		};
+		ensure!(adjusted_lease_period_at_end <= first_period, Error::<T>::EndTooFarInFuture);
+
+		// Can't start a crowdloan for a lease period that already passed.
+		if let Some((current_lease_period, _)) = T::Auctioneer::lease_period_index(now) {
+			ensure!(first_period >= current_lease_period, Error::<T>::FirstPeriodInPast);
+		}
+
+		// There should not be an existing fund.
+		ensure!(!Funds::<T>::contains_key(index), Error::<T>::FundNotEnded);
+
+		let manager = T::Registrar::manager_of(index).ok_or(Error::<T>::InvalidParaId)?;
+		ensure!(depositor == manager, Error::<T>::InvalidOrigin);
+		ensure!(T::Registrar::is_registered(index), Error::<T>::InvalidParaId);
+
+		let fund_index = Self::next_fund_index();
+		let new_fund_index = fund_index.checked_add(1).ok_or(Error::<T>::Overflow)?;
+
+		let deposit = T::SubmissionDeposit::get();
+
+		frame_system::Pallet::<T>::inc_providers(&Self::fund_account_id(fund_index));
+		CurrencyOf::<T>::reserve(&depositor, deposit)?;
+
+		Funds::<T>::insert(
+			index,
+			FundInfo {
+				depositor,
+				verifier,
+				deposit,
+				raised: Zero::zero(),
+				end,
+				cap,
+				last_contribution: LastContribution::Never,
+				first_period,
+				last_period,
+				fund_index,
+			},
+		);
+
+		NextFundIndex::<T>::put(new_fund_index);
+		// Add a lock to the para so that the configuration cannot be changed.
+		T::Registrar::apply_lock(index);
+
+		Self::deposit_event(Event::<T>::Created { para_id: index });
+		Ok(fund_index)
+	}
+
 	/// The account ID of the fund pot.
 	///
 	/// This actually does computation. If you need to keep using it, then make sure you cache the
@@ -723,6 +839,49 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	/// Returns at most `limit` contributions to the crowdloan of `para`, starting after the
+	/// contributor `start_after` if given, or `None` if there is no such crowdloan.
+	pub fn contributions(
+		para: ParaId,
+		start_after: Option<T::AccountId>,
+		limit: u32,
+	) -> Option<Vec<runtime_api::Contribution<T::AccountId, BalanceOf<T>>>> {
+		let fund = Self::funds(para)?;
+		Some(Self::contributions_page(fund.fund_index, start_after, limit))
+	}
+
+	/// Returns at most `limit` contributions to the fund of `index`, starting after the
+	/// contributor `start_after` if given.
+	///
+	/// The contributions are returned in the order of their keys in the child trie of the fund.
+	pub fn contributions_page(
+		index: FundIndex,
+		start_after: Option<T::AccountId>,
+		limit: u32,
+	) -> Vec<runtime_api::Contribution<T::AccountId, BalanceOf<T>>> {
+		let child_info = Self::id_from_index(index);
+		let mut key = start_after.map(|who| who.encode()).unwrap_or_default();
+		let mut contributions = Vec::new();
+
+		while contributions.len() < limit as usize {
+			key = match sp_io::default_child_storage::next_key(child_info.storage_key(), &key) {
+				Some(next) => next,
+				None => break,
+			};
+			let who = match T::AccountId::decode(&mut &key[..]) {
+				Ok(who) => who,
+				Err(_) => continue,
+			};
+			if let Some((amount, memo)) =
+				child::get::<(BalanceOf<T>, Vec<u8>)>(&child_info, &key[..])
+			{
+				contributions.push(runtime_api::Contribution { who, amount, memo });
+			}
+		}
+
+		contributions
+	}
+
 	/// This function checks all conditions which would qualify a crowdloan has ended.
 	/// * If we have reached the `fund.end` block OR the first lease period the fund is trying to
 	///   bid for has started already.
@@ -842,6 +1001,21 @@ impl<T: Config> crate::traits::OnSwap for Pallet<T> {
 	}
 }
 
+impl<T: Config> crate::traits::SlotClaim for Pallet<T> {
+	fn has_slot_claim(id: ParaId) -> bool {
+		Funds::<T>::contains_key(id)
+	}
+
+	fn note_slot_claims(claims: &mut sp_std::collections::btree_set::BTreeSet<ParaId>) -> u64 {
+		let mut reads = 0u64;
+		for para in Funds::<T>::iter_keys() {
+			reads.saturating_inc();
+			claims.insert(para);
+		}
+		reads
+	}
+}
+
 #[cfg(any(feature = "runtime-benchmarks", test))]
 mod crypto {
 	use sp_core::ed25519;
@@ -1748,7 +1922,16 @@ mod tests {
 			let issuance = Balances::total_issuance();
 
 			// Set up a crowdloan
-			assert_ok!(Crowdloan::create(RuntimeOrigin::signed(1), para, 1000, 1, 1, 9, None));
+			assert_ok!(Crowdloan::create_transferable(
+				RuntimeOrigin::signed(1),
+				para,
+				1000,
+				1,
+				1,
+				9,
+				None
+			));
+			assert!(TransferableFunds::<Test>::contains_key(0));
 			assert_ok!(Crowdloan::contribute(RuntimeOrigin::signed(2), para, 100, None));
 			assert_ok!(Crowdloan::contribute(RuntimeOrigin::signed(3), para, 50, None));
 
@@ -1782,6 +1965,7 @@ mod tests {
 			assert_eq!(Balances::free_balance(2), 2000);
 			assert_eq!(Balances::free_balance(3), 3000);
 			assert_eq!(Balances::total_issuance(), issuance);
+			assert!(!TransferableFunds::<Test>::contains_key(0));
 		});
 	}
 
@@ -1923,6 +2107,148 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn transfer_contribution_works() {
+		new_test_ext().execute_with(|| {
+			let para = new_para();
+
+			assert_ok!(Crowdloan::create_transferable(
+				RuntimeOrigin::signed(1),
+				para,
+				1000,
+				1,
+				1,
+				9,
+				None
+			));
+			// Can't transfer a contribution before you have contributed.
+			assert_noop!(
+				Crowdloan::transfer_contribution(RuntimeOrigin::signed(1), para, 2, None),
+				Error::<Test>::NoContributions,
+			);
+
+			assert_ok!(Crowdloan::contribute(RuntimeOrigin::signed(1), para, 100, None));
+			assert_ok!(Crowdloan::add_memo(RuntimeOrigin::signed(1), para, b"one".to_vec()));
+			assert_ok!(Crowdloan::contribute(RuntimeOrigin::signed(2), para, 50, None));
+			assert_noop!(
+				Crowdloan::transfer_contribution(RuntimeOrigin::signed(1), para, 1, None),
+				Error::<Test>::SelfTransfer,
+			);
+
+			// The memo doesn't follow the contribution to a new holder.
+			assert_ok!(Crowdloan::transfer_contribution(RuntimeOrigin::signed(1), para, 3, None));
+			assert_eq!(
+				last_event(),
+				super::Event::<Test>::ContributionTransferred {
+					from: 1,
+					to: 3,
+					para_id: para,
+					amount: 100
+				}
+				.into()
+			);
+			assert_eq!(Crowdloan::contribution_get(0u32, &1), (0, vec![]));
+			assert_eq!(Crowdloan::contribution_get(0u32, &3), (100, vec![]));
+
+			// Transfers to an existing contributor are merged.
+			assert_ok!(Crowdloan::add_memo(RuntimeOrigin::signed(2), para, b"two".to_vec()));
+			assert_ok!(Crowdloan::transfer_contribution(RuntimeOrigin::signed(3), para, 2, None));
+			assert_eq!(Crowdloan::contribution_get(0u32, &2), (150, b"two".to_vec()));
+			assert_eq!(Crowdloan::funds(para).unwrap().raised, 150);
+
+			// The new holder is refunded.
+			run_to_block(10);
+			assert_ok!(Crowdloan::withdraw(RuntimeOrigin::signed(1), 2, para));
+			assert_eq!(Balances::free_balance(2), 2100);
+			assert_noop!(
+				Crowdloan::withdraw(RuntimeOrigin::signed(1), 1, para),
+				Error::<Test>::NoContributions,
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_contribution_requires_opt_in() {
+		new_test_ext().execute_with(|| {
+			let para = new_para();
+
+			assert_ok!(Crowdloan::create(RuntimeOrigin::signed(1), para, 1000, 1, 1, 9, None));
+			assert_ok!(Crowdloan::contribute(RuntimeOrigin::signed(1), para, 100, None));
+			assert_noop!(
+				Crowdloan::transfer_contribution(RuntimeOrigin::signed(1), para, 2, None),
+				Error::<Test>::NotTransferable,
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_contribution_with_verifier_works() {
+		new_test_ext().execute_with(|| {
+			let para = new_para();
+			let pubkey = crypto::create_ed25519_pubkey(b"//verifier".to_vec());
+			assert_ok!(Crowdloan::create_transferable(
+				RuntimeOrigin::signed(1),
+				para,
+				1000,
+				1,
+				4,
+				9,
+				Some(pubkey.clone())
+			));
+
+			let payload = (0u32, 1u64, 0u64, 49u64);
+			let signature = crypto::create_ed25519_signature(&payload.encode(), pubkey.clone());
+			assert_ok!(Crowdloan::contribute(RuntimeOrigin::signed(1), para, 49, Some(signature)));
+
+			assert_noop!(
+				Crowdloan::transfer_contribution(RuntimeOrigin::signed(1), para, 2, None),
+				Error::<Test>::InvalidSignature,
+			);
+			let payload = (0u32, 1u64, 3u64, 49u64);
+			let signature = crypto::create_ed25519_signature(&payload.encode(), pubkey.clone());
+			assert_noop!(
+				Crowdloan::transfer_contribution(
+					RuntimeOrigin::signed(1),
+					para,
+					2,
+					Some(signature.clone())
+				),
+				Error::<Test>::InvalidSignature,
+			);
+			assert_ok!(Crowdloan::transfer_contribution(
+				RuntimeOrigin::signed(1),
+				para,
+				3,
+				Some(signature)
+			));
+			assert_eq!(Crowdloan::contribution_get(0u32, &3).0, 49);
+		});
+	}
+
+	#[test]
+	fn contributions_page_works() {
+		new_test_ext().execute_with(|| {
+			let para = new_para();
+
+			assert_ok!(Crowdloan::create(RuntimeOrigin::signed(1), para, 1000, 1, 1, 9, None));
+			for who in 1..=3 {
+				assert_ok!(Crowdloan::contribute(RuntimeOrigin::signed(who), para, 49 + who, None));
+			}
+			assert_ok!(Crowdloan::add_memo(RuntimeOrigin::signed(2), para, b"two".to_vec()));
+
+			let all = Crowdloan::contributions_page(0, None, 10);
+			assert_eq!(all.len(), 3);
+			assert!(all.iter().any(|c| c.who == 2 && c.amount == 51 && c.memo == b"two".to_vec()));
+
+			// Pages resume after the last contributor of the previous page.
+			let first = Crowdloan::contributions_page(0, None, 2);
+			assert_eq!(first, all[..2].to_vec());
+			let second = Crowdloan::contributions_page(0, Some(first[1].who), 2);
+			assert_eq!(second, all[2..].to_vec());
+			assert!(Crowdloan::contributions_page(0, Some(all[2].who), 2).is_empty());
+		});
+	}
+
 	#[test]
 	fn poke_works() {
 		new_test_ext().execute_with(|| {
@@ -1999,7 +2325,7 @@ mod benchmarking {
 		));
 		T::Registrar::execute_pending_transitions();
 
-		assert_ok!(Crowdloan::<T>::create(
+		assert_ok!(Crowdloan::<T>::create_transferable(
 			RawOrigin::Signed(caller).into(),
 			para_id,
 			cap,
@@ -2059,6 +2385,35 @@ mod benchmarking {
 			assert_last_event::<T>(Event::<T>::Created { para_id }.into())
 		}
 
+		create_transferable {
+			let para_id = ParaId::from(1_u32);
+			let cap = BalanceOf::<T>::max_value();
+			let first_period = 0u32.into();
+			let last_period = 3u32.into();
+			let (lpl, offset) = T::Auctioneer::lease_period_length();
+			let end = lpl + offset;
+
+			let caller: T::AccountId = whitelisted_caller();
+			let head_data = T::Registrar::worst_head_data();
+			let validation_code = T::Registrar::worst_validation_code();
+
+			let verifier = MultiSigner::unchecked_from(account::<[u8; 32]>("verifier", 0, 0));
+
+			CurrencyOf::<T>::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+			T::Registrar::register(caller.clone(), para_id, head_data, validation_code.clone())?;
+			assert_ok!(paras::Pallet::<T>::add_trusted_validation_code(
+				frame_system::Origin::<T>::Root.into(),
+				validation_code,
+			));
+
+			T::Registrar::execute_pending_transitions();
+
+		}: _(RawOrigin::Signed(caller), para_id, cap, first_period, last_period, end, Some(verifier))
+		verify {
+			assert_last_event::<T>(Event::<T>::Created { para_id }.into());
+			assert!(TransferableFunds::<T>::contains_key(Crowdloan::<T>::funds(para_id).unwrap().fund_index));
+		}
+
 		// Contribute has two arms: PreEnding and Ending, but both are equal complexity.
 		contribute {
 			let (lpl, offset) = T::Auctioneer::lease_period_length();
@@ -2174,6 +2529,28 @@ mod benchmarking {
 			);
 		}
 
+		transfer_contribution {
+			let (lpl, offset) = T::Auctioneer::lease_period_length();
+			let end = lpl + offset;
+			let fund_index = create_fund::<T>(1, end);
+			let caller: T::AccountId = whitelisted_caller();
+			let recipient: T::AccountId = account("recipient", 0, 0);
+			contribute_fund::<T>(&caller, fund_index);
+			// Worst case: the contributions are merged with an existing one.
+			contribute_fund::<T>(&recipient, fund_index);
+
+			let pubkey = crypto::create_ed25519_pubkey(b"//verifier".to_vec());
+			let payload = (fund_index, &caller, &recipient, T::MinContribution::get());
+			let sig = crypto::create_ed25519_signature(&payload.encode(), pubkey);
+		}: _(RawOrigin::Signed(caller.clone()), fund_index, recipient.clone(), Some(sig))
+		verify {
+			let fund = Funds::<T>::get(fund_index).expect("fund was created...");
+			assert_eq!(
+				Crowdloan::<T>::contribution_get(fund.fund_index, &recipient).0,
+				T::MinContribution::get().saturating_mul(2u32.into()),
+			);
+		}
+
 		poke {
 			let (lpl, offset) = T::Auctioneer::lease_period_length();
 			let end = lpl + offset;
@@ -0,0 +1,54 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API exposing the contributions made to crowdloans.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use primitives::Id as ParaId;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A contribution made to a crowdloan.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Contribution<AccountId, Balance> {
+	/// The account currently holding the contribution.
+	pub who: AccountId,
+	/// The amount contributed.
+	pub amount: Balance,
+	/// The memo attached to the contribution.
+	pub memo: Vec<u8>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to enumerate the contributions made to crowdloans.
+	pub trait CrowdloanApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Returns at most `limit` contributions to the crowdloan of `para`, in the order of the
+		/// contributors' keys in the fund's child trie.
+		///
+		/// The enumeration starts after the contributor `start_after`, if given, so the last
+		/// contributor of a page can be used to request the next one. Returns `None` if there is
+		/// no crowdloan for `para`.
+		fn contributions(
+			para: ParaId,
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Option<Vec<Contribution<AccountId, Balance>>>;
+	}
+}
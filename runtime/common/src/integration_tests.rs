@@ -37,7 +37,7 @@ use primitives::{
 	BlockNumber, HeadData, Id as ParaId, SessionIndex, ValidationCode, LOWEST_PUBLIC_ID,
 };
 use runtime_parachains::{
-	configuration, origin, paras, shared, Origin as ParaOrigin, ParaLifecycle,
+	configuration, dmp, origin, paras, shared, Origin as ParaOrigin, ParaLifecycle,
 };
 use sp_core::H256;
 use sp_io::TestExternalities;
@@ -82,6 +82,7 @@ frame_support::construct_runtime!(
 		Paras: paras::{Pallet, Call, Storage, Event, Config<T>},
 		ParasShared: shared::{Pallet, Call, Storage},
 		ParachainsOrigin: origin::{Pallet, Origin},
+		Dmp: dmp::{Pallet, Storage},
 
 		// Para Onboarding Pallets
 		Registrar: paras_registrar::{Pallet, Call, Storage, Event<T>},
@@ -192,6 +193,10 @@ impl configuration::Config for Test {
 
 impl shared::Config for Test {}
 
+impl dmp::Config for Test {
+	type MaxPriorityDownwardMessages = ConstU32<4>;
+}
+
 impl origin::Config for Test {}
 
 parameter_types! {
@@ -209,13 +214,16 @@ impl paras::Config for Test {
 parameter_types! {
 	pub const ParaDeposit: Balance = 500;
 	pub const DataDepositPerByte: Balance = 1;
+	pub const ReservationExpiry: BlockNumber = 100;
 }
 
 impl paras_registrar::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type OnSwap = (Crowdloan, Slots);
+	type SlotClaims = (Slots, Crowdloan, Auctions);
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
+	type ReservationExpiry = ReservationExpiry;
 	type Currency = Balances;
 	type RuntimeOrigin = RuntimeOrigin;
 	type WeightInfo = crate::paras_registrar::TestWeightInfo;
@@ -249,6 +257,7 @@ impl slots::Config for Test {
 	type LeasePeriod = LeasePeriod;
 	type LeaseOffset = LeaseOffset;
 	type ForceOrigin = EnsureRoot<AccountId>;
+	type Auctioneer = Auctions;
 	type WeightInfo = crate::slots::TestWeightInfo;
 }
 
@@ -279,6 +288,7 @@ pub fn new_test_ext() -> TestExternalities {
 		config: configuration::HostConfiguration {
 			max_code_size: 2 * 1024 * 1024,      // 2 MB
 			max_head_data_size: 1 * 1024 * 1024, // 1 MB
+			max_downward_message_size: 1024,
 			..Default::default()
 		},
 	}
@@ -1265,6 +1275,123 @@ fn crowdloan_ending_period_bid() {
 	})
 }
 
+#[test]
+fn release_lease_keeps_crowdloan_leases() {
+	new_test_ext().execute_with(|| {
+		assert!(System::block_number().is_one()); /* So events are emitted */
+
+		const START_SESSION_INDEX: SessionIndex = 1;
+		run_to_session(START_SESSION_INDEX);
+
+		Balances::make_free_balance_be(&account_id(1), 1_000_000_000);
+		let validation_code = test_validation_code(10);
+		assert_ok!(Registrar::reserve(signed(1)));
+		assert_ok!(Registrar::register(
+			signed(1),
+			ParaId::from(2000),
+			test_genesis_head(10),
+			validation_code.clone(),
+		));
+		conclude_pvf_checking::<Test>(&validation_code, VALIDATORS, START_SESSION_INDEX);
+
+		let duration = 99u32;
+		let lease_period_index_start = 4u32;
+		assert_ok!(Auctions::new_auction(
+			RuntimeOrigin::root(),
+			duration,
+			lease_period_index_start
+		));
+
+		run_to_session(START_SESSION_INDEX + 2);
+		assert_eq!(Paras::lifecycle(ParaId::from(2000)), Some(ParaLifecycle::Parathread));
+
+		// The manager raises the lease with a crowdloan.
+		assert_ok!(Crowdloan::create(
+			signed(1),
+			ParaId::from(2000),
+			1_000_000,                    // Cap
+			lease_period_index_start + 0, // First Slot
+			lease_period_index_start + 3, // Last Slot
+			200,                          // Block End
+			None,
+		));
+		let fund = Crowdloan::funds(ParaId::from(2000)).unwrap();
+		let crowdloan_account = Crowdloan::fund_account_id(fund.fund_index);
+		Balances::make_free_balance_be(&account_id(10), 1_000_000_000);
+		assert_ok!(Crowdloan::contribute(signed(10), ParaId::from(2000), 900, None));
+
+		// Go to end of auction where the crowdloan won the slots.
+		run_to_block(200);
+		let crowdloan_leases = slots::Leases::<Test>::get(ParaId::from(2000));
+		assert_eq!(crowdloan_leases.iter().filter(|lease| lease.is_some()).count(), 4);
+		assert_eq!(Balances::reserved_balance(&crowdloan_account), 900);
+
+		// The manager can't give back the periods the crowdloan leased.
+		assert_noop!(
+			Slots::release_lease(signed(1), ParaId::from(2000)),
+			slots::Error::<Test>::NothingToRelease
+		);
+
+		// But it can give back a period leased on its own.
+		assert_ok!(Slots::force_lease(
+			RuntimeOrigin::root(),
+			ParaId::from(2000),
+			account_id(1),
+			100,
+			lease_period_index_start + 4,
+			1,
+		));
+		let reserved = Balances::reserved_balance(&account_id(1));
+		assert_ok!(Slots::release_lease(signed(1), ParaId::from(2000)));
+		assert_eq!(Balances::reserved_balance(&account_id(1)), reserved - 100);
+		assert_eq!(slots::Leases::<Test>::get(ParaId::from(2000)), crowdloan_leases);
+		assert_eq!(Balances::reserved_balance(&crowdloan_account), 900);
+	})
+}
+
+#[test]
+fn leased_paras_are_not_abandoned() {
+	new_test_ext().execute_with(|| {
+		assert!(System::block_number().is_one()); /* So events are emitted */
+
+		const START_SESSION_INDEX: SessionIndex = 1;
+		run_to_session(START_SESSION_INDEX);
+
+		Balances::make_free_balance_be(&account_id(1), 1_000_000_000);
+		let validation_code = test_validation_code(10);
+		assert_ok!(Registrar::reserve(signed(1)));
+		assert_ok!(Registrar::register(
+			signed(1),
+			ParaId::from(2000),
+			test_genesis_head(10),
+			validation_code.clone(),
+		));
+		conclude_pvf_checking::<Test>(&validation_code, VALIDATORS, START_SESSION_INDEX);
+		run_to_session(START_SESSION_INDEX + 2);
+		assert_eq!(Paras::lifecycle(ParaId::from(2000)), Some(ParaLifecycle::Parathread));
+
+		// The para is granted a lease in the future and never has a block included.
+		assert_ok!(Slots::force_lease(
+			RuntimeOrigin::root(),
+			ParaId::from(2000),
+			account_id(1),
+			100,
+			4,
+			2,
+		));
+		run_to_block(System::block_number() + ReservationExpiry::get());
+
+		assert_noop!(
+			Registrar::deregister_expired_reservation(signed(2), ParaId::from(2000)),
+			paras_registrar::Error::<Test>::NotReservation
+		);
+
+		// Without the lease, the para is abandoned.
+		assert_ok!(Slots::clear_all_leases(RuntimeOrigin::root(), ParaId::from(2000)));
+		assert_ok!(Registrar::deregister_expired_reservation(signed(2), ParaId::from(2000)));
+	});
+}
+
 #[test]
 fn auction_bid_requires_registered_para() {
 	new_test_ext().execute_with(|| {
@@ -16,9 +16,10 @@
 
 //! Mocking utilities for testing.
 
-use crate::traits::Registrar;
+use crate::traits::{AuctionStatus, Auctioneer, Registrar};
 use frame_support::{
 	dispatch::{DispatchError, DispatchResult},
+	traits::{Currency, ReservableCurrency},
 	weights::Weight,
 };
 use frame_system::pallet_prelude::BlockNumberFor;
@@ -36,6 +37,7 @@ thread_local! {
 	static PARATHREADS: RefCell<Vec<ParaId>> = RefCell::new(Vec::new());
 	static LOCKS: RefCell<HashMap<ParaId, bool>> = RefCell::new(HashMap::new());
 	static MANAGERS: RefCell<HashMap<ParaId, Vec<u8>>> = RefCell::new(HashMap::new());
+	static AUCTION_IN_PROGRESS: RefCell<bool> = RefCell::new(false);
 }
 
 pub struct TestRegistrar<T>(sp_std::marker::PhantomData<T>);
@@ -56,6 +58,11 @@ impl<T: frame_system::Config> Registrar for TestRegistrar<T> {
 		PARATHREADS.with(|x| x.borrow().binary_search(&id).is_ok())
 	}
 
+	// Upgrades to lease holding parachains are applied immediately.
+	fn is_upgrading(_id: ParaId) -> bool {
+		false
+	}
+
 	fn apply_lock(id: ParaId) {
 		LOCKS.with(|x| x.borrow_mut().insert(id, true));
 	}
@@ -205,12 +212,10 @@ impl<T: frame_system::Config> TestRegistrar<T> {
 			.with(|x| x.borrow().iter().map(|(p, b, c)| (*p, (*b).into(), *c)).collect::<Vec<_>>())
 	}
 
-	#[allow(dead_code)]
 	pub fn parachains() -> Vec<ParaId> {
 		PARACHAINS.with(|x| x.borrow().clone())
 	}
 
-	#[allow(dead_code)]
 	pub fn parathreads() -> Vec<ParaId> {
 		PARATHREADS.with(|x| x.borrow().clone())
 	}
@@ -224,6 +229,64 @@ impl<T: frame_system::Config> TestRegistrar<T> {
 	}
 }
 
+/// An auctioneer which only tracks whether an auction is in progress. Bids are accepted and
+/// ignored.
+pub struct TestAuctioneer<T, C>(sp_std::marker::PhantomData<(T, C)>);
+
+impl<T, C> Auctioneer<BlockNumberFor<T>> for TestAuctioneer<T, C>
+where
+	T: frame_system::Config,
+	C: ReservableCurrency<T::AccountId>,
+{
+	type AccountId = T::AccountId;
+	type LeasePeriod = BlockNumberFor<T>;
+	type Currency = C;
+
+	fn new_auction(
+		_duration: BlockNumberFor<T>,
+		_lease_period_index: Self::LeasePeriod,
+	) -> DispatchResult {
+		AUCTION_IN_PROGRESS.with(|x| *x.borrow_mut() = true);
+		Ok(())
+	}
+
+	fn auction_status(_now: BlockNumberFor<T>) -> AuctionStatus<BlockNumberFor<T>> {
+		if AUCTION_IN_PROGRESS.with(|x| *x.borrow()) {
+			AuctionStatus::StartingPeriod
+		} else {
+			AuctionStatus::NotStarted
+		}
+	}
+
+	fn place_bid(
+		_bidder: Self::AccountId,
+		_para: ParaId,
+		_first_slot: Self::LeasePeriod,
+		_last_slot: Self::LeasePeriod,
+		_amount: <C as Currency<T::AccountId>>::Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn lease_period_length() -> (BlockNumberFor<T>, BlockNumberFor<T>) {
+		(10u32.into(), 0u32.into())
+	}
+
+	fn lease_period_index(_block: BlockNumberFor<T>) -> Option<(Self::LeasePeriod, bool)> {
+		None
+	}
+
+	fn has_won_an_auction(_para: ParaId, _bidder: &Self::AccountId) -> bool {
+		false
+	}
+}
+
+impl<T, C> TestAuctioneer<T, C> {
+	pub fn end_auction() {
+		AUCTION_IN_PROGRESS.with(|x| *x.borrow_mut() = false);
+	}
+}
+
 /// A very dumb implementation of `EstimateNextSessionRotation`. At the moment of writing, this
 /// is more to satisfy type requirements rather than to test anything.
 pub struct TestNextSessionRotation;
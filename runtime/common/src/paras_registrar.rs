@@ -23,7 +23,7 @@ use frame_support::{
 	pallet_prelude::Weight,
 	traits::{Currency, Get, ReservableCurrency},
 };
-use frame_system::{self, ensure_root, ensure_signed};
+use frame_system::{self, ensure_root, ensure_signed, pallet_prelude::BlockNumberFor};
 use primitives::{HeadData, Id as ParaId, ValidationCode, LOWEST_PUBLIC_ID};
 use runtime_parachains::{
 	configuration, ensure_parachain,
@@ -32,13 +32,15 @@ use runtime_parachains::{
 };
 use sp_std::{prelude::*, result};
 
-use crate::traits::{OnSwap, Registrar};
+use crate::traits::{OnSwap, Registrar, SlotClaim};
 pub use pallet::*;
 use parity_scale_codec::{Decode, Encode};
+
+pub mod migration;
 use runtime_parachains::paras::ParaKind;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{CheckedSub, Saturating},
+	traits::{CheckedSub, Saturating, Zero},
 	RuntimeDebug,
 };
 
@@ -63,6 +65,10 @@ pub trait WeightInfo {
 	fn swap() -> Weight;
 	fn schedule_code_upgrade(b: u32) -> Weight;
 	fn set_current_head(b: u32) -> Weight;
+	fn transfer_manager() -> Weight;
+	fn accept_manager() -> Weight;
+	fn deregister_expired_reservation() -> Weight;
+	fn set_authorized_collators(c: u32) -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -88,6 +94,18 @@ impl WeightInfo for TestWeightInfo {
 	fn set_current_head(_b: u32) -> Weight {
 		Weight::zero()
 	}
+	fn transfer_manager() -> Weight {
+		Weight::zero()
+	}
+	fn accept_manager() -> Weight {
+		Weight::zero()
+	}
+	fn deregister_expired_reservation() -> Weight {
+		Weight::zero()
+	}
+	fn set_authorized_collators(_c: u32) -> Weight {
+		Weight::zero()
+	}
 }
 
 #[frame_support::pallet]
@@ -119,6 +137,10 @@ pub mod pallet {
 		/// Runtime hook for when a lease holding parachain and on-demand parachain swap.
 		type OnSwap: crate::traits::OnSwap;
 
+		/// Tells whether a para holds or may still win a lease holding parachain slot. Such paras
+		/// are never considered abandoned.
+		type SlotClaims: crate::traits::SlotClaim;
+
 		/// The deposit to be paid to run a on-demand parachain.
 		/// This should include the cost for storing the genesis head and validation code.
 		#[pallet::constant]
@@ -128,6 +150,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type DataDepositPerByte: Get<BalanceOf<Self>>;
 
+		/// The number of blocks after which a reserved para ID that was never used to register a
+		/// para may be released by anyone, returning the deposit to its manager.
+		#[pallet::constant]
+		type ReservationExpiry: Get<BlockNumberFor<Self>>;
+
 		/// Weight Information for the Extrinsics in the Pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -139,6 +166,8 @@ pub mod pallet {
 		Deregistered { para_id: ParaId },
 		Reserved { para_id: ParaId, who: T::AccountId },
 		Swapped { para_id: ParaId, other_id: ParaId },
+		ManagerTransferProposed { para_id: ParaId, new_manager: T::AccountId },
+		ManagerChanged { para_id: ParaId, old_manager: T::AccountId, new_manager: T::AccountId },
 	}
 
 	#[pallet::error]
@@ -173,6 +202,12 @@ pub mod pallet {
 		/// Cannot perform a parachain slot / lifecycle swap. Check that the state of both paras
 		/// are correct for the swap to work.
 		CannotSwap,
+		/// The caller is not the pending manager of this Id.
+		NotPendingManager,
+		/// The Id is not an abandoned reservation.
+		NotReservation,
+		/// The reservation of the Id has not expired yet.
+		ReservationNotExpired,
 	}
 
 	/// Pending swap operations.
@@ -191,6 +226,18 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type NextFreeParaId<T> = StorageValue<_, ParaId, ValueQuery>;
 
+	/// Manager transfers proposed by the manager of a para, waiting for the acceptance of the new
+	/// manager.
+	#[pallet::storage]
+	pub type PendingManager<T: Config> = StorageMap<_, Twox64Concat, ParaId, T::AccountId>;
+
+	/// The block at which a para ID was reserved.
+	///
+	/// Kept for as long as the para ID is reserved or registered, and used to release the para ID
+	/// once it is abandoned, see [`Pallet::deregister_expired_reservation`].
+	#[pallet::storage]
+	pub type ReservedAt<T: Config> = StorageMap<_, Twox64Concat, ParaId, BlockNumberFor<T>>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		#[serde(skip)]
@@ -327,6 +374,9 @@ pub mod pallet {
 				}
 				Self::deposit_event(Event::<T>::Swapped { para_id: id, other_id: other });
 				PendingSwap::<T>::remove(other);
+				// Manager transfers were proposed for what the paras were before the swap.
+				PendingManager::<T>::remove(id);
+				PendingManager::<T>::remove(other);
 			} else {
 				PendingSwap::<T>::insert(id, other);
 			}
@@ -417,6 +467,106 @@ pub mod pallet {
 			runtime_parachains::set_current_head::<T>(para, new_head);
 			Ok(())
 		}
+
+		/// Propose to hand over the management of a para to another account.
+		///
+		/// The transfer only happens once `new_manager` calls `accept_manager`. Proposing again
+		/// replaces any pending transfer.
+		///
+		/// Can be called by Root, the parachain, or the parachain manager if the parachain is
+		/// unlocked.
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_manager())]
+		pub fn transfer_manager(
+			origin: OriginFor<T>,
+			para: ParaId,
+			new_manager: T::AccountId,
+		) -> DispatchResult {
+			Self::ensure_root_para_or_owner(origin, para)?;
+			ensure!(Paras::<T>::contains_key(para), Error::<T>::NotRegistered);
+			PendingManager::<T>::insert(para, new_manager.clone());
+			Self::deposit_event(Event::<T>::ManagerTransferProposed { para_id: para, new_manager });
+			Ok(())
+		}
+
+		/// Accept a pending transfer of the management of a para.
+		///
+		/// ## Deposits/Fees
+		/// The origin takes over the deposit of the para: it reserves the deposit of the para and
+		/// the same amount is unreserved from the previous manager.
+		///
+		/// ## Events
+		/// The `ManagerChanged` event is emitted in case of success.
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T as Config>::WeightInfo::accept_manager())]
+		pub fn accept_manager(origin: OriginFor<T>, para: ParaId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				PendingManager::<T>::get(para).as_ref() == Some(&who),
+				Error::<T>::NotPendingManager
+			);
+			let mut info = Paras::<T>::get(para).ok_or(Error::<T>::NotRegistered)?;
+
+			<T as Config>::Currency::reserve(&who, info.deposit)?;
+			<T as Config>::Currency::unreserve(&info.manager, info.deposit);
+			let old_manager = sp_std::mem::replace(&mut info.manager, who.clone());
+
+			Paras::<T>::insert(para, info);
+			PendingManager::<T>::remove(para);
+			Self::deposit_event(Event::<T>::ManagerChanged {
+				para_id: para,
+				old_manager,
+				new_manager: who,
+			});
+			Ok(())
+		}
+
+		/// Release a para ID that was reserved more than `ReservationExpiry` blocks ago and is
+		/// abandoned: either no para was registered with it, or the registered para is an unlocked
+		/// on-demand parachain which never had a block included.
+		///
+		/// Can be called by any `Signed` origin. The deposit is returned to the manager of the
+		/// para ID.
+		///
+		/// ## Events
+		/// The `Deregistered` event is emitted in case of success.
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as Config>::WeightInfo::deregister_expired_reservation())]
+		pub fn deregister_expired_reservation(
+			origin: OriginFor<T>,
+			para: ParaId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let reserved_at = ReservedAt::<T>::get(para).ok_or(Error::<T>::NotReservation)?;
+			ensure!(Self::is_abandoned(para), Error::<T>::NotReservation);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >=
+					reserved_at.saturating_add(T::ReservationExpiry::get()),
+				Error::<T>::ReservationNotExpired
+			);
+			Self::do_deregister(para)
+		}
+
+		/// Restrict the collators allowed to collate for a para to `collators`, or lift the
+		/// restriction with `None`.
+		///
+		/// Validators refuse declarations of collators that are not in the list. An empty list
+		/// keeps any collator from collating for the para.
+		///
+		/// Can be called by Root, the parachain, or the parachain manager if the parachain is
+		/// unlocked.
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_authorized_collators(
+			collators.as_ref().map_or(0, |collators| collators.len() as u32)
+		))]
+		pub fn set_authorized_collators(
+			origin: OriginFor<T>,
+			para: ParaId,
+			collators: Option<paras::AuthorizedCollatorList>,
+		) -> DispatchResult {
+			Self::ensure_root_para_or_owner(origin, para)?;
+			runtime_parachains::set_authorized_collators::<T>(para, collators)
+		}
 	}
 }
 
@@ -444,6 +594,12 @@ impl<T: Config> Registrar for Pallet<T> {
 		paras::Pallet::<T>::is_parachain(id)
 	}
 
+	// Return if a para is an on-demand parachain which is being upgraded to a lease holding
+	// parachain
+	fn is_upgrading(id: ParaId) -> bool {
+		paras::Pallet::<T>::lifecycle(id) == Some(ParaLifecycle::UpgradingParathread)
+	}
+
 	// Apply a lock to the parachain.
 	fn apply_lock(id: ParaId) {
 		Paras::<T>::mutate(id, |x| x.as_mut().map(|info| info.locked = true));
@@ -569,6 +725,7 @@ impl<T: Config> Pallet<T> {
 		let info = ParaInfo { manager: who.clone(), deposit, locked: false };
 
 		Paras::<T>::insert(id, info);
+		ReservedAt::<T>::insert(id, frame_system::Pallet::<T>::block_number());
 		Self::deposit_event(Event::<T>::Reserved { para_id: id, who });
 		Ok(())
 	}
@@ -626,10 +783,32 @@ impl<T: Config> Pallet<T> {
 		}
 
 		PendingSwap::<T>::remove(id);
+		PendingManager::<T>::remove(id);
+		ReservedAt::<T>::remove(id);
 		Self::deposit_event(Event::<T>::Deregistered { para_id: id });
 		Ok(())
 	}
 
+	/// Whether the para ID is abandoned: no para was registered with it, or the para is an unlocked
+	/// on-demand parachain which never had a block included since its genesis. Paras with a claim
+	/// on a slot are never abandoned, even before they are onboarded as lease holders.
+	pub(crate) fn is_abandoned(id: ParaId) -> bool {
+		!T::SlotClaims::has_slot_claim(id) && Self::is_unused(id)
+	}
+
+	/// Whether the para ID was never used to register a para, or the para is an unlocked
+	/// on-demand parachain which never had a block included since its genesis, regardless of any
+	/// claim on a slot.
+	pub(crate) fn is_unused(id: ParaId) -> bool {
+		match paras::Pallet::<T>::lifecycle(id) {
+			None => true,
+			Some(ParaLifecycle::Parathread) =>
+				!Paras::<T>::get(id).map_or(false, |info| info.locked) &&
+					paras::Pallet::<T>::para_most_recent_context(id) == Some(Zero::zero()),
+			_ => false,
+		}
+	}
+
 	/// Verifies the onboarding data is valid for a para.
 	///
 	/// Returns `ParaGenesisArgs` and the deposit needed for the data.
@@ -672,7 +851,7 @@ mod tests {
 		mock::conclude_pvf_checking, paras_registrar, traits::Registrar as RegistrarTrait,
 	};
 	use frame_support::{
-		assert_noop, assert_ok,
+		assert_noop, assert_ok, bounded_vec,
 		error::BadOrigin,
 		parameter_types,
 		traits::{ConstU32, OnFinalize, OnInitialize},
@@ -794,6 +973,7 @@ mod tests {
 		pub const ParaDeposit: Balance = 10;
 		pub const DataDepositPerByte: Balance = 1;
 		pub const MaxRetries: u32 = 3;
+		pub const ReservationExpiry: BlockNumber = 10;
 	}
 
 	impl Config for Test {
@@ -801,8 +981,10 @@ mod tests {
 		type RuntimeEvent = RuntimeEvent;
 		type Currency = Balances;
 		type OnSwap = MockSwap;
+		type SlotClaims = ();
 		type ParaDeposit = ParaDeposit;
 		type DataDepositPerByte = DataDepositPerByte;
+		type ReservationExpiry = ReservationExpiry;
 		type WeightInfo = TestWeightInfo;
 	}
 
@@ -1176,14 +1358,25 @@ mod tests {
 			assert!(!Parachains::is_parachain(para_2));
 			assert!(Parachains::is_parathread(para_2));
 
+			// Pending manager transfers don't survive a swap.
+			assert_ok!(Registrar::transfer_manager(RuntimeOrigin::signed(1), para_1, 3));
+			assert_ok!(Registrar::transfer_manager(RuntimeOrigin::signed(2), para_2, 4));
+
 			// Both paras initiate a swap
 			// Swap between parachain and parathread
 			assert_ok!(Registrar::swap(para_origin(para_1), para_1, para_2,));
+			assert_eq!(PendingManager::<Test>::get(para_1), Some(3));
 			assert_ok!(Registrar::swap(para_origin(para_2), para_2, para_1,));
 			System::assert_last_event(RuntimeEvent::Registrar(paras_registrar::Event::Swapped {
 				para_id: para_2,
 				other_id: para_1,
 			}));
+			assert_eq!(PendingManager::<Test>::get(para_1), None);
+			assert_eq!(PendingManager::<Test>::get(para_2), None);
+			assert_noop!(
+				Registrar::accept_manager(RuntimeOrigin::signed(3), para_1),
+				Error::<Test>::NotPendingManager
+			);
 
 			run_to_session(START_SESSION_INDEX + 6);
 
@@ -1286,6 +1479,228 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn transfer_manager_works() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			assert_ok!(Registrar::reserve(RuntimeOrigin::signed(1)));
+			let para_id = LOWEST_PUBLIC_ID;
+			let deposit = <Test as Config>::ParaDeposit::get();
+
+			// Only the manager can propose a transfer.
+			assert_noop!(
+				Registrar::transfer_manager(RuntimeOrigin::signed(2), para_id, 2),
+				BadOrigin
+			);
+			assert_ok!(Registrar::transfer_manager(RuntimeOrigin::signed(1), para_id, 2));
+			System::assert_last_event(RuntimeEvent::Registrar(
+				paras_registrar::Event::ManagerTransferProposed { para_id, new_manager: 2 },
+			));
+
+			// Only the proposed manager can accept it.
+			assert_noop!(
+				Registrar::accept_manager(RuntimeOrigin::signed(3), para_id),
+				Error::<Test>::NotPendingManager
+			);
+			assert_ok!(Registrar::accept_manager(RuntimeOrigin::signed(2), para_id));
+			System::assert_last_event(RuntimeEvent::Registrar(
+				paras_registrar::Event::ManagerChanged { para_id, old_manager: 1, new_manager: 2 },
+			));
+
+			// The deposit moved to the new manager.
+			assert_eq!(Registrar::manager_of(para_id), Some(2));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_eq!(Balances::reserved_balance(&2), deposit);
+			assert_eq!(PendingManager::<Test>::get(para_id), None);
+
+			// The new manager can use the para ID, the previous one can't.
+			assert_noop!(
+				Registrar::register(
+					RuntimeOrigin::signed(1),
+					para_id,
+					vec![1; 3].into(),
+					vec![1, 2, 3].into(),
+				),
+				Error::<Test>::NotOwner
+			);
+			assert_ok!(Registrar::register(
+				RuntimeOrigin::signed(2),
+				para_id,
+				vec![1; 3].into(),
+				vec![1, 2, 3].into(),
+			));
+
+			// A locked para can't be transferred by its manager.
+			assert_ok!(Registrar::add_lock(RuntimeOrigin::signed(2), para_id));
+			assert_noop!(
+				Registrar::transfer_manager(RuntimeOrigin::signed(2), para_id, 3),
+				BadOrigin
+			);
+			assert_ok!(Registrar::transfer_manager(para_origin(para_id), para_id, 3));
+		});
+	}
+
+	#[test]
+	fn deregister_expired_reservation_works() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			assert_ok!(Registrar::reserve(RuntimeOrigin::signed(1)));
+			assert_ok!(Registrar::reserve(RuntimeOrigin::signed(2)));
+			let para_1 = LOWEST_PUBLIC_ID;
+			let para_2 = LOWEST_PUBLIC_ID + 1;
+			assert_ok!(Registrar::register(
+				RuntimeOrigin::signed(2),
+				para_2,
+				vec![1; 3].into(),
+				vec![1, 2, 3].into(),
+			));
+
+			assert_noop!(
+				Registrar::deregister_expired_reservation(RuntimeOrigin::signed(3), para_1),
+				Error::<Test>::ReservationNotExpired
+			);
+
+			run_to_block(1 + ReservationExpiry::get());
+
+			// Paras which are still onboarding can't be released.
+			assert_noop!(
+				Registrar::deregister_expired_reservation(RuntimeOrigin::signed(3), para_2),
+				Error::<Test>::NotReservation
+			);
+
+			// Anyone can release the unused reservation, the deposit goes back to the manager.
+			assert_ok!(Registrar::deregister_expired_reservation(RuntimeOrigin::signed(3), para_1));
+			System::assert_last_event(RuntimeEvent::Registrar(
+				paras_registrar::Event::Deregistered { para_id: para_1 },
+			));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert!(Paras::<Test>::get(para_1).is_none());
+			assert!(ReservedAt::<Test>::get(para_1).is_none());
+
+			assert_noop!(
+				Registrar::deregister_expired_reservation(RuntimeOrigin::signed(3), para_1),
+				Error::<Test>::NotReservation
+			);
+		});
+	}
+
+	#[test]
+	fn deregister_expired_reservation_releases_abandoned_parathreads() {
+		new_test_ext().execute_with(|| {
+			const START_SESSION_INDEX: SessionIndex = 1;
+			run_to_session(START_SESSION_INDEX);
+
+			let para_1 = LOWEST_PUBLIC_ID;
+			let para_2 = LOWEST_PUBLIC_ID + 1;
+			let validation_code = test_validation_code(32);
+			for (who, para_id) in [(1, para_1), (2, para_2)] {
+				assert_ok!(Registrar::reserve(RuntimeOrigin::signed(who)));
+				assert_ok!(Registrar::register(
+					RuntimeOrigin::signed(who),
+					para_id,
+					test_genesis_head(32),
+					validation_code.clone(),
+				));
+			}
+			conclude_pvf_checking::<Test>(&validation_code, VALIDATORS, START_SESSION_INDEX);
+			run_to_session(START_SESSION_INDEX + 2);
+			assert!(Parachains::is_parathread(para_1));
+			assert!(Parachains::is_parathread(para_2));
+
+			// The reservation period keeps running after registration.
+			assert_noop!(
+				Registrar::deregister_expired_reservation(RuntimeOrigin::signed(3), para_1),
+				Error::<Test>::ReservationNotExpired
+			);
+			run_to_block(System::block_number() + ReservationExpiry::get());
+
+			// Paras which had a block included are in use.
+			assert_ok!(Parachains::force_set_most_recent_context(RuntimeOrigin::root(), para_2, 5));
+			assert_noop!(
+				Registrar::deregister_expired_reservation(RuntimeOrigin::signed(3), para_2),
+				Error::<Test>::NotReservation
+			);
+
+			// Locked paras are kept.
+			assert_ok!(Registrar::add_lock(RuntimeOrigin::signed(1), para_1));
+			assert_noop!(
+				Registrar::deregister_expired_reservation(RuntimeOrigin::signed(3), para_1),
+				Error::<Test>::NotReservation
+			);
+			assert_ok!(Registrar::remove_lock(para_origin(para_1), para_1));
+
+			// Anyone can release the abandoned para, the deposit goes back to the manager.
+			assert_ok!(Registrar::deregister_expired_reservation(RuntimeOrigin::signed(3), para_1));
+			System::assert_last_event(RuntimeEvent::Registrar(
+				paras_registrar::Event::Deregistered { para_id: para_1 },
+			));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert!(Paras::<Test>::get(para_1).is_none());
+			assert!(ReservedAt::<Test>::get(para_1).is_none());
+			assert!(Parachains::is_offboarding(para_1));
+		});
+	}
+
+	#[test]
+	fn set_authorized_collators_works() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			assert_ok!(Registrar::reserve(RuntimeOrigin::signed(1)));
+			let para_id = LOWEST_PUBLIC_ID;
+			let collators: paras::AuthorizedCollatorList = bounded_vec![
+				Sr25519Keyring::Alice.public().into(),
+				Sr25519Keyring::Bob.public().into()
+			];
+
+			// Para IDs which are only reserved can't restrict their collators.
+			assert_noop!(
+				Registrar::set_authorized_collators(
+					RuntimeOrigin::signed(1),
+					para_id,
+					Some(collators.clone())
+				),
+				paras::Error::<Test>::NotRegistered
+			);
+
+			assert_ok!(Registrar::register(
+				RuntimeOrigin::signed(1),
+				para_id,
+				vec![1; 3].into(),
+				vec![1, 2, 3].into(),
+			));
+
+			// Only the manager, the para itself or root can set the allow-list.
+			assert_noop!(
+				Registrar::set_authorized_collators(
+					RuntimeOrigin::signed(2),
+					para_id,
+					Some(collators.clone())
+				),
+				BadOrigin
+			);
+			assert_ok!(Registrar::set_authorized_collators(
+				RuntimeOrigin::signed(1),
+				para_id,
+				Some(collators.clone())
+			));
+			let mut expected = collators.into_inner();
+			expected.sort();
+			assert_eq!(Parachains::authorized_collators(para_id), Some(expected));
+
+			// Once locked, the manager can't change the allow-list anymore.
+			assert_ok!(Registrar::add_lock(RuntimeOrigin::signed(1), para_id));
+			assert_noop!(
+				Registrar::set_authorized_collators(RuntimeOrigin::signed(1), para_id, None),
+				BadOrigin
+			);
+			assert_ok!(Registrar::set_authorized_collators(para_origin(para_id), para_id, None));
+			assert_eq!(Parachains::authorized_collators(para_id), None);
+		});
+	}
+
 	#[test]
 	fn swap_handles_bad_states() {
 		new_test_ext().execute_with(|| {
@@ -1564,6 +1979,56 @@ mod benchmarking {
 			let para_id = ParaId::from(1000);
 		}: _(RawOrigin::Root, para_id, new_head)
 
+		transfer_manager {
+			let para = register_para::<T>(LOWEST_PUBLIC_ID.into());
+			let caller: T::AccountId = whitelisted_caller();
+			let new_manager: T::AccountId = account("manager", 0, 0);
+		}: _(RawOrigin::Signed(caller), para, new_manager.clone())
+		verify {
+			assert_last_event::<T>(Event::<T>::ManagerTransferProposed { para_id: para, new_manager }.into());
+		}
+
+		accept_manager {
+			let para = register_para::<T>(LOWEST_PUBLIC_ID.into());
+			let new_manager: T::AccountId = account("manager", 0, 0);
+			T::Currency::make_free_balance_be(&new_manager, BalanceOf::<T>::max_value());
+			Registrar::<T>::transfer_manager(RawOrigin::Root.into(), para, new_manager.clone())?;
+		}: _(RawOrigin::Signed(new_manager.clone()), para)
+		verify {
+			assert_eq!(Registrar::<T>::manager_of(para), Some(new_manager));
+		}
+
+		deregister_expired_reservation {
+			// Worst case: a registered on-demand parachain which never had a block included.
+			let para = register_para::<T>(LOWEST_PUBLIC_ID.into());
+			next_scheduled_session::<T>();
+			assert_eq!(paras::Pallet::<T>::lifecycle(para), Some(ParaLifecycle::Parathread));
+			frame_system::Pallet::<T>::set_block_number(
+				frame_system::Pallet::<T>::block_number().saturating_add(T::ReservationExpiry::get())
+			);
+			let caller: T::AccountId = whitelisted_caller();
+		}: _(RawOrigin::Signed(caller), para)
+		verify {
+			assert_last_event::<T>(Event::<T>::Deregistered { para_id: para }.into());
+		}
+
+		set_authorized_collators {
+			let c in 0 .. paras::MAX_AUTHORIZED_COLLATORS;
+			let para = register_para::<T>(LOWEST_PUBLIC_ID.into());
+			let collators: paras::AuthorizedCollatorList = (0..c)
+				.map(|i| {
+					let mut raw = [0u8; 32];
+					raw[..4].copy_from_slice(&i.to_be_bytes());
+					primitives::CollatorId::from(sp_core::sr25519::Public::from_raw(raw))
+				})
+				.collect::<Vec<_>>()
+				.try_into()
+				.expect("`c` is at most `MAX_AUTHORIZED_COLLATORS`; qed");
+		}: _(RawOrigin::Root, para, Some(collators.clone()))
+		verify {
+			assert_eq!(paras::Pallet::<T>::authorized_collators(para), Some(collators.into_inner()));
+		}
+
 		impl_benchmark_test_suite!(
 			Registrar,
 			crate::integration_tests::new_test_ext(),
@@ -0,0 +1,68 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{Config, Pallet, Paras, ReservedAt};
+use crate::traits::SlotClaim;
+use frame_support::traits::{Get, OnRuntimeUpgrade};
+use sp_runtime::Saturating;
+use sp_std::collections::btree_set::BTreeSet;
+
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Starts the expiry period of the para IDs that were reserved before `ReservedAt` was tracked
+/// and are abandoned: still not used to register a para, or registered as an on-demand parachain
+/// which never had a block included. Paras holding a lease, or with a pending crowdloan or auction
+/// bid for one, are left alone even if they never were onboarded as lease holders.
+///
+/// The migration only adds missing entries, so running it more than once is harmless.
+pub struct NoteUnusedReservations<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for NoteUnusedReservations<T> {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		let now = frame_system::Pallet::<T>::block_number();
+		// Collect the claims once: checking them per para would walk all the winning bids of an
+		// ongoing auction for every para.
+		let mut claims = BTreeSet::new();
+		let mut reads = T::SlotClaims::note_slot_claims(&mut claims).saturating_add(1);
+		let mut writes = 0u64;
+
+		for id in Paras::<T>::iter_keys() {
+			// `Paras`, `ReservedAt`, the lifecycle and the most recent context.
+			reads.saturating_accrue(4);
+			if !claims.contains(&id) &&
+				!ReservedAt::<T>::contains_key(id) &&
+				Pallet::<T>::is_unused(id)
+			{
+				ReservedAt::<T>::insert(id, now);
+				writes.saturating_inc();
+			}
+		}
+
+		log::info!(target: "runtime::registrar", "Noted {} unused reservations", writes);
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		for id in Paras::<T>::iter_keys() {
+			frame_support::ensure!(
+				!Pallet::<T>::is_abandoned(id) || ReservedAt::<T>::contains_key(id),
+				"every abandoned reservation must have an expiry period"
+			);
+		}
+		Ok(())
+	}
+}
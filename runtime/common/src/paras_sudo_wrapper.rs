@@ -141,7 +141,8 @@ pub mod pallet {
 			let config = <configuration::Pallet<T>>::config();
 			<dmp::Pallet<T>>::queue_downward_message(&config, id, xcm.encode()).map_err(|e| match e
 			{
-				dmp::QueueDownwardMessageError::ExceedsMaxMessageSize =>
+				dmp::QueueDownwardMessageError::ExceedsMaxMessageSize |
+				dmp::QueueDownwardMessageError::ExceedsMaxPriorityMessages =>
 					Error::<T>::ExceedsMaxMessageSize.into(),
 			})
 		}
@@ -21,10 +21,19 @@
 //! This doesn't handle the mechanics of determining which para ID actually ends up with a parachain
 //! lease. This must handled by a separately, through the trait interface that this pallet provides
 //! or the root dispatchables.
+//!
+//! Once governance has set an extension price, an existing lease may also be extended for a fixed
+//! price per lease period without going through an auction. Extensions are refused while an
+//! auction is in progress, so that they never collide with bids placed for the same para. The para
+//! manager may also give back all lease periods after the current one, which frees them for
+//! future auctions and returns any deposit no longer needed for the current period.
 
 pub mod migration;
 
-use crate::traits::{LeaseError, Leaser, Registrar};
+use crate::{
+	slot_range::SlotRange,
+	traits::{Auctioneer, LeaseError, Leaser, Registrar},
+};
 use frame_support::{
 	pallet_prelude::*,
 	traits::{Currency, ReservableCurrency},
@@ -45,6 +54,9 @@ pub trait WeightInfo {
 	fn manage_lease_period_start(c: u32, t: u32) -> Weight;
 	fn clear_all_leases() -> Weight;
 	fn trigger_onboard() -> Weight;
+	fn set_extension_price() -> Weight;
+	fn extend_lease() -> Weight;
+	fn release_lease() -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -61,6 +73,15 @@ impl WeightInfo for TestWeightInfo {
 	fn trigger_onboard() -> Weight {
 		Weight::zero()
 	}
+	fn set_extension_price() -> Weight {
+		Weight::zero()
+	}
+	fn extend_lease() -> Weight {
+		Weight::zero()
+	}
+	fn release_lease() -> Weight {
+		Weight::zero()
+	}
 }
 
 #[frame_support::pallet]
@@ -93,6 +114,10 @@ pub mod pallet {
 		/// The origin which may forcibly create or clear leases. Root can always do this.
 		type ForceOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
 
+		/// The auctioneer selling lease periods. Leases cannot be extended while it is running an
+		/// auction.
+		type Auctioneer: Auctioneer<BlockNumberFor<Self>>;
+
 		/// Weight Information for the Extrinsics in the Pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -118,6 +143,12 @@ pub mod pallet {
 	pub type Leases<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, Vec<Option<(T::AccountId, BalanceOf<T>)>>, ValueQuery>;
 
+	/// The price per lease period for extending an existing lease.
+	///
+	/// Extensions are disabled while this is `None`.
+	#[pallet::storage]
+	pub type ExtensionPrice<T: Config> = StorageValue<_, BalanceOf<T>, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -134,6 +165,11 @@ pub mod pallet {
 			extra_reserved: BalanceOf<T>,
 			total_amount: BalanceOf<T>,
 		},
+		/// The price per lease period for lease extensions was changed. `None` disables them.
+		ExtensionPriceSet { price: Option<BalanceOf<T>> },
+		/// `leaser` gave back the `period_count` lease periods it held for a para after the
+		/// current one.
+		LeaseReleased { para_id: ParaId, leaser: T::AccountId, period_count: LeasePeriodOf<T> },
 	}
 
 	#[pallet::error]
@@ -142,6 +178,20 @@ pub mod pallet {
 		ParaNotOnboarding,
 		/// There was an error with the lease.
 		LeaseError,
+		/// Lease extensions are currently disabled.
+		ExtensionsDisabled,
+		/// The para does not hold any lease.
+		NoLease,
+		/// The caller is neither the para manager nor the holder of its last lease period.
+		NotLeaseHolder,
+		/// The caller is not the manager of the para.
+		NotParaManager,
+		/// An auction is in progress, leases cannot be extended until it concludes.
+		AuctionInProgress,
+		/// The lease would reach further ahead than a single auction can sell.
+		TooManyPeriods,
+		/// The caller holds no lease periods of the para after the current one.
+		NothingToRelease,
 	}
 
 	#[pallet::hooks]
@@ -221,6 +271,131 @@ pub mod pallet {
 			};
 			Ok(())
 		}
+
+		/// Set the price per lease period at which existing leases may be extended, or `None` to
+		/// disable extensions.
+		///
+		/// The dispatch origin for this call must match `T::ForceOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_extension_price())]
+		pub fn set_extension_price(
+			origin: OriginFor<T>,
+			price: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ExtensionPrice::<T>::set(price);
+			Self::deposit_event(Event::<T>::ExtensionPriceSet { price });
+			Ok(())
+		}
+
+		/// Extend the lease of `para` by `period_count` lease periods directly after its last
+		/// leased period, at the current `ExtensionPrice` per period.
+		///
+		/// The caller becomes the leaser of the new periods and has the extension price times
+		/// `period_count` held on deposit for them, minus whatever it already holds for `para`.
+		/// A lease cannot reach further ahead than `SlotRange::LEASE_PERIODS_PER_SLOT` periods.
+		///
+		/// The dispatch origin for this call must be signed by the para manager or the holder of
+		/// the last leased period, and no auction may be in progress.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::extend_lease())]
+		pub fn extend_lease(
+			origin: OriginFor<T>,
+			para: ParaId,
+			period_count: LeasePeriodOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let price = ExtensionPrice::<T>::get().ok_or(Error::<T>::ExtensionsDisabled)?;
+			let leases = Leases::<T>::get(para);
+			// There are never trailing `None`s, so the last entry is the last leased period.
+			let (holder, _) = leases.last().cloned().flatten().ok_or(Error::<T>::NoLease)?;
+			ensure!(
+				who == holder || T::Registrar::manager_of(para).as_ref() == Some(&who),
+				Error::<T>::NotLeaseHolder
+			);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			// Any running auction may hold bids for these very periods, which would otherwise end
+			// up confiscated as an attempt to lease the same period twice.
+			ensure!(
+				!T::Auctioneer::auction_status(now).is_in_progress(),
+				Error::<T>::AuctionInProgress
+			);
+			let (current_lease_period, _) =
+				Self::lease_period_index(now).ok_or(Error::<T>::LeaseError)?;
+
+			let count: u32 = period_count.checked_into().ok_or(Error::<T>::TooManyPeriods)?;
+			ensure!(count > 0, Error::<T>::LeaseError);
+			let held = leases.len() as u32;
+			ensure!(
+				held.saturating_add(count) <= SlotRange::LEASE_PERIODS_PER_SLOT as u32,
+				Error::<T>::TooManyPeriods
+			);
+
+			let period_begin = current_lease_period + LeasePeriodOf::<T>::from(held);
+			let amount = price.saturating_mul(BalanceOf::<T>::from(count));
+			Self::lease_out(para, &who, amount, period_begin, period_count)
+				.map_err(|_| Error::<T>::LeaseError)?;
+			Ok(())
+		}
+
+		/// Give back every lease period of `para` which is leased by the origin.
+		///
+		/// The released periods become available to auctions again, and the origin gets back the
+		/// part of its deposit which is no longer needed. The current lease period is only given
+		/// back if `para` is not onboarded as a lease holding parachain, nor being upgraded to one;
+		/// otherwise it is kept, along with its deposit, until it ends as usual. Periods leased by
+		/// other accounts, such as a crowdloan, are kept.
+		///
+		/// The dispatch origin for this call must be signed by the para manager.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::release_lease())]
+		pub fn release_lease(origin: OriginFor<T>, para: ParaId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				T::Registrar::manager_of(para).as_ref() == Some(&who),
+				Error::<T>::NotParaManager
+			);
+
+			let deposit = Self::deposit_held(para, &who);
+			let mut leases = Leases::<T>::get(para);
+			// Keep the current lease period if the para is using it; it is released when it ends.
+			let onboarded = T::Registrar::is_parachain(para) || T::Registrar::is_upgrading(para);
+			let keep = match leases.first() {
+				Some(Some(_)) if onboarded => 1,
+				_ => 0,
+			};
+			let mut released = 0u32;
+			for lease in leases.iter_mut().skip(keep) {
+				if matches!(lease, Some((leaser, _)) if leaser == &who) {
+					*lease = None;
+					released += 1;
+				}
+			}
+			ensure!(released > 0, Error::<T>::NothingToRelease);
+
+			while let Some(None) = leases.last() {
+				leases.pop();
+			}
+			if leases.is_empty() {
+				Leases::<T>::remove(para);
+			} else {
+				Leases::<T>::insert(para, &leases);
+			}
+
+			let now_held = Self::deposit_held(para, &who);
+			if let Some(rebate) = deposit.checked_sub(&now_held) {
+				let err_amount = T::Currency::unreserve(&who, rebate);
+				debug_assert!(err_amount.is_zero());
+			}
+
+			Self::deposit_event(Event::<T>::LeaseReleased {
+				para_id: para,
+				leaser: who,
+				period_count: released.into(),
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -334,6 +509,23 @@ impl<T: Config> crate::traits::OnSwap for Pallet<T> {
 	}
 }
 
+impl<T: Config> crate::traits::SlotClaim for Pallet<T> {
+	fn has_slot_claim(id: ParaId) -> bool {
+		!Leases::<T>::get(id).is_empty()
+	}
+
+	fn note_slot_claims(claims: &mut sp_std::collections::btree_set::BTreeSet<ParaId>) -> u64 {
+		let mut reads = 0u64;
+		for (para, leases) in Leases::<T>::iter() {
+			reads.saturating_inc();
+			if !leases.is_empty() {
+				claims.insert(para);
+			}
+		}
+		reads
+	}
+}
+
 impl<T: Config> Leaser<BlockNumberFor<T>> for Pallet<T> {
 	type AccountId = T::AccountId;
 	type LeasePeriod = BlockNumberFor<T>;
@@ -503,7 +695,10 @@ impl<T: Config> Leaser<BlockNumberFor<T>> for Pallet<T> {
 mod tests {
 	use super::*;
 
-	use crate::{mock::TestRegistrar, slots};
+	use crate::{
+		mock::{TestAuctioneer, TestRegistrar},
+		slots,
+	};
 	use ::test_helpers::{dummy_head_data, dummy_validation_code};
 	use frame_support::{assert_noop, assert_ok, parameter_types};
 	use frame_system::EnsureRoot;
@@ -588,6 +783,7 @@ mod tests {
 		type LeasePeriod = LeasePeriod;
 		type LeaseOffset = LeaseOffset;
 		type ForceOrigin = EnsureRoot<Self::AccountId>;
+		type Auctioneer = crate::mock::TestAuctioneer<Test, Balances>;
 		type WeightInfo = crate::slots::TestWeightInfo;
 	}
 
@@ -982,6 +1178,145 @@ mod tests {
 			assert_eq!(Slots::lease_period_index(2 * lpl + offset + 1), Some((2, false)));
 		});
 	}
+
+	#[test]
+	fn extend_lease_works() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+			let para = ParaId::from(1_u32);
+			assert_ok!(TestRegistrar::<Test>::register(
+				1,
+				para,
+				dummy_head_data(),
+				dummy_validation_code()
+			));
+
+			assert_noop!(
+				Slots::extend_lease(RuntimeOrigin::signed(1), para, 1),
+				Error::<Test>::ExtensionsDisabled
+			);
+			assert_noop!(
+				Slots::set_extension_price(RuntimeOrigin::signed(1), Some(2)),
+				sp_runtime::DispatchError::BadOrigin
+			);
+			assert_ok!(Slots::set_extension_price(RuntimeOrigin::root(), Some(2)));
+			assert_noop!(
+				Slots::extend_lease(RuntimeOrigin::signed(1), para, 1),
+				Error::<Test>::NoLease
+			);
+
+			// Periods 0 and 1 are leased.
+			assert_ok!(Slots::lease_out(para, &1, 1, 0, 2));
+			assert_eq!(Balances::reserved_balance(1), 1);
+
+			assert_noop!(
+				Slots::extend_lease(RuntimeOrigin::signed(2), para, 1),
+				Error::<Test>::NotLeaseHolder
+			);
+			assert_noop!(
+				Slots::extend_lease(RuntimeOrigin::signed(1), para, 7),
+				Error::<Test>::TooManyPeriods
+			);
+
+			assert_ok!(TestAuctioneer::<Test, Balances>::new_auction(5, 0));
+			assert_noop!(
+				Slots::extend_lease(RuntimeOrigin::signed(1), para, 1),
+				Error::<Test>::AuctionInProgress
+			);
+			TestAuctioneer::<Test, Balances>::end_auction();
+
+			// Three more periods at a price of 2 each.
+			assert_ok!(Slots::extend_lease(RuntimeOrigin::signed(1), para, 3));
+			assert_eq!(
+				Slots::lease(para),
+				vec![Some((1, 1)), Some((1, 1)), Some((1, 6)), Some((1, 6)), Some((1, 6))]
+			);
+			assert_eq!(Balances::reserved_balance(1), 6);
+			assert!(Slots::already_leased(para, 2, 4));
+			assert!(!Slots::already_leased(para, 5, 7));
+
+			run_to_block(50);
+			assert_eq!(Slots::deposit_held(para, &1), 0);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(
+				TestRegistrar::<Test>::operations(),
+				vec![(para, 1, true), (para, 50, false)]
+			);
+		});
+	}
+
+	#[test]
+	fn release_lease_works() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+			let para = ParaId::from(1_u32);
+			assert_ok!(TestRegistrar::<Test>::register(
+				1,
+				para,
+				dummy_head_data(),
+				dummy_validation_code()
+			));
+
+			// The manager leases periods 0, 2 and 3; period 1 is leased by another account, such
+			// as a crowdloan.
+			assert_ok!(Slots::lease_out(para, &1, 2, 0, 1));
+			assert_ok!(Slots::lease_out(para, &2, 5, 1, 1));
+			assert_ok!(Slots::lease_out(para, &1, 3, 2, 2));
+			assert_eq!(Balances::reserved_balance(1), 3);
+			assert_eq!(Balances::reserved_balance(2), 5);
+
+			assert_noop!(
+				Slots::release_lease(RuntimeOrigin::signed(2), para),
+				Error::<Test>::NotParaManager
+			);
+			assert_ok!(Slots::release_lease(RuntimeOrigin::signed(1), para));
+			System::assert_last_event(
+				Event::<Test>::LeaseReleased { para_id: para, leaser: 1, period_count: 2 }.into(),
+			);
+
+			// The current period and the periods of other leasers stay leased.
+			assert_eq!(Slots::lease(para), vec![Some((1, 2)), Some((2, 5))]);
+			assert_eq!(Balances::reserved_balance(1), 2);
+			assert_eq!(Balances::reserved_balance(2), 5);
+			assert!(!Slots::already_leased(para, 2, 3));
+			assert_noop!(
+				Slots::release_lease(RuntimeOrigin::signed(1), para),
+				Error::<Test>::NothingToRelease
+			);
+			assert_eq!(TestRegistrar::<Test>::parachains(), vec![para]);
+
+			run_to_block(10);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::reserved_balance(2), 5);
+
+			run_to_block(20);
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_eq!(TestRegistrar::<Test>::parathreads(), vec![para]);
+			assert_eq!(
+				TestRegistrar::<Test>::operations(),
+				vec![(para, 1, true), (para, 20, false)]
+			);
+
+			// A lease which has not started yet is given back entirely.
+			assert_ok!(Slots::lease_out(para, &1, 4, 3, 2));
+			assert_eq!(Balances::reserved_balance(1), 4);
+			assert_ok!(Slots::release_lease(RuntimeOrigin::signed(1), para));
+			assert!(Slots::lease(para).is_empty());
+			assert_eq!(Balances::reserved_balance(1), 0);
+
+			// So is the current period of a para which failed to onboard as a parachain.
+			assert_ok!(Slots::lease_out(para, &1, 4, 2, 2));
+			assert_eq!(TestRegistrar::<Test>::parachains(), vec![para]);
+			assert_ok!(TestRegistrar::<Test>::make_parathread(para));
+			assert_ok!(Slots::release_lease(RuntimeOrigin::signed(1), para));
+			System::assert_last_event(
+				Event::<Test>::LeaseReleased { para_id: para, leaser: 1, period_count: 2 }.into(),
+			);
+			assert!(Slots::lease(para).is_empty());
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert!(!Slots::already_leased(para, 2, 3));
+		});
+	}
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -1159,6 +1494,54 @@ mod benchmarking {
 			assert!(T::Registrar::is_parachain(para));
 		}
 
+		set_extension_price {
+			let price = T::Currency::minimum_balance();
+			let origin =
+				T::ForceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		}: _<T::RuntimeOrigin>(origin, Some(price))
+		verify {
+			assert_last_event::<T>(Event::<T>::ExtensionPriceSet { price: Some(price) }.into());
+		}
+
+		extend_lease {
+			let (para, leaser) = register_a_parathread::<T>(1);
+
+			// If there is an offset, we need to be on that block to be able to do lease things.
+			frame_system::Pallet::<T>::set_block_number(T::LeaseOffset::get() + One::one());
+			let amount = T::Currency::minimum_balance();
+			let origin = T::ForceOrigin::try_successful_origin()
+				.expect("ForceOrigin has no successful origin required for the benchmark");
+			Slots::<T>::force_lease(origin, para, leaser.clone(), amount, 1u32.into(), 1u32.into())?;
+			ExtensionPrice::<T>::put(amount);
+			let period_count: LeasePeriodOf<T> = 4u32.into();
+		}: _(RawOrigin::Signed(leaser.clone()), para, period_count)
+		verify {
+			assert_eq!(Leases::<T>::get(para).len(), 6);
+		}
+
+		release_lease {
+			let (para, manager) = register_a_parathread::<T>(1);
+
+			// If there is an offset, we need to be on that block to be able to do lease things.
+			frame_system::Pallet::<T>::set_block_number(T::LeaseOffset::get() + One::one());
+
+			// Worst case: the manager leases every future lease period.
+			let max_periods = SlotRange::LEASE_PERIODS_PER_SLOT as u32 - 1;
+			for i in 0 .. max_periods {
+				let amount = T::Currency::minimum_balance();
+				let origin = T::ForceOrigin::try_successful_origin()
+					.expect("ForceOrigin has no successful origin required for the benchmark");
+				Slots::<T>::force_lease(origin, para, manager.clone(), amount, (i + 1).into(), One::one())?;
+			}
+		}: _(RawOrigin::Signed(manager.clone()), para)
+		verify {
+			assert_last_event::<T>(Event::<T>::LeaseReleased {
+				para_id: para,
+				leaser: manager,
+				period_count: max_periods.into(),
+			}.into());
+		}
+
 		impl_benchmark_test_suite!(
 			Slots,
 			crate::integration_tests::new_test_ext(),
@@ -21,7 +21,7 @@ use frame_support::{
 	traits::{Currency, ReservableCurrency},
 };
 use primitives::{HeadData, Id as ParaId, ValidationCode};
-use sp_std::vec::*;
+use sp_std::{collections::btree_set::BTreeSet, vec::*};
 
 /// Parachain registration API.
 pub trait Registrar {
@@ -43,6 +43,10 @@ pub trait Registrar {
 	/// Return if a `ParaId` is a Parathread (on-demand parachain).
 	fn is_parathread(id: ParaId) -> bool;
 
+	/// Return if a `ParaId` is a Parathread (on-demand parachain) which is scheduled to be
+	/// upgraded to a lease holding Parachain.
+	fn is_upgrading(id: ParaId) -> bool;
+
 	/// Return if a `ParaId` is registered in the system.
 	fn is_registered(id: ParaId) -> bool {
 		Self::is_parathread(id) || Self::is_parachain(id)
@@ -263,3 +267,29 @@ pub trait OnSwap {
 	/// such as leases, deposits held and thread/chain nature are swapped.
 	fn on_swap(one: ParaId, other: ParaId);
 }
+
+/// Runtime hook telling whether a para holds, or may still win, a lease holding parachain slot.
+pub trait SlotClaim {
+	/// Whether `id` holds a current or future lease, or a crowdloan or auction bid for a lease is
+	/// pending.
+	fn has_slot_claim(id: ParaId) -> bool;
+
+	/// Add every para with a slot claim to `claims`, returning the number of storage items read.
+	///
+	/// Cheaper than calling `has_slot_claim` for each of many paras.
+	fn note_slot_claims(claims: &mut BTreeSet<ParaId>) -> u64;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl SlotClaim for Tuple {
+	fn has_slot_claim(id: ParaId) -> bool {
+		for_tuples!( #( if Tuple::has_slot_claim(id) { return true } )* );
+		false
+	}
+
+	fn note_slot_claims(claims: &mut BTreeSet<ParaId>) -> u64 {
+		let mut reads = 0u64;
+		for_tuples!( #( reads = reads.saturating_add(Tuple::note_slot_claims(claims)); )* );
+		reads
+	}
+}
@@ -16,7 +16,7 @@
 
 //! XCM sender for relay chain.
 
-use frame_support::traits::Get;
+use frame_support::traits::{Contains, Get};
 use frame_system::pallet_prelude::BlockNumberFor;
 use parity_scale_codec::Encode;
 use primitives::Id as ParaId;
@@ -27,6 +27,7 @@ use runtime_parachains::{
 use sp_runtime::FixedPointNumber;
 use sp_std::{marker::PhantomData, prelude::*};
 use xcm::prelude::*;
+use xcm_executor::send_origin::current_send_origin;
 use SendError::*;
 
 /// Simple value-bearing trait for determining/expressing the assets required to be paid for a
@@ -109,10 +110,64 @@ impl<T: configuration::Config + dmp::Config, W: xcm::WrapVersion, P: PriceForPar
 	}
 }
 
+/// XCM sender for relay chain which queues the downward messages of the origins matching `O` in
+/// the priority lane of the DMP queue.
+///
+/// The origin is taken from [`current_send_origin`], never from the message itself, so only
+/// messages sent by code which authenticated their sender, like `pallet_xcm::send`, can be
+/// prioritized. It is meant to be placed before [`ChildParachainRouter`] in the router tuple.
+/// Messages of other or unknown origins are not applicable and fall through to the next router.
+pub struct PriorityChildParachainRouter<T, W, P, O>(PhantomData<(T, W, P, O)>);
+
+impl<
+		T: configuration::Config + dmp::Config,
+		W: xcm::WrapVersion,
+		P: PriceForParachainDelivery,
+		O: Contains<MultiLocation>,
+	> SendXcm for PriorityChildParachainRouter<T, W, P, O>
+{
+	type Ticket = (HostConfiguration<BlockNumberFor<T>>, ParaId, Vec<u8>);
+
+	fn validate(
+		dest: &mut Option<MultiLocation>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<(HostConfiguration<BlockNumberFor<T>>, ParaId, Vec<u8>)> {
+		let d = dest.take().ok_or(MissingArgument)?;
+		let is_priority = current_send_origin().map_or(false, |origin| O::contains(&origin));
+		let id = match d {
+			MultiLocation { parents: 0, interior: X1(Parachain(id)) } if is_priority => id,
+			_ => {
+				*dest = Some(d);
+				return Err(NotApplicable)
+			},
+		};
+
+		// Downward message passing.
+		let xcm = msg.take().ok_or(MissingArgument)?;
+		let config = <configuration::Pallet<T>>::config();
+		let para = id.into();
+		let price = P::price_for_parachain_delivery(para, &xcm);
+		let blob = W::wrap_version(&d, xcm).map_err(|()| DestinationUnsupported)?.encode();
+		<dmp::Pallet<T>>::can_queue_priority_downward_message(&config, &para, &blob)
+			.map_err(Into::<SendError>::into)?;
+
+		Ok(((config, para, blob), price))
+	}
+
+	fn deliver(
+		(config, para, blob): (HostConfiguration<BlockNumberFor<T>>, ParaId, Vec<u8>),
+	) -> Result<XcmHash, SendError> {
+		let hash = sp_io::hashing::blake2_256(&blob[..]);
+		<dmp::Pallet<T>>::queue_priority_downward_message(&config, para, blob)
+			.map(|()| hash)
+			.map_err(|_| SendError::Transport(&"Error placing into DMP priority queue"))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use frame_support::parameter_types;
+	use frame_support::{assert_ok, parameter_types};
 	use runtime_parachains::FeeTracker;
 	use sp_runtime::FixedU128;
 
@@ -163,4 +218,43 @@ mod tests {
 			(FeeAssetId::get(), result).into()
 		);
 	}
+
+	#[test]
+	fn priority_router_takes_the_origin_from_the_send_context() {
+		use crate::integration_tests::{new_test_ext, Test};
+		use runtime_parachains::runtime_api_impl::v5::dmq_contents;
+		use xcm_executor::send_origin::with_send_origin;
+
+		frame_support::match_types! {
+			pub type RelayChainItself: impl Contains<MultiLocation> = {
+				MultiLocation { parents: 0, interior: Here }
+			};
+		}
+		type Router = PriorityChildParachainRouter<Test, (), (), RelayChainItself>;
+
+		new_test_ext().execute_with(|| {
+			let dest: MultiLocation = Parachain(2000).into();
+			let para = ParaId::from(2000);
+
+			// Without a send context, the message is never prioritized, even if it looks like
+			// it was sent by a prioritized origin.
+			assert_eq!(send_xcm::<Router>(dest, Xcm(vec![ClearOrigin])), Err(NotApplicable));
+			let forged = Xcm(vec![DescendOrigin(Here), ClearOrigin]);
+			assert_eq!(send_xcm::<Router>(dest, forged), Err(NotApplicable));
+
+			// Messages of other origins fall through to the next router.
+			let other: MultiLocation = AccountId32 { network: None, id: [1; 32] }.into();
+			assert_eq!(
+				with_send_origin(Some(other), || send_xcm::<Router>(dest, Xcm(vec![ClearOrigin]))),
+				Err(NotApplicable)
+			);
+			assert!(dmq_contents::<Test>(para).is_empty());
+
+			assert_ok!(with_send_origin(Some(Here.into()), || send_xcm::<Router>(
+				dest,
+				Xcm(vec![ClearOrigin])
+			)));
+			assert_eq!(dmq_contents::<Test>(para).len(), 1);
+		});
+	}
 }
@@ -1268,7 +1268,9 @@ impl pallet_message_queue::Config for Runtime {
 	type WeightInfo = weights::pallet_message_queue::WeightInfo<Runtime>;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type MaxPriorityDownwardMessages = ConstU32<64>;
+}
 
 impl parachains_hrmp::Config for Runtime {
 	type RuntimeOrigin = RuntimeOrigin;
@@ -1316,10 +1318,15 @@ impl parachains_slashing::Config for Runtime {
 	>;
 	type WeightInfo = weights::runtime_parachains_disputes_slashing::WeightInfo<Runtime>;
 	type BenchmarkingConfig = parachains_slashing::BenchConfig<1000>;
+	// Slash a fixed fraction, regardless of any offence history.
+	type SlashCurve = ();
+	type MaxOffenceHistory = ConstU32<16>;
+	type MaxSessionOffenders = MaxAuthorities;
 }
 
 parameter_types! {
 	pub const ParaDeposit: Balance = 40 * UNITS;
+	pub const ReservationExpiry: BlockNumber = 28 * DAYS;
 }
 
 impl paras_registrar::Config for Runtime {
@@ -1327,8 +1334,10 @@ impl paras_registrar::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type OnSwap = (Crowdloan, Slots);
+	type SlotClaims = (Slots, Crowdloan, Auctions);
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
+	type ReservationExpiry = ReservationExpiry;
 	type WeightInfo = weights::runtime_common_paras_registrar::WeightInfo<Runtime>;
 }
 
@@ -1344,6 +1353,7 @@ impl slots::Config for Runtime {
 	type LeasePeriod = LeasePeriod;
 	type LeaseOffset = ();
 	type ForceOrigin = EitherOf<EnsureRoot<Self::AccountId>, LeaseAdmin>;
+	type Auctioneer = Auctions;
 	type WeightInfo = weights::runtime_common_slots::WeightInfo<Runtime>;
 }
 
@@ -1724,6 +1734,8 @@ pub mod migrations {
 		parachains_configuration::migration::v7::MigrateToV7<Runtime>,
 		parachains_scheduler::migration::v1::MigrateToV1<Runtime>,
 		parachains_configuration::migration::v8::MigrateToV8<Runtime>,
+		parachains_slashing::migration::v1::MigrateToV1<Runtime>,
+		paras_registrar::migration::NoteUnusedReservations<Runtime>,
 
 		// Unlock/unreserve balances from Gov v1 pallets that hold them
 		// https://github.com/paritytech/polkadot/issues/6749
@@ -2221,6 +2233,36 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl crowdloan::runtime_api::CrowdloanApi<Block, AccountId, Balance> for Runtime {
+		fn contributions(
+			para: ParaId,
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Option<Vec<crowdloan::runtime_api::Contribution<AccountId, Balance>>> {
+			Crowdloan::contributions(para, start_after, limit)
+		}
+	}
+
+	impl pallet_xcm::dry_run::XcmDryRunApi<Block, RuntimeCall, RuntimeEvent> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			message: xcm::VersionedXcm<RuntimeCall>,
+		) -> Result<
+			pallet_xcm::dry_run::XcmDryRunEffects<RuntimeEvent>,
+			pallet_xcm::dry_run::XcmDryRunError,
+		> {
+			XcmPallet::dry_run_xcm(origin, message)
+		}
+	}
+
+	impl pallet_xcm::asset_traps::AssetTrapsApi<Block, BlockNumber> for Runtime {
+		fn trapped_assets(
+			origin: xcm::VersionedMultiLocation,
+		) -> Vec<pallet_xcm::asset_traps::TrappedAssets<BlockNumber>> {
+			XcmPallet::trapped_assets(origin)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -175,3 +175,9 @@ fn check_whitelist() {
 	// XcmPallet SafeXcmVersion
 	assert!(whitelist.contains("1405f2411d0af5a7ff397e7c9dc68d196323ae84c43568be0d1394d5d0d522c4"));
 }
+
+#[test]
+fn xcm_weigher_charges_converted_instructions_the_same() {
+	use xcm::conversion_harness::check_all_variants_weighed_by;
+	check_all_variants_weighed_by::<xcm::v3::Instruction<()>, weights::xcm::KusamaXcmWeight<()>>();
+}
@@ -30,7 +30,7 @@ use frame_system::EnsureRoot;
 use kusama_runtime_constants::currency::CENTS;
 use runtime_common::{
 	crowdloan, paras_registrar,
-	xcm_sender::{ChildParachainRouter, ExponentialPrice},
+	xcm_sender::{ChildParachainRouter, ExponentialPrice, PriorityChildParachainRouter},
 	ToAuthor,
 };
 use sp_core::ConstU32;
@@ -113,10 +113,26 @@ parameter_types! {
 	pub const BaseDeliveryFee: u128 = CENTS.saturating_mul(3);
 }
 
+// The origins whose messages to child parachains skip the regular downward message queue:
+// the relay chain itself and its governance bodies.
+match_types! {
+	pub type PriorityDownwardOrigins: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 0, interior: Here } |
+		MultiLocation { parents: 0, interior: X1(Plurality { .. }) }
+	};
+}
+
 /// The XCM router. When we want to send an XCM message, we use this type. It amalgamates all of our
 /// individual routers.
 pub type XcmRouter = WithUniqueTopic<(
-	// Only one router so far - use DMP to communicate with child parachains.
+	// Use the priority lane of DMP for messages of the priority origins to child parachains...
+	PriorityChildParachainRouter<
+		Runtime,
+		XcmPallet,
+		ExponentialPrice<FeeAssetId, BaseDeliveryFee, TransactionByteFee, Dmp>,
+		PriorityDownwardOrigins,
+	>,
+	// ...and regular DMP for all other messages to child parachains.
 	ChildParachainRouter<
 		Runtime,
 		XcmPallet,
@@ -418,6 +434,7 @@ impl pallet_xcm::Config for Runtime {
 	type MaxLockers = ConstU32<8>;
 	type MaxRemoteLockConsumers = ConstU32<0>;
 	type RemoteLockConsumerIdentifier = ();
+	type NonFungibleLocker = ();
 	type WeightInfo = crate::weights::pallet_xcm::WeightInfo<Runtime>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type ReachableDest = ReachableDest;
@@ -100,6 +100,10 @@ pub trait SlashingHandler<BlockNumber> {
 		backers: impl IntoIterator<Item = ValidatorIndex>,
 	);
 
+	/// The weight of punishing up to `offenders` validators for a single
+	/// dispute, on top of the weight of importing the dispute.
+	fn punish_weight(offenders: u32) -> Weight;
+
 	/// Called by the initializer to initialize the slashing pallet.
 	fn initializer_initialize(now: BlockNumber) -> Weight;
 
@@ -127,6 +131,10 @@ impl<BlockNumber> SlashingHandler<BlockNumber> for () {
 	) {
 	}
 
+	fn punish_weight(_: u32) -> Weight {
+		Weight::zero()
+	}
+
 	fn initializer_initialize(_now: BlockNumber) -> Weight {
 		Weight::zero()
 	}
@@ -41,17 +41,31 @@
 //! chain, without `FullIdentification` of the offenders. Later on, a block
 //! producer can submit an unsigned transaction with `KeyOwnershipProof` of an
 //! offender and submit it to the runtime to produce an offence.
-
-use crate::{disputes, initializer::ValidatorSetCount, session_info::IdentificationTuple};
+//!
+//! The fraction slashed for a lost dispute is given by a configurable
+//! [`SlashCurve`]. It escalates with the number of disputes the offenders
+//! already lost within a window of sessions, and with the number of other
+//! validators who already lost a dispute in the same session. The fraction is
+//! fixed when the dispute concludes and is recorded with the unapplied slashes.
+
+use crate::{
+	disputes,
+	initializer::ValidatorSetCount,
+	session_info::{AccountId, IdentificationTuple},
+};
 use frame_support::{
 	dispatch::Pays,
 	traits::{Defensive, Get, KeyOwnerProofSystem, ValidatorSet, ValidatorSetWithIdentification},
 	weights::Weight,
 };
 use frame_system::pallet_prelude::BlockNumberFor;
+use parity_scale_codec::{Decode, Encode};
 
 use primitives::{
-	vstaging::slashing::{DisputeProof, DisputesTimeSlot, PendingSlashes, SlashingOffenceKind},
+	vstaging::slashing::{
+		DisputeProof, DisputesTimeSlot, PendingSlashes, PendingSlashesWithFraction,
+		SlashingOffenceKind,
+	},
 	CandidateHash, SessionIndex, ValidatorId, ValidatorIndex,
 };
 use scale_info::TypeInfo;
@@ -61,7 +75,7 @@ use sp_runtime::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
 		TransactionValidityError, ValidTransaction,
 	},
-	KeyTypeId, Perbill,
+	KeyTypeId, Perbill, RuntimeDebug,
 };
 use sp_session::{GetSessionNumber, GetValidatorCount};
 use sp_staking::offence::{DisableStrategy, Kind, Offence, OffenceError, ReportOffence};
@@ -72,14 +86,85 @@ use sp_std::{
 
 const LOG_TARGET: &str = "runtime::parachains::slashing";
 
-// These are constants, but we want to make them configurable
-// via `HostConfiguration` in the future.
+// The base fractions of the default slash curve.
 const SLASH_FOR_INVALID: Perbill = Perbill::from_percent(100);
 const SLASH_AGAINST_VALID: Perbill = Perbill::zero();
 const DEFENSIVE_PROOF: &'static str = "disputes module should bail on old session";
 
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
+pub mod migration;
+
+/// The curve giving the fraction slashed for a lost dispute.
+///
+/// A dispute is slashed by the base fraction for its kind, plus `repeat_step`
+/// for every dispute the worst offender already lost within the last `window`
+/// sessions, plus `correlation` times the share of the validator set which
+/// already lost another dispute about a candidate of the same session. The
+/// result is capped at `max`.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct SlashCurve {
+	/// The base fraction for backing an invalid candidate.
+	pub for_invalid: Perbill,
+	/// The base fraction for disputing a valid candidate.
+	pub against_valid: Perbill,
+	/// The increase for every dispute lost within the window.
+	pub repeat_step: Perbill,
+	/// The number of sessions before the dispute's session in which lost
+	/// disputes count towards `repeat_step`.
+	pub window: SessionIndex,
+	/// The multiplier applied to the share of other validators which lost a
+	/// dispute in the same session.
+	pub correlation: u32,
+	/// The maximum fraction slashed for a single dispute.
+	pub max: Perbill,
+}
+
+impl Default for SlashCurve {
+	/// Fixed fractions, regardless of any offence history.
+	fn default() -> Self {
+		Self {
+			for_invalid: SLASH_FOR_INVALID,
+			against_valid: SLASH_AGAINST_VALID,
+			repeat_step: Perbill::zero(),
+			window: 0,
+			correlation: 0,
+			max: Perbill::one(),
+		}
+	}
+}
+
+impl SlashCurve {
+	/// The fraction slashed for a first offence of the given kind.
+	pub fn base(&self, kind: SlashingOffenceKind) -> Perbill {
+		match kind {
+			SlashingOffenceKind::ForInvalid => self.for_invalid,
+			SlashingOffenceKind::AgainstValid => self.against_valid,
+		}
+	}
+
+	/// The fraction slashed for a dispute of the given kind, where the worst
+	/// offender lost `prior_losses` disputes within the window, and
+	/// `other_offenders` out of `validator_set_count` validators, not counting
+	/// the offenders of this dispute, lost a dispute in the same session.
+	pub fn slash_fraction(
+		&self,
+		kind: SlashingOffenceKind,
+		prior_losses: u32,
+		other_offenders: u32,
+		validator_set_count: ValidatorSetCount,
+	) -> Perbill {
+		// `from_parts` and `from_rational` both saturate at 100%.
+		let repeat =
+			Perbill::from_parts(self.repeat_step.deconstruct().saturating_mul(prior_losses));
+		let correlated = Perbill::from_rational(
+			self.correlation.saturating_mul(other_offenders),
+			validator_set_count.max(1),
+		);
+
+		self.base(kind).saturating_add(repeat).saturating_add(correlated).min(self.max)
+	}
+}
 
 /// The benchmarking configuration.
 pub trait BenchmarkingConfiguration {
@@ -155,12 +240,9 @@ impl<KeyOwnerIdentification> SlashingOffence<KeyOwnerIdentification> {
 		validator_set_count: ValidatorSetCount,
 		offenders: Vec<KeyOwnerIdentification>,
 		kind: SlashingOffenceKind,
+		slash_fraction: Perbill,
 	) -> Self {
 		let time_slot = DisputesTimeSlot::new(session_index, candidate_hash);
-		let slash_fraction = match kind {
-			SlashingOffenceKind::ForInvalid => SLASH_FOR_INVALID,
-			SlashingOffenceKind::AgainstValid => SLASH_AGAINST_VALID,
-		};
 		Self { time_slot, validator_set_count, offenders, slash_fraction, kind }
 	}
 }
@@ -238,15 +320,25 @@ where
 			None => return,
 		};
 
+		let validator_set_count = session_info.discovery_keys.len() as ValidatorSetCount;
+		let account_keys = crate::session_info::Pallet::<T>::account_keys(session_index)
+			.defensive_unwrap_or_default();
+		let accounts: Vec<AccountId<T>> = to_punish
+			.iter()
+			.filter_map(|i| account_keys.get(i.0 as usize).cloned())
+			.collect();
+		let slash_fraction =
+			Pallet::<T>::note_offenders(session_index, kind, &accounts, validator_set_count);
+
 		let maybe = Self::maybe_identify_validators(session_index, to_punish.iter().cloned());
 		if let Some(offenders) = maybe {
-			let validator_set_count = session_info.discovery_keys.len() as ValidatorSetCount;
 			let offence = SlashingOffence::new(
 				session_index,
 				candidate_hash,
 				validator_set_count,
 				offenders,
 				kind,
+				slash_fraction,
 			);
 			// This is the first time we report an offence for this dispute,
 			// so it is not a duplicate.
@@ -258,13 +350,17 @@ where
 			.into_iter()
 			.filter_map(|i| session_info.validators.get(i).cloned().map(|id| (i, id)))
 			.collect();
-		let unapplied = PendingSlashes { keys, kind };
-
-		let append = |old: &mut Option<PendingSlashes>| {
-			let old = old
-				.get_or_insert(PendingSlashes { keys: Default::default(), kind: unapplied.kind });
+		let unapplied = PendingSlashesWithFraction { keys, kind, slash_fraction };
+
+		let append = |old: &mut Option<PendingSlashesWithFraction>| {
+			let old = old.get_or_insert(PendingSlashesWithFraction {
+				keys: Default::default(),
+				kind: unapplied.kind,
+				slash_fraction: unapplied.slash_fraction,
+			});
 			debug_assert_eq!(old.kind, unapplied.kind);
 
+			old.slash_fraction = old.slash_fraction.max(unapplied.slash_fraction);
 			old.keys.extend(unapplied.keys)
 		};
 		<UnappliedSlashes<T>>::mutate(session_index, candidate_hash, append);
@@ -295,6 +391,10 @@ where
 		// NOTE: changing that requires modifying `do_punish` implementation
 	}
 
+	fn punish_weight(offenders: u32) -> Weight {
+		<T as Config>::WeightInfo::note_offenders(offenders)
+	}
+
 	fn initializer_initialize(now: BlockNumberFor<T>) -> Weight {
 		Pallet::<T>::initializer_initialize(now)
 	}
@@ -363,6 +463,7 @@ impl<T: Config> HandleReports<T> for () {
 
 pub trait WeightInfo {
 	fn report_dispute_lost(validator_count: ValidatorSetCount) -> Weight;
+	fn note_offenders(offenders: u32) -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -370,6 +471,9 @@ impl WeightInfo for TestWeightInfo {
 	fn report_dispute_lost(_validator_count: ValidatorSetCount) -> Weight {
 		Weight::zero()
 	}
+	fn note_offenders(_offenders: u32) -> Weight {
+		Weight::zero()
+	}
 }
 
 pub use pallet::*;
@@ -410,9 +514,26 @@ pub mod pallet {
 
 		/// Benchmarking configuration.
 		type BenchmarkingConfig: BenchmarkingConfiguration;
+
+		/// The curve giving the fraction slashed for a lost dispute.
+		#[pallet::constant]
+		type SlashCurve: Get<SlashCurve>;
+
+		/// The maximum number of lost disputes remembered per validator.
+		#[pallet::constant]
+		type MaxOffenceHistory: Get<u32>;
+
+		/// The maximum number of validators which lose a dispute in a single
+		/// session. Should be at least the size of the validator set.
+		#[pallet::constant]
+		type MaxSessionOffenders: Get<u32>;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -424,7 +545,7 @@ pub mod pallet {
 		SessionIndex,
 		Blake2_128Concat,
 		CandidateHash,
-		PendingSlashes,
+		PendingSlashesWithFraction,
 	>;
 
 	/// `ValidatorSetCount` per session.
@@ -432,6 +553,31 @@ pub mod pallet {
 	pub(super) type ValidatorSetCounts<T> =
 		StorageMap<_, Twox64Concat, SessionIndex, ValidatorSetCount>;
 
+	/// The sessions of the candidates each validator account lost a dispute
+	/// about, in the order the disputes concluded.
+	///
+	/// Holds at most `MaxOffenceHistory` entries per account. Entries are pruned
+	/// once they can no longer count towards the slash curve.
+	#[pallet::storage]
+	pub(super) type OffenceHistory<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		AccountId<T>,
+		BoundedVec<SessionIndex, T::MaxOffenceHistory>,
+		ValueQuery,
+	>;
+
+	/// The validator accounts which lost a dispute about a candidate of the
+	/// given session.
+	#[pallet::storage]
+	pub(super) type SessionOffenders<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		BoundedVec<AccountId<T>, T::MaxSessionOffenders>,
+		ValueQuery,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// The key ownership proof is invalid.
@@ -474,31 +620,38 @@ pub mod pallet {
 			// check that there is a pending slash for the given
 			// validator index and candidate hash
 			let candidate_hash = dispute_proof.time_slot.candidate_hash;
-			let try_remove = |v: &mut Option<PendingSlashes>| -> Result<(), DispatchError> {
-				let pending = v.as_mut().ok_or(Error::<T>::InvalidCandidateHash)?;
-				if pending.kind != dispute_proof.kind {
-					return Err(Error::<T>::InvalidCandidateHash.into())
-				}
-
-				match pending.keys.entry(dispute_proof.validator_index) {
-					Entry::Vacant(_) => return Err(Error::<T>::InvalidValidatorIndex.into()),
-					// check that `validator_index` matches `validator_id`
-					Entry::Occupied(e) if e.get() != &dispute_proof.validator_id =>
-						return Err(Error::<T>::ValidatorIndexIdMismatch.into()),
-					Entry::Occupied(e) => {
-						e.remove(); // the report is correct
-					},
-				}
-
-				// if the last validator is slashed for this dispute, clean up the storage
-				if pending.keys.is_empty() {
-					*v = None;
-				}
-
-				Ok(())
-			};
-
-			<UnappliedSlashes<T>>::try_mutate_exists(&session_index, &candidate_hash, try_remove)?;
+			let try_remove =
+				|v: &mut Option<PendingSlashesWithFraction>| -> Result<Perbill, DispatchError> {
+					let pending = v.as_mut().ok_or(Error::<T>::InvalidCandidateHash)?;
+					if pending.kind != dispute_proof.kind {
+						return Err(Error::<T>::InvalidCandidateHash.into())
+					}
+
+					match pending.keys.entry(dispute_proof.validator_index) {
+						Entry::Vacant(_) => return Err(Error::<T>::InvalidValidatorIndex.into()),
+						// check that `validator_index` matches `validator_id`
+						Entry::Occupied(e) if e.get() != &dispute_proof.validator_id =>
+							return Err(Error::<T>::ValidatorIndexIdMismatch.into()),
+						Entry::Occupied(e) => {
+							e.remove(); // the report is correct
+						},
+					}
+
+					let slash_fraction = pending.slash_fraction;
+
+					// if the last validator is slashed for this dispute, clean up the storage
+					if pending.keys.is_empty() {
+						*v = None;
+					}
+
+					Ok(slash_fraction)
+				};
+
+			let slash_fraction = <UnappliedSlashes<T>>::try_mutate_exists(
+				&session_index,
+				&candidate_hash,
+				try_remove,
+			)?;
 
 			let offence = SlashingOffence::new(
 				session_index,
@@ -506,6 +659,7 @@ pub mod pallet {
 				validator_set_count,
 				vec![offender],
 				dispute_proof.kind,
+				slash_fraction,
 			);
 
 			<T::HandleReports as HandleReports<T>>::report_offence(offence)
@@ -545,6 +699,17 @@ impl<T: Config> Pallet<T> {
 		const REMOVE_LIMIT: u32 = u32::MAX;
 
 		let config = <crate::configuration::Pallet<T>>::config();
+
+		// Disputes can still conclude about candidates up to `dispute_period`
+		// sessions back, and count offences of `window` sessions before those.
+		let history_depth = config
+			.dispute_period
+			.saturating_add(T::SlashCurve::get().window)
+			.saturating_add(1);
+		if let Some(old_session) = session_index.checked_sub(history_depth) {
+			Self::prune_offence_history(old_session);
+		}
+
 		if session_index <= config.dispute_period + 1 {
 			return
 		}
@@ -553,7 +718,94 @@ impl<T: Config> Pallet<T> {
 		let _ = <UnappliedSlashes<T>>::clear_prefix(old_session, REMOVE_LIMIT, None);
 	}
 
+	/// Record that `offenders` lost a dispute about a candidate of
+	/// `session_index`, and return the fraction they are to be slashed by.
+	///
+	/// All offenders of a dispute share one fraction, escalated by the history of
+	/// the worst of them: they are reported under the same time slot, and the
+	/// offences pallet applies the fraction of the latest report to every
+	/// offender of a time slot.
+	///
+	/// The fraction is fixed when the dispute concludes, so the correlation only
+	/// counts the validators which lost a dispute of the session before: those
+	/// are not escalated again by the offenders of disputes concluding later.
+	/// Offenders which do not fit in `SessionOffenders` are slashed but not
+	/// remembered, since their history could not be pruned with the session.
+	pub(crate) fn note_offenders(
+		session_index: SessionIndex,
+		kind: SlashingOffenceKind,
+		offenders: &[AccountId<T>],
+		validator_set_count: ValidatorSetCount,
+	) -> Perbill {
+		let curve = T::SlashCurve::get();
+		let earliest = session_index.saturating_sub(curve.window);
+		let prior_losses = offenders
+			.iter()
+			.map(|who| {
+				<OffenceHistory<T>>::get(who)
+					.iter()
+					.filter(|s| (earliest..=session_index).contains(*s))
+					.count() as u32
+			})
+			.max()
+			.unwrap_or(0);
+
+		// Only the other validators of the session count towards the correlation,
+		// so that a single offence isn't escalated by itself.
+		let (other_offenders, recorded) =
+			<SessionOffenders<T>>::mutate(session_index, |accounts| {
+				let others = accounts.iter().filter(|who| !offenders.contains(who)).count() as u32;
+				let mut recorded = Vec::with_capacity(offenders.len());
+				for who in offenders {
+					if accounts.contains(who) || accounts.try_push(who.clone()).is_ok() {
+						recorded.push(who);
+					} else {
+						log::warn!(
+							target: LOG_TARGET,
+							"More than `MaxSessionOffenders` validators lost a dispute in session {}",
+							session_index,
+						);
+					}
+				}
+				(others, recorded)
+			});
+
+		for who in recorded {
+			<OffenceHistory<T>>::mutate(who, |history| {
+				// Forget the oldest offence to make room, if needed.
+				if !history.is_empty() && history.len() >= T::MaxOffenceHistory::get() as usize {
+					history.remove(0);
+				}
+				let _ = history.try_push(session_index);
+			});
+		}
+
+		curve.slash_fraction(kind, prior_losses, other_offenders, validator_set_count)
+	}
+
+	/// Forget the offences committed in `session_index` or before by the
+	/// validators which lost a dispute in that session.
+	fn prune_offence_history(session_index: SessionIndex) {
+		for who in <SessionOffenders<T>>::take(session_index) {
+			<OffenceHistory<T>>::mutate_exists(&who, |maybe_history| {
+				if let Some(history) = maybe_history {
+					history.retain(|s| *s > session_index);
+					if history.is_empty() {
+						*maybe_history = None;
+					}
+				}
+			});
+		}
+	}
+
 	pub(crate) fn unapplied_slashes() -> Vec<(SessionIndex, CandidateHash, PendingSlashes)> {
+		<UnappliedSlashes<T>>::iter()
+			.map(|(session, candidate_hash, pending)| (session, candidate_hash, pending.into()))
+			.collect()
+	}
+
+	pub(crate) fn unapplied_slashes_with_fraction(
+	) -> Vec<(SessionIndex, CandidateHash, PendingSlashesWithFraction)> {
 		<UnappliedSlashes<T>>::iter().collect()
 	}
 
@@ -726,3 +978,133 @@ where
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Slashing, Test, TestSlashCurve};
+	use sp_std::collections::btree_map::BTreeMap;
+
+	#[test]
+	fn slash_curve_escalates() {
+		let curve = SlashCurve {
+			for_invalid: Perbill::from_percent(10),
+			against_valid: Perbill::zero(),
+			repeat_step: Perbill::from_percent(5),
+			window: 4,
+			correlation: 3,
+			max: Perbill::from_percent(50),
+		};
+		let kind = SlashingOffenceKind::ForInvalid;
+
+		assert_eq!(curve.slash_fraction(kind, 0, 1, 100), Perbill::from_rational(13u32, 100));
+		// Two disputes lost before within the window.
+		assert_eq!(curve.slash_fraction(kind, 2, 0, 100), Perbill::from_percent(20));
+		// Five other out of a hundred validators lost a dispute in the same session.
+		assert_eq!(curve.slash_fraction(kind, 0, 5, 100), Perbill::from_percent(25));
+		assert_eq!(curve.slash_fraction(kind, 10, 50, 100), Perbill::from_percent(50));
+		assert_eq!(
+			curve.slash_fraction(SlashingOffenceKind::AgainstValid, 0, 0, 100),
+			Perbill::zero()
+		);
+
+		// The default curve ignores any history.
+		assert_eq!(SlashCurve::default().slash_fraction(kind, 0, 0, 100), Perbill::one());
+		assert_eq!(SlashCurve::default().slash_fraction(kind, 10, 50, 0), Perbill::one());
+	}
+
+	#[test]
+	fn note_offenders_escalates_with_history_and_correlation() {
+		new_test_ext(Default::default()).execute_with(|| {
+			let kind = SlashingOffenceKind::ForInvalid;
+
+			// A single first offence is slashed by the base fraction.
+			assert_eq!(Slashing::note_offenders(1, kind, &[1], 10), Perbill::from_percent(10));
+			// The same offender again: one prior loss, no other offenders.
+			assert_eq!(Slashing::note_offenders(1, kind, &[1], 10), Perbill::from_percent(15));
+			// A new offender in the same session: one other offender out of ten.
+			assert_eq!(Slashing::note_offenders(1, kind, &[2], 10), Perbill::from_percent(30));
+			// Capped at the maximum of the curve.
+			assert_eq!(Slashing::note_offenders(1, kind, &[3, 4], 10), Perbill::from_percent(40));
+
+			assert_eq!(OffenceHistory::<Test>::get(1).into_inner(), vec![1, 1]);
+			assert_eq!(SessionOffenders::<Test>::get(1).into_inner(), vec![1, 2, 3, 4]);
+
+			// Offences outside of the window are not counted.
+			assert_eq!(Slashing::note_offenders(4, kind, &[1], 10), Perbill::from_percent(10));
+			assert_eq!(OffenceHistory::<Test>::get(1).into_inner(), vec![1, 1, 4]);
+
+			// Offenders beyond `MaxSessionOffenders` are still slashed, but not remembered,
+			// since their history could not be pruned.
+			assert_eq!(Slashing::note_offenders(1, kind, &[5], 10), Perbill::from_percent(40));
+			assert_eq!(SessionOffenders::<Test>::get(1).len(), 4);
+			assert!(!OffenceHistory::<Test>::contains_key(5));
+		});
+	}
+
+	#[test]
+	fn offence_history_is_pruned() {
+		new_test_ext(Default::default()).execute_with(|| {
+			let kind = SlashingOffenceKind::ForInvalid;
+			let dispute_period = crate::configuration::Pallet::<Test>::config().dispute_period;
+			let history_depth = dispute_period + TestSlashCurve::get().window + 1;
+
+			Slashing::note_offenders(1, kind, &[1, 2], 10);
+			Slashing::note_offenders(2, kind, &[2], 10);
+
+			Slashing::initializer_on_new_session(history_depth);
+			assert_eq!(OffenceHistory::<Test>::get(1).into_inner(), vec![1]);
+			assert!(SessionOffenders::<Test>::contains_key(1));
+
+			Slashing::initializer_on_new_session(history_depth + 1);
+			assert!(!OffenceHistory::<Test>::contains_key(1));
+			assert_eq!(OffenceHistory::<Test>::get(2).into_inner(), vec![2]);
+			assert!(!SessionOffenders::<Test>::contains_key(1));
+
+			Slashing::initializer_on_new_session(history_depth + 2);
+			assert!(!OffenceHistory::<Test>::contains_key(2));
+			assert!(!SessionOffenders::<Test>::contains_key(2));
+		});
+	}
+
+	#[test]
+	fn migration_to_v1_records_base_fraction() {
+		use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+		new_test_ext(Default::default()).execute_with(|| {
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(1));
+			let keys: BTreeMap<_, _> = [(
+				ValidatorIndex(0),
+				ValidatorId::from(sp_core::sr25519::Public::from_raw([0; 32])),
+			)]
+			.into_iter()
+			.collect();
+			let old = PendingSlashes { keys: keys.clone(), kind: SlashingOffenceKind::ForInvalid };
+			frame_support::storage::unhashed::put_raw(
+				&UnappliedSlashes::<Test>::hashed_key_for(1, candidate_hash),
+				&old.encode(),
+			);
+			StorageVersion::new(0).put::<Slashing>();
+
+			migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+			assert_eq!(StorageVersion::get::<Slashing>(), 1);
+			assert_eq!(
+				UnappliedSlashes::<Test>::get(1, candidate_hash),
+				Some(PendingSlashesWithFraction {
+					keys,
+					kind: SlashingOffenceKind::ForInvalid,
+					slash_fraction: Perbill::from_percent(10),
+				}),
+			);
+			// The stable runtime API still returns the slashes without the fraction.
+			assert_eq!(
+				Slashing::unapplied_slashes()
+					.into_iter()
+					.map(|(_, _, p)| p.keys)
+					.collect::<Vec<_>>(),
+				vec![keys],
+			);
+		});
+	}
+}
@@ -17,8 +17,11 @@
 use super::*;
 
 use crate::{disputes::SlashingHandler, initializer, shared};
-use frame_benchmarking::{benchmarks, whitelist_account};
-use frame_support::traits::{OnFinalize, OnInitialize};
+use frame_benchmarking::{account, benchmarks, whitelist_account};
+use frame_support::{
+	traits::{OnFinalize, OnInitialize},
+	BoundedVec,
+};
 use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 use pallet_staking::testing_utils::create_validators;
 use parity_scale_codec::Decode;
@@ -160,4 +163,20 @@ benchmarks! {
 		let unapplied = <UnappliedSlashes<T>>::get(session_index, CANDIDATE_HASH);
 		assert!(unapplied.is_none());
 	}
+
+	// `n` validators, each with a full offence history, lose a dispute
+	note_offenders {
+		let n in 1..<<T as super::Config>::BenchmarkingConfig as BenchmarkingConfiguration>::MAX_VALIDATORS;
+
+		let session_index: SessionIndex = 1;
+		let offenders: Vec<AccountId<T>> = (0..n).map(|i| account("offender", i, 0)).collect();
+		let history = vec![session_index; T::MaxOffenceHistory::get() as usize];
+		for who in &offenders {
+			<OffenceHistory<T>>::insert(who, BoundedVec::truncate_from(history.clone()));
+		}
+	}: {
+		Pallet::<T>::note_offenders(session_index, SlashingOffenceKind::ForInvalid, &offenders, n);
+	} verify {
+		assert!(!<SessionOffenders<T>>::get(session_index).is_empty());
+	}
 }
@@ -0,0 +1,65 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migration(s) related to the disputes slashing pallet.
+
+use frame_support::traits::StorageVersion;
+
+pub mod v1 {
+	use super::*;
+	use crate::disputes::slashing::{Config, Pallet, UnappliedSlashes, LOG_TARGET};
+	use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade, weights::Weight};
+	use primitives::vstaging::slashing::{PendingSlashes, PendingSlashesWithFraction};
+	use sp_std::prelude::*;
+
+	/// Records the base fraction of the slash curve on all unapplied slashes.
+	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 1 {
+				log::info!(
+					target: LOG_TARGET,
+					"Disputes slashing storage up to date - no need for migration"
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			log::info!(target: LOG_TARGET, "Migrating disputes slashing storage to v1");
+			let curve = T::SlashCurve::get();
+			let mut translated = 0u64;
+			UnappliedSlashes::<T>::translate::<PendingSlashes, _>(|_, _, old| {
+				translated += 1;
+				Some(PendingSlashesWithFraction {
+					keys: old.keys,
+					kind: old.kind,
+					slash_fraction: curve.base(old.kind),
+				})
+			});
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				StorageVersion::get::<Pallet<T>>() >= 1,
+				"Storage version should be `1` after the migration"
+			);
+			Ok(())
+		}
+	}
+}
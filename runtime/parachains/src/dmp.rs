@@ -41,6 +41,16 @@
 //! limit is set to the number of messages in the DownwardMessageQueue. Messages
 //! that would increase the number of messages in the queue above this hard
 //! limit are dropped.
+//!
+//! Messages queued through [`Pallet::queue_priority_downward_message`] form a
+//! priority lane with a budget of its own: at most `Config::MaxPriorityDownwardMessages`
+//! of them may be pending in a queue. They count neither towards the `max_messages`
+//! hard limit nor towards the growth of the `DeliveryFeeFactor`, so regular traffic
+//! can't crowd them out and they don't make regular messages more expensive. Within a block,
+//! priority messages are placed ahead of all the regular messages queued in the same block,
+//! so the parachain sees them first. Messages queued in earlier blocks are never
+//! reordered: they may already be visible to candidates, and the parachain relies
+//! on both the MQC and the `sent_at` order of the queue.
 
 use crate::{
 	configuration::{self, HostConfiguration},
@@ -71,12 +81,16 @@ const MESSAGE_SIZE_FEE_BASE: FixedU128 = FixedU128::from_rational(1, 1000); // 0
 pub enum QueueDownwardMessageError {
 	/// The message being sent exceeds the configured max message size.
 	ExceedsMaxMessageSize,
+	/// The queue already holds `Config::MaxPriorityDownwardMessages` priority messages.
+	ExceedsMaxPriorityMessages,
 }
 
 impl From<QueueDownwardMessageError> for SendError {
 	fn from(err: QueueDownwardMessageError) -> Self {
 		match err {
 			QueueDownwardMessageError::ExceedsMaxMessageSize => SendError::ExceedsMaxMessageSize,
+			QueueDownwardMessageError::ExceedsMaxPriorityMessages =>
+				SendError::Transport("Too many pending priority downward messages"),
 		}
 	}
 }
@@ -115,7 +129,12 @@ pub mod pallet {
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config + configuration::Config {}
+	pub trait Config: frame_system::Config + configuration::Config {
+		/// The number of priority messages which may be pending in a downward message queue, on
+		/// top of the regular messages.
+		#[pallet::constant]
+		type MaxPriorityDownwardMessages: Get<u32>;
+	}
 
 	/// The downward messages addressed for a certain para.
 	#[pallet::storage]
@@ -148,6 +167,32 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type DeliveryFeeFactor<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, FixedU128, ValueQuery, InitialFactor>;
+
+	/// The window of messages queued for a para in the latest block that queued any.
+	///
+	/// Priority messages are inserted into this window, ahead of the regular messages.
+	#[pallet::storage]
+	pub(crate) type DownwardMessageQueueWindows<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, QueueWindow<BlockNumberFor<T>>, OptionQuery>;
+
+	/// The positions of the pending priority messages in the downward message queue of a para,
+	/// in ascending order.
+	#[pallet::storage]
+	pub(crate) type DownwardPriorityMessageIndices<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, Vec<u32>, ValueQuery>;
+}
+
+/// The tail of a downward message queue holding the messages queued in a single block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct QueueWindow<BlockNumber> {
+	/// The block in which the messages of the window were queued.
+	pub(crate) at: BlockNumber,
+	/// The MQC head before the first message of the window.
+	pub(crate) head_before: Hash,
+	/// The number of messages in the window, at the end of the queue.
+	pub(crate) len: u32,
+	/// The number of priority messages at the start of the window.
+	pub(crate) priority_len: u32,
 }
 /// Routines and getters related to downward message passing.
 impl<T: Config> Pallet<T> {
@@ -179,6 +224,8 @@ impl<T: Config> Pallet<T> {
 	fn clean_dmp_after_outgoing(outgoing_para: &ParaId) {
 		DownwardMessageQueues::<T>::remove(outgoing_para);
 		DownwardMessageQueueHeads::<T>::remove(outgoing_para);
+		DownwardMessageQueueWindows::<T>::remove(outgoing_para);
+		DownwardPriorityMessageIndices::<T>::remove(outgoing_para);
 	}
 
 	/// Determine whether enqueuing a downward message to a specific recipient para would result
@@ -195,7 +242,8 @@ impl<T: Config> Pallet<T> {
 		}
 
 		// Hard limit on Queue size
-		if Self::dmq_length(*para) > Self::dmq_max_length(config.max_downward_message_size) {
+		if Self::regular_dmq_length(*para) > Self::dmq_max_length(config.max_downward_message_size)
+		{
 			return Err(QueueDownwardMessageError::ExceedsMaxMessageSize)
 		}
 
@@ -221,7 +269,7 @@ impl<T: Config> Pallet<T> {
 		}
 
 		// Hard limit on Queue size
-		if Self::dmq_length(para) > Self::dmq_max_length(config.max_downward_message_size) {
+		if Self::regular_dmq_length(para) > Self::dmq_max_length(config.max_downward_message_size) {
 			return Err(QueueDownwardMessageError::ExceedsMaxMessageSize)
 		}
 
@@ -229,20 +277,19 @@ impl<T: Config> Pallet<T> {
 			InboundDownwardMessage { msg, sent_at: <frame_system::Pallet<T>>::block_number() };
 
 		// obtain the new link in the MQC and update the head.
-		DownwardMessageQueueHeads::<T>::mutate(para, |head| {
-			let new_head =
-				BlakeTwo256::hash_of(&(*head, inbound.sent_at, T::Hashing::hash_of(&inbound.msg)));
-			*head = new_head;
+		let head_before = DownwardMessageQueueHeads::<T>::mutate(para, |head| {
+			let head_before = *head;
+			*head = Self::mqc_link(head_before, &inbound);
+			head_before
 		});
+		Self::note_window_message(para, inbound.sent_at, head_before);
 
-		let q_len = DownwardMessageQueues::<T>::mutate(para, |v| {
-			v.push(inbound);
-			v.len()
-		});
+		DownwardMessageQueues::<T>::mutate(para, |v| v.push(inbound));
+		let q_len = Self::regular_dmq_length(para);
 
 		let threshold =
 			Self::dmq_max_length(config.max_downward_message_size).saturating_div(THRESHOLD_FACTOR);
-		if q_len > (threshold as usize) {
+		if q_len > threshold {
 			let message_size_factor =
 				FixedU128::from_u32(serialized_len.saturating_div(1024) as u32)
 					.saturating_mul(MESSAGE_SIZE_FEE_BASE);
@@ -252,6 +299,107 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Determine whether enqueuing a priority downward message to a specific recipient para would
+	/// result in an error. If this returns `Ok(())` the caller can be certain that a call to
+	/// `queue_priority_downward_message` with the same parameters will be successful.
+	pub fn can_queue_priority_downward_message(
+		config: &HostConfiguration<BlockNumberFor<T>>,
+		para: &ParaId,
+		msg: &DownwardMessage,
+	) -> Result<(), QueueDownwardMessageError> {
+		let serialized_len = msg.len() as u32;
+		if serialized_len > config.max_downward_message_size {
+			return Err(QueueDownwardMessageError::ExceedsMaxMessageSize)
+		}
+
+		// The priority lane has a budget of its own, regardless of the regular messages.
+		if Self::priority_dmq_length(*para) >= T::MaxPriorityDownwardMessages::get() {
+			return Err(QueueDownwardMessageError::ExceedsMaxPriorityMessages)
+		}
+
+		Ok(())
+	}
+
+	/// Enqueue a downward message to a specific recipient para through the priority lane.
+	///
+	/// The message is placed after the priority messages but ahead of the regular messages that
+	/// were queued for the para in the current block. Unlike `queue_downward_message`, this
+	/// doesn't increase the delivery fee factor of the para and the message doesn't count towards
+	/// the hard limit on the queue size. Instead, at most `Config::MaxPriorityDownwardMessages`
+	/// priority messages may be pending.
+	///
+	/// The same remarks about non-existent paras apply as for `queue_downward_message`.
+	pub fn queue_priority_downward_message(
+		config: &HostConfiguration<BlockNumberFor<T>>,
+		para: ParaId,
+		msg: DownwardMessage,
+	) -> Result<(), QueueDownwardMessageError> {
+		Self::can_queue_priority_downward_message(config, &para, &msg)?;
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		let inbound = InboundDownwardMessage { msg, sent_at: now };
+
+		let head = DownwardMessageQueueHeads::<T>::get(para);
+		let window = Self::note_window_message(para, now, head);
+
+		// The regular messages of the window follow the priority ones, so the MQC has to be
+		// rebuilt from the start of the window.
+		DownwardMessageQueues::<T>::mutate(para, |q| {
+			let window_start = q.len().saturating_sub(window.len.saturating_sub(1) as usize);
+			let at = window_start.saturating_add(window.priority_len as usize).min(q.len());
+			q.insert(at, inbound);
+
+			DownwardPriorityMessageIndices::<T>::mutate(para, |indices| {
+				let at = at as u32;
+				for index in indices.iter_mut().filter(|index| **index >= at) {
+					index.saturating_inc();
+				}
+				let position = indices.partition_point(|index| *index < at);
+				indices.insert(position, at);
+			});
+
+			let new_head = q[window_start..]
+				.iter()
+				.fold(window.head_before, |head, msg| Self::mqc_link(head, msg));
+			DownwardMessageQueueHeads::<T>::insert(para, new_head);
+		});
+
+		DownwardMessageQueueWindows::<T>::mutate(para, |w| {
+			if let Some(w) = w {
+				w.priority_len.saturating_inc();
+			}
+		});
+
+		Ok(())
+	}
+
+	/// Account for a message queued for `para` at block `now` in the queue window of the para.
+	///
+	/// Opens a new window starting at `head_before` if the current one belongs to an earlier
+	/// block. Returns the window as it was after accounting for the message.
+	fn note_window_message(
+		para: ParaId,
+		now: BlockNumberFor<T>,
+		head_before: Hash,
+	) -> QueueWindow<BlockNumberFor<T>> {
+		DownwardMessageQueueWindows::<T>::mutate(para, |w| {
+			let window = match w.take() {
+				Some(mut window) if window.at == now => {
+					window.len.saturating_inc();
+					window
+				},
+				_ => QueueWindow { at: now, head_before, len: 1, priority_len: 0 },
+			};
+			*w = Some(window.clone());
+			window
+		})
+	}
+
+	/// Computes the next link of the MQC from the previous head and the appended message.
+	fn mqc_link(head: Hash, msg: &InboundDownwardMessage<BlockNumberFor<T>>) -> Hash {
+		BlakeTwo256::hash_of(&(head, msg.sent_at, T::Hashing::hash_of(&msg.msg)))
+	}
+
 	/// Checks if the number of processed downward messages is valid.
 	pub(crate) fn check_processed_downward_messages(
 		para: ParaId,
@@ -288,7 +436,7 @@ impl<T: Config> Pallet<T> {
 
 	/// Prunes the specified number of messages from the downward message queue of the given para.
 	pub(crate) fn prune_dmq(para: ParaId, processed_downward_messages: u32) -> Weight {
-		let q_len = DownwardMessageQueues::<T>::mutate(para, |q| {
+		DownwardMessageQueues::<T>::mutate(para, |q| {
 			let processed_downward_messages = processed_downward_messages as usize;
 			if processed_downward_messages > q.len() {
 				// reaching this branch is unexpected due to the constraint established by
@@ -297,16 +445,36 @@ impl<T: Config> Pallet<T> {
 			} else {
 				*q = q.split_off(processed_downward_messages);
 			}
-			q.len()
+		});
+
+		DownwardPriorityMessageIndices::<T>::mutate_exists(para, |maybe_indices| {
+			if let Some(indices) = maybe_indices {
+				indices.retain(|index| *index >= processed_downward_messages);
+				for index in indices.iter_mut() {
+					*index -= processed_downward_messages;
+				}
+				if indices.is_empty() {
+					*maybe_indices = None;
+				}
+			}
+		});
+		let q_len = Self::regular_dmq_length(para);
+
+		// A window that was partially pruned can no longer be used to place priority messages
+		// as its starting head doesn't correspond to the queue anymore.
+		DownwardMessageQueueWindows::<T>::mutate_exists(para, |w| {
+			if w.as_ref().map_or(false, |w| Self::dmq_length(para) < w.len) {
+				*w = None;
+			}
 		});
 
 		let config = configuration::ActiveConfig::<T>::get();
 		let threshold =
 			Self::dmq_max_length(config.max_downward_message_size).saturating_div(THRESHOLD_FACTOR);
-		if q_len <= (threshold as usize) {
+		if q_len <= threshold {
 			Self::decrement_fee_factor(para);
 		}
-		T::DbWeight::get().reads_writes(1, 1)
+		T::DbWeight::get().reads_writes(3, 3)
 	}
 
 	/// Returns the Head of Message Queue Chain for the given para or `None` if there is none
@@ -329,9 +497,23 @@ impl<T: Config> Pallet<T> {
 		MAX_POSSIBLE_ALLOCATION.checked_div(max_downward_message_size).unwrap_or(0)
 	}
 
+	/// Returns the number of pending priority messages addressed to the given para.
+	pub(crate) fn priority_dmq_length(para: ParaId) -> u32 {
+		DownwardPriorityMessageIndices::<T>::decode_len(&para)
+			.unwrap_or(0)
+			.saturated_into::<u32>()
+	}
+
+	/// Returns the number of pending regular messages addressed to the given para, which are
+	/// subject to the hard limit on the queue size and raise the delivery fee factor.
+	fn regular_dmq_length(para: ParaId) -> u32 {
+		Self::dmq_length(para).saturating_sub(Self::priority_dmq_length(para))
+	}
+
 	/// Returns the downward message queue contents for the given para.
 	///
-	/// The most recent messages are the latest in the vector.
+	/// The most recent messages are the latest in the vector. Priority messages precede the
+	/// regular messages queued in the same block.
 	pub(crate) fn dmq_contents(
 		recipient: ParaId,
 	) -> Vec<InboundDownwardMessage<BlockNumberFor<T>>> {
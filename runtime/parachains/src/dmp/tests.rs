@@ -17,7 +17,10 @@
 use super::*;
 use crate::{
 	configuration::ActiveConfig,
-	mock::{new_test_ext, Configuration, Dmp, MockGenesisConfig, Paras, System, Test},
+	mock::{
+		new_test_ext, Configuration, Dmp, MaxPriorityDownwardMessages, MockGenesisConfig, Paras,
+		System, Test,
+	},
 };
 use frame_support::assert_ok;
 use hex_literal::hex;
@@ -297,3 +300,146 @@ fn verify_fee_factor_reaches_high_value() {
 		assert!(total_fee_factor > FixedU128::from_u32(100_000_000));
 	});
 }
+
+#[test]
+fn priority_messages_precede_regular_messages_of_the_same_block() {
+	let a = ParaId::from(1312);
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		run_to_block(2, None);
+		queue_downward_message(a, vec![1]).unwrap();
+
+		run_to_block(3, None);
+		queue_downward_message(a, vec![2]).unwrap();
+		queue_downward_message(a, vec![3]).unwrap();
+		Dmp::queue_priority_downward_message(&Configuration::config(), a, vec![4]).unwrap();
+		Dmp::queue_priority_downward_message(&Configuration::config(), a, vec![5]).unwrap();
+
+		let contents = Dmp::dmq_contents(a);
+		assert_eq!(
+			contents.iter().map(|m| (m.sent_at, m.msg.clone())).collect::<Vec<_>>(),
+			vec![(2, vec![1]), (3, vec![4]), (3, vec![5]), (3, vec![2]), (3, vec![3])],
+		);
+
+		// The MQC head corresponds to the messages in the order the para sees them.
+		let head = contents.iter().fold(Hash::zero(), |head, m| {
+			BlakeTwo256::hash_of(&(head, m.sent_at, BlakeTwo256::hash_of(&m.msg)))
+		});
+		assert_eq!(Dmp::dmq_mqc_head(a), head);
+
+		// Priority messages don't reorder the messages of previous blocks.
+		run_to_block(4, None);
+		Dmp::queue_priority_downward_message(&Configuration::config(), a, vec![6]).unwrap();
+		assert_eq!(Dmp::dmq_contents(a).last().map(|m| m.msg.clone()), Some(vec![6]));
+	});
+}
+
+#[test]
+fn priority_window_survives_pruning_of_previous_blocks() {
+	let a = ParaId::from(1312);
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		run_to_block(2, None);
+		queue_downward_message(a, vec![1]).unwrap();
+
+		run_to_block(3, None);
+		queue_downward_message(a, vec![2]).unwrap();
+		Dmp::prune_dmq(a, 1);
+		Dmp::queue_priority_downward_message(&Configuration::config(), a, vec![3]).unwrap();
+
+		assert_eq!(
+			Dmp::dmq_contents(a).into_iter().map(|m| m.msg).collect::<Vec<_>>(),
+			vec![vec![3], vec![2]],
+		);
+
+		// Pruning into the window closes it, later messages are appended.
+		Dmp::prune_dmq(a, 1);
+		queue_downward_message(a, vec![4]).unwrap();
+		Dmp::queue_priority_downward_message(&Configuration::config(), a, vec![5]).unwrap();
+		assert_eq!(
+			Dmp::dmq_contents(a).into_iter().map(|m| m.msg).collect::<Vec<_>>(),
+			vec![vec![2], vec![5], vec![4]],
+		);
+
+		let head = Dmp::dmq_contents(a).iter().fold(
+			BlakeTwo256::hash_of(&(
+				BlakeTwo256::hash_of(&(Hash::zero(), 2u32, BlakeTwo256::hash_of(&vec![1u8]))),
+				3u32,
+				BlakeTwo256::hash_of(&vec![3u8]),
+			)),
+			|head, m| BlakeTwo256::hash_of(&(head, m.sent_at, BlakeTwo256::hash_of(&m.msg))),
+		);
+		assert_eq!(Dmp::dmq_mqc_head(a), head);
+	});
+}
+
+#[test]
+fn priority_messages_do_not_increase_fee_factor() {
+	let a = ParaId::from(123);
+	let mut genesis = default_genesis_config();
+	genesis.configuration.config.max_downward_message_size = 16777216;
+	new_test_ext(genesis).execute_with(|| {
+		let initial = InitialFactor::get();
+
+		queue_downward_message(a, vec![1]).unwrap();
+		Dmp::queue_priority_downward_message(&Configuration::config(), a, vec![1]).unwrap();
+		Dmp::queue_priority_downward_message(&Configuration::config(), a, vec![1]).unwrap();
+		assert_eq!(DeliveryFeeFactor::<Test>::get(a), initial);
+	});
+}
+
+#[test]
+fn priority_messages_have_their_own_limit() {
+	let a = ParaId::from(123);
+	let mut genesis = default_genesis_config();
+	genesis.configuration.config.max_downward_message_size = 16777216;
+	new_test_ext(genesis).execute_with(|| {
+		let config = Configuration::config();
+		let max_length = Dmp::dmq_max_length(config.max_downward_message_size);
+
+		let max_priority = MaxPriorityDownwardMessages::get();
+
+		// Pending priority messages don't take room from the regular messages.
+		Dmp::queue_priority_downward_message(&config, a, vec![]).unwrap();
+		while queue_downward_message(a, vec![]).is_ok() {}
+		assert_eq!(Dmp::dmq_length(a), max_length + 2);
+		assert!(matches!(
+			queue_downward_message(a, vec![]),
+			Err(QueueDownwardMessageError::ExceedsMaxMessageSize),
+		));
+
+		// A full regular queue doesn't block the priority lane, which has a budget of its own.
+		while Dmp::queue_priority_downward_message(&config, a, vec![]).is_ok() {}
+		assert_eq!(Dmp::priority_dmq_length(a), max_priority);
+		assert_eq!(Dmp::dmq_length(a), max_length + 1 + max_priority);
+		assert!(matches!(
+			Dmp::queue_priority_downward_message(&config, a, vec![]),
+			Err(QueueDownwardMessageError::ExceedsMaxPriorityMessages),
+		));
+	});
+}
+
+#[test]
+fn pruning_priority_messages_frees_their_budget() {
+	let a = ParaId::from(123);
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		let config = Configuration::config();
+		run_to_block(2, None);
+		queue_downward_message(a, vec![1]).unwrap();
+
+		run_to_block(3, None);
+		queue_downward_message(a, vec![2]).unwrap();
+		while Dmp::queue_priority_downward_message(&config, a, vec![3]).is_ok() {}
+		assert_eq!(Dmp::priority_dmq_length(a), MaxPriorityDownwardMessages::get());
+
+		// The regular message of the previous block precedes the priority messages.
+		Dmp::prune_dmq(a, 1);
+		assert_eq!(Dmp::priority_dmq_length(a), MaxPriorityDownwardMessages::get());
+
+		Dmp::prune_dmq(a, 2);
+		assert_eq!(Dmp::priority_dmq_length(a), MaxPriorityDownwardMessages::get() - 2);
+		Dmp::queue_priority_downward_message(&config, a, vec![4]).unwrap();
+		assert_eq!(Dmp::priority_dmq_length(a), MaxPriorityDownwardMessages::get() - 1);
+	});
+}
@@ -56,6 +56,7 @@ pub use origin::{ensure_parachain, Origin};
 pub use paras::ParaLifecycle;
 use primitives::{HeadData, Id as ParaId, ValidationCode};
 use sp_runtime::{DispatchResult, FixedU128};
+use sp_std::vec::Vec;
 
 /// Trait for tracking message delivery fees on a transport protocol.
 pub trait FeeTracker {
@@ -105,3 +106,13 @@ pub fn schedule_code_upgrade<T: paras::Config>(
 pub fn set_current_head<T: paras::Config>(id: ParaId, new_head: HeadData) {
 	paras::Pallet::<T>::set_current_head(id, new_head)
 }
+
+/// Sets the collators allowed to collate for the para with the given id, `None` allowing any.
+///
+/// This simply calls [`crate::paras::Pallet::set_authorized_collators`].
+pub fn set_authorized_collators<T: paras::Config>(
+	id: ParaId,
+	collators: Option<paras::AuthorizedCollatorList>,
+) -> DispatchResult {
+	paras::Pallet::<T>::set_authorized_collators(id, collators)
+}
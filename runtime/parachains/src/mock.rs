@@ -27,7 +27,8 @@ use crate::{
 use frame_support::{
 	assert_ok, parameter_types,
 	traits::{
-		Currency, ProcessMessage, ProcessMessageError, ValidatorSet, ValidatorSetWithIdentification,
+		Currency, KeyOwnerProofSystem, ProcessMessage, ProcessMessageError, ValidatorSet,
+		ValidatorSetWithIdentification,
 	},
 	weights::{Weight, WeightMeter},
 };
@@ -36,14 +37,14 @@ use frame_system::limits;
 use parity_scale_codec::Decode;
 use primitives::{
 	AuthorityDiscoveryId, Balance, BlockNumber, CandidateHash, Moment, SessionIndex, UpwardMessage,
-	ValidationCode, ValidatorIndex,
+	ValidationCode, ValidatorId, ValidatorIndex,
 };
 use sp_core::{ConstU32, H256};
 use sp_io::TestExternalities;
 use sp_runtime::{
 	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
 	transaction_validity::TransactionPriority,
-	BuildStorage, FixedU128, Perbill, Permill,
+	BuildStorage, FixedU128, KeyTypeId, Perbill, Permill,
 };
 use std::{cell::RefCell, collections::HashMap};
 
@@ -71,6 +72,7 @@ frame_support::construct_runtime!(
 		ParachainsOrigin: origin,
 		SessionInfo: session_info,
 		Disputes: disputes,
+		Slashing: disputes::slashing,
 		Babe: pallet_babe,
 	}
 );
@@ -216,7 +218,13 @@ impl crate::paras::Config for Test {
 	type NextSessionRotation = TestNextSessionRotation;
 }
 
-impl crate::dmp::Config for Test {}
+parameter_types! {
+	pub const MaxPriorityDownwardMessages: u32 = 4;
+}
+
+impl crate::dmp::Config for Test {
+	type MaxPriorityDownwardMessages = MaxPriorityDownwardMessages;
+}
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
@@ -275,6 +283,10 @@ impl crate::disputes::SlashingHandler<BlockNumber> for Test {
 			.with(|r| r.borrow_mut().push((session, losers.into_iter().collect())))
 	}
 
+	fn punish_weight(_offenders: u32) -> Weight {
+		Weight::zero()
+	}
+
 	fn initializer_initialize(_now: BlockNumber) -> Weight {
 		Weight::zero()
 	}
@@ -284,6 +296,45 @@ impl crate::disputes::SlashingHandler<BlockNumber> for Test {
 	fn initializer_on_new_session(_: SessionIndex) {}
 }
 
+parameter_types! {
+	pub const TestSlashCurve: disputes::slashing::SlashCurve = disputes::slashing::SlashCurve {
+		for_invalid: Perbill::from_percent(10),
+		against_valid: Perbill::zero(),
+		repeat_step: Perbill::from_percent(5),
+		window: 2,
+		correlation: 2,
+		max: Perbill::from_percent(40),
+	};
+}
+
+/// A key owner proof system which never proves anything, for tests which only
+/// care about the slashing pallet's bookkeeping.
+pub struct TestKeyOwnerProofSystem;
+impl KeyOwnerProofSystem<(KeyTypeId, ValidatorId)> for TestKeyOwnerProofSystem {
+	type Proof = sp_session::MembershipProof;
+	type IdentificationTuple = ();
+
+	fn prove(_key: (KeyTypeId, ValidatorId)) -> Option<Self::Proof> {
+		None
+	}
+
+	fn check_proof(_key: (KeyTypeId, ValidatorId), _proof: Self::Proof) -> Option<()> {
+		None
+	}
+}
+
+impl disputes::slashing::Config for Test {
+	type KeyOwnerProof = sp_session::MembershipProof;
+	type KeyOwnerIdentification = ();
+	type KeyOwnerProofSystem = TestKeyOwnerProofSystem;
+	type HandleReports = ();
+	type WeightInfo = disputes::slashing::TestWeightInfo;
+	type BenchmarkingConfig = disputes::slashing::BenchConfig<1000>;
+	type SlashCurve = TestSlashCurve;
+	type MaxOffenceHistory = ConstU32<4>;
+	type MaxSessionOffenders = ConstU32<4>;
+}
+
 impl crate::scheduler::Config for Test {
 	type AssignmentProvider = Assigner;
 }
@@ -118,8 +118,8 @@ use frame_support::{pallet_prelude::*, traits::EstimateNextSessionRotation, Defa
 use frame_system::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
 use primitives::{
-	ConsensusLog, HeadData, Id as ParaId, PvfCheckStatement, SessionIndex, UpgradeGoAhead,
-	UpgradeRestriction, ValidationCode, ValidationCodeHash, ValidatorSignature,
+	CollatorId, ConsensusLog, HeadData, Id as ParaId, PvfCheckStatement, SessionIndex,
+	UpgradeGoAhead, UpgradeRestriction, ValidationCode, ValidationCodeHash, ValidatorSignature,
 };
 use scale_info::{Type, TypeInfo};
 use sp_core::RuntimeDebug;
@@ -143,6 +143,12 @@ pub use pallet::*;
 
 const LOG_TARGET: &str = "runtime::paras";
 
+/// The maximum number of collators in the allow-list of a para.
+pub const MAX_AUTHORIZED_COLLATORS: u32 = 100;
+
+/// The allow-list of the collators of a para.
+pub type AuthorizedCollatorList = BoundedVec<CollatorId, ConstU32<MAX_AUTHORIZED_COLLATORS>>;
+
 // the two key times necessary to track for every code replacement.
 #[derive(Default, Encode, Decode, TypeInfo)]
 #[cfg_attr(test, derive(Debug, Clone, PartialEq))]
@@ -601,6 +607,8 @@ pub mod pallet {
 		/// The given validation code was rejected by the PVF pre-checking vote.
 		/// `code_hash` `para_id`
 		PvfCheckRejected(ValidationCodeHash, ParaId),
+		/// The collators allowed to collate for a para have been updated. `para_id`
+		AuthorizedCollatorsUpdated(ParaId),
 	}
 
 	#[pallet::error]
@@ -793,6 +801,13 @@ pub mod pallet {
 	pub(super) type CodeByHash<T: Config> =
 		StorageMap<_, Identity, ValidationCodeHash, ValidationCode>;
 
+	/// The collators allowed to collate for a para, sorted.
+	///
+	/// Any collator may collate for paras without an entry.
+	#[pallet::storage]
+	pub(super) type AuthorizedCollators<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, AuthorizedCollatorList>;
+
 	#[pallet::genesis_config]
 	#[derive(DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -1266,6 +1281,7 @@ impl<T: Config> Pallet<T> {
 					FutureCodeUpgrades::<T>::remove(&para);
 					UpgradeGoAheadSignal::<T>::remove(&para);
 					UpgradeRestrictionSignal::<T>::remove(&para);
+					AuthorizedCollators::<T>::remove(&para);
 					ParaLifecycles::<T>::remove(&para);
 					let removed_future_code_hash = FutureCodeHash::<T>::take(&para);
 					if let Some(removed_future_code_hash) = removed_future_code_hash {
@@ -2033,6 +2049,32 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Returns the collators allowed to collate for the para, `None` if any collator may.
+	pub fn authorized_collators(id: ParaId) -> Option<Vec<CollatorId>> {
+		AuthorizedCollators::<T>::get(&id).map(BoundedVec::into_inner)
+	}
+
+	/// Restrict the collators of a para to `collators`, or lift the restriction with `None`.
+	///
+	/// An empty allow-list keeps any collator from collating for the para.
+	pub fn set_authorized_collators(
+		id: ParaId,
+		collators: Option<AuthorizedCollatorList>,
+	) -> DispatchResult {
+		ensure!(ParaLifecycles::<T>::contains_key(&id), Error::<T>::NotRegistered);
+		match collators {
+			Some(collators) => {
+				let mut collators = collators.into_inner();
+				collators.sort();
+				collators.dedup();
+				AuthorizedCollators::<T>::insert(&id, BoundedVec::truncate_from(collators));
+			},
+			None => AuthorizedCollators::<T>::remove(&id),
+		}
+		Self::deposit_event(Event::AuthorizedCollatorsUpdated(id));
+		Ok(())
+	}
+
 	/// Returns the current lifecycle state of the para.
 	pub fn lifecycle(id: ParaId) -> Option<ParaLifecycle> {
 		ParaLifecycles::<T>::get(&id)
@@ -15,7 +15,7 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::*;
-use frame_support::{assert_err, assert_ok, assert_storage_noop};
+use frame_support::{assert_err, assert_ok, assert_storage_noop, bounded_vec};
 use keyring::Sr25519Keyring;
 use primitives::{BlockNumber, ValidatorId, PARACHAIN_KEY_TYPE_ID};
 use sc_keystore::LocalKeystore;
@@ -1713,6 +1713,62 @@ fn most_recent_context() {
 	})
 }
 
+#[test]
+fn authorized_collators() {
+	let validation_code: ValidationCode = vec![1, 2, 3].into();
+
+	let genesis_config = MockGenesisConfig::default();
+
+	new_test_ext(genesis_config).execute_with(|| {
+		const EXPECTED_SESSION: SessionIndex = 1;
+		run_to_block(1, Some(vec![1]));
+
+		let para_id = ParaId::from(111);
+		let alice: CollatorId = Sr25519Keyring::Alice.public().into();
+		let bob: CollatorId = Sr25519Keyring::Bob.public().into();
+
+		// Unknown paras can't restrict their collators.
+		assert_err!(
+			Paras::set_authorized_collators(para_id, Some(bounded_vec![alice.clone()])),
+			Error::<Test>::NotRegistered,
+		);
+
+		assert_ok!(Paras::schedule_para_initialize(
+			para_id,
+			ParaGenesisArgs {
+				para_kind: ParaKind::Parachain,
+				genesis_head: vec![1].into(),
+				validation_code: validation_code.clone(),
+			},
+		));
+		submit_super_majority_pvf_votes(&validation_code, EXPECTED_SESSION, true);
+		assert_eq!(Paras::authorized_collators(para_id), None);
+
+		// Duplicates are removed.
+		assert_ok!(Paras::set_authorized_collators(
+			para_id,
+			Some(bounded_vec![bob.clone(), alice.clone(), bob.clone()])
+		));
+		let mut expected = vec![alice.clone(), bob.clone()];
+		expected.sort();
+		assert_eq!(Paras::authorized_collators(para_id), Some(expected));
+
+		// Lifting the restriction.
+		assert_ok!(Paras::set_authorized_collators(para_id, None));
+		assert_eq!(Paras::authorized_collators(para_id), None);
+
+		// Two sessions pass, so action queue is triggered.
+		run_to_block(4, Some(vec![3, 4]));
+		assert_eq!(ParaLifecycles::<Test>::get(&para_id), Some(ParaLifecycle::Parachain));
+
+		// Finally, offboard the para and expect the allow-list to be cleared.
+		assert_ok!(Paras::set_authorized_collators(para_id, Some(bounded_vec![alice])));
+		assert_ok!(Paras::schedule_para_cleanup(para_id));
+		run_to_block(6, Some(vec![5, 6]));
+		assert_eq!(Paras::authorized_collators(para_id), None);
+	})
+}
+
 #[test]
 fn parakind_encodes_decodes_to_bool_scale() {
 	let chain_kind = ParaKind::Parachain.encode();
@@ -26,6 +26,7 @@ use primitives::{
 };
 
 use super::{BackedCandidate, Config, DisputeStatementSet, Weight};
+use crate::disputes::SlashingHandler;
 
 pub trait WeightInfo {
 	/// Variant over `v`, the count of dispute statements in a dispute statement set. This gives the
@@ -120,10 +121,13 @@ where
 	T: Config,
 	D: AsRef<DisputeStatementSet> + WrapperTypeEncode + Sized + Encode,
 {
+	// Every validator with a statement in the set may be punished when the dispute concludes.
+	let statements = statement_set.as_ref().statements.len() as u32;
 	set_proof_size_to_tx_size(
-		<<T as Config>::WeightInfo as WeightInfo>::enter_variable_disputes(
-			statement_set.as_ref().statements.len() as u32,
-		),
+		<<T as Config>::WeightInfo as WeightInfo>::enter_variable_disputes(statements)
+			.saturating_add(<T as crate::disputes::Config>::SlashingHandler::punish_weight(
+				statements,
+			)),
 		statement_set,
 	)
 }
@@ -16,14 +16,15 @@
 
 //! Put implementations of functions from staging APIs here.
 
-use crate::{configuration, dmp, hrmp, inclusion, initializer, paras, shared};
+use crate::{configuration, disputes, dmp, hrmp, inclusion, initializer, paras, shared};
 use frame_system::pallet_prelude::BlockNumberFor;
 use primitives::{
 	vstaging::{
-		AsyncBackingParams, BackingState, CandidatePendingAvailability, Constraints,
-		InboundHrmpLimitations, OutboundHrmpChannelLimitations,
+		slashing::PendingSlashesWithFraction, AsyncBackingParams, BackingState,
+		CandidatePendingAvailability, Constraints, InboundHrmpLimitations,
+		OutboundHrmpChannelLimitations,
 	},
-	Id as ParaId,
+	CandidateHash, CollatorId, Id as ParaId, SessionIndex,
 };
 use sp_std::prelude::*;
 
@@ -118,3 +119,14 @@ pub fn backing_state<T: initializer::Config>(
 pub fn async_backing_params<T: configuration::Config>() -> AsyncBackingParams {
 	<configuration::Pallet<T>>::config().async_backing_params
 }
+
+/// Implementation for `StagingAuthorizedCollators` function from the runtime API
+pub fn authorized_collators<T: paras::Config>(para_id: ParaId) -> Option<Vec<CollatorId>> {
+	<paras::Pallet<T>>::authorized_collators(para_id)
+}
+
+/// Implementation for `StagingUnappliedSlashesWithFraction` function from the runtime API
+pub fn unapplied_slashes_with_fraction<T: disputes::slashing::Config>(
+) -> Vec<(SessionIndex, CandidateHash, PendingSlashesWithFraction)> {
+	<disputes::slashing::Pallet<T>>::unapplied_slashes_with_fraction()
+}
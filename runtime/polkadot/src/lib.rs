@@ -1144,7 +1144,9 @@ impl pallet_message_queue::Config for Runtime {
 	type WeightInfo = weights::pallet_message_queue::WeightInfo<Runtime>;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type MaxPriorityDownwardMessages = ConstU32<64>;
+}
 
 impl parachains_hrmp::Config for Runtime {
 	type RuntimeOrigin = RuntimeOrigin;
@@ -1192,6 +1194,10 @@ impl parachains_slashing::Config for Runtime {
 	>;
 	type WeightInfo = weights::runtime_parachains_disputes_slashing::WeightInfo<Runtime>;
 	type BenchmarkingConfig = parachains_slashing::BenchConfig<1000>;
+	// Slash a fixed fraction, regardless of any offence history.
+	type SlashCurve = ();
+	type MaxOffenceHistory = ConstU32<16>;
+	type MaxSessionOffenders = MaxAuthorities;
 }
 
 parameter_types! {
@@ -1199,6 +1205,7 @@ parameter_types! {
 	// `ParaId`s.
 	pub const ParaDeposit: Balance = 100 * DOLLARS;
 	pub const ParaDataByteDeposit: Balance = deposit(0, 1);
+	pub const ReservationExpiry: BlockNumber = 28 * DAYS;
 }
 
 impl paras_registrar::Config for Runtime {
@@ -1206,8 +1213,10 @@ impl paras_registrar::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type OnSwap = (Crowdloan, Slots);
+	type SlotClaims = (Slots, Crowdloan, Auctions);
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = ParaDataByteDeposit;
+	type ReservationExpiry = ReservationExpiry;
 	type WeightInfo = weights::runtime_common_paras_registrar::WeightInfo<Runtime>;
 }
 
@@ -1229,6 +1238,7 @@ impl slots::Config for Runtime {
 	type LeasePeriod = LeasePeriod;
 	type LeaseOffset = LeaseOffset;
 	type ForceOrigin = EitherOf<EnsureRoot<Self::AccountId>, LeaseAdmin>;
+	type Auctioneer = Auctions;
 	type WeightInfo = weights::runtime_common_slots::WeightInfo<Runtime>;
 }
 
@@ -1511,6 +1521,8 @@ pub mod migrations {
 		parachains_configuration::migration::v7::MigrateToV7<Runtime>,
 		parachains_scheduler::migration::v1::MigrateToV1<Runtime>,
 		parachains_configuration::migration::v8::MigrateToV8<Runtime>,
+		parachains_slashing::migration::v1::MigrateToV1<Runtime>,
+		paras_registrar::migration::NoteUnusedReservations<Runtime>,
 
 		// Gov v1 storage migrations
 		// https://github.com/paritytech/polkadot/issues/6749
@@ -1992,6 +2004,36 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl crowdloan::runtime_api::CrowdloanApi<Block, AccountId, Balance> for Runtime {
+		fn contributions(
+			para: ParaId,
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Option<Vec<crowdloan::runtime_api::Contribution<AccountId, Balance>>> {
+			Crowdloan::contributions(para, start_after, limit)
+		}
+	}
+
+	impl pallet_xcm::dry_run::XcmDryRunApi<Block, RuntimeCall, RuntimeEvent> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			message: xcm::VersionedXcm<RuntimeCall>,
+		) -> Result<
+			pallet_xcm::dry_run::XcmDryRunEffects<RuntimeEvent>,
+			pallet_xcm::dry_run::XcmDryRunError,
+		> {
+			XcmPallet::dry_run_xcm(origin, message)
+		}
+	}
+
+	impl pallet_xcm::asset_traps::AssetTrapsApi<Block, BlockNumber> for Runtime {
+		fn trapped_assets(
+			origin: xcm::VersionedMultiLocation,
+		) -> Vec<pallet_xcm::asset_traps::TrappedAssets<BlockNumber>> {
+			XcmPallet::trapped_assets(origin)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -2386,6 +2428,15 @@ mod test {
 			whitelist.contains("1405f2411d0af5a7ff397e7c9dc68d196323ae84c43568be0d1394d5d0d522c4")
 		);
 	}
+
+	#[test]
+	fn xcm_weigher_charges_converted_instructions_the_same() {
+		use xcm::conversion_harness::check_all_variants_weighed_by;
+		check_all_variants_weighed_by::<
+			xcm::v3::Instruction<()>,
+			weights::xcm::PolkadotXcmWeight<()>,
+		>();
+	}
 }
 
 #[cfg(test)]
@@ -281,4 +281,30 @@ impl<T: frame_system::Config> pallet_xcm::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(11))
 			.saturating_add(T::DbWeight::get().writes(5))
 	}
+	/// Storage: XcmPallet AssetTraps (r:1 w:0)
+	/// Proof Skipped: XcmPallet AssetTraps (max_values: None, max_size: None, mode: Measured)
+	fn claim_assets() -> Weight {
+		// Not benchmarked: derived from the measured `reserve_transfer_assets`, which converts the
+		// origin and executes a local message the same way, plus the trap lookup this call does
+		// before executing. The claim itself is charged by the XCM weigher.
+		<Self as pallet_xcm::WeightInfo>::reserve_transfer_assets()
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+	/// Storage: Dmp DeliveryFeeFactor (r:1 w:0)
+	/// Proof Skipped: Dmp DeliveryFeeFactor (max_values: None, max_size: None, mode: Measured)
+	/// Storage: XcmPallet SupportedVersion (r:1 w:0)
+	/// Proof Skipped: XcmPallet SupportedVersion (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Dmp DownwardMessageQueues (r:1 w:1)
+	/// Proof Skipped: Dmp DownwardMessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Dmp DownwardMessageQueueHeads (r:1 w:1)
+	/// Proof Skipped: Dmp DownwardMessageQueueHeads (max_values: None, max_size: None, mode: Measured)
+	fn transfer_assets() -> Weight {
+		// Not benchmarked: composed from the measured `reserve_transfer_assets` and
+		// `teleport_assets`, charging the dearer of them since the call does either, plus the
+		// delivery of the one message it sends.
+		<Self as pallet_xcm::WeightInfo>::reserve_transfer_assets()
+			.max(<Self as pallet_xcm::WeightInfo>::teleport_assets())
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
 }
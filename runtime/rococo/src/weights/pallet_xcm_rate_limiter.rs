@@ -0,0 +1,53 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_xcm_rate_limiter`.
+//!
+//! These have not been generated by the benchmark CLI yet. Until they are, the execution time of
+//! each call is a conservative estimate, and its storage accesses are counted in full.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_xcm_rate_limiter`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_xcm_rate_limiter::WeightInfo for WeightInfo<T> {
+	/// Storage: XcmRateLimiter Limits (r:0 w:1)
+	/// Proof: XcmRateLimiter Limits (max_values: None, max_size: Some(46), added: 2521, mode: MaxEncodedLen)
+	fn set_limit() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: XcmRateLimiter Limits (r:1 w:0)
+	/// Storage: XcmRateLimiter Usages (r:1 w:1)
+	/// Storage: System Number (r:1 w:0)
+	fn try_consume() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// The range of component `n` is `[0, 65536]`.
+	fn decode_message(n: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 0)
+			// Estimated at a nanosecond per byte decoded.
+			.saturating_add(Weight::from_parts(1_000, 0).saturating_mul(n.into()))
+	}
+}
@@ -67,6 +67,13 @@ impl<T: frame_system::Config> runtime_common::crowdloan::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().reads(5))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
+	/// Storage: Crowdloan TransferableFunds (r:0 w:1)
+	/// Proof Skipped: Crowdloan TransferableFunds (max_values: None, max_size: None, mode: Measured)
+	fn create_transferable() -> Weight {
+		// Derived from `create`, which it runs before marking the fund as transferable.
+		<Self as runtime_common::crowdloan::WeightInfo>::create()
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 	/// Storage: Crowdloan Funds (r:1 w:1)
 	/// Proof Skipped: Crowdloan Funds (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Slots Leases (r:1 w:0)
@@ -219,4 +226,14 @@ impl<T: frame_system::Config> runtime_common::crowdloan::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2832).saturating_mul(n.into()))
 	}
+	/// Storage: Crowdloan Funds (r:1 w:0)
+	/// Storage: Crowdloan TransferableFunds (r:1 w:0)
+	/// Storage: unknown `0xd861ea1ebf4800d4b89f4ff787ad79ee96d9a708c85b57da7eb8f9ddeda61291` (r:2 w:2)
+	fn transfer_contribution() -> Weight {
+		// Not generated yet: an estimated execution time, dominated by verifying the signature of
+		// the fund's verifier, plus the storage accessed.
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
 }
@@ -218,4 +218,48 @@ impl<T: frame_system::Config> runtime_common::paras_registrar::WeightInfo for We
 			.saturating_add(Weight::from_parts(855, 0).saturating_mul(b.into()))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: Registrar Paras (r:1 w:0)
+	/// Storage: Registrar PendingManager (r:0 w:1)
+	fn transfer_manager() -> Weight {
+		// Not generated yet: an estimated execution time, plus the storage accessed.
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Registrar PendingManager (r:1 w:1)
+	/// Storage: Registrar Paras (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn accept_manager() -> Weight {
+		// Not generated yet: an estimated execution time for moving a reserve between two
+		// accounts, plus the storage accessed.
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: Registrar ReservedAt (r:1 w:1)
+	/// Storage: Slots Leases (r:1 w:0)
+	/// Storage: Crowdloan Funds (r:1 w:0)
+	/// Storage: Auctions Winning (r:3601 w:0)
+	/// Storage: Paras MostRecentContext (r:1 w:0)
+	/// Storage: Registrar PendingManager (r:0 w:1)
+	fn deregister_expired_reservation() -> Weight {
+		// The measured `deregister`, which this call runs once it finds the reservation
+		// abandoned, plus the checks for that: any auction sample may hold a bid for the para.
+		let auction_samples = (crate::EndingPeriod::get() / crate::SampleLength::get()) as u64 + 1;
+		<Self as runtime_common::paras_registrar::WeightInfo>::deregister()
+			.saturating_add(T::DbWeight::get().reads(4_u64.saturating_add(auction_samples)))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: Registrar Paras (r:1 w:0)
+	/// Storage: Paras ParaLifecycles (r:1 w:0)
+	/// Storage: Paras AuthorizedCollators (r:0 w:1)
+	/// The range of component `c` is `[0, 100]`.
+	fn set_authorized_collators(c: u32, ) -> Weight {
+		// Not generated yet: an estimated execution time, growing with the allow-list which is
+		// sorted and stored, plus the storage accessed.
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(250_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
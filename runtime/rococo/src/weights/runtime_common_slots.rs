@@ -129,4 +129,33 @@ impl<T: frame_system::Config> runtime_common::slots::WeightInfo for WeightInfo<T
 			.saturating_add(T::DbWeight::get().reads(5))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
+	/// Storage: Slots ExtensionPrice (r:0 w:1)
+	fn set_extension_price() -> Weight {
+		// Not generated yet: an estimated execution time, plus the storage accessed.
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Slots ExtensionPrice (r:1 w:0)
+	/// Storage: Slots Leases (r:1 w:1)
+	/// Storage: Registrar Paras (r:1 w:0)
+	/// Storage: Auctions AuctionInfo (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn extend_lease() -> Weight {
+		// Not generated yet: an estimated execution time for reserving the extension deposit,
+		// plus the storage accessed.
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: Registrar Paras (r:1 w:0)
+	/// Storage: Slots Leases (r:1 w:1)
+	/// Storage: Paras ParaLifecycles (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn release_lease() -> Weight {
+		// Not generated yet: an estimated execution time for unreserving the released deposit,
+		// plus the storage accessed.
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
 }
@@ -17,31 +17,35 @@
 //! XCM configuration for Rococo.
 
 use super::{
-	parachains_origin, AccountId, AllPalletsWithSystem, Balances, Dmp, ParaId, Runtime,
-	RuntimeCall, RuntimeEvent, RuntimeOrigin, TransactionByteFee, WeightToFee, XcmPallet,
+	parachains_origin, AccountId, AllPalletsWithSystem, Balances, BlockNumber, Dmp, ParaId,
+	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, TransactionByteFee, WeightToFee,
+	XcmFeeRates, XcmPallet,
 };
 use frame_support::{
 	match_types, parameter_types,
 	traits::{Contains, Everything, Nothing},
-	weights::Weight,
+	weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight},
 };
 use frame_system::EnsureRoot;
+use pallet_xcm_rate_limiter::RateLimit;
+use polkadot_parachain::primitives::IsSystem;
 use rococo_runtime_constants::currency::CENTS;
 use runtime_common::{
 	crowdloan, paras_registrar,
-	xcm_sender::{ChildParachainRouter, ExponentialPrice},
+	xcm_sender::{ChildParachainRouter, ExponentialPrice, PriorityChildParachainRouter},
 	ToAuthor,
 };
 use sp_core::ConstU32;
+use sp_runtime::traits::TryConvert;
 use xcm::latest::prelude::*;
 use xcm_builder::{
 	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses,
 	AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom, ChildParachainAsNative,
 	ChildParachainConvertsVia, ChildSystemParachainAsSuperuser,
 	CurrencyAdapter as XcmCurrencyAdapter, FixedWeightBounds, IsChildSystemParachain, IsConcrete,
-	MintLocation, SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation,
-	TakeWeightCredit, TrailingSetTopicAsId, UsingComponents, WeightInfoBounds, WithComputedOrigin,
-	WithUniqueTopic,
+	MintLocation, RateOfFungibleFrom, SignedAccountId32AsNative, SignedToAccountId32,
+	SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId, UsingComponents,
+	WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
 };
 use xcm_executor::{traits::WithOriginFilter, XcmExecutor};
 
@@ -94,10 +98,25 @@ parameter_types! {
 	pub const BaseDeliveryFee: u128 = CENTS.saturating_mul(3);
 }
 
+// The origins whose messages to child parachains skip the regular downward message queue:
+// the relay chain itself, i.e. `Root`.
+match_types! {
+	pub type PriorityDownwardOrigins: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 0, interior: Here }
+	};
+}
+
 /// The XCM router. When we want to send an XCM message, we use this type. It amalgamates all of our
 /// individual routers.
 pub type XcmRouter = WithUniqueTopic<(
-	// Only one router so far - use DMP to communicate with child parachains.
+	// Use the priority lane of DMP for messages of the priority origins to child parachains...
+	PriorityChildParachainRouter<
+		Runtime,
+		XcmPallet,
+		ExponentialPrice<FeeAssetId, BaseDeliveryFee, TransactionByteFee, Dmp>,
+		PriorityDownwardOrigins,
+	>,
+	// ...and regular DMP for all other messages to child parachains.
 	ChildParachainRouter<
 		Runtime,
 		XcmPallet,
@@ -304,8 +323,11 @@ impl xcm_executor::Config for XcmConfig {
 		RuntimeCall,
 		MaxInstructions,
 	>;
-	type Trader =
-		UsingComponents<WeightToFee, TokenLocation, AccountId, Balances, ToAuthor<Runtime>>;
+	type Trader = (
+		UsingComponents<WeightToFee, TokenLocation, AccountId, Balances, ToAuthor<Runtime>>,
+		// Any other asset accepted as payment, at the rate kept for it on-chain.
+		RateOfFungibleFrom<XcmFeeRates, ()>,
+	);
 	type ResponseHandler = XcmPallet;
 	type AssetTrap = XcmPallet;
 	type AssetLocker = ();
@@ -358,8 +380,54 @@ impl pallet_xcm::Config for Runtime {
 	type MaxLockers = ConstU32<8>;
 	type MaxRemoteLockConsumers = ConstU32<0>;
 	type RemoteLockConsumerIdentifier = ();
+	type NonFungibleLocker = ();
 	type WeightInfo = crate::weights::pallet_xcm::WeightInfo<Runtime>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type ReachableDest = ReachableDest;
 	type AdminOrigin = EnsureRoot<AccountId>;
 }
+
+/// Rate-limits the XCM from each child parachain which is not a system parachain, along with
+/// those from any location within it.
+pub struct NonSystemChildParachain;
+impl TryConvert<MultiLocation, ParaId> for NonSystemChildParachain {
+	fn try_convert(location: MultiLocation) -> Result<ParaId, MultiLocation> {
+		match (location.parents, location.first_interior()) {
+			(0, Some(Parachain(id))) if !ParaId::from(*id).is_system() => Ok((*id).into()),
+			_ => Err(location),
+		}
+	}
+}
+
+parameter_types! {
+	/// Unless given other limits, each parachain may have up to 100 messages, using up to a
+	/// quarter of a block's worth of weight, executed every 10 blocks.
+	pub const ParachainXcmRateLimit: RateLimit<BlockNumber> = RateLimit {
+		max_messages: 100,
+		max_weight: Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 2, u64::MAX),
+		window: 10,
+	};
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+parameter_types! {
+	pub BenchmarkParachain: MultiLocation = Parachain(2000).into();
+}
+
+impl pallet_xcm_rate_limiter::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type OriginClass = ParaId;
+	type ClassOf = NonSystemChildParachain;
+	type DefaultLimit = ParachainXcmRateLimit;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = crate::weights::pallet_xcm_rate_limiter::WeightInfo<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkOrigin = BenchmarkParachain;
+}
+
+impl pallet_xcm_fee_rates::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Oracle = ();
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = crate::weights::pallet_xcm_fee_rates::WeightInfo<Runtime>;
+}
@@ -514,6 +514,10 @@ impl parachains_slashing::Config for Runtime {
 	>;
 	type WeightInfo = parachains_disputes::slashing::TestWeightInfo;
 	type BenchmarkingConfig = parachains_slashing::BenchConfig<1000>;
+	// Slash a fixed fraction, regardless of any offence history.
+	type SlashCurve = ();
+	type MaxOffenceHistory = ConstU32<16>;
+	type MaxSessionOffenders = MaxAuthorities;
 }
 
 impl parachains_paras_inherent::Config for Runtime {
@@ -542,7 +546,9 @@ impl parachains_paras::Config for Runtime {
 	type NextSessionRotation = Babe;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type MaxPriorityDownwardMessages = ConstU32<64>;
+}
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
@@ -62,7 +62,9 @@ use runtime_parachains::{
 	inclusion::{AggregateMessageOrigin, UmpQueueId},
 	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, reward_points as parachains_reward_points,
-	runtime_api_impl::v5 as parachains_runtime_api_impl,
+	runtime_api_impl::{
+		v5 as parachains_runtime_api_impl, vstaging as parachains_staging_runtime_api_impl,
+	},
 	scheduler as parachains_scheduler, session_info as parachains_session_info,
 	shared as parachains_shared,
 };
@@ -1072,10 +1074,12 @@ impl ProcessMessage for MessageProcessor {
 		let para = match origin {
 			AggregateMessageOrigin::Ump(UmpQueueId::Para(para)) => para,
 		};
-		xcm_builder::ProcessXcmMessage::<
+		// Messages from parachains over their limits stay in their queue until a later window.
+		xcm_builder::ProcessRateLimitedXcmMessage::<
 			Junction,
 			xcm_executor::XcmExecutor<xcm_config::XcmConfig>,
 			RuntimeCall,
+			XcmRateLimiter,
 		>::process_message(message, Junction::Parachain(para.into()), meter, id)
 	}
 }
@@ -1096,7 +1100,9 @@ impl pallet_message_queue::Config for Runtime {
 	type WeightInfo = weights::pallet_message_queue::WeightInfo<Runtime>;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type MaxPriorityDownwardMessages = ConstU32<64>;
+}
 
 impl parachains_hrmp::Config for Runtime {
 	type RuntimeOrigin = RuntimeOrigin;
@@ -1147,6 +1153,19 @@ impl parachains_disputes::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_disputes::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	// Validators which back invalid candidates again within a few sessions, or together with a
+	// large share of the validator set, are slashed up to their whole stake.
+	pub DisputeSlashCurve: parachains_slashing::SlashCurve = parachains_slashing::SlashCurve {
+		for_invalid: Perbill::from_percent(50),
+		against_valid: Perbill::from_percent(0),
+		repeat_step: Perbill::from_percent(25),
+		window: 6,
+		correlation: 3,
+		max: Perbill::from_percent(100),
+	};
+}
+
 impl parachains_slashing::Config for Runtime {
 	type KeyOwnerProofSystem = Historical;
 	type KeyOwnerProof =
@@ -1162,11 +1181,15 @@ impl parachains_slashing::Config for Runtime {
 	>;
 	type WeightInfo = weights::runtime_parachains_disputes_slashing::WeightInfo<Runtime>;
 	type BenchmarkingConfig = parachains_slashing::BenchConfig<300>;
+	type SlashCurve = DisputeSlashCurve;
+	type MaxOffenceHistory = ConstU32<16>;
+	type MaxSessionOffenders = MaxAuthorities;
 }
 
 parameter_types! {
 	pub const ParaDeposit: Balance = 2000 * CENTS;
 	pub const DataDepositPerByte: Balance = deposit(0, 1);
+	pub const ReservationExpiry: BlockNumber = 7 * DAYS;
 }
 
 impl paras_registrar::Config for Runtime {
@@ -1174,8 +1197,10 @@ impl paras_registrar::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type OnSwap = (Crowdloan, Slots);
+	type SlotClaims = (Slots, Crowdloan, Auctions);
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
+	type ReservationExpiry = ReservationExpiry;
 	type WeightInfo = weights::runtime_common_paras_registrar::WeightInfo<Runtime>;
 }
 
@@ -1190,6 +1215,7 @@ impl slots::Config for Runtime {
 	type LeasePeriod = LeasePeriod;
 	type LeaseOffset = ();
 	type ForceOrigin = EnsureRoot<AccountId>;
+	type Auctioneer = Auctions;
 	type WeightInfo = weights::runtime_common_slots::WeightInfo<Runtime>;
 }
 
@@ -1364,6 +1390,12 @@ construct_runtime! {
 
 		// Generalized message queue
 		MessageQueue: pallet_message_queue::{Pallet, Call, Storage, Event<T>} = 100,
+
+		// Per-origin limits on the XCM executed.
+		XcmRateLimiter: pallet_xcm_rate_limiter::{Pallet, Call, Storage, Event<T>} = 101,
+
+		// The assets accepted as payment for XCM execution besides the native token.
+		XcmFeeRates: pallet_xcm_fee_rates::{Pallet, Call, Storage, Event<T>} = 102,
 	}
 }
 
@@ -1424,6 +1456,8 @@ pub mod migrations {
 		assigned_slots::migration::v1::VersionCheckedMigrateToV1<Runtime>,
 		parachains_scheduler::migration::v1::MigrateToV1<Runtime>,
 		parachains_configuration::migration::v8::MigrateToV8<Runtime>,
+		parachains_slashing::migration::v1::MigrateToV1<Runtime>,
+		paras_registrar::migration::NoteUnusedReservations<Runtime>,
 		UpgradeSessionKeys,
 	);
 }
@@ -1488,6 +1522,8 @@ mod benches {
 		[pallet_vesting, Vesting]
 		// XCM
 		[pallet_xcm, XcmPallet]
+		[pallet_xcm_fee_rates, XcmFeeRates]
+		[pallet_xcm_rate_limiter, XcmRateLimiter]
 		// NOTE: Make sure you point to the individual modules below.
 		[pallet_xcm_benchmarks::fungible, XcmBalances]
 		[pallet_xcm_benchmarks::generic, XcmGeneric]
@@ -1561,6 +1597,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(99)]
 	impl primitives::runtime_api::ParachainHost<Block, Hash, BlockNumber> for Runtime {
 		fn validators() -> Vec<ValidatorId> {
 			parachains_runtime_api_impl::validators::<Runtime>()
@@ -1691,6 +1728,29 @@ sp_api::impl_runtime_apis! {
 				key_ownership_proof,
 			)
 		}
+
+		fn staging_para_backing_state(
+			para_id: ParaId,
+		) -> Option<primitives::vstaging::BackingState> {
+			parachains_staging_runtime_api_impl::backing_state::<Runtime>(para_id)
+		}
+
+		fn staging_async_backing_params() -> primitives::vstaging::AsyncBackingParams {
+			parachains_staging_runtime_api_impl::async_backing_params::<Runtime>()
+		}
+
+		fn staging_authorized_collators(para_id: ParaId) -> Option<Vec<primitives::CollatorId>> {
+			parachains_staging_runtime_api_impl::authorized_collators::<Runtime>(para_id)
+		}
+
+		fn staging_unapplied_slashes_with_fraction(
+		) -> Vec<(
+			SessionIndex,
+			CandidateHash,
+			primitives::vstaging::slashing::PendingSlashesWithFraction,
+		)> {
+			parachains_staging_runtime_api_impl::unapplied_slashes_with_fraction::<Runtime>()
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block, BeefyId> for Runtime {
@@ -1895,6 +1955,36 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl crowdloan::runtime_api::CrowdloanApi<Block, AccountId, Balance> for Runtime {
+		fn contributions(
+			para: ParaId,
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Option<Vec<crowdloan::runtime_api::Contribution<AccountId, Balance>>> {
+			Crowdloan::contributions(para, start_after, limit)
+		}
+	}
+
+	impl pallet_xcm::dry_run::XcmDryRunApi<Block, RuntimeCall, RuntimeEvent> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			message: xcm::VersionedXcm<RuntimeCall>,
+		) -> Result<
+			pallet_xcm::dry_run::XcmDryRunEffects<RuntimeEvent>,
+			pallet_xcm::dry_run::XcmDryRunError,
+		> {
+			XcmPallet::dry_run_xcm(origin, message)
+		}
+	}
+
+	impl pallet_xcm::asset_traps::AssetTrapsApi<Block, BlockNumber> for Runtime {
+		fn trapped_assets(
+			origin: xcm::VersionedMultiLocation,
+		) -> Vec<pallet_xcm::asset_traps::TrappedAssets<BlockNumber>> {
+			XcmPallet::trapped_assets(origin)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
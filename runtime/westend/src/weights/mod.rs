@@ -37,6 +37,8 @@ pub mod pallet_timestamp;
 pub mod pallet_utility;
 pub mod pallet_vesting;
 pub mod pallet_xcm;
+pub mod pallet_xcm_fee_rates;
+pub mod pallet_xcm_rate_limiter;
 pub mod runtime_common_assigned_slots;
 pub mod runtime_common_auctions;
 pub mod runtime_common_crowdloan;
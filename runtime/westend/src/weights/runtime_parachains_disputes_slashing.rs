@@ -98,4 +98,15 @@ impl<T: frame_system::Config> runtime_parachains::disputes::slashing::WeightInfo
 			.saturating_add(T::DbWeight::get().writes(9))
 			.saturating_add(Weight::from_parts(0, 192).saturating_mul(n.into()))
 	}
+	/// Storage: ParasSlashing OffenceHistory (r:300 w:300)
+	/// Proof Skipped: ParasSlashing OffenceHistory (max_values: None, max_size: None, mode: Measured)
+	/// Storage: ParasSlashing SessionOffenders (r:1 w:1)
+	/// Proof Skipped: ParasSlashing SessionOffenders (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 300]`.
+	fn note_offenders(n: u32, ) -> Weight {
+		// Derived from the storage accessed by `note_offenders` until its benchmark is run on the
+		// reference hardware.
+		T::DbWeight::get().reads_writes(1, 1)
+			.saturating_add(T::DbWeight::get().reads_writes(n.into(), n.into()))
+	}
 }
@@ -18,7 +18,7 @@ mod pallet_xcm_benchmarks_fungible;
 mod pallet_xcm_benchmarks_generic;
 
 use crate::Runtime;
-use frame_support::weights::Weight;
+use frame_support::{traits::Get, weights::Weight};
 use sp_std::prelude::*;
 use xcm::{
 	latest::{prelude::*, QueryResponseInfo},
@@ -183,7 +183,9 @@ impl<RuntimeCall> XcmWeightInfo<RuntimeCall> for WestendXcmWeight<RuntimeCall> {
 		XcmGeneric::<Runtime>::report_holding()
 	}
 	fn buy_execution(_fees: &MultiAsset, _weight_limit: &WeightLimit) -> Weight {
+		// `RateOfFungibleFrom` reads the fee rate of fees paid in other assets than the native one.
 		XcmGeneric::<Runtime>::buy_execution()
+			.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads(1))
 	}
 	fn refund_surplus() -> Weight {
 		XcmGeneric::<Runtime>::refund_surplus()
@@ -0,0 +1,50 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_benchmarking::{benchmarks, BenchmarkError};
+
+fn benchmark_asset_id() -> VersionedAssetId {
+	Concrete(Parachain(1000).into()).into()
+}
+
+benchmarks! {
+	set_fee_asset {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let asset_id = benchmark_asset_id();
+		let source = FeeRateSource::Fixed(FeeRate { units_per_second: 1_000, units_per_mb: 10 });
+	}: _<T::RuntimeOrigin>(origin, Box::new(asset_id.clone()), source)
+	verify {
+		assert_eq!(FeeAssets::<T>::get(&asset_id), Some(source));
+	}
+
+	remove_fee_asset {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let asset_id = benchmark_asset_id();
+		FeeAssets::<T>::insert(&asset_id, FeeRateSource::Oracle);
+	}: _<T::RuntimeOrigin>(origin, Box::new(asset_id.clone()))
+	verify {
+		assert!(FeeAssets::<T>::get(&asset_id).is_none());
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::new_test_ext(),
+		crate::mock::Test
+	);
+}
@@ -0,0 +1,195 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet keeping the assets which are accepted as payment for XCM execution, along with the rates
+//! weight is sold at for each of them.
+//!
+//! The rate of an asset is either fixed by the [`Config::AdminOrigin`], or taken from the
+//! [`Config::Oracle`] whenever weight is bought. This pallet implements [`WeightFeeRates`], to be
+//! used with the `RateOfFungibleFrom` trader.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::*, weights::Weight};
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_std::boxed::Box;
+use xcm::{latest::prelude::*, VersionedAssetId};
+use xcm_executor::traits::WeightFeeRates;
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// The rate weight is sold at for an asset.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct FeeRate {
+	/// The units of the asset charged for one second of weight.
+	pub units_per_second: u128,
+	/// The units of the asset charged for 1 MB of proof.
+	pub units_per_mb: u128,
+}
+
+/// Where the rate of an asset comes from.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum FeeRateSource {
+	/// The rate is fixed.
+	Fixed(FeeRate),
+	/// The rate is whatever the oracle currently reports. The asset is not accepted while the
+	/// oracle has no rate for it.
+	Oracle,
+}
+
+/// A source of up-to-date rates for assets, e.g. derived from their prices.
+pub trait FeeRateOracle {
+	/// The current rate of the asset `id`, if known.
+	fn fee_rate(id: &AssetId) -> Option<FeeRate>;
+}
+
+impl FeeRateOracle for () {
+	fn fee_rate(_: &AssetId) -> Option<FeeRate> {
+		None
+	}
+}
+
+pub trait WeightInfo {
+	fn set_fee_asset() -> Weight;
+	fn remove_fee_asset() -> Weight;
+}
+
+/// fallback implementation
+pub struct TestWeightInfo;
+impl WeightInfo for TestWeightInfo {
+	fn set_fee_asset() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
+
+	fn remove_fee_asset() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The oracle consulted for the rates of the assets using [`FeeRateSource::Oracle`].
+		type Oracle: FeeRateOracle;
+
+		/// The origin which may manage the assets accepted as payment and their rates.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An asset is accepted as payment for weight, at rates from the given source.
+		FeeAssetSet { asset_id: VersionedAssetId, source: FeeRateSource },
+		/// An asset is no longer accepted as payment for weight.
+		FeeAssetRemoved { asset_id: VersionedAssetId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The version of the `VersionedAssetId` value used is not able to be interpreted.
+		BadVersion,
+		/// The asset is not accepted as payment for weight.
+		NotFeeAsset,
+	}
+
+	/// The assets accepted as payment for weight, and where their rates come from.
+	#[pallet::storage]
+	pub type FeeAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, VersionedAssetId, FeeRateSource, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Accept an asset as payment for weight, or change where its rate comes from.
+		///
+		/// - `origin`: Must be an origin specified by AdminOrigin.
+		/// - `asset_id`: The asset to accept.
+		/// - `source`: Where the rate of the asset comes from.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_fee_asset())]
+		pub fn set_fee_asset(
+			origin: OriginFor<T>,
+			asset_id: Box<VersionedAssetId>,
+			source: FeeRateSource,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let asset_id = Self::latest(*asset_id)?;
+			FeeAssets::<T>::insert(&asset_id, source);
+			Self::deposit_event(Event::FeeAssetSet { asset_id, source });
+			Ok(())
+		}
+
+		/// Stop accepting an asset as payment for weight.
+		///
+		/// - `origin`: Must be an origin specified by AdminOrigin.
+		/// - `asset_id`: The asset to no longer accept.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_fee_asset())]
+		pub fn remove_fee_asset(
+			origin: OriginFor<T>,
+			asset_id: Box<VersionedAssetId>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let asset_id = Self::latest(*asset_id)?;
+			FeeAssets::<T>::take(&asset_id).ok_or(Error::<T>::NotFeeAsset)?;
+			Self::deposit_event(Event::FeeAssetRemoved { asset_id });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// `asset_id` in the latest version, which is the version assets are stored under.
+	fn latest(asset_id: VersionedAssetId) -> Result<VersionedAssetId, Error<T>> {
+		let asset_id: AssetId = asset_id.try_into().map_err(|()| Error::<T>::BadVersion)?;
+		Ok(asset_id.into())
+	}
+
+	/// The current rate of the asset `id`, if it is accepted as payment for weight.
+	pub fn fee_rate(id: &AssetId) -> Option<FeeRate> {
+		match FeeAssets::<T>::get(VersionedAssetId::from(*id))? {
+			FeeRateSource::Fixed(rate) => Some(rate),
+			FeeRateSource::Oracle => T::Oracle::fee_rate(id),
+		}
+	}
+}
+
+impl<T: Config> WeightFeeRates for Pallet<T> {
+	fn fee_rates(id: &AssetId) -> Option<(u128, u128)> {
+		Self::fee_rate(id).map(|rate| (rate.units_per_second, rate.units_per_mb))
+	}
+}
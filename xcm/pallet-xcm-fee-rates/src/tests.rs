@@ -0,0 +1,100 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{mock::*, Error, Event, FeeAssets, FeeRate, FeeRateSource};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+use xcm::{latest::prelude::*, VersionedAssetId};
+use xcm_executor::traits::WeightFeeRates;
+
+const RATE: FeeRate = FeeRate { units_per_second: 1_000, units_per_mb: 10 };
+
+fn para_token(id: u32) -> AssetId {
+	Concrete(Parachain(id).into())
+}
+
+#[test]
+fn set_fee_asset_works() {
+	new_test_ext().execute_with(|| {
+		let asset_id = Box::new(VersionedAssetId::from(para_token(1)));
+		assert_noop!(
+			FeeRates::set_fee_asset(
+				RuntimeOrigin::signed(1),
+				asset_id.clone(),
+				FeeRateSource::Fixed(RATE)
+			),
+			DispatchError::BadOrigin,
+		);
+		assert_eq!(FeeRates::fee_rates(&para_token(1)), None);
+
+		assert_ok!(FeeRates::set_fee_asset(
+			RuntimeOrigin::root(),
+			asset_id.clone(),
+			FeeRateSource::Fixed(RATE)
+		));
+		assert_eq!(FeeRates::fee_rates(&para_token(1)), Some((1_000, 10)));
+		assert_eq!(FeeRates::fee_rates(&para_token(2)), None);
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(
+				Event::FeeAssetSet { asset_id: *asset_id, source: FeeRateSource::Fixed(RATE) }
+					.into()
+			)
+		);
+	});
+}
+
+#[test]
+fn oracle_rates_are_used() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FeeRates::set_fee_asset(
+			RuntimeOrigin::root(),
+			Box::new(para_token(1000).into()),
+			FeeRateSource::Oracle
+		));
+		// Not accepted while the oracle has no rate for it.
+		assert_eq!(FeeRates::fee_rates(&para_token(1000)), None);
+
+		OracleRate::set(Some(RATE));
+		assert_eq!(FeeRates::fee_rates(&para_token(1000)), Some((1_000, 10)));
+		OracleRate::set(Some(FeeRate { units_per_second: 2_000, units_per_mb: 20 }));
+		assert_eq!(FeeRates::fee_rates(&para_token(1000)), Some((2_000, 20)));
+		OracleRate::set(None);
+	});
+}
+
+#[test]
+fn remove_fee_asset_works() {
+	new_test_ext().execute_with(|| {
+		let asset_id = Box::new(VersionedAssetId::from(para_token(1)));
+		assert_noop!(
+			FeeRates::remove_fee_asset(RuntimeOrigin::root(), asset_id.clone()),
+			Error::<Test>::NotFeeAsset,
+		);
+		assert_ok!(FeeRates::set_fee_asset(
+			RuntimeOrigin::root(),
+			asset_id.clone(),
+			FeeRateSource::Fixed(RATE)
+		));
+		assert_ok!(FeeRates::remove_fee_asset(RuntimeOrigin::root(), asset_id.clone()));
+		assert!(FeeAssets::<Test>::get(&*asset_id).is_none());
+		assert_eq!(FeeRates::fee_rates(&para_token(1)), None);
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(Event::FeeAssetRemoved { asset_id: *asset_id }.into())
+		);
+	});
+}
@@ -0,0 +1,75 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_benchmarking::{benchmarks, BenchmarkError};
+use sp_std::vec;
+use xcm::VersionedXcm;
+
+/// The class of `T::BenchmarkOrigin`.
+fn benchmark_class<T: Config>() -> Result<T::OriginClass, BenchmarkError> {
+	T::ClassOf::try_convert(T::BenchmarkOrigin::get()).map_err(|_| BenchmarkError::Weightless)
+}
+
+benchmarks! {
+	set_limit {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let class = benchmark_class::<T>()?;
+		let limit = RateLimit {
+			max_messages: 10,
+			max_weight: Weight::from_parts(1_000_000_000, 64 * 1024),
+			window: 10u32.into(),
+		};
+	}: _<T::RuntimeOrigin>(origin, class.clone(), Some(limit))
+	verify {
+		assert_eq!(Limits::<T>::get(&class), Some(limit));
+	}
+
+	// The worst case is the first message throttled in a new window.
+	try_consume {
+		let origin = T::BenchmarkOrigin::get();
+		let class = benchmark_class::<T>()?;
+		let limit = RateLimit { max_messages: 0, max_weight: Weight::zero(), window: 1u32.into() };
+		Limits::<T>::insert(&class, limit);
+		Usages::<T>::insert(&class, Usage { messages: 1, throttled: true, ..Default::default() });
+		frame_system::Pallet::<T>::set_block_number(10u32.into());
+	}: {
+		assert!(!<Pallet<T> as RateLimiter>::try_consume(&origin, Weight::zero()));
+	}
+	verify {
+		assert!(Usages::<T>::get(&class).throttled);
+	}
+
+	// Messages up to the largest a message queue page holds.
+	decode_message {
+		let n in 0 .. 64 * 1024;
+		let message = VersionedXcm::<()>::from(Xcm(vec![Transact {
+			origin_kind: OriginKind::SovereignAccount,
+			require_weight_at_most: Weight::zero(),
+			call: vec![0u8; n as usize].into(),
+		}]))
+		.encode();
+	}: {
+		VersionedXcm::<()>::decode(&mut &message[..]).map_err(|_| BenchmarkError::Weightless)?;
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::new_test_ext(),
+		crate::mock::Test
+	);
+}
@@ -0,0 +1,217 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet keeping track of the XCM messages executed per origin, so that an origin which sends
+//! too many or too expensive messages can be throttled.
+//!
+//! Origins are grouped into classes by [`Config::ClassOf`], and the origins of a class may have up
+//! to `max_messages` messages of up to `max_weight` in total executed per window of `window`
+//! blocks. This pallet implements [`RateLimiter`], to be used with the message processor
+//! `xcm_builder::ProcessRateLimitedXcmMessage`, which leaves the messages over the limits in their
+//! queue so that they are processed in a later window.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::*, weights::Weight};
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::traits::{Saturating, TryConvert};
+use xcm::latest::prelude::*;
+use xcm_executor::traits::RateLimiter;
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// The limits on the messages executed for the origins of a class.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct RateLimit<BlockNumber> {
+	/// The maximum number of messages executed per window.
+	pub max_messages: u32,
+	/// The maximum total weight of the messages executed per window.
+	pub max_weight: Weight,
+	/// The length of a window, in blocks.
+	pub window: BlockNumber,
+}
+
+/// The messages executed for the origins of a class in the current window.
+#[derive(Clone, Default, Eq, PartialEq, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct Usage<BlockNumber> {
+	/// The block the current window started in.
+	pub window_start: BlockNumber,
+	/// The number of messages executed in the window.
+	pub messages: u32,
+	/// The total weight of the messages executed in the window.
+	pub weight: Weight,
+	/// Whether a message was throttled in the window.
+	pub throttled: bool,
+}
+
+pub trait WeightInfo {
+	fn set_limit() -> Weight;
+	fn try_consume() -> Weight;
+	fn decode_message(n: u32) -> Weight;
+}
+
+/// fallback implementation
+pub struct TestWeightInfo;
+impl WeightInfo for TestWeightInfo {
+	fn set_limit() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
+
+	fn try_consume() -> Weight {
+		Weight::zero()
+	}
+
+	fn decode_message(_n: u32) -> Weight {
+		Weight::zero()
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The classes which origins are rate-limited as.
+		type OriginClass: Member + Parameter + MaxEncodedLen;
+
+		/// The class of an origin. Origins which cannot be converted are not rate-limited.
+		type ClassOf: TryConvert<MultiLocation, Self::OriginClass>;
+
+		/// The limits of the classes which have no limits of their own set.
+		#[pallet::constant]
+		type DefaultLimit: Get<RateLimit<BlockNumberFor<Self>>>;
+
+		/// The origin which may set the limits of a class.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// An origin which is rate-limited, to use in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkOrigin: Get<MultiLocation>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The limits of a class were set, or reset to the default if `None`.
+		LimitSet { class: T::OriginClass, limit: Option<RateLimit<BlockNumberFor<T>>> },
+		/// A message from an origin was throttled, since it would take its class over its limits.
+		/// Only the first message throttled in a window is reported.
+		Throttled { origin: MultiLocation, class: T::OriginClass, weight: Weight },
+	}
+
+	/// The limits of the classes which do not use the default limits.
+	#[pallet::storage]
+	pub type Limits<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::OriginClass, RateLimit<BlockNumberFor<T>>, OptionQuery>;
+
+	/// The messages executed for the origins of each class in their current window.
+	#[pallet::storage]
+	pub type Usages<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::OriginClass, Usage<BlockNumberFor<T>>, ValueQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the limits of a class of origins, or reset them to the default.
+		///
+		/// - `origin`: Must be an origin specified by AdminOrigin.
+		/// - `class`: The class to set the limits of.
+		/// - `limit`: The new limits, or `None` to use the default limits.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_limit())]
+		pub fn set_limit(
+			origin: OriginFor<T>,
+			class: T::OriginClass,
+			limit: Option<RateLimit<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Limits::<T>::set(&class, limit);
+			Self::deposit_event(Event::LimitSet { class, limit });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The limits applying to `class`.
+	pub fn limit_of(class: &T::OriginClass) -> RateLimit<BlockNumberFor<T>> {
+		Limits::<T>::get(class).unwrap_or_else(T::DefaultLimit::get)
+	}
+}
+
+impl<T: Config> RateLimiter for Pallet<T> {
+	fn try_consume(origin: &MultiLocation, weight: Weight) -> bool {
+		let class = match T::ClassOf::try_convert(*origin) {
+			Ok(class) => class,
+			Err(_) => return true,
+		};
+		let limit = Self::limit_of(&class);
+		let now = frame_system::Pallet::<T>::block_number();
+		Usages::<T>::mutate(&class, |usage| {
+			if now >= usage.window_start.saturating_add(limit.window) {
+				*usage = Usage { window_start: now, ..Default::default() };
+			}
+			let messages = usage.messages.saturating_add(1);
+			let total_weight = usage.weight.saturating_add(weight);
+			// A message heavier than the whole window's weight budget is still let through when it
+			// is the first of its window, so that it cannot hold up its origin forever.
+			let over_limit = messages > limit.max_messages ||
+				(total_weight.any_gt(limit.max_weight) && usage.messages > 0);
+			if over_limit {
+				if !usage.throttled {
+					usage.throttled = true;
+					log::debug!(
+						target: "xcm::rate-limiter",
+						"Throttling messages from {:?}, usage: {:?}",
+						origin,
+						usage,
+					);
+					Self::deposit_event(Event::Throttled {
+						origin: *origin,
+						class: class.clone(),
+						weight,
+					});
+				}
+				return false
+			}
+			usage.messages = messages;
+			usage.weight = total_weight;
+			true
+		})
+	}
+
+	fn check_weight(len: u32) -> Weight {
+		T::WeightInfo::decode_message(len).saturating_add(T::WeightInfo::try_consume())
+	}
+}
@@ -0,0 +1,153 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate as pallet_xcm_rate_limiter;
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstU32, ConstU64, Everything},
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup, TryConvert},
+	BuildStorage,
+};
+use xcm::latest::prelude::*;
+use xcm_builder::ProcessRateLimitedXcmMessage;
+
+use crate::RateLimit;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Storage, Config<T>, Event<T>},
+		RateLimiter: pallet_xcm_rate_limiter::{Pallet, Call, Storage, Event<T>},
+		MessageQueue: pallet_message_queue::{Pallet, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+/// Rate-limits child parachains, each as a class of its own.
+pub struct ChildParachains;
+impl TryConvert<MultiLocation, u32> for ChildParachains {
+	fn try_convert(location: MultiLocation) -> Result<u32, MultiLocation> {
+		match location {
+			MultiLocation { parents: 0, interior: X1(Parachain(id)) } => Ok(id),
+			_ => Err(location),
+		}
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+parameter_types! {
+	pub BenchmarkParachain: MultiLocation = Parachain(1000).into();
+}
+
+parameter_types! {
+	pub const DefaultLimit: RateLimit<u64> =
+		RateLimit { max_messages: 2, max_weight: Weight::from_parts(100, 100), window: 10 };
+}
+
+impl crate::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type OriginClass = u32;
+	type ClassOf = ChildParachains;
+	type DefaultLimit = DefaultLimit;
+	type AdminOrigin = EnsureRoot<u64>;
+	type WeightInfo = crate::TestWeightInfo;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkOrigin = BenchmarkParachain;
+}
+
+parameter_types! {
+	pub MessageQueueServiceWeight: Weight = Weight::from_parts(1_000_000_000, 1_000_000);
+	pub static Executed: Vec<(MultiLocation, Xcm<()>)> = vec![];
+}
+
+impl pallet_message_queue::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Size = u32;
+	type HeapSize = ConstU32<65_536>;
+	type MaxStale = ConstU32<16>;
+	type ServiceWeight = MessageQueueServiceWeight;
+	type MessageProcessor = ProcessRateLimitedXcmMessage<Junction, TestExecutor, (), RateLimiter>;
+	type QueueChangeHandler = ();
+	type QueuePausedQuery = ();
+	type WeightInfo = ();
+}
+
+/// A message weighed at 10 per instruction.
+pub struct TestMessage(Weight, Xcm<()>);
+impl PreparedMessage for TestMessage {
+	fn weight_of(&self) -> Weight {
+		self.0
+	}
+}
+
+/// Executes any message, noting it in `Executed`.
+pub struct TestExecutor;
+impl ExecuteXcm<()> for TestExecutor {
+	type Prepared = TestMessage;
+	fn prepare(message: Xcm<()>) -> Result<TestMessage, Xcm<()>> {
+		Ok(TestMessage(Weight::from_parts(10, 10).saturating_mul(message.len() as u64), message))
+	}
+	fn execute(
+		origin: impl Into<MultiLocation>,
+		TestMessage(weight, message): TestMessage,
+		_id: &mut XcmHash,
+		_weight_credit: Weight,
+	) -> Outcome {
+		Executed::mutate(|executed| executed.push((origin.into(), message)));
+		Outcome::Complete(weight)
+	}
+	fn charge_fees(_location: impl Into<MultiLocation>, _fees: MultiAssets) -> XcmResult {
+		Ok(())
+	}
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
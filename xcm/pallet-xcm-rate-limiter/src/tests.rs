@@ -0,0 +1,140 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{mock::*, Event, RateLimit, Usages};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{EnqueueMessage, ServiceQueues},
+	weights::Weight,
+};
+use sp_runtime::DispatchError;
+use xcm::{latest::prelude::*, VersionedXcm};
+use xcm_executor::traits::RateLimiter as _;
+
+fn try_consume(para: u32, weight: u64) -> bool {
+	RateLimiter::try_consume(&Parachain(para).into(), Weight::from_parts(weight, weight))
+}
+
+#[test]
+fn message_count_is_limited_per_window() {
+	new_test_ext().execute_with(|| {
+		assert!(try_consume(1, 10));
+		assert!(try_consume(1, 10));
+		assert!(!try_consume(1, 10));
+		// Other classes have their own limits.
+		assert!(try_consume(2, 10));
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(
+				Event::Throttled {
+					origin: Parachain(1).into(),
+					class: 1,
+					weight: Weight::from_parts(10, 10)
+				}
+				.into()
+			)
+		);
+
+		// Only the first message throttled in a window is reported.
+		let events = System::events().len();
+		assert!(!try_consume(1, 10));
+		assert_eq!(System::events().len(), events);
+
+		// The limits apply again from the start of the next window.
+		System::set_block_number(11);
+		assert!(try_consume(1, 10));
+		assert_eq!(Usages::<Test>::get(1).window_start, 11);
+		assert_eq!(Usages::<Test>::get(1).messages, 1);
+	});
+}
+
+#[test]
+fn weight_is_limited_per_window() {
+	new_test_ext().execute_with(|| {
+		assert!(try_consume(1, 60));
+		assert!(!try_consume(1, 60));
+		assert!(try_consume(1, 40));
+		assert_eq!(Usages::<Test>::get(1).weight, Weight::from_parts(100, 100));
+
+		// A message heavier than the whole budget passes only as the first of its window.
+		System::set_block_number(11);
+		assert!(try_consume(2, 10));
+		assert!(!try_consume(2, 500));
+		System::set_block_number(21);
+		assert!(try_consume(2, 500));
+	});
+}
+
+#[test]
+fn origins_without_class_are_not_limited() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..10 {
+			assert!(RateLimiter::try_consume(&Parent.into(), Weight::MAX));
+		}
+		assert_eq!(Usages::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn set_limit_works() {
+	new_test_ext().execute_with(|| {
+		let limit = RateLimit { max_messages: 1, max_weight: Weight::MAX, window: 5 };
+		assert_noop!(
+			RateLimiter::set_limit(RuntimeOrigin::signed(1), 1, Some(limit)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(RateLimiter::set_limit(RuntimeOrigin::root(), 1, Some(limit)));
+		System::assert_last_event(Event::LimitSet { class: 1, limit: Some(limit) }.into());
+
+		assert!(try_consume(1, 10));
+		assert!(!try_consume(1, 10));
+		System::set_block_number(6);
+		assert!(try_consume(1, 10));
+
+		// Back to the default limits.
+		assert_ok!(RateLimiter::set_limit(RuntimeOrigin::root(), 1, None));
+		assert!(try_consume(1, 10));
+		assert!(!try_consume(1, 10));
+	});
+}
+
+#[test]
+fn throttled_messages_stay_queued_until_a_later_window() {
+	new_test_ext().execute_with(|| {
+		let message = |i: u8| Xcm::<()>(vec![SetTopic([i; 32])]);
+		for i in 0..3 {
+			let encoded = VersionedXcm::from(message(i)).encode();
+			MessageQueue::enqueue_message(encoded.as_slice().try_into().unwrap(), Parachain(1));
+		}
+
+		// Only two messages from a parachain are executed per window.
+		MessageQueue::service_queues(Weight::MAX);
+		let origin = MultiLocation::from(Parachain(1));
+		assert_eq!(Executed::get(), vec![(origin, message(0)), (origin, message(1))]);
+
+		// The third is retried, and executed once the next window starts.
+		System::set_block_number(5);
+		MessageQueue::service_queues(Weight::MAX);
+		assert_eq!(Executed::get().len(), 2);
+		System::set_block_number(11);
+		MessageQueue::service_queues(Weight::MAX);
+		assert_eq!(
+			Executed::get(),
+			vec![(origin, message(0)), (origin, message(1)), (origin, message(2))]
+		);
+	});
+}
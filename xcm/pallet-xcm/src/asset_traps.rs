@@ -0,0 +1,48 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API for listing the assets trapped for an origin, so that they can be claimed.
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+use xcm::prelude::*;
+
+/// Assets trapped for an origin.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct TrappedAssets<BlockNumber> {
+	/// The hash the trap is stored under.
+	pub hash: H256,
+	/// The trapped assets. These are what should be passed to `claim_assets`.
+	pub assets: VersionedMultiAssets,
+	/// The number of times these assets have been trapped, and so may be claimed.
+	pub count: u32,
+	/// The block in which the assets were last trapped.
+	pub trapped_at: BlockNumber,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API for listing asset traps.
+	pub trait AssetTrapsApi<BlockNumber>
+	where
+		BlockNumber: Codec,
+	{
+		/// The assets currently trapped for `origin`.
+		fn trapped_assets(origin: VersionedMultiLocation) -> Vec<TrappedAssets<BlockNumber>>;
+	}
+}
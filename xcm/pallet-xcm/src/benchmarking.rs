@@ -17,7 +17,7 @@
 use super::*;
 use bounded_collections::{ConstU32, WeakBoundedVec};
 use frame_benchmarking::{benchmarks, BenchmarkError, BenchmarkResult};
-use frame_support::weights::Weight;
+use frame_support::{bounded_vec, weights::Weight};
 use frame_system::RawOrigin;
 use sp_std::prelude::*;
 use xcm::{latest::prelude::*, v2};
@@ -117,6 +117,44 @@ benchmarks! {
 
 	force_suspension {}: _(RawOrigin::Root, true)
 
+	claim_assets {
+		let claim_origin =
+			T::ExecuteXcmOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let origin_location = T::ExecuteXcmOrigin::try_origin(claim_origin.clone())
+			.map_err(|_| BenchmarkError::Override(BenchmarkResult::from_weight(Weight::MAX)))?;
+		let assets: MultiAssets = MultiAsset::from((Here, 10)).into();
+		Pallet::<T>::drop_assets(
+			&origin_location,
+			assets.clone().into(),
+			&XcmContext::with_message_id([0; 32]),
+		);
+
+		let recipient = [0u8; 32];
+		let versioned_beneficiary: VersionedMultiLocation =
+			AccountId32 { network: None, id: recipient.into() }.into();
+		let versioned_assets: VersionedMultiAssets = assets.into();
+	}: _<RuntimeOrigin<T>>(claim_origin, Box::new(versioned_assets), Box::new(versioned_beneficiary))
+
+	transfer_assets {
+		let asset: MultiAsset = (Here, 10).into();
+		let send_origin =
+			T::ExecuteXcmOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let origin_location = T::ExecuteXcmOrigin::try_origin(send_origin.clone())
+			.map_err(|_| BenchmarkError::Override(BenchmarkResult::from_weight(Weight::MAX)))?;
+		if !T::XcmReserveTransferFilter::contains(&(origin_location, vec![asset.clone()])) {
+			return Err(BenchmarkError::Override(BenchmarkResult::from_weight(Weight::MAX)))
+		}
+
+		let recipient = [0u8; 32];
+		let versioned_dest: VersionedMultiLocation = T::ReachableDest::get().ok_or(
+			BenchmarkError::Override(BenchmarkResult::from_weight(Weight::MAX)),
+		)?
+		.into();
+		let versioned_beneficiary: VersionedMultiLocation =
+			AccountId32 { network: None, id: recipient.into() }.into();
+		let versioned_assets: VersionedMultiAssets = asset.into();
+	}: _<RuntimeOrigin<T>>(send_origin, Box::new(versioned_dest), Box::new(versioned_beneficiary), Box::new(versioned_assets), 0, bounded_vec![Unlimited])
+
 	migrate_supported_version {
 		let old_version = XCM_VERSION - 1;
 		let loc = VersionedMultiLocation::from(MultiLocation::from(Parent));
@@ -0,0 +1,68 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API for dry-running XCM messages, to learn what they would do and cost before sending
+//! them.
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+use xcm::prelude::*;
+
+/// What executing an XCM would do, without it actually being done.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct XcmDryRunEffects<Event> {
+	/// The outcome of the execution.
+	pub outcome: Outcome,
+	/// The weight the execution used.
+	pub weight_used: Weight,
+	/// The fees charged by the `WeightTrader` for the weight bought, net of refunds, and the
+	/// fees charged for delivery and other services.
+	pub fees: VersionedMultiAssets,
+	/// The assets which would be trapped by the `AssetTrap`.
+	pub trapped: VersionedMultiAssets,
+	/// The events emitted during the execution.
+	pub events: Vec<Event>,
+	/// The messages sent through the `XcmSender` and this pallet's `XcmRouter`, grouped by
+	/// destination.
+	pub forwarded: Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)>,
+}
+
+/// Why an XCM could not be dry-run.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum XcmDryRunError {
+	/// The origin or message could not be converted to the latest XCM version.
+	BadVersion,
+	/// The transaction could not be rolled back, so nothing was executed.
+	TransactionalLimit,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API for dry-running XCM messages.
+	pub trait XcmDryRunApi<Call, Event>
+	where
+		Call: Codec,
+		Event: Codec,
+	{
+		/// Execute `xcm` as if it was received from `origin`, and report its effects. Nothing
+		/// that happens during the execution is committed.
+		fn dry_run_xcm(
+			origin: VersionedMultiLocation,
+			xcm: VersionedXcm<Call>,
+		) -> Result<XcmDryRunEffects<Event>, XcmDryRunError>;
+	}
+}
@@ -25,6 +25,8 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod asset_traps;
+pub mod dry_run;
 pub mod migration;
 
 use codec::{Decode, Encode, EncodeLike, MaxEncodedLen};
@@ -36,11 +38,16 @@ use sp_runtime::{
 	traits::{
 		AccountIdConversion, BadOrigin, BlakeTwo256, BlockNumberProvider, Hash, Saturating, Zero,
 	},
-	RuntimeDebug,
+	PerThing, Perbill, RuntimeDebug,
 };
 use sp_std::{boxed::Box, marker::PhantomData, prelude::*, result::Result, vec};
 use xcm::{latest::QueryResponseInfo, prelude::*};
-use xcm_executor::traits::{ConvertOrigin, Properties};
+use xcm_executor::traits::{
+	ConvertOrigin, Properties, TransactAsset, TransferType, XcmAssetTransfers,
+};
+
+use asset_traps::TrappedAssets;
+use dry_run::{XcmDryRunEffects, XcmDryRunError};
 
 use frame_support::{
 	dispatch::{Dispatchable, GetDispatchInfo},
@@ -51,13 +58,18 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
 use xcm_executor::{
+	dry_run::RecordingSender,
 	traits::{
-		CheckSuspension, ClaimAssets, ConvertLocation, DropAssets, MatchesFungible, OnResponse,
-		QueryHandler, QueryResponseStatus, VersionChangeNotifier, WeightBounds,
+		CheckSuspension, ClaimAssets, ConvertLocation, DropAssets, LockNonFungibles,
+		MatchesFungible, OnResponse, QueryHandler, QueryResponseStatus, VersionChangeNotifier,
+		WeightBounds,
 	},
 	Assets,
 };
 
+/// The router through which this pallet sends messages, recording them for dry-runs.
+type Router<T> = RecordingSender<<T as Config>::XcmRouter>;
+
 pub trait WeightInfo {
 	fn send() -> Weight;
 	fn teleport_assets() -> Weight;
@@ -75,6 +87,8 @@ pub trait WeightInfo {
 	fn notify_target_migration_fail() -> Weight;
 	fn migrate_version_notify_targets() -> Weight;
 	fn migrate_and_notify_old_targets() -> Weight;
+	fn claim_assets() -> Weight;
+	fn transfer_assets() -> Weight;
 }
 
 /// fallback implementation
@@ -143,6 +157,14 @@ impl WeightInfo for TestWeightInfo {
 	fn migrate_and_notify_old_targets() -> Weight {
 		Weight::from_parts(100_000_000, 0)
 	}
+
+	fn claim_assets() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
+
+	fn transfer_assets() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
 }
 
 #[frame_support::pallet]
@@ -207,7 +229,14 @@ pub mod pallet {
 		type XcmExecuteFilter: Contains<(MultiLocation, Xcm<<Self as SysConfig>::RuntimeCall>)>;
 
 		/// Something to execute an XCM message.
-		type XcmExecutor: ExecuteXcm<<Self as SysConfig>::RuntimeCall>;
+		///
+		/// It must also expose how assets may be transferred, for `transfer_assets`. Runtimes
+		/// using `xcm_executor::XcmExecutor` need no change, since it implements
+		/// `XcmAssetTransfers` with the `IsReserve`, `IsTeleporter` and `AssetTransactor` of its
+		/// configuration.
+		/// Runtimes using another executor, or wrapping it, must implement `XcmAssetTransfers`
+		/// for it, e.g. by forwarding to the wrapped executor.
+		type XcmExecutor: ExecuteXcm<<Self as SysConfig>::RuntimeCall> + XcmAssetTransfers;
 
 		/// Our XCM filter which messages to be teleported using the dedicated extrinsic must pass.
 		type XcmTeleportFilter: Contains<(MultiLocation, Vec<MultiAsset>)>;
@@ -259,6 +288,10 @@ pub mod pallet {
 		/// The ID type for local consumers of remote locks.
 		type RemoteLockConsumerIdentifier: Parameter + Member + MaxEncodedLen + Ord + Copy;
 
+		/// How to lock the non-fungible assets held by an account, so that they may be locked by
+		/// `LockAsset` as fungible assets are with `Currency`.
+		type NonFungibleLocker: LockNonFungibles<Self::AccountId>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
@@ -444,6 +477,19 @@ pub mod pallet {
 		LockNotFound,
 		/// The unlock operation cannot succeed because there are still consumers of the lock.
 		InUse,
+		/// The given assets have not been trapped for the origin.
+		UnknownClaim,
+		/// The reserve of an asset could not be determined, or is not trusted.
+		InvalidAssetUnknownReserve,
+		/// Assets moved through a remote reserve cannot be transferred together with assets
+		/// which are moved in another way.
+		InvalidAssetTransferTypeMismatch,
+		/// More weight limits were given than the transfer makes hops.
+		TooManyWeightLimits,
+		/// The local execution of a transfer did not complete.
+		LocalExecutionIncomplete,
+		/// The teleported assets could not be checked out of the local chain.
+		CannotCheckOutTeleport,
 	}
 
 	impl<T: Config> From<SendError> for Error<T> {
@@ -476,6 +522,16 @@ pub mod pallet {
 		Ready { response: VersionedResponse, at: BlockNumber },
 	}
 
+	/// What was trapped in an asset trap, kept so that the trap can be found and claimed.
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct TrapPreimage<BlockNumber> {
+		/// The trapped assets, in the version they were hashed with. Holds at most
+		/// `MAX_ASSETS_IN_TRAP_PREIMAGE` assets.
+		pub assets: VersionedMultiAssets,
+		/// The block in which the assets were last trapped.
+		pub trapped_at: BlockNumber,
+	}
+
 	#[derive(Copy, Clone)]
 	pub(crate) struct LatestVersionedMultiLocation<'a>(pub(crate) &'a MultiLocation);
 	impl<'a> EncodeLike<VersionedMultiLocation> for LatestVersionedMultiLocation<'a> {}
@@ -520,6 +576,22 @@ pub mod pallet {
 	#[pallet::getter(fn asset_trap)]
 	pub(super) type AssetTraps<T: Config> = StorageMap<_, Identity, H256, u32, ValueQuery>;
 
+	/// The preimages of the existing asset traps, by the origin which they were trapped for.
+	///
+	/// Traps of more than `MAX_ASSETS_IN_TRAP_PREIMAGE` assets, and traps which were created
+	/// before preimages were recorded, are only found in `AssetTraps`. They can still be claimed
+	/// with the assets of their `AssetsTrapped` event.
+	#[pallet::storage]
+	pub(super) type AssetTrapPreimages<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		VersionedMultiLocation,
+		Identity,
+		H256,
+		TrapPreimage<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	/// Default version to encode XCM when latest version of destination is unknown. If `None`,
 	/// then the destinations whose XCM version is unknown are considered unreachable.
 	#[pallet::storage]
@@ -632,6 +704,42 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// A non-fungible asset which we know is locked on a remote chain.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct RemoteLockedNonFungibleRecord {
+		/// The owner of the locked asset.
+		pub owner: VersionedMultiLocation,
+		/// The location which holds the original lock.
+		pub locker: VersionedMultiLocation,
+	}
+
+	/// Non-fungible assets which we know are locked on a remote chain.
+	#[pallet::storage]
+	pub(super) type RemoteLockedNonFungibles<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, XcmVersion>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, VersionedMultiAsset>,
+		),
+		RemoteLockedNonFungibleRecord,
+		OptionQuery,
+	>;
+
+	/// Non-fungible assets which we know are locked on this chain, along with the location which
+	/// may unlock them.
+	#[pallet::storage]
+	pub(super) type LockedNonFungibles<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, XcmVersion>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, VersionedMultiAsset>,
+		),
+		VersionedMultiLocation,
+		OptionQuery,
+	>;
+
 	/// Global suspension state of the XCM executor.
 	#[pallet::storage]
 	pub(super) type XcmExecutionSuspended<T: Config> = StorageValue<_, bool, ValueQuery>;
@@ -1125,12 +1233,216 @@ pub mod pallet {
 			XcmExecutionSuspended::<T>::set(suspended);
 			Ok(())
 		}
+
+		/// Claim assets which were trapped for the origin and deposit them to a local
+		/// beneficiary.
+		///
+		/// - `origin`: Must be capable of executing XCM; the assets must have been trapped for the
+		///   location it converts to.
+		/// - `assets`: Exactly the assets which were trapped, as listed by the `AssetTrapsApi` or
+		///   the `AssetsTrapped` event, in the XCM version they were trapped in.
+		/// - `beneficiary`: The local location which should receive the assets. Will generally be
+		///   an `AccountId32` value.
+		#[pallet::call_index(11)]
+		#[pallet::weight({
+			let maybe_assets: Result<MultiAssets, ()> = (*assets.clone()).try_into();
+			let maybe_beneficiary: Result<MultiLocation, ()> = (*beneficiary.clone()).try_into();
+			match (maybe_assets, maybe_beneficiary) {
+				(Ok(assets), Ok(beneficiary)) => {
+					use sp_std::vec;
+					let count = assets.len() as u32;
+					let mut message = Xcm(vec![
+						ClaimAsset { assets, ticket: Here.into() },
+						DepositAsset { assets: Wild(AllCounted(count)), beneficiary },
+					]);
+					T::Weigher::weight(&mut message).map_or(Weight::MAX, |w| T::WeightInfo::claim_assets().saturating_add(w))
+				}
+				_ => Weight::MAX,
+			}
+		})]
+		pub fn claim_assets(
+			origin: OriginFor<T>,
+			assets: Box<VersionedMultiAssets>,
+			beneficiary: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			let origin_location = T::ExecuteXcmOrigin::ensure_origin(origin)?;
+			// Traps are found under the hash of the assets in the version which was the latest
+			// when they were made, so the assets are hashed in the version they are given in.
+			let trap = BlakeTwo256::hash_of(&(&origin_location, &*assets));
+			let ticket: MultiLocation = match *assets {
+				VersionedMultiAssets::V2(_) => GeneralIndex(2).into(),
+				VersionedMultiAssets::V3(_) => Here.into(),
+			};
+			let assets: MultiAssets = (*assets).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let beneficiary: MultiLocation =
+				(*beneficiary).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(!assets.is_none(), Error::<T>::Empty);
+			ensure!(AssetTraps::<T>::contains_key(trap), Error::<T>::UnknownClaim);
+
+			let count = assets.len() as u32;
+			let mut message = Xcm(vec![
+				ClaimAsset { assets, ticket },
+				DepositAsset { assets: Wild(AllCounted(count)), beneficiary },
+			]);
+			let weight =
+				T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
+			let hash = message.using_encoded(sp_io::hashing::blake2_256);
+			let outcome = T::XcmExecutor::execute_xcm_in_credit(
+				origin_location,
+				message,
+				hash,
+				weight,
+				weight,
+			);
+			Self::deposit_event(Event::Attempted { outcome });
+			Ok(())
+		}
+
+		/// Transfer some assets from the local chain to a destination chain, working out whether
+		/// they should be teleported or moved through a reserve.
+		///
+		/// Assets are teleported if `dest` is trusted as a teleporter of them. Otherwise they are
+		/// moved through their reserve: `dest` if it is trusted as one, the local chain for its
+		/// own assets, or else the chain the assets live in if it is trusted as their reserve. In
+		/// the last case the assets make two hops, through the remote reserve and on to `dest`,
+		/// so they cannot be transferred together with assets which are moved in another way.
+		///
+		/// Fee payment on each hop is made from the asset in the `assets` vector of index
+		/// `fee_asset_item`. When the assets make two hops, the fee asset is split between them in
+		/// proportion to their weight limits, or evenly if either is unlimited. When the assets are
+		/// moved in different ways, they are received on `dest` by a single message sent by the
+		/// local chain, which receives the group of the fee asset first and pays for the execution
+		/// of all of them with it. The delivery of that message is paid for by `origin`.
+		///
+		/// - `origin`: Must be capable of withdrawing the `assets` and executing XCM.
+		/// - `dest`: Destination context for the assets. Will typically be `X2(Parent,
+		///   Parachain(..))` to send from parachain to parachain, or `X1(Parachain(..))` to send
+		///   from relay to parachain.
+		/// - `beneficiary`: A beneficiary location for the assets in the context of `dest`. Will
+		///   generally be an `AccountId32` value.
+		/// - `assets`: The assets to be withdrawn. This should include the assets used to pay the
+		///   fees.
+		/// - `fee_asset_item`: The index into `assets` of the item which should be used to pay
+		///   fees.
+		/// - `weight_limits`: The weight limit for the XCM fee purchase on each hop, in order. The
+		///   last one is used for any hops beyond those given, and `Unlimited` if none are given.
+		///   There may not be more of them than the transfer makes hops.
+		#[pallet::call_index(12)]
+		#[pallet::weight({
+			let maybe_assets: Result<MultiAssets, ()> = (*assets.clone()).try_into();
+			let maybe_dest: Result<MultiLocation, ()> = (*dest.clone()).try_into();
+			match (maybe_assets, maybe_dest) {
+				(Ok(assets), Ok(dest)) => {
+					use sp_std::vec;
+					let count = assets.len() as u32;
+					// The local program is one of these, depending on how the assets are transferred.
+					let programs = [
+						vec![
+							WithdrawAsset(assets.clone()),
+							SetFeesMode { jit_withdraw: true },
+							InitiateReserveWithdraw { assets: Wild(AllCounted(count)), reserve: dest, xcm: Xcm(vec![]) },
+						],
+						vec![
+							WithdrawAsset(assets.clone()),
+							SetFeesMode { jit_withdraw: true },
+							InitiateTeleport { assets: Wild(AllCounted(count)), dest, xcm: Xcm(vec![]) },
+						],
+						vec![
+							SetFeesMode { jit_withdraw: true },
+							TransferReserveAsset { assets: assets.clone(), dest, xcm: Xcm(vec![]) },
+						],
+						vec![
+							WithdrawAsset(assets.clone()),
+							BurnAsset(assets.clone()),
+							DepositAsset { assets: Definite(assets), beneficiary: dest },
+						],
+					];
+					programs
+						.into_iter()
+						.try_fold(Weight::zero(), |max, program| T::Weigher::weight(&mut Xcm(program)).map(|w| max.max(w)))
+						.map_or(Weight::MAX, |w| T::WeightInfo::transfer_assets().saturating_add(w))
+				}
+				_ => Weight::MAX,
+			}
+		})]
+		pub fn transfer_assets(
+			origin: OriginFor<T>,
+			dest: Box<VersionedMultiLocation>,
+			beneficiary: Box<VersionedMultiLocation>,
+			assets: Box<VersionedMultiAssets>,
+			fee_asset_item: u32,
+			weight_limits: BoundedVec<WeightLimit, ConstU32<MAX_TRANSFER_HOPS>>,
+		) -> DispatchResult {
+			let origin_location = T::ExecuteXcmOrigin::ensure_origin(origin)?;
+			let dest: MultiLocation = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let beneficiary: MultiLocation =
+				(*beneficiary).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let assets: MultiAssets = (*assets).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			ensure!(assets.len() <= MAX_ASSETS_FOR_TRANSFER, Error::<T>::TooManyAssets);
+			let fees = assets.get(fee_asset_item as usize).ok_or(Error::<T>::Empty)?.clone();
+
+			let groups = Self::group_by_transfer_type(&assets, &fees, &dest)?;
+			let hops = match &groups[..] {
+				[(TransferType::RemoteReserve(_), _)] => 2,
+				_ => 1,
+			};
+			ensure!(weight_limits.len() <= hops, Error::<T>::TooManyWeightLimits);
+			for (transfer_type, group) in &groups {
+				let value = (origin_location, group.clone());
+				match transfer_type {
+					TransferType::Teleport =>
+						ensure!(T::XcmTeleportFilter::contains(&value), Error::<T>::Filtered),
+					_ =>
+						ensure!(T::XcmReserveTransferFilter::contains(&value), Error::<T>::Filtered),
+				}
+			}
+
+			let weight_limit = |hop: usize| {
+				weight_limits.get(hop).or(weight_limits.last()).cloned().unwrap_or(Unlimited)
+			};
+			let transfer_type = match &groups[..] {
+				[(transfer_type, _)] => transfer_type.clone(),
+				_ =>
+					return Self::do_transfer_groups(
+						origin_location,
+						dest,
+						beneficiary,
+						fees,
+						groups,
+						weight_limit(0),
+					),
+			};
+			let mut message = Self::transfer_program(
+				dest,
+				beneficiary,
+				assets,
+				fees,
+				transfer_type,
+				&weight_limit,
+			)?;
+			let weight =
+				T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
+			let hash = message.using_encoded(sp_io::hashing::blake2_256);
+			let outcome = T::XcmExecutor::execute_xcm_in_credit(
+				origin_location,
+				message,
+				hash,
+				weight,
+				weight,
+			);
+			Self::deposit_event(Event::Attempted { outcome });
+			Ok(())
+		}
 	}
 }
 
 /// The maximum number of distinct assets allowed to be transferred in a single helper extrinsic.
 const MAX_ASSETS_FOR_TRANSFER: usize = 2;
 
+/// The maximum number of hops a transfer made by `transfer_assets` takes.
+pub const MAX_TRANSFER_HOPS: u32 = 2;
+
 impl<T: Config> QueryHandler for Pallet<T> {
 	type QueryId = u64;
 	type BlockNumber = BlockNumberFor<T>;
@@ -1313,6 +1625,241 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Group `assets` by the way they should be transferred to `dest`, starting with the group of
+	/// `fees`. Assets moved through a remote reserve cannot be grouped with others, since only
+	/// they make a hop through the reserve.
+	fn group_by_transfer_type(
+		assets: &MultiAssets,
+		fees: &MultiAsset,
+		dest: &MultiLocation,
+	) -> Result<Vec<(TransferType, Vec<MultiAsset>)>, Error<T>> {
+		let transfer_type = |asset: &MultiAsset| {
+			T::XcmExecutor::determine_for(asset, dest)
+				.map_err(|_| Error::<T>::InvalidAssetUnknownReserve)
+		};
+		let mut groups = vec![(transfer_type(fees)?, Vec::new())];
+		for asset in assets.inner() {
+			let asset_transfer_type = transfer_type(asset)?;
+			match groups
+				.iter_mut()
+				.find(|(transfer_type, _)| *transfer_type == asset_transfer_type)
+			{
+				Some((_, group)) => group.push(asset.clone()),
+				None => groups.push((asset_transfer_type, vec![asset.clone()])),
+			}
+		}
+		ensure!(
+			groups.len() == 1 ||
+				!groups.iter().any(|(transfer_type, _)| {
+					matches!(transfer_type, TransferType::RemoteReserve(_))
+				}),
+			Error::<T>::InvalidAssetTransferTypeMismatch
+		);
+		Ok(groups)
+	}
+
+	/// Transfer `groups` of assets, each moved to `dest` in a different way, to `beneficiary`.
+	///
+	/// Each group is moved out locally: assets we are the reserve of into the sovereign account of
+	/// `dest`, while teleported assets and assets `dest` is the reserve of are burnt. A single
+	/// message sent by the local chain then receives every group on `dest` in the way it was
+	/// moved, starting with the group of `fees` so that it can buy execution with `fees` before
+	/// the other groups are received. `origin_location` pays for its delivery.
+	fn do_transfer_groups(
+		origin_location: MultiLocation,
+		dest: MultiLocation,
+		beneficiary: MultiLocation,
+		fees: MultiAsset,
+		groups: Vec<(TransferType, Vec<MultiAsset>)>,
+		weight_limit: WeightLimit,
+	) -> DispatchResult {
+		let context = T::UniversalLocation::get();
+		let reanchored = |assets: &MultiAssets| {
+			assets
+				.clone()
+				.reanchored(&dest, context)
+				.map_err(|_| Error::<T>::CannotReanchor)
+		};
+		let all: Vec<MultiAsset> = groups.iter().flat_map(|(_, group)| group.clone()).collect();
+		let max_assets = all.len() as u32;
+		let mut local = vec![WithdrawAsset(all.into())];
+		let mut remote = Vec::new();
+		let mut teleported = Vec::new();
+		for (transfer_type, group) in groups {
+			let group: MultiAssets = group.into();
+			let received = reanchored(&group)?;
+			match transfer_type {
+				TransferType::Teleport => {
+					teleported.extend(group.inner().iter().cloned());
+					local.push(BurnAsset(group));
+					remote.push(ReceiveTeleportedAsset(received));
+				},
+				TransferType::DestinationReserve => {
+					local.push(BurnAsset(group));
+					remote.push(WithdrawAsset(received));
+				},
+				TransferType::LocalReserve => {
+					local.push(DepositAsset { assets: Definite(group), beneficiary: dest });
+					remote.push(ReserveAssetDeposited(received));
+				},
+				TransferType::RemoteReserve(_) =>
+					return Err(Error::<T>::InvalidAssetTransferTypeMismatch.into()),
+			}
+			if remote.len() == 1 {
+				let fees = fees
+					.clone()
+					.reanchored(&dest, context)
+					.map_err(|_| Error::<T>::CannotReanchor)?;
+				remote.push(BuyExecution { fees, weight_limit: weight_limit.clone() });
+			}
+		}
+		remote.extend([
+			ClearOrigin,
+			DepositAsset { assets: Wild(AllCounted(max_assets)), beneficiary },
+		]);
+		let remote = Xcm(remote);
+
+		let mut message = Xcm(local);
+		let weight =
+			T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
+		let hash = message.using_encoded(sp_io::hashing::blake2_256);
+		let outcome =
+			T::XcmExecutor::execute_xcm_in_credit(origin_location, message, hash, weight, weight);
+		ensure!(matches!(outcome, Outcome::Complete(_)), Error::<T>::LocalExecutionIncomplete);
+		Self::deposit_event(Event::Attempted { outcome });
+
+		// Account for the teleported assets like `InitiateTeleport` does, now that they have left.
+		let xcm_context =
+			XcmContext { origin: Some(origin_location), message_id: hash, topic: None };
+		for asset in &teleported {
+			<T::XcmExecutor as XcmAssetTransfers>::AssetTransactor::can_check_out(
+				&dest,
+				asset,
+				&xcm_context,
+			)
+			.map_err(|_| Error::<T>::CannotCheckOutTeleport)?;
+		}
+		for asset in &teleported {
+			<T::XcmExecutor as XcmAssetTransfers>::AssetTransactor::check_out(
+				&dest,
+				asset,
+				&xcm_context,
+			);
+		}
+
+		let message_id = xcm_executor::send_origin::with_send_origin(Some(Here.into()), || {
+			let (ticket, price) = validate_send::<Router<T>>(dest, remote.clone())?;
+			Self::charge_fees(origin_location, price).map_err(|_| SendError::Fees)?;
+			Router::<T>::deliver(ticket)
+		})
+		.map_err(Error::<T>::from)?;
+		Self::deposit_event(Event::Sent {
+			origin: origin_location,
+			destination: dest,
+			message: remote,
+			message_id,
+		});
+		Ok(())
+	}
+
+	/// Build the local program which transfers `assets` to `beneficiary` on `dest` in the way
+	/// given by `transfer_type`, paying for execution on each hop with `fees`.
+	fn transfer_program(
+		dest: MultiLocation,
+		beneficiary: MultiLocation,
+		assets: MultiAssets,
+		fees: MultiAsset,
+		transfer_type: TransferType,
+		weight_limit: &impl Fn(usize) -> WeightLimit,
+	) -> Result<Xcm<<T as Config>::RuntimeCall>, Error<T>> {
+		let context = T::UniversalLocation::get();
+		let max_assets = assets.len() as u32;
+		let reanchored = |fees: MultiAsset, location: &MultiLocation| {
+			fees.reanchored(location, context).map_err(|_| Error::<T>::CannotReanchor)
+		};
+		let deposit = DepositAsset { assets: Wild(AllCounted(max_assets)), beneficiary };
+
+		let message = match transfer_type {
+			TransferType::Teleport | TransferType::DestinationReserve => {
+				let fees = reanchored(fees, &dest)?;
+				let xcm = Xcm(vec![BuyExecution { fees, weight_limit: weight_limit(0) }, deposit]);
+				let to_transfer = Wild(AllCounted(max_assets));
+				let transfer = match transfer_type {
+					TransferType::Teleport => InitiateTeleport { assets: to_transfer, dest, xcm },
+					_ => InitiateReserveWithdraw { assets: to_transfer, reserve: dest, xcm },
+				};
+				Xcm(vec![WithdrawAsset(assets), SetFeesMode { jit_withdraw: true }, transfer])
+			},
+			TransferType::LocalReserve => {
+				let fees = reanchored(fees, &dest)?;
+				let xcm = Xcm(vec![BuyExecution { fees, weight_limit: weight_limit(0) }, deposit]);
+				Xcm(vec![
+					SetFeesMode { jit_withdraw: true },
+					TransferReserveAsset { assets, dest, xcm },
+				])
+			},
+			TransferType::RemoteReserve(reserve) => {
+				// The fees are split between the hops, since whatever is left of them after the
+				// first hop travels on with the assets.
+				let (reserve_fees, dest_fees) = match fees.fun {
+					Fungible(amount) => {
+						let on_reserve = Self::reserve_fee_share(weight_limit(0), weight_limit(1))
+							.mul_floor(amount);
+						(
+							MultiAsset { id: fees.id, fun: Fungible(on_reserve) },
+							MultiAsset { id: fees.id, fun: Fungible(amount - on_reserve) },
+						)
+					},
+					NonFungible(_) => return Err(Error::<T>::InvalidAsset),
+				};
+				let xcm_on_dest = Xcm(vec![
+					BuyExecution {
+						fees: reanchored(dest_fees, &dest)?,
+						weight_limit: weight_limit(1),
+					},
+					deposit,
+				]);
+				let dest_from_reserve =
+					dest.reanchored(&reserve, context).map_err(|_| Error::<T>::CannotReanchor)?;
+				let xcm_on_reserve = Xcm(vec![
+					BuyExecution {
+						fees: reanchored(reserve_fees, &reserve)?,
+						weight_limit: weight_limit(0),
+					},
+					DepositReserveAsset {
+						assets: Wild(AllCounted(max_assets)),
+						dest: dest_from_reserve,
+						xcm: xcm_on_dest,
+					},
+				]);
+				Xcm(vec![
+					WithdrawAsset(assets),
+					SetFeesMode { jit_withdraw: true },
+					InitiateReserveWithdraw {
+						assets: Wild(AllCounted(max_assets)),
+						reserve,
+						xcm: xcm_on_reserve,
+					},
+				])
+			},
+		};
+		Ok(message)
+	}
+
+	/// The share of the fees of a transfer through a remote reserve which is offered on the
+	/// reserve, given the weight limits on the reserve and on the destination.
+	fn reserve_fee_share(on_reserve: WeightLimit, on_dest: WeightLimit) -> Perbill {
+		match (on_reserve, on_dest) {
+			(Limited(on_reserve), Limited(on_dest))
+				if on_reserve.ref_time().saturating_add(on_dest.ref_time()) > 0 =>
+				Perbill::from_rational(
+					on_reserve.ref_time(),
+					on_reserve.ref_time().saturating_add(on_dest.ref_time()),
+				),
+			_ => Perbill::from_percent(50),
+		}
+	}
+
 	/// Will always make progress, and will do its best not to use much more than `weight_cutoff`
 	/// in doing so.
 	pub(crate) fn check_xcm_version_change(
@@ -1383,7 +1930,7 @@ impl<T: Config> Pallet<T> {
 				let response = Response::Version(xcm_version);
 				let message =
 					Xcm(vec![QueryResponse { query_id, response, max_weight, querier: None }]);
-				let event = match send_xcm::<T::XcmRouter>(new_key, message) {
+				let event = match send_xcm::<Router<T>>(new_key, message) {
 					Ok((message_id, cost)) => {
 						let value = (query_id, max_weight, xcm_version);
 						VersionNotifyTargets::<T>::insert(XCM_VERSION, key, value);
@@ -1440,7 +1987,7 @@ impl<T: Config> Pallet<T> {
 							max_weight,
 							querier: None,
 						}]);
-						let event = match send_xcm::<T::XcmRouter>(new_key, message) {
+						let event = match send_xcm::<Router<T>>(new_key, message) {
 							Ok((message_id, cost)) => {
 								VersionNotifyTargets::<T>::insert(
 									XCM_VERSION,
@@ -1485,7 +2032,7 @@ impl<T: Config> Pallet<T> {
 		});
 		// TODO #3735: Correct weight.
 		let instruction = SubscribeVersion { query_id, max_response_weight: Weight::zero() };
-		let (message_id, cost) = send_xcm::<T::XcmRouter>(dest, Xcm(vec![instruction]))?;
+		let (message_id, cost) = send_xcm::<Router<T>>(dest, Xcm(vec![instruction]))?;
 		Self::deposit_event(Event::VersionNotifyRequested { destination: dest, cost, message_id });
 		VersionNotifiers::<T>::insert(XCM_VERSION, &versioned_dest, query_id);
 		let query_status =
@@ -1500,7 +2047,7 @@ impl<T: Config> Pallet<T> {
 		let versioned_dest = LatestVersionedMultiLocation(&dest);
 		let query_id = VersionNotifiers::<T>::take(XCM_VERSION, versioned_dest)
 			.ok_or(XcmError::InvalidLocation)?;
-		let (message_id, cost) = send_xcm::<T::XcmRouter>(dest, Xcm(vec![UnsubscribeVersion]))?;
+		let (message_id, cost) = send_xcm::<Router<T>>(dest, Xcm(vec![UnsubscribeVersion]))?;
 		Self::deposit_event(Event::VersionNotifyUnrequested {
 			destination: dest,
 			cost,
@@ -1527,11 +2074,13 @@ impl<T: Config> Pallet<T> {
 			None
 		};
 		log::debug!(target: "xcm::send_xcm", "dest: {:?}, message: {:?}", &dest, &message);
-		let (ticket, price) = validate_send::<T::XcmRouter>(dest, message)?;
-		if let Some(fee_payer) = maybe_fee_payer {
-			Self::charge_fees(fee_payer, price).map_err(|_| SendError::Fees)?;
-		}
-		T::XcmRouter::deliver(ticket)
+		xcm_executor::send_origin::with_send_origin(Some(interior.into()), || {
+			let (ticket, price) = validate_send::<Router<T>>(dest, message)?;
+			if let Some(fee_payer) = maybe_fee_payer {
+				Self::charge_fees(fee_payer, price).map_err(|_| SendError::Fees)?;
+			}
+			Router::<T>::deliver(ticket)
+		})
 	}
 
 	pub fn check_account() -> T::AccountId {
@@ -1647,61 +2196,150 @@ impl<T: Config> Pallet<T> {
 		Self::deposit_event(Event::FeesPaid { paying: location, fees: assets });
 		Ok(())
 	}
+
+	/// Execute `xcm` as if it was received from `origin` and report its effects, without
+	/// committing any of them.
+	///
+	/// Fees, trapped assets and forwarded messages are only reported when `T::XcmExecutor` is
+	/// the `xcm_executor::XcmExecutor`. Forwarded messages include those sent through
+	/// `T::XcmRouter` by this pallet, e.g. by a `Transact` of `send`.
+	pub fn dry_run_xcm(
+		origin: VersionedMultiLocation,
+		xcm: VersionedXcm<<T as SysConfig>::RuntimeCall>,
+	) -> Result<XcmDryRunEffects<<T as SysConfig>::RuntimeEvent>, XcmDryRunError> {
+		let origin: MultiLocation = origin.try_into().map_err(|()| XcmDryRunError::BadVersion)?;
+		let hash = xcm.using_encoded(sp_io::hashing::blake2_256);
+		let xcm: Xcm<<T as SysConfig>::RuntimeCall> =
+			xcm.try_into().map_err(|()| XcmDryRunError::BadVersion)?;
+
+		frame_support::storage::with_transaction(|| {
+			let events_before = frame_system::Pallet::<T>::event_count() as usize;
+			let (outcome, effects) = xcm_executor::dry_run::record(|| {
+				T::XcmExecutor::execute_xcm(origin, xcm, hash, Weight::MAX)
+			});
+			let events = frame_system::Pallet::<T>::read_events_no_consensus()
+				.skip(events_before)
+				.map(|record| record.event)
+				.collect();
+
+			let mut forwarded: Vec<(MultiLocation, Vec<VersionedXcm<()>>)> = Vec::new();
+			for (dest, message) in effects.forwarded {
+				match forwarded.iter_mut().find(|(d, _)| *d == dest) {
+					Some((_, messages)) => messages.push(message.into()),
+					None => forwarded.push((dest, vec![message.into()])),
+				}
+			}
+
+			let dry_run = XcmDryRunEffects {
+				weight_used: outcome.weight_used(),
+				outcome,
+				fees: MultiAssets::from(effects.fees).into(),
+				trapped: MultiAssets::from(effects.trapped).into(),
+				events,
+				forwarded: forwarded.into_iter().map(|(dest, msgs)| (dest.into(), msgs)).collect(),
+			};
+			sp_runtime::TransactionOutcome::Rollback(Ok(dry_run))
+		})
+		.map_err(|_: sp_runtime::DispatchError| XcmDryRunError::TransactionalLimit)
+	}
+
+	/// The assets currently trapped for `origin`, along with what is needed to claim them.
+	///
+	/// Returns nothing if `origin` cannot be converted to the latest XCM version.
+	pub fn trapped_assets(origin: VersionedMultiLocation) -> Vec<TrappedAssets<BlockNumberFor<T>>> {
+		let origin: MultiLocation = match origin.try_into() {
+			Ok(origin) => origin,
+			Err(()) => return Vec::new(),
+		};
+		AssetTrapPreimages::<T>::iter_prefix(LatestVersionedMultiLocation(&origin))
+			.map(|(hash, preimage)| TrappedAssets {
+				hash,
+				assets: preimage.assets,
+				count: AssetTraps::<T>::get(hash),
+				trapped_at: preimage.trapped_at,
+			})
+			.collect()
+	}
 }
 
-pub struct LockTicket<T: Config> {
-	sovereign_account: T::AccountId,
-	amount: BalanceOf<T>,
-	unlocker: MultiLocation,
-	item_index: Option<usize>,
+/// The key of the records of locked non-fungible assets.
+type NonFungibleLockKey<T> =
+	(XcmVersion, <T as frame_system::Config>::AccountId, VersionedMultiAsset);
+
+pub enum LockTicket<T: Config> {
+	Fungible {
+		sovereign_account: T::AccountId,
+		amount: BalanceOf<T>,
+		unlocker: MultiLocation,
+		item_index: Option<usize>,
+	},
+	NonFungible {
+		key: NonFungibleLockKey<T>,
+		asset: MultiAsset,
+		unlocker: MultiLocation,
+	},
 }
 
 impl<T: Config> xcm_executor::traits::Enact for LockTicket<T> {
 	fn enact(self) -> Result<(), xcm_executor::traits::LockError> {
 		use xcm_executor::traits::LockError::UnexpectedState;
-		let mut locks = LockedFungibles::<T>::get(&self.sovereign_account).unwrap_or_default();
-		match self.item_index {
+		let (sovereign_account, amount, unlocker, item_index) = match self {
+			LockTicket::Fungible { sovereign_account, amount, unlocker, item_index } =>
+				(sovereign_account, amount, unlocker, item_index),
+			LockTicket::NonFungible { key, asset, unlocker } => {
+				ensure!(!LockedNonFungibles::<T>::contains_key(&key), UnexpectedState);
+				T::NonFungibleLocker::lock(&key.1, &asset)?;
+				LockedNonFungibles::<T>::insert(&key, VersionedMultiLocation::from(unlocker));
+				return Ok(())
+			},
+		};
+		let mut locks = LockedFungibles::<T>::get(&sovereign_account).unwrap_or_default();
+		match item_index {
 			Some(index) => {
 				ensure!(locks.len() > index, UnexpectedState);
-				ensure!(locks[index].1.try_as::<_>() == Ok(&self.unlocker), UnexpectedState);
-				locks[index].0 = locks[index].0.max(self.amount);
+				ensure!(locks[index].1.try_as::<_>() == Ok(&unlocker), UnexpectedState);
+				locks[index].0 = locks[index].0.max(amount);
 			},
 			None => {
 				locks
-					.try_push((self.amount, self.unlocker.into()))
+					.try_push((amount, unlocker.into()))
 					.map_err(|(_balance, _location)| UnexpectedState)?;
 			},
 		}
-		LockedFungibles::<T>::insert(&self.sovereign_account, locks);
-		T::Currency::extend_lock(
-			*b"py/xcmlk",
-			&self.sovereign_account,
-			self.amount,
-			WithdrawReasons::all(),
-		);
+		LockedFungibles::<T>::insert(&sovereign_account, locks);
+		T::Currency::extend_lock(*b"py/xcmlk", &sovereign_account, amount, WithdrawReasons::all());
 		Ok(())
 	}
 }
 
-pub struct UnlockTicket<T: Config> {
-	sovereign_account: T::AccountId,
-	amount: BalanceOf<T>,
-	unlocker: MultiLocation,
+pub enum UnlockTicket<T: Config> {
+	Fungible { sovereign_account: T::AccountId, amount: BalanceOf<T>, unlocker: MultiLocation },
+	NonFungible { key: NonFungibleLockKey<T>, asset: MultiAsset, unlocker: MultiLocation },
 }
 
 impl<T: Config> xcm_executor::traits::Enact for UnlockTicket<T> {
 	fn enact(self) -> Result<(), xcm_executor::traits::LockError> {
 		use xcm_executor::traits::LockError::UnexpectedState;
-		let mut locks =
-			LockedFungibles::<T>::get(&self.sovereign_account).ok_or(UnexpectedState)?;
+		let (sovereign_account, amount, unlocker) = match self {
+			UnlockTicket::Fungible { sovereign_account, amount, unlocker } =>
+				(sovereign_account, amount, unlocker),
+			UnlockTicket::NonFungible { key, asset, unlocker } => {
+				let locked_by = LockedNonFungibles::<T>::get(&key).ok_or(UnexpectedState)?;
+				ensure!(locked_by.try_as::<_>() == Ok(&unlocker), UnexpectedState);
+				T::NonFungibleLocker::unlock(&key.1, &asset)?;
+				LockedNonFungibles::<T>::remove(&key);
+				return Ok(())
+			},
+		};
+		let mut locks = LockedFungibles::<T>::get(&sovereign_account).ok_or(UnexpectedState)?;
 		let mut maybe_remove_index = None;
 		let mut locked = BalanceOf::<T>::zero();
 		let mut found = false;
 		// We could just as well do with with an into_iter, filter_map and collect, however this way
 		// avoids making an allocation.
 		for (i, x) in locks.iter_mut().enumerate() {
-			if x.1.try_as::<_>().defensive() == Ok(&self.unlocker) {
-				x.0 = x.0.saturating_sub(self.amount);
+			if x.1.try_as::<_>().defensive() == Ok(&unlocker) {
+				x.0 = x.0.saturating_sub(amount);
 				if x.0.is_zero() {
 					maybe_remove_index = Some(i);
 				}
@@ -1713,32 +2351,48 @@ impl<T: Config> xcm_executor::traits::Enact for UnlockTicket<T> {
 		if let Some(remove_index) = maybe_remove_index {
 			locks.swap_remove(remove_index);
 		}
-		LockedFungibles::<T>::insert(&self.sovereign_account, locks);
+		LockedFungibles::<T>::insert(&sovereign_account, locks);
 		let reasons = WithdrawReasons::all();
-		T::Currency::set_lock(*b"py/xcmlk", &self.sovereign_account, locked, reasons);
+		T::Currency::set_lock(*b"py/xcmlk", &sovereign_account, locked, reasons);
 		Ok(())
 	}
 }
 
-pub struct ReduceTicket<T: Config> {
-	key: (u32, T::AccountId, VersionedAssetId),
-	amount: u128,
-	locker: VersionedMultiLocation,
-	owner: VersionedMultiLocation,
+pub enum ReduceTicket<T: Config> {
+	Fungible {
+		key: (u32, T::AccountId, VersionedAssetId),
+		amount: u128,
+		locker: VersionedMultiLocation,
+		owner: VersionedMultiLocation,
+	},
+	NonFungible {
+		key: NonFungibleLockKey<T>,
+		locker: VersionedMultiLocation,
+		owner: VersionedMultiLocation,
+	},
 }
 
 impl<T: Config> xcm_executor::traits::Enact for ReduceTicket<T> {
 	fn enact(self) -> Result<(), xcm_executor::traits::LockError> {
 		use xcm_executor::traits::LockError::UnexpectedState;
-		let mut record = RemoteLockedFungibles::<T>::get(&self.key).ok_or(UnexpectedState)?;
-		ensure!(self.locker == record.locker && self.owner == record.owner, UnexpectedState);
-		let new_amount = record.amount.checked_sub(self.amount).ok_or(UnexpectedState)?;
-		ensure!(record.amount_held().map_or(true, |h| new_amount >= h), UnexpectedState);
-		if new_amount == 0 {
-			RemoteLockedFungibles::<T>::remove(&self.key);
-		} else {
-			record.amount = new_amount;
-			RemoteLockedFungibles::<T>::insert(&self.key, &record);
+		match self {
+			ReduceTicket::Fungible { key, amount, locker, owner } => {
+				let mut record = RemoteLockedFungibles::<T>::get(&key).ok_or(UnexpectedState)?;
+				ensure!(locker == record.locker && owner == record.owner, UnexpectedState);
+				let new_amount = record.amount.checked_sub(amount).ok_or(UnexpectedState)?;
+				ensure!(record.amount_held().map_or(true, |h| new_amount >= h), UnexpectedState);
+				if new_amount == 0 {
+					RemoteLockedFungibles::<T>::remove(&key);
+				} else {
+					record.amount = new_amount;
+					RemoteLockedFungibles::<T>::insert(&key, &record);
+				}
+			},
+			ReduceTicket::NonFungible { key, locker, owner } => {
+				let record = RemoteLockedNonFungibles::<T>::get(&key).ok_or(UnexpectedState)?;
+				ensure!(locker == record.locker && owner == record.owner, UnexpectedState);
+				RemoteLockedNonFungibles::<T>::remove(&key);
+			},
 		}
 		Ok(())
 	}
@@ -1756,12 +2410,19 @@ impl<T: Config> xcm_executor::traits::AssetLock for Pallet<T> {
 	) -> Result<LockTicket<T>, xcm_executor::traits::LockError> {
 		use xcm_executor::traits::LockError::*;
 		let sovereign_account = T::SovereignAccountOf::convert_location(&owner).ok_or(BadOwner)?;
+		if let NonFungible(_) = asset.fun {
+			T::NonFungibleLocker::can_lock(&sovereign_account, &asset)?;
+			let key = (XCM_VERSION, sovereign_account, asset.clone().into());
+			// Each instance may only be locked once.
+			ensure!(!LockedNonFungibles::<T>::contains_key(&key), WouldClobber);
+			return Ok(LockTicket::NonFungible { key, asset, unlocker })
+		}
 		let amount = T::CurrencyMatcher::matches_fungible(&asset).ok_or(UnknownAsset)?;
 		ensure!(T::Currency::free_balance(&sovereign_account) >= amount, AssetNotOwned);
 		let locks = LockedFungibles::<T>::get(&sovereign_account).unwrap_or_default();
 		let item_index = locks.iter().position(|x| x.1.try_as::<_>() == Ok(&unlocker));
 		ensure!(item_index.is_some() || locks.len() < T::MaxLockers::get() as usize, NoResources);
-		Ok(LockTicket { sovereign_account, amount, unlocker, item_index })
+		Ok(LockTicket::Fungible { sovereign_account, amount, unlocker, item_index })
 	}
 
 	fn prepare_unlock(
@@ -1771,13 +2432,19 @@ impl<T: Config> xcm_executor::traits::AssetLock for Pallet<T> {
 	) -> Result<UnlockTicket<T>, xcm_executor::traits::LockError> {
 		use xcm_executor::traits::LockError::*;
 		let sovereign_account = T::SovereignAccountOf::convert_location(&owner).ok_or(BadOwner)?;
+		if let NonFungible(_) = asset.fun {
+			let key = (XCM_VERSION, sovereign_account, asset.clone().into());
+			let locked_by = LockedNonFungibles::<T>::get(&key).ok_or(NotLocked)?;
+			ensure!(locked_by.try_as::<_>() == Ok(&unlocker), NotLocked);
+			return Ok(UnlockTicket::NonFungible { key, asset, unlocker })
+		}
 		let amount = T::CurrencyMatcher::matches_fungible(&asset).ok_or(UnknownAsset)?;
 		ensure!(T::Currency::free_balance(&sovereign_account) >= amount, AssetNotOwned);
 		let locks = LockedFungibles::<T>::get(&sovereign_account).unwrap_or_default();
 		let item_index =
 			locks.iter().position(|x| x.1.try_as::<_>() == Ok(&unlocker)).ok_or(NotLocked)?;
 		ensure!(locks[item_index].0 >= amount, NotLocked);
-		Ok(UnlockTicket { sovereign_account, amount, unlocker })
+		Ok(UnlockTicket::Fungible { sovereign_account, amount, unlocker })
 	}
 
 	fn note_unlockable(
@@ -1787,14 +2454,23 @@ impl<T: Config> xcm_executor::traits::AssetLock for Pallet<T> {
 	) -> Result<(), xcm_executor::traits::LockError> {
 		use xcm_executor::traits::LockError::*;
 		ensure!(T::TrustedLockers::contains(&locker, &asset), NotTrusted);
-		let amount = match asset.fun {
-			Fungible(a) => a,
-			NonFungible(_) => return Err(Unimplemented),
-		};
 		owner.remove_network_id();
 		let account = T::SovereignAccountOf::convert_location(&owner).ok_or(BadOwner)?;
 		let locker = locker.into();
 		let owner = owner.into();
+		let amount = match asset.fun {
+			Fungible(a) => a,
+			NonFungible(_) => {
+				let key = (XCM_VERSION, account, VersionedMultiAsset::from(asset));
+				let record = RemoteLockedNonFungibleRecord { owner, locker };
+				if let Some(old) = RemoteLockedNonFungibles::<T>::get(&key) {
+					// Make sure that the new record wouldn't clobber any old data.
+					ensure!(old == record, WouldClobber);
+				}
+				RemoteLockedNonFungibles::<T>::insert(&key, record);
+				return Ok(())
+			},
+		};
 		let id: VersionedAssetId = asset.id.into();
 		let key = (XCM_VERSION, account, id);
 		let mut record =
@@ -1815,14 +2491,20 @@ impl<T: Config> xcm_executor::traits::AssetLock for Pallet<T> {
 		mut owner: MultiLocation,
 	) -> Result<Self::ReduceTicket, xcm_executor::traits::LockError> {
 		use xcm_executor::traits::LockError::*;
-		let amount = match asset.fun {
-			Fungible(a) => a,
-			NonFungible(_) => return Err(Unimplemented),
-		};
 		owner.remove_network_id();
 		let sovereign_account = T::SovereignAccountOf::convert_location(&owner).ok_or(BadOwner)?;
 		let locker = locker.into();
 		let owner = owner.into();
+		let amount = match asset.fun {
+			Fungible(a) => a,
+			NonFungible(_) => {
+				let key = (XCM_VERSION, sovereign_account, VersionedMultiAsset::from(asset));
+				let record = RemoteLockedNonFungibles::<T>::get(&key).ok_or(NotLocked)?;
+				// Make sure that the record contains what we expect.
+				ensure!(locker == record.locker && owner == record.owner, WouldClobber);
+				return Ok(ReduceTicket::NonFungible { key, locker, owner })
+			},
+		};
 		let id: VersionedAssetId = asset.id.into();
 		let key = (XCM_VERSION, sovereign_account, id);
 
@@ -1834,7 +2516,7 @@ impl<T: Config> xcm_executor::traits::AssetLock for Pallet<T> {
 			record.amount_held().map_or(true, |h| record.amount.saturating_sub(amount) >= h),
 			InUse
 		);
-		Ok(ReduceTicket { key, amount, locker, owner })
+		Ok(ReduceTicket::Fungible { key, amount, locker, owner })
 	}
 }
 
@@ -1882,7 +2564,7 @@ impl<T: Config> VersionChangeNotifier for Pallet<T> {
 		let xcm_version = T::AdvertisedXcmVersion::get();
 		let response = Response::Version(xcm_version);
 		let instruction = QueryResponse { query_id, response, max_weight, querier: None };
-		let (message_id, cost) = send_xcm::<T::XcmRouter>(*dest, Xcm(vec![instruction]))?;
+		let (message_id, cost) = send_xcm::<Router<T>>(*dest, Xcm(vec![instruction]))?;
 		Self::deposit_event(Event::<T>::VersionNotifyStarted {
 			destination: *dest,
 			cost,
@@ -1908,17 +2590,31 @@ impl<T: Config> VersionChangeNotifier for Pallet<T> {
 	}
 }
 
+/// The maximum number of assets of a trap for which the preimage is recorded, matching the bound
+/// `MultiAssets` are expected to keep to.
+pub const MAX_ASSETS_IN_TRAP_PREIMAGE: usize = 20;
+
 impl<T: Config> DropAssets for Pallet<T> {
 	fn drop_assets(origin: &MultiLocation, assets: Assets, _context: &XcmContext) -> Weight {
 		if assets.is_empty() {
 			return Weight::zero()
 		}
+		let record_preimage = assets.len() <= MAX_ASSETS_IN_TRAP_PREIMAGE;
 		let versioned = VersionedMultiAssets::from(MultiAssets::from(assets));
 		let hash = BlakeTwo256::hash_of(&(&origin, &versioned));
 		AssetTraps::<T>::mutate(hash, |n| *n += 1);
+		if record_preimage {
+			AssetTrapPreimages::<T>::insert(
+				LatestVersionedMultiLocation(origin),
+				hash,
+				TrapPreimage {
+					assets: versioned.clone(),
+					trapped_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+		}
 		Self::deposit_event(Event::AssetsTrapped { hash, origin: *origin, assets: versioned });
-		// TODO #3735: Put the real weight in there.
-		Weight::zero()
+		T::DbWeight::get().reads_writes(1, if record_preimage { 2 } else { 1 })
 	}
 }
 
@@ -1942,7 +2638,10 @@ impl<T: Config> ClaimAssets for Pallet<T> {
 		let hash = BlakeTwo256::hash_of(&(origin, versioned.clone()));
 		match AssetTraps::<T>::get(hash) {
 			0 => return false,
-			1 => AssetTraps::<T>::remove(hash),
+			1 => {
+				AssetTraps::<T>::remove(hash);
+				AssetTrapPreimages::<T>::remove(LatestVersionedMultiLocation(origin), hash);
+			},
 			n => AssetTraps::<T>::insert(hash, n - 1),
 		}
 		Self::deposit_event(Event::AssetsClaimed { hash, origin: *origin, assets: versioned });
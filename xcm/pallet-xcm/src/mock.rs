@@ -16,7 +16,7 @@
 
 use codec::Encode;
 use frame_support::{
-	construct_runtime, parameter_types,
+	construct_runtime, ensure, parameter_types,
 	traits::{ConstU32, Everything, Nothing},
 	weights::Weight,
 };
@@ -34,7 +34,10 @@ use xcm_builder::{
 	FixedWeightBounds, IsConcrete, SignedAccountId32AsNative, SignedToAccountId32,
 	SovereignSignedViaLocation, TakeWeightCredit,
 };
-use xcm_executor::XcmExecutor;
+use xcm_executor::{
+	traits::{LockError, LockNonFungibles, TransactAsset},
+	Assets, XcmExecutor,
+};
 
 use crate::{self as pallet_xcm, TestWeightInfo};
 
@@ -154,6 +157,10 @@ pub(crate) fn take_sent_xcm() -> Vec<(MultiLocation, Xcm<()>)> {
 		r
 	})
 }
+parameter_types! {
+	/// The price of delivering any message with the test senders.
+	pub static DeliveryFees: MultiAssets = MultiAssets::new();
+}
 /// Sender that never returns error, always sends
 pub struct TestSendXcm;
 impl SendXcm for TestSendXcm {
@@ -163,7 +170,7 @@ impl SendXcm for TestSendXcm {
 		msg: &mut Option<Xcm<()>>,
 	) -> SendResult<(MultiLocation, Xcm<()>)> {
 		let pair = (dest.take().unwrap(), msg.take().unwrap());
-		Ok((pair, MultiAssets::new()))
+		Ok((pair, DeliveryFees::get()))
 	}
 	fn deliver(pair: (MultiLocation, Xcm<()>)) -> Result<XcmHash, SendError> {
 		let hash = fake_message_hash(&pair.1);
@@ -183,7 +190,7 @@ impl SendXcm for TestSendXcmErrX8 {
 		if dest.len() == 8 {
 			Err(SendError::Transport("Destination location full"))
 		} else {
-			Ok(((dest, msg), MultiAssets::new()))
+			Ok(((dest, msg), DeliveryFees::get()))
 		}
 	}
 	fn deliver(pair: (MultiLocation, Xcm<()>)) -> Result<XcmHash, SendError> {
@@ -193,6 +200,37 @@ impl SendXcm for TestSendXcmErrX8 {
 	}
 }
 
+thread_local! {
+	pub static OWNED_NFTS: RefCell<Vec<(AccountId, MultiAsset)>> = RefCell::new(Vec::new());
+	pub static LOCKED_NFTS: RefCell<Vec<(AccountId, MultiAsset)>> = RefCell::new(Vec::new());
+}
+pub(crate) fn give_nft(who: AccountId, asset: impl Into<MultiAsset>) {
+	OWNED_NFTS.with(|o| o.borrow_mut().push((who, asset.into())));
+}
+pub(crate) fn nft_locked(who: &AccountId, asset: &MultiAsset) -> bool {
+	LOCKED_NFTS.with(|l| l.borrow().iter().any(|(w, a)| w == who && a == asset))
+}
+/// Locker of the non-fungible assets given with `give_nft`.
+pub struct TestNonFungibleLocker;
+impl LockNonFungibles<AccountId> for TestNonFungibleLocker {
+	fn can_lock(who: &AccountId, asset: &MultiAsset) -> Result<(), LockError> {
+		let owned = OWNED_NFTS.with(|o| o.borrow().iter().any(|(w, a)| w == who && a == asset));
+		ensure!(owned, LockError::AssetNotOwned);
+		ensure!(!nft_locked(who, asset), LockError::InUse);
+		Ok(())
+	}
+	fn lock(who: &AccountId, asset: &MultiAsset) -> Result<(), LockError> {
+		Self::can_lock(who, asset)?;
+		LOCKED_NFTS.with(|l| l.borrow_mut().push((who.clone(), asset.clone())));
+		Ok(())
+	}
+	fn unlock(who: &AccountId, asset: &MultiAsset) -> Result<(), LockError> {
+		ensure!(nft_locked(who, asset), LockError::NotLocked);
+		LOCKED_NFTS.with(|l| l.borrow_mut().retain(|(w, a)| w != who || a != asset));
+		Ok(())
+	}
+}
+
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 }
@@ -258,6 +296,82 @@ pub type SovereignAccountOf =
 pub type LocalAssetTransactor =
 	XcmCurrencyAdapter<Balances, IsConcrete<RelayLocation>, SovereignAccountOf, AccountId, ()>;
 
+/// The parachain which foreign assets can be teleported to.
+pub const TELEPORT_PARA_ID: u32 = 2000;
+/// The parachain which is the reserve of `ForeignAsset`.
+pub const FOREIGN_PARA_ID: u32 = 2001;
+
+parameter_types! {
+	/// A local asset which is teleported to `TELEPORT_PARA_ID`.
+	pub TeleportableAsset: MultiLocation = GeneralIndex(1).into();
+	/// The native asset of `FOREIGN_PARA_ID`, which is trusted as its reserve.
+	pub ForeignAsset: MultiLocation = Parachain(FOREIGN_PARA_ID).into();
+	pub TeleportableAssets: (MultiAssetFilter, MultiLocation) = (
+		Wild(AllOf { id: Concrete(TeleportableAsset::get()), fun: WildFungible }),
+		Parachain(TELEPORT_PARA_ID).into(),
+	);
+	pub ForeignReserve: (MultiAssetFilter, MultiLocation) = (
+		Wild(AllOf { id: Concrete(ForeignAsset::get()), fun: WildFungible }),
+		Parachain(FOREIGN_PARA_ID).into(),
+	);
+}
+
+thread_local! {
+	pub static OTHER_BALANCES: RefCell<Vec<(MultiLocation, MultiLocation, Balance)>> = RefCell::new(Vec::new());
+}
+/// The balance of `who` in the asset `id`, which must be `TeleportableAsset` or `ForeignAsset`.
+pub(crate) fn other_balance(who: &MultiLocation, id: &MultiLocation) -> Balance {
+	OTHER_BALANCES.with(|b| {
+		b.borrow()
+			.iter()
+			.find(|(w, i, _)| w == who && i == id)
+			.map_or(0, |(_, _, amount)| *amount)
+	})
+}
+pub(crate) fn set_other_balance(who: MultiLocation, id: MultiLocation, amount: Balance) {
+	OTHER_BALANCES.with(|b| {
+		let mut b = b.borrow_mut();
+		b.retain(|(w, i, _)| !(w == &who && i == &id));
+		b.push((who, id, amount));
+	})
+}
+/// Transactor of `TeleportableAsset` and `ForeignAsset`, keeping balances by location.
+pub struct OtherAssetsTransactor;
+impl OtherAssetsTransactor {
+	fn matches(what: &MultiAsset) -> Result<(MultiLocation, Balance), XcmError> {
+		match what {
+			MultiAsset { id: Concrete(id), fun: Fungible(amount) }
+				if *id == TeleportableAsset::get() || *id == ForeignAsset::get() =>
+				Ok((*id, *amount)),
+			_ => Err(XcmError::AssetNotFound),
+		}
+	}
+}
+impl TransactAsset for OtherAssetsTransactor {
+	fn can_check_out(_dest: &MultiLocation, what: &MultiAsset, _context: &XcmContext) -> XcmResult {
+		Self::matches(what).map(|_| ())
+	}
+
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation, _context: &XcmContext) -> XcmResult {
+		let (id, amount) = Self::matches(what)?;
+		set_other_balance(*who, id, other_balance(who, &id) + amount);
+		Ok(())
+	}
+
+	fn withdraw_asset(
+		what: &MultiAsset,
+		who: &MultiLocation,
+		_maybe_context: Option<&XcmContext>,
+	) -> Result<Assets, XcmError> {
+		let (id, amount) = Self::matches(what)?;
+		let balance = other_balance(who, &id)
+			.checked_sub(amount)
+			.ok_or(XcmError::FailedToTransactAsset("Insufficient balance"))?;
+		set_other_balance(*who, id, balance);
+		Ok(what.clone().into())
+	}
+}
+
 type LocalOriginConverter = (
 	SovereignSignedViaLocation<SovereignAccountOf, RuntimeOrigin>,
 	ChildParachainAsNative<origin::Origin, RuntimeOrigin>,
@@ -284,10 +398,10 @@ pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
 	type RuntimeCall = RuntimeCall;
 	type XcmSender = TestSendXcm;
-	type AssetTransactor = LocalAssetTransactor;
+	type AssetTransactor = (LocalAssetTransactor, OtherAssetsTransactor);
 	type OriginConverter = LocalOriginConverter;
-	type IsReserve = ();
-	type IsTeleporter = Case<TrustedAssets>;
+	type IsReserve = Case<ForeignReserve>;
+	type IsTeleporter = (Case<TrustedAssets>, Case<TeleportableAssets>);
 	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
@@ -341,6 +455,7 @@ impl pallet_xcm::Config for Test {
 	type MaxLockers = frame_support::traits::ConstU32<8>;
 	type MaxRemoteLockConsumers = frame_support::traits::ConstU32<0>;
 	type RemoteLockConsumerIdentifier = ();
+	type NonFungibleLocker = TestNonFungibleLocker;
 	type WeightInfo = TestWeightInfo;
 	#[cfg(feature = "runtime-benchmarks")]
 	type ReachableDest = ReachableDest;
@@ -15,12 +15,12 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	mock::*, AssetTraps, CurrentMigration, Error, LatestVersionedMultiLocation, Queries,
-	QueryStatus, VersionDiscoveryQueue, VersionMigrationStage, VersionNotifiers,
-	VersionNotifyTargets,
+	asset_traps::TrappedAssets, mock::*, AssetTrapPreimages, AssetTraps, CurrentMigration, Error,
+	LatestVersionedMultiLocation, LockedNonFungibles, Queries, QueryStatus, VersionDiscoveryQueue,
+	VersionMigrationStage, VersionNotifiers, VersionNotifyTargets, MAX_ASSETS_IN_TRAP_PREIMAGE,
 };
 use frame_support::{
-	assert_noop, assert_ok,
+	assert_noop, assert_ok, bounded_vec,
 	traits::{Currency, Hooks},
 	weights::Weight,
 };
@@ -29,7 +29,10 @@ use sp_runtime::traits::{AccountIdConversion, BlakeTwo256, Hash};
 use xcm::{latest::QueryResponseInfo, prelude::*};
 use xcm_builder::AllowKnownQueryResponses;
 use xcm_executor::{
-	traits::{Properties, QueryHandler, QueryResponseStatus, ShouldExecute},
+	traits::{
+		AssetLock, DropAssets, Enact, LockError, Properties, QueryHandler, QueryResponseStatus,
+		ShouldExecute,
+	},
 	XcmExecutor,
 };
 
@@ -616,6 +619,233 @@ fn unlimited_reserve_transfer_assets_works() {
 	});
 }
 
+/// Test `transfer_assets` with an asset which the local chain is the reserve of
+///
+/// Asserts that the sender's balance is decreased and the beneficiary's balance
+/// is increased. Verifies the correct message is sent and event is emitted.
+#[test]
+fn transfer_assets_with_local_reserve_works() {
+	let balances = vec![
+		(ALICE, INITIAL_BALANCE),
+		(ParaId::from(PARA_ID).into_account_truncating(), INITIAL_BALANCE),
+	];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let weight = BaseXcmWeight::get() * 2;
+		let dest: MultiLocation = Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		assert_ok!(XcmPallet::transfer_assets(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(Parachain(PARA_ID).into()),
+			Box::new(dest.into()),
+			Box::new((Here, SEND_AMOUNT).into()),
+			0,
+			bounded_vec![WeightLimit::Limited(Weight::from_parts(5000, 5000))],
+		));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - SEND_AMOUNT);
+		let para_acc: AccountId = ParaId::from(PARA_ID).into_account_truncating();
+		assert_eq!(Balances::free_balance(para_acc), INITIAL_BALANCE + SEND_AMOUNT);
+		assert_eq!(
+			sent_xcm(),
+			vec![(
+				Parachain(PARA_ID).into(),
+				Xcm(vec![
+					ReserveAssetDeposited((Parent, SEND_AMOUNT).into()),
+					ClearOrigin,
+					buy_limited_execution((Parent, SEND_AMOUNT), Weight::from_parts(5000, 5000)),
+					DepositAsset { assets: AllCounted(1).into(), beneficiary: dest },
+				]),
+			)]
+		);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::Attempted { outcome: Outcome::Complete(weight) })
+		);
+	});
+}
+
+/// Test `transfer_assets` with a teleported asset and an asset which the local chain is the
+/// reserve of
+///
+/// Asserts that both assets leave the sender and that a single message receives both of them on
+/// the destination, buying execution with the fee asset before receiving the other one.
+#[test]
+fn transfer_assets_with_mixed_transfer_types_works() {
+	let balances = vec![
+		(ALICE, INITIAL_BALANCE),
+		(ParaId::from(TELEPORT_PARA_ID).into_account_truncating(), INITIAL_BALANCE),
+	];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let weight = BaseXcmWeight::get() * 3;
+		let sender: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		set_other_balance(sender, TeleportableAsset::get(), INITIAL_BALANCE);
+		let assets: MultiAssets =
+			vec![(Here, SEND_AMOUNT).into(), (TeleportableAsset::get(), SEND_AMOUNT).into()].into();
+		assert_ok!(XcmPallet::transfer_assets(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(Parachain(TELEPORT_PARA_ID).into()),
+			Box::new(sender.into()),
+			Box::new(assets.into()),
+			0,
+			bounded_vec![WeightLimit::Limited(Weight::from_parts(5000, 5000))],
+		));
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - SEND_AMOUNT);
+		let para_acc: AccountId = ParaId::from(TELEPORT_PARA_ID).into_account_truncating();
+		assert_eq!(Balances::free_balance(para_acc), INITIAL_BALANCE + SEND_AMOUNT);
+		assert_eq!(
+			other_balance(&sender, &TeleportableAsset::get()),
+			INITIAL_BALANCE - SEND_AMOUNT
+		);
+		let message = Xcm(vec![
+			ReserveAssetDeposited((Parent, SEND_AMOUNT).into()),
+			buy_limited_execution((Parent, SEND_AMOUNT), Weight::from_parts(5000, 5000)),
+			ReceiveTeleportedAsset(((Parent, GeneralIndex(1)), SEND_AMOUNT).into()),
+			ClearOrigin,
+			DepositAsset { assets: AllCounted(2).into(), beneficiary: sender },
+		]);
+		assert_eq!(sent_xcm(), vec![(Parachain(TELEPORT_PARA_ID).into(), message.clone())]);
+		assert_eq!(
+			last_events(3),
+			vec![
+				RuntimeEvent::XcmPallet(crate::Event::Attempted {
+					outcome: Outcome::Complete(weight)
+				}),
+				RuntimeEvent::XcmPallet(crate::Event::FeesPaid {
+					paying: sender,
+					fees: MultiAssets::new()
+				}),
+				RuntimeEvent::XcmPallet(crate::Event::Sent {
+					origin: sender,
+					destination: Parachain(TELEPORT_PARA_ID).into(),
+					message_id: fake_message_hash(&message),
+					message,
+				}),
+			]
+		);
+	});
+}
+
+/// Test `transfer_assets` with an asset which is moved through a remote reserve and one which is
+/// not
+///
+/// Asserts that the call is rejected, since only one of the assets would hop through the reserve.
+#[test]
+fn transfer_assets_with_remote_reserve_and_other_transfer_types_fails() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let sender: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		set_other_balance(sender, ForeignAsset::get(), INITIAL_BALANCE);
+		let assets: MultiAssets =
+			vec![(Here, SEND_AMOUNT).into(), (ForeignAsset::get(), SEND_AMOUNT).into()].into();
+		assert_noop!(
+			XcmPallet::transfer_assets(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(Parachain(PARA_ID).into()),
+				Box::new(sender.into()),
+				Box::new(assets.into()),
+				0,
+				bounded_vec![WeightLimit::Limited(Weight::from_parts(5000, 5000))],
+			),
+			Error::<Test>::InvalidAssetTransferTypeMismatch
+		);
+	});
+}
+
+/// Test `transfer_assets` with more weight limits than the transfer makes hops
+#[test]
+fn transfer_assets_with_too_many_weight_limits_fails() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let dest: MultiLocation = Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		assert_noop!(
+			XcmPallet::transfer_assets(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(Parachain(PARA_ID).into()),
+				Box::new(dest.into()),
+				Box::new((Here, SEND_AMOUNT).into()),
+				0,
+				bounded_vec![Unlimited, Unlimited],
+			),
+			Error::<Test>::TooManyWeightLimits
+		);
+	});
+}
+
+/// Test `transfer_assets` with an asset which is moved through a remote reserve
+///
+/// Asserts that the assets are sent to the reserve, and on to the destination from there, with
+/// the fees split between the hops in proportion to their weight limits.
+#[test]
+fn transfer_assets_through_remote_reserve_works() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let sender: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		set_other_balance(sender, ForeignAsset::get(), INITIAL_BALANCE);
+		let on_reserve = Weight::from_parts(3000, 3000);
+		let on_dest = Weight::from_parts(1000, 1000);
+		assert_ok!(XcmPallet::transfer_assets(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(Parachain(PARA_ID).into()),
+			Box::new(sender.into()),
+			Box::new((ForeignAsset::get(), 100).into()),
+			0,
+			bounded_vec![WeightLimit::Limited(on_reserve), WeightLimit::Limited(on_dest)],
+		));
+
+		assert_eq!(other_balance(&sender, &ForeignAsset::get()), INITIAL_BALANCE - 100);
+		assert_eq!(
+			sent_xcm(),
+			vec![(
+				Parachain(FOREIGN_PARA_ID).into(),
+				Xcm(vec![
+					WithdrawAsset((Here, 100).into()),
+					ClearOrigin,
+					buy_limited_execution((Here, 75), on_reserve),
+					DepositReserveAsset {
+						assets: AllCounted(1).into(),
+						dest: (Parent, Parachain(PARA_ID)).into(),
+						xcm: Xcm(vec![
+							buy_limited_execution(
+								((Parent, Parachain(FOREIGN_PARA_ID)), 25),
+								on_dest
+							),
+							DepositAsset { assets: AllCounted(1).into(), beneficiary: sender },
+						]),
+					},
+				]),
+			)]
+		);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::Attempted {
+				outcome: Outcome::Complete(BaseXcmWeight::get() * 3)
+			})
+		);
+	});
+}
+
+/// Test `transfer_assets` with assets which have no trusted reserve
+#[test]
+fn transfer_assets_with_unknown_reserve_fails() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let dest: MultiLocation = Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		assert_noop!(
+			XcmPallet::transfer_assets(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(Parachain(PARA_ID).into()),
+				Box::new(dest.into()),
+				Box::new((Parent, SEND_AMOUNT).into()),
+				0,
+				bounded_vec![Unlimited],
+			),
+			Error::<Test>::InvalidAssetUnknownReserve
+		);
+	});
+}
+
 /// Test local execution of XCM
 ///
 /// Asserts that the sender's balance is decreased and the beneficiary's balance
@@ -724,6 +954,214 @@ fn trapped_assets_can_be_claimed() {
 	});
 }
 
+/// Test listing and claiming trapped assets through `claim_assets`.
+#[test]
+fn claim_assets_works() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let weight = BaseXcmWeight::get() * 3;
+		let source: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let dest: MultiLocation = Junction::AccountId32 { network: None, id: BOB.into() }.into();
+		let vma = VersionedMultiAssets::from(MultiAssets::from((Here, SEND_AMOUNT)));
+
+		// Nothing has been trapped yet.
+		assert_noop!(
+			XcmPallet::claim_assets(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(vma.clone()),
+				Box::new(dest.into()),
+			),
+			Error::<Test>::UnknownClaim
+		);
+
+		System::set_block_number(2);
+		assert_ok!(XcmPallet::execute(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(VersionedXcm::from(Xcm(vec![
+				WithdrawAsset((Here, SEND_AMOUNT).into()),
+				buy_execution((Here, SEND_AMOUNT)),
+				Trap(0),
+			]))),
+			weight
+		));
+		let hash = BlakeTwo256::hash_of(&(source, vma.clone()));
+		assert_eq!(
+			XcmPallet::trapped_assets(source.into()),
+			vec![TrappedAssets { hash, assets: vma.clone(), count: 1, trapped_at: 2 }]
+		);
+		assert!(XcmPallet::trapped_assets(dest.into()).is_empty());
+
+		// Only the origin the assets were trapped for can claim them.
+		assert_noop!(
+			XcmPallet::claim_assets(
+				RuntimeOrigin::signed(BOB),
+				Box::new(vma.clone()),
+				Box::new(dest.into()),
+			),
+			Error::<Test>::UnknownClaim
+		);
+
+		assert_ok!(XcmPallet::claim_assets(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(vma),
+			Box::new(dest.into()),
+		));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::Attempted {
+				outcome: Outcome::Complete(BaseXcmWeight::get() * 2)
+			})
+		);
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT);
+		assert_eq!(Balances::total_balance(&BOB), INITIAL_BALANCE + SEND_AMOUNT);
+		assert_eq!(AssetTraps::<Test>::iter().count(), 0);
+		assert_eq!(AssetTrapPreimages::<Test>::iter().count(), 0);
+		assert!(XcmPallet::trapped_assets(source.into()).is_empty());
+	});
+}
+
+/// Test that traps without a recorded preimage can still be claimed through `claim_assets`.
+#[test]
+fn claim_assets_works_without_preimage() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let source: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let dest: MultiLocation = Junction::AccountId32 { network: None, id: BOB.into() }.into();
+
+		// Too many assets for the preimage to be recorded.
+		let many: MultiAssets = (0..=MAX_ASSETS_IN_TRAP_PREIMAGE as u128)
+			.map(|i| MultiAsset::from((GeneralIndex(i), SEND_AMOUNT)))
+			.collect::<Vec<_>>()
+			.into();
+		let context = XcmContext { origin: None, message_id: [0; 32], topic: None };
+		XcmPallet::drop_assets(&source, many.clone().into(), &context);
+		let hash = BlakeTwo256::hash_of(&(source, VersionedMultiAssets::from(many)));
+		assert_eq!(AssetTraps::<Test>::get(hash), 1);
+		assert!(XcmPallet::trapped_assets(source.into()).is_empty());
+
+		// A trap made while XCM v2 was the latest version.
+		let v2_assets = VersionedMultiAssets::from(MultiAssets::from((Here, SEND_AMOUNT)))
+			.into_version(2)
+			.unwrap();
+		let hash = BlakeTwo256::hash_of(&(source, v2_assets.clone()));
+		AssetTraps::<Test>::insert(hash, 1);
+
+		assert_ok!(XcmPallet::claim_assets(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(v2_assets),
+			Box::new(dest.into()),
+		));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::Attempted {
+				outcome: Outcome::Complete(BaseXcmWeight::get() * 2)
+			})
+		);
+		assert_eq!(Balances::total_balance(&BOB), INITIAL_BALANCE + SEND_AMOUNT);
+		assert!(!AssetTraps::<Test>::contains_key(hash));
+	});
+}
+
+/// Test that dry-running a message reports its effects without committing them.
+#[test]
+fn dry_run_xcm_works() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let origin: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let dest: MultiLocation = Junction::AccountId32 { network: None, id: BOB.into() }.into();
+
+		let effects = XcmPallet::dry_run_xcm(
+			origin.into(),
+			VersionedXcm::from(Xcm(vec![
+				WithdrawAsset((Here, SEND_AMOUNT).into()),
+				buy_execution((Here, SEND_AMOUNT)),
+				DepositAsset { assets: AllCounted(1).into(), beneficiary: dest },
+			])),
+		)
+		.unwrap();
+		assert_eq!(effects.outcome, Outcome::Complete(BaseXcmWeight::get() * 3));
+		assert_eq!(effects.weight_used, BaseXcmWeight::get() * 3);
+		assert_eq!(effects.trapped, VersionedMultiAssets::from(MultiAssets::new()));
+		assert!(effects.forwarded.is_empty());
+		assert!(!effects.events.is_empty());
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::total_balance(&BOB), INITIAL_BALANCE);
+
+		let effects = XcmPallet::dry_run_xcm(
+			origin.into(),
+			VersionedXcm::from(Xcm(vec![
+				WithdrawAsset((Here, SEND_AMOUNT).into()),
+				buy_execution((Here, SEND_AMOUNT)),
+				Trap(0),
+			])),
+		)
+		.unwrap();
+		assert_eq!(
+			effects.outcome,
+			Outcome::Incomplete(BaseXcmWeight::get() * 3, XcmError::Trap(0))
+		);
+		assert_eq!(
+			effects.trapped,
+			VersionedMultiAssets::from(MultiAssets::from((Here, SEND_AMOUNT)))
+		);
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE);
+		assert_eq!(AssetTraps::<Test>::iter().count(), 0);
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+/// Test that a dry-run reports the messages sent and the delivery fees charged by this pallet,
+/// and not only those of the executor.
+#[test]
+fn dry_run_xcm_reports_sends_of_transacts() {
+	use codec::Encode;
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let origin: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let dest: MultiLocation = Parachain(PARA_ID).into();
+		DeliveryFees::set((Here, SEND_AMOUNT).into());
+
+		let message = Xcm(vec![ClearOrigin]);
+		let call = RuntimeCall::XcmPallet(crate::Call::send {
+			dest: Box::new(dest.into()),
+			message: Box::new(VersionedXcm::from(message.clone())),
+		});
+		let effects = XcmPallet::dry_run_xcm(
+			origin.into(),
+			VersionedXcm::from(Xcm(vec![
+				WithdrawAsset((Here, SEND_AMOUNT).into()),
+				buy_execution((Here, SEND_AMOUNT)),
+				Transact {
+					origin_kind: OriginKind::SovereignAccount,
+					require_weight_at_most: Weight::from_parts(1_000_000_000, 1_000_000),
+					call: call.encode().into(),
+				},
+			])),
+		)
+		.unwrap();
+
+		let sent_message = Xcm(Some(DescendOrigin(origin.try_into().unwrap()))
+			.into_iter()
+			.chain(message.0.into_iter())
+			.collect());
+		assert_eq!(
+			effects.forwarded,
+			vec![(dest.into(), vec![VersionedXcm::from(sent_message.clone())])]
+		);
+		assert_eq!(
+			effects.fees,
+			VersionedMultiAssets::from(MultiAssets::from((Here, SEND_AMOUNT)))
+		);
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE);
+		// The mock router can't be rolled back.
+		assert_eq!(take_sent_xcm(), vec![(dest, sent_message)]);
+	});
+}
+
 #[test]
 fn fake_latest_versioned_multilocation_works() {
 	use codec::Encode;
@@ -1214,3 +1652,43 @@ fn subscription_side_upgrades_work_with_multistage_notify() {
 		);
 	});
 }
+
+/// Test that non-fungible assets are locked and unlocked, and that only their unlocker may unlock
+/// them.
+#[test]
+fn nonfungible_lock_and_unlock_works() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let nft: MultiAsset = ((Parent, GeneralIndex(1)), Index(42)).into();
+		let owner: MultiLocation = AccountId32 { network: None, id: ALICE.into() }.into();
+		let unlocker: MultiLocation = Parachain(PARA_ID).into();
+		let key = (XCM_VERSION, ALICE, VersionedMultiAsset::from(nft.clone()));
+
+		// Bob's instance cannot be locked by Alice.
+		give_nft(BOB, nft.clone());
+		assert!(matches!(
+			XcmPallet::prepare_lock(unlocker, nft.clone(), owner),
+			Err(LockError::AssetNotOwned)
+		));
+
+		give_nft(ALICE, nft.clone());
+		assert_ok!(XcmPallet::prepare_lock(unlocker, nft.clone(), owner).unwrap().enact());
+		assert!(nft_locked(&ALICE, &nft));
+		assert_eq!(LockedNonFungibles::<Test>::get(&key), Some(unlocker.into_versioned()));
+
+		// It cannot be locked again, nor unlocked by anyone but its unlocker.
+		assert!(XcmPallet::prepare_lock(Parachain(1).into(), nft.clone(), owner).is_err());
+		assert!(matches!(
+			XcmPallet::prepare_unlock(Parachain(1).into(), nft.clone(), owner),
+			Err(LockError::NotLocked)
+		));
+
+		assert_ok!(XcmPallet::prepare_unlock(unlocker, nft.clone(), owner).unwrap().enact());
+		assert!(!nft_locked(&ALICE, &nft));
+		assert_eq!(LockedNonFungibles::<Test>::get(&key), None);
+		assert!(matches!(
+			XcmPallet::prepare_unlock(unlocker, nft, owner),
+			Err(LockError::NotLocked)
+		));
+	});
+}
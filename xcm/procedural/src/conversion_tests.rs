@@ -0,0 +1,122 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Derive generating tests of how each variant of an XCM `Instruction` converts between versions.
+
+use inflector::Inflector;
+use quote::{format_ident, quote};
+
+pub fn derive(item: proc_macro::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+	let input: syn::DeriveInput = syn::parse(item)?;
+	let harness = harness(&input.attrs)?.ok_or_else(|| {
+		let msg =
+			"'derive(ConversionTests)' requires a '#[conversion_tests(harness = ...)]' attribute";
+		syn::Error::new(input.ident.span(), msg)
+	})?;
+
+	let variants = match input.data {
+		syn::Data::Enum(syn::DataEnum { variants, .. }) => variants,
+		syn::Data::Struct(syn::DataStruct { struct_token, .. }) => {
+			let msg = "structs are not supported by 'derive(ConversionTests)'";
+			return Err(syn::Error::new(struct_token.span, msg))
+		},
+		syn::Data::Union(syn::DataUnion { union_token, .. }) => {
+			let msg = "unions are not supported by 'derive(ConversionTests)'";
+			return Err(syn::Error::new(union_token.span, msg))
+		},
+	};
+
+	let ident = &input.ident;
+	let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+	// The instances checked use `()` for every type parameter, e.g. for the call of `Transact`.
+	let unit_params = input.generics.type_params().map(|_| quote!(()));
+	let instance_ty = quote!(super::#ident<#(#unit_params),*>);
+
+	let mut weigher_methods = Vec::new();
+	let mut tests = Vec::new();
+	for (position, variant) in variants.into_iter().enumerate() {
+		let index = codec_index(&variant.attrs)?.unwrap_or(position as u8);
+		let name = variant.ident.to_string();
+		let snake_cased_ident = format_ident!("{}", name.to_snake_case());
+		let args = variant.fields.into_iter().map(|syn::Field { ty, .. }| match ty {
+			syn::Type::Reference(r) => quote!(_: #r),
+			t => quote!(_: &#t),
+		});
+		weigher_methods.push(quote! {
+			fn #snake_cased_ident(#(#args),*) -> Weight {
+				<Weight as #harness::VariantWeight>::of_variant(#index)
+			}
+		});
+		tests.push(quote! {
+			#[test]
+			fn #snake_cased_ident() {
+				#harness::check_variant::<#instance_ty>(#index, #name);
+			}
+		});
+	}
+
+	let tests_mod = format_ident!("{}_conversion_tests", ident.to_string().to_snake_case());
+	Ok(quote! {
+		/// Weighs each instruction as the index of its variant, so that the weights of instructions
+		/// of different versions may be compared to check they are converted to the same kind of
+		/// instruction.
+		#[cfg(any(test, feature = "conversion-harness"))]
+		pub struct VariantIndexWeigher;
+
+		#[cfg(any(test, feature = "conversion-harness"))]
+		impl #impl_generics XcmWeightInfo #ty_generics for VariantIndexWeigher {
+			#(#weigher_methods)*
+		}
+
+		#[cfg(test)]
+		mod #tests_mod {
+			#(#tests)*
+		}
+	})
+}
+
+/// The path of the harness given by `#[conversion_tests(harness = path)]`.
+fn harness(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::Path>> {
+	let mut harness = None;
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("conversion_tests")) {
+		attr.parse_nested_meta(|meta| {
+			if meta.path.is_ident("harness") {
+				harness = Some(meta.value()?.parse()?);
+				Ok(())
+			} else {
+				Err(meta.error("unsupported conversion_tests attribute"))
+			}
+		})?;
+	}
+	Ok(harness)
+}
+
+/// The index given by `#[codec(index = n)]`, if any.
+fn codec_index(attrs: &[syn::Attribute]) -> syn::Result<Option<u8>> {
+	let mut index = None;
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("codec")) {
+		attr.parse_nested_meta(|meta| {
+			if meta.path.is_ident("index") {
+				let lit: syn::LitInt = meta.value()?.parse()?;
+				index = Some(lit.base10_parse()?);
+			} else if meta.input.peek(syn::Token![=]) {
+				meta.value()?.parse::<syn::Expr>()?;
+			}
+			Ok(())
+		})?;
+	}
+	Ok(index)
+}
@@ -18,6 +18,7 @@
 
 use proc_macro::TokenStream;
 
+mod conversion_tests;
 mod v2;
 mod v3;
 mod weight_info;
@@ -34,6 +35,19 @@ pub fn derive_xcm_weight_info(item: TokenStream) -> TokenStream {
 	weight_info::derive(item)
 }
 
+/// Generate tests checking that arbitrary instances of each variant of an XCM `Instruction` convert
+/// cleanly between versions, using the harness given by `#[conversion_tests(harness = path)]`.
+///
+/// This also generates a `VariantIndexWeigher`, implementing the `XcmWeightInfo` trait of the
+/// version, for the harness to check that instructions keep their kind when converted. Whether they
+/// keep their weight is checked against the weighers of runtimes.
+#[proc_macro_derive(ConversionTests, attributes(conversion_tests))]
+pub fn derive_conversion_tests(item: TokenStream) -> TokenStream {
+	conversion_tests::derive(item)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
 #[proc_macro]
 pub fn impl_conversion_functions_for_multilocation_v3(input: TokenStream) -> TokenStream {
 	v3::multilocation::generate_conversion_functions(input)
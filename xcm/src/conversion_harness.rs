@@ -0,0 +1,190 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Harness checking that XCM instructions convert cleanly between versions.
+//!
+//! An instruction must either fail to be converted to another version, or be converted to an
+//! instruction of the same kind, which converts back to one which is converted the same way again
+//! and weighs the same. The tests generated by `derive(ConversionTests)` check this for arbitrary
+//! instances of each variant of an `Instruction`, and the functions here may also be used to check
+//! instructions from elsewhere, e.g. by fuzzing, or with the weighers of runtimes.
+
+use crate::{v2, v3, GetWeight, MAX_XCM_DECODE_DEPTH};
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use parity_scale_codec::DecodeLimit;
+use scale_info::{TypeDef, TypeInfo};
+
+/// The number of arbitrary instances of a variant checked by [`check_variant`].
+pub const INSTANCES_PER_VARIANT: usize = 100;
+
+/// The number of attempts [`check_variant`] makes to generate its instances.
+const MAX_ATTEMPTS: usize = 100 * INSTANCES_PER_VARIANT;
+
+/// A weight standing for the variant `index` of an instruction, as given by the
+/// `VariantIndexWeigher`s.
+pub trait VariantWeight {
+	fn of_variant(index: u8) -> Self;
+}
+
+impl VariantWeight for u64 {
+	fn of_variant(index: u8) -> Self {
+		index as u64
+	}
+}
+
+impl VariantWeight for sp_weights::Weight {
+	fn of_variant(index: u8) -> Self {
+		sp_weights::Weight::from_parts(index as u64, 0)
+	}
+}
+
+/// An instruction whose conversions to the other versions can be checked.
+pub trait CheckConversions: DecodeLimit + TypeInfo + Debug + 'static {
+	/// Check the conversions of `self`, panicking if they are not clean. Instructions are weighed
+	/// by `W`, e.g. the XCM weigher of a runtime.
+	fn check_conversions<W: v3::XcmWeightInfo<()>>(&self);
+}
+
+impl CheckConversions for v3::Instruction<()> {
+	fn check_conversions<W: v3::XcmWeightInfo<()>>(&self) {
+		check_downgrade::<W>(self)
+	}
+}
+
+impl CheckConversions for v2::Instruction<()> {
+	fn check_conversions<W: v3::XcmWeightInfo<()>>(&self) {
+		// v2 instructions are weighed as their upgrades, which `check_upgrade` checks to convert
+		// back to themselves.
+		check_upgrade(self)
+	}
+}
+
+/// Check that `instruction` either cannot be downgraded to v2, or is downgraded to one of the same
+/// kind which converts back and forth stably, and which is upgraded back to an instruction which
+/// weighs the same according to `W` as `instruction` does.
+pub fn check_downgrade<W: v3::XcmWeightInfo<()>>(instruction: &v3::Instruction<()>) {
+	let downgraded = match v2::Instruction::<()>::try_from(instruction.clone()) {
+		Ok(downgraded) => downgraded,
+		Err(()) => return,
+	};
+	let upgraded = v3::Instruction::<()>::try_from(downgraded.clone()).unwrap_or_else(|()| {
+		panic!("{:?} is downgraded to {:?}, which cannot be upgraded", instruction, downgraded)
+	});
+	let downgraded_again = v2::Instruction::<()>::try_from(upgraded.clone()).unwrap_or_else(|()| {
+		panic!("{:?} is upgraded to {:?}, which cannot be downgraded", downgraded, upgraded)
+	});
+	assert_eq!(downgraded, downgraded_again, "{:?} does not convert stably", instruction);
+	check_same_kind(&downgraded, instruction);
+	assert_eq!(
+		GetWeight::<W>::weight(instruction),
+		GetWeight::<W>::weight(&upgraded),
+		"{:?} is converted to {:?}, which differs in weight",
+		instruction,
+		upgraded
+	);
+}
+
+/// Check that `instruction` either cannot be upgraded to v3, or is upgraded to one of the same
+/// kind which converts back and forth stably.
+pub fn check_upgrade(instruction: &v2::Instruction<()>) {
+	let upgraded = match v3::Instruction::<()>::try_from(instruction.clone()) {
+		Ok(upgraded) => upgraded,
+		Err(()) => return,
+	};
+	let downgraded = v2::Instruction::<()>::try_from(upgraded.clone()).unwrap_or_else(|()| {
+		panic!("{:?} is upgraded to {:?}, which cannot be downgraded", instruction, upgraded)
+	});
+	let upgraded_again = v3::Instruction::<()>::try_from(downgraded.clone()).unwrap_or_else(|()| {
+		panic!("{:?} is downgraded to {:?}, which cannot be upgraded", upgraded, downgraded)
+	});
+	assert_eq!(upgraded, upgraded_again, "{:?} does not convert stably", instruction);
+	check_same_kind(instruction, &upgraded);
+}
+
+/// Check that the v2 and v3 forms of an instruction are of the same kind, i.e. variant.
+pub fn check_same_kind(old: &v2::Instruction<()>, new: &v3::Instruction<()>) {
+	let old_kind = GetWeight::<v2::VariantIndexWeigher>::weight(old);
+	let new_kind = GetWeight::<v3::VariantIndexWeigher>::weight(new);
+	assert_eq!(old_kind.ref_time(), new_kind.ref_time(), "{:?} and {:?} differ in kind", old, new);
+}
+
+/// Check the conversions of arbitrary instances of the variant `index` of the instruction `I`,
+/// weighing them only by their kind, and panicking if none can be generated.
+pub fn check_variant<I: CheckConversions>(index: u8, name: &str) {
+	check_variant_weighed_by::<I, v3::VariantIndexWeigher>(index, name)
+}
+
+/// Check the conversions of arbitrary instances of the variant `index` of the instruction `I`,
+/// weighing them by `W`, and panicking if none can be generated.
+pub fn check_variant_weighed_by<I: CheckConversions, W: v3::XcmWeightInfo<()>>(
+	index: u8,
+	name: &str,
+) {
+	let mut rng = XorShift(0x9e37_79b9_7f4a_7c15 ^ index as u64);
+	let mut checked = 0;
+	for _ in 0..MAX_ATTEMPTS {
+		if checked == INSTANCES_PER_VARIANT {
+			break
+		}
+		// The variant index is followed by arbitrary bytes for its fields, most of which are
+		// small so that lengths and indices decode to reasonable values.
+		let len = rng.next() as usize % 256;
+		let mut encoded = Vec::with_capacity(len + 1);
+		encoded.push(index);
+		encoded.extend((0..len).map(|_| rng.next_byte()));
+		if let Ok(instruction) = I::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut &encoded[..])
+		{
+			instruction.check_conversions::<W>();
+			checked += 1;
+		}
+	}
+	assert!(checked > 0, "No instances of {} could be generated", name);
+}
+
+/// Check the conversions of arbitrary instances of every variant of the instruction `I`, weighing
+/// them by `W`. Runtimes use this to check that their XCM weigher charges instructions the same
+/// before and after they are converted.
+pub fn check_all_variants_weighed_by<I: CheckConversions, W: v3::XcmWeightInfo<()>>() {
+	let variants = match I::type_info().type_def {
+		TypeDef::Variant(def) => def.variants,
+		_ => panic!("instructions are enums"),
+	};
+	for variant in variants {
+		check_variant_weighed_by::<I, W>(variant.index, variant.name);
+	}
+}
+
+/// A small deterministic generator, so that any failures are reproducible.
+struct XorShift(u64);
+
+impl XorShift {
+	fn next(&mut self) -> u64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+
+	fn next_byte(&mut self) -> u8 {
+		let r = self.next();
+		if r % 4 == 0 {
+			(r >> 8) as u8
+		} else {
+			(r >> 8) as u8 % 4
+		}
+	}
+}
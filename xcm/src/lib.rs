@@ -41,6 +41,9 @@ pub mod latest {
 mod double_encoded;
 pub use double_encoded::DoubleEncoded;
 
+#[cfg(any(test, feature = "conversion-harness"))]
+pub mod conversion_harness;
+
 #[cfg(test)]
 mod tests;
 
@@ -62,7 +62,7 @@ pub use barriers::{
 };
 
 mod process_xcm_message;
-pub use process_xcm_message::ProcessXcmMessage;
+pub use process_xcm_message::{ProcessRateLimitedXcmMessage, ProcessXcmMessage};
 
 mod currency_adapter;
 pub use currency_adapter::CurrencyAdapter;
@@ -75,12 +75,14 @@ pub use fungibles_adapter::{
 
 mod nonfungibles_adapter;
 pub use nonfungibles_adapter::{
-	NonFungiblesAdapter, NonFungiblesMutateAdapter, NonFungiblesTransferAdapter,
+	NonFungiblesAdapter, NonFungiblesLockAdapter, NonFungiblesMutateAdapter,
+	NonFungiblesTransferAdapter,
 };
 
 mod weight;
 pub use weight::{
-	FixedRateOfFungible, FixedWeightBounds, TakeRevenue, UsingComponents, WeightInfoBounds,
+	FixedRateOfFungible, FixedWeightBounds, RateOfFungibleFrom, TakeRevenue, UsingComponents,
+	WeightInfoBounds,
 };
 
 mod matches_token;
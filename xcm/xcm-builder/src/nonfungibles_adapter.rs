@@ -19,12 +19,16 @@
 use crate::{AssetChecking, MintLocation};
 use frame_support::{
 	ensure,
-	traits::{tokens::nonfungibles, Get},
+	traits::{
+		tokens::{nonfungibles, nonfungibles_v2},
+		Get,
+	},
 };
 use sp_std::{marker::PhantomData, prelude::*, result};
 use xcm::latest::prelude::*;
 use xcm_executor::traits::{
-	ConvertLocation, Error as MatchError, MatchesNonFungibles, TransactAsset,
+	ConvertLocation, Error as MatchError, LockError, LockNonFungibles, MatchesNonFungibles,
+	TransactAsset,
 };
 
 const LOG_TARGET: &str = "xcm::nonfungibles_adapter";
@@ -239,6 +243,8 @@ impl<
 		let who = AccountIdConverter::convert_location(who)
 			.ok_or(MatchError::AccountIdConversionFailed)?;
 		let (class, instance) = Matcher::matches_nonfungibles(what)?;
+		// Instances which cannot be transferred, e.g. because they are locked, cannot leave either.
+		ensure!(Assets::can_transfer(&class, &instance), XcmError::NotWithdrawable);
 		Assets::burn(&class, &instance, Some(&who))
 			.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
 		Ok(what.clone().into())
@@ -261,7 +267,14 @@ impl<
 		CheckAsset: AssetChecking<Assets::CollectionId>,
 		CheckingAccount: Get<Option<AccountId>>,
 	> TransactAsset
-	for NonFungiblesAdapter<Assets, Matcher, AccountIdConverter, AccountId, CheckAsset, CheckingAccount>
+	for NonFungiblesAdapter<
+		Assets,
+		Matcher,
+		AccountIdConverter,
+		AccountId,
+		CheckAsset,
+		CheckingAccount,
+	>
 {
 	fn can_check_in(origin: &MultiLocation, what: &MultiAsset, context: &XcmContext) -> XcmResult {
 		NonFungiblesMutateAdapter::<
@@ -344,3 +357,185 @@ impl<
 		)
 	}
 }
+
+/// Locks the non-fungible asset instances held by an account, e.g. for `pallet-xcm` to handle
+/// `LockAsset` for them, by disabling their transfer until they are unlocked.
+pub struct NonFungiblesLockAdapter<Assets, Matcher, AccountId>(
+	PhantomData<(Assets, Matcher, AccountId)>,
+);
+impl<
+		Assets: nonfungibles_v2::Transfer<AccountId>,
+		Matcher: MatchesNonFungibles<Assets::CollectionId, Assets::ItemId>,
+		AccountId: Eq,
+	> NonFungiblesLockAdapter<Assets, Matcher, AccountId>
+{
+	fn owned_instance(
+		who: &AccountId,
+		asset: &MultiAsset,
+	) -> Result<(Assets::CollectionId, Assets::ItemId), LockError> {
+		let (class, instance) =
+			Matcher::matches_nonfungibles(asset).map_err(|_| LockError::UnknownAsset)?;
+		ensure!(Assets::owner(&class, &instance).as_ref() == Some(who), LockError::AssetNotOwned);
+		Ok((class, instance))
+	}
+}
+
+impl<
+		Assets: nonfungibles_v2::Transfer<AccountId>,
+		Matcher: MatchesNonFungibles<Assets::CollectionId, Assets::ItemId>,
+		AccountId: Eq,
+	> LockNonFungibles<AccountId> for NonFungiblesLockAdapter<Assets, Matcher, AccountId>
+{
+	fn can_lock(who: &AccountId, asset: &MultiAsset) -> Result<(), LockError> {
+		let (class, instance) = Self::owned_instance(who, asset)?;
+		// An instance which cannot be transferred is already locked, by us or otherwise.
+		ensure!(Assets::can_transfer(&class, &instance), LockError::InUse);
+		Ok(())
+	}
+
+	fn lock(who: &AccountId, asset: &MultiAsset) -> Result<(), LockError> {
+		Self::can_lock(who, asset)?;
+		let (class, instance) = Self::owned_instance(who, asset)?;
+		log::trace!(target: LOG_TARGET, "lock asset: {:?}", asset);
+		Assets::disable_transfer(&class, &instance).map_err(|_| LockError::UnexpectedState)
+	}
+
+	fn unlock(who: &AccountId, asset: &MultiAsset) -> Result<(), LockError> {
+		let (class, instance) = Self::owned_instance(who, asset)?;
+		log::trace!(target: LOG_TARGET, "unlock asset: {:?}", asset);
+		Assets::enable_transfer(&class, &instance).map_err(|_| LockError::UnexpectedState)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::dispatch::DispatchResult;
+	use nonfungibles_v2::{Inspect, Transfer};
+	use sp_runtime::DispatchError;
+	use std::{cell::RefCell, collections::BTreeMap};
+
+	thread_local! {
+		/// The owner of each `(collection, item)` and whether it may be transferred.
+		static ITEMS: RefCell<BTreeMap<(u32, u32), (u64, bool)>> = RefCell::new(BTreeMap::new());
+	}
+
+	fn mint(collection: u32, item: u32, owner: u64) {
+		ITEMS.with(|i| i.borrow_mut().insert((collection, item), (owner, true)));
+	}
+
+	fn set_transferable(collection: u32, item: u32, transferable: bool) -> DispatchResult {
+		ITEMS.with(|i| match i.borrow_mut().get_mut(&(collection, item)) {
+			Some((_, t)) => {
+				*t = transferable;
+				Ok(())
+			},
+			None => Err(DispatchError::Other("unknown item")),
+		})
+	}
+
+	pub struct TestNfts;
+	impl Inspect<u64> for TestNfts {
+		type ItemId = u32;
+		type CollectionId = u32;
+
+		fn owner(collection: &u32, item: &u32) -> Option<u64> {
+			ITEMS.with(|i| i.borrow().get(&(*collection, *item)).map(|(owner, _)| *owner))
+		}
+
+		fn can_transfer(collection: &u32, item: &u32) -> bool {
+			ITEMS.with(|i| i.borrow().get(&(*collection, *item)).map_or(false, |(_, t)| *t))
+		}
+	}
+	impl Transfer<u64> for TestNfts {
+		fn transfer(collection: &u32, item: &u32, destination: &u64) -> DispatchResult {
+			ITEMS.with(|i| match i.borrow_mut().get_mut(&(*collection, *item)) {
+				Some((owner, true)) => {
+					*owner = *destination;
+					Ok(())
+				},
+				_ => Err(DispatchError::Other("not transferable")),
+			})
+		}
+
+		fn disable_transfer(collection: &u32, item: &u32) -> DispatchResult {
+			set_transferable(*collection, *item, false)
+		}
+
+		fn enable_transfer(collection: &u32, item: &u32) -> DispatchResult {
+			set_transferable(*collection, *item, true)
+		}
+	}
+
+	/// Matches `((Parent, GeneralIndex(collection)), Index(item))`.
+	pub struct TestMatcher;
+	impl MatchesNonFungibles<u32, u32> for TestMatcher {
+		fn matches_nonfungibles(a: &MultiAsset) -> result::Result<(u32, u32), MatchError> {
+			match (&a.id, &a.fun) {
+				(
+					Concrete(MultiLocation { parents: 1, interior: X1(GeneralIndex(collection)) }),
+					NonFungible(AssetInstance::Index(item)),
+				) => Ok((
+					(*collection).try_into().map_err(|_| MatchError::AssetIdConversionFailed)?,
+					(*item).try_into().map_err(|_| MatchError::InstanceConversionFailed)?,
+				)),
+				_ => Err(MatchError::AssetNotHandled),
+			}
+		}
+	}
+
+	type Locker = NonFungiblesLockAdapter<TestNfts, TestMatcher, u64>;
+
+	fn nft(collection: u128, item: u128) -> MultiAsset {
+		((Parent, GeneralIndex(collection)), Index(item)).into()
+	}
+
+	#[test]
+	fn lock_disables_transfer_until_unlocked() {
+		mint(1, 42, 3);
+
+		assert!(matches!(Locker::can_lock(&3, &nft(1, 42)), Ok(())));
+		assert!(matches!(Locker::lock(&3, &nft(1, 42)), Ok(())));
+		assert!(!TestNfts::can_transfer(&1, &42));
+		assert!(TestNfts::transfer(&1, &42, &4).is_err());
+
+		// A locked instance cannot be locked again.
+		assert!(matches!(Locker::can_lock(&3, &nft(1, 42)), Err(LockError::InUse)));
+		assert!(matches!(Locker::lock(&3, &nft(1, 42)), Err(LockError::InUse)));
+
+		assert!(matches!(Locker::unlock(&3, &nft(1, 42)), Ok(())));
+		assert!(TestNfts::can_transfer(&1, &42));
+		assert_eq!(TestNfts::transfer(&1, &42, &4), Ok(()));
+		assert_eq!(TestNfts::owner(&1, &42), Some(4));
+	}
+
+	#[test]
+	fn only_the_owner_can_lock_or_unlock() {
+		mint(1, 42, 3);
+
+		assert!(matches!(Locker::can_lock(&4, &nft(1, 42)), Err(LockError::AssetNotOwned)));
+		assert!(matches!(Locker::lock(&4, &nft(1, 42)), Err(LockError::AssetNotOwned)));
+		assert!(TestNfts::can_transfer(&1, &42));
+
+		assert!(matches!(Locker::lock(&3, &nft(1, 42)), Ok(())));
+		assert!(matches!(Locker::unlock(&4, &nft(1, 42)), Err(LockError::AssetNotOwned)));
+		assert!(!TestNfts::can_transfer(&1, &42));
+	}
+
+	#[test]
+	fn unknown_assets_cannot_be_locked() {
+		mint(1, 42, 3);
+
+		// Not matched by the matcher.
+		assert!(matches!(
+			Locker::lock(&3, &(Parent, 100u128).into()),
+			Err(LockError::UnknownAsset)
+		));
+		assert!(matches!(
+			Locker::lock(&3, &((Parent, Parachain(1)), Index(42)).into()),
+			Err(LockError::UnknownAsset)
+		));
+		// Matched, but never minted.
+		assert!(matches!(Locker::lock(&3, &nft(1, 43)), Err(LockError::AssetNotOwned)));
+	}
+}
@@ -22,9 +22,11 @@ use frame_support::{
 };
 use parity_scale_codec::{Decode, FullCodec, MaxEncodedLen};
 use scale_info::TypeInfo;
+use sp_runtime::traits::SaturatedConversion;
 use sp_std::{fmt::Debug, marker::PhantomData};
 use sp_weights::{Weight, WeightMeter};
 use xcm::prelude::*;
+use xcm_executor::traits::RateLimiter;
 
 /// A message processor that delegates execution to an [`XcmExecutor`].
 pub struct ProcessXcmMessage<MessageOrigin, XcmExecutor, Call>(
@@ -45,6 +47,42 @@ impl<
 		meter: &mut WeightMeter,
 		id: &mut XcmHash,
 	) -> Result<bool, ProcessMessageError> {
+		ProcessRateLimitedXcmMessage::<MessageOrigin, XcmExecutor, Call, ()>::process_message(
+			message, origin, meter, id,
+		)
+	}
+}
+
+/// A message processor that delegates execution to an [`XcmExecutor`], as long as the origin of
+/// the message is within the limits kept by `Limiter`.
+///
+/// Messages which would take their origin over its limits are yielded, so that they stay in their
+/// queue and are processed again later. Every message which is executed counts against the
+/// limits, whether or not the barrier lets it through. Checking a message against the limits is
+/// charged each time it is processed, since a yielded message is decoded and checked again.
+pub struct ProcessRateLimitedXcmMessage<MessageOrigin, XcmExecutor, Call, Limiter>(
+	PhantomData<(MessageOrigin, XcmExecutor, Call, Limiter)>,
+);
+impl<
+		MessageOrigin: Into<MultiLocation> + FullCodec + MaxEncodedLen + Clone + Eq + PartialEq + TypeInfo + Debug,
+		XcmExecutor: ExecuteXcm<Call>,
+		Call,
+		Limiter: RateLimiter,
+	> ProcessMessage for ProcessRateLimitedXcmMessage<MessageOrigin, XcmExecutor, Call, Limiter>
+{
+	type Origin = MessageOrigin;
+
+	/// Process the given message, using no more than the remaining `weight` to do so.
+	fn process_message(
+		message: &[u8],
+		origin: Self::Origin,
+		meter: &mut WeightMeter,
+		id: &mut XcmHash,
+	) -> Result<bool, ProcessMessageError> {
+		let check = Limiter::check_weight(message.len().saturated_into());
+		ensure!(meter.can_consume(check), ProcessMessageError::Overweight(check));
+		meter.consume(check);
+
 		let versioned_message = VersionedXcm::<Call>::decode(&mut &message[..])
 			.map_err(|_| ProcessMessageError::Corrupt)?;
 		let message = Xcm::<Call>::try_from(versioned_message)
@@ -53,8 +91,17 @@ impl<
 		let required = pre.weight_of();
 		ensure!(meter.can_consume(required), ProcessMessageError::Overweight(required));
 
-		let (consumed, result) = match XcmExecutor::execute(origin.into(), pre, id, Weight::zero())
-		{
+		let origin = origin.into();
+		if !Limiter::try_consume(&origin, required) {
+			log::trace!(
+				target: "xcm::process-message",
+				"Origin {:?} is over its limits, yielding a message of weight {:?}",
+				origin, required,
+			);
+			return Err(ProcessMessageError::Yield)
+		}
+
+		let (consumed, result) = match XcmExecutor::execute(origin, pre, id, Weight::zero()) {
 			Outcome::Complete(w) => (w, Ok(true)),
 			Outcome::Incomplete(w, _) => (w, Ok(false)),
 			// In the error-case we assume the worst case and consume all possible weight.
@@ -69,7 +116,7 @@ impl<
 mod tests {
 	use super::*;
 	use frame_support::{
-		assert_err, assert_ok,
+		assert_err, assert_ok, parameter_types,
 		traits::{ProcessMessageError, ProcessMessageError::*},
 	};
 	use parity_scale_codec::Encode;
@@ -103,6 +150,52 @@ mod tests {
 		}
 	}
 
+	parameter_types! {
+		pub static MessageBudget: u32 = 0;
+	}
+
+	/// Lets through as many messages as there are left in `MessageBudget`.
+	struct TestLimiter;
+	impl RateLimiter for TestLimiter {
+		fn try_consume(_origin: &MultiLocation, _weight: Weight) -> bool {
+			let budget = MessageBudget::get();
+			if budget == 0 {
+				return false
+			}
+			MessageBudget::set(budget - 1);
+			true
+		}
+
+		fn check_weight(_len: u32) -> Weight {
+			Weight::from_parts(10, 10)
+		}
+	}
+
+	#[test]
+	fn process_message_over_rate_limit_yields() {
+		type Processor = ProcessRateLimitedXcmMessage<
+			Junction,
+			xcm_executor::XcmExecutor<xcm_config::XcmConfig>,
+			RuntimeCall,
+			TestLimiter,
+		>;
+		MessageBudget::set(2);
+		let msg = &v3_xcm(true).encode()[..];
+		let corrupt: &[u8] = &[55, 66];
+
+		let meter = &mut WeightMeter::max_limit();
+		assert_ok!(Processor::process_message(msg, ORIGIN, meter, &mut [0; 32]), true);
+		// Messages which cannot be decoded do not count against the limits, but the check does
+		// consume weight.
+		assert_err!(Processor::process_message(corrupt, ORIGIN, meter, &mut [0; 32]), Corrupt);
+		assert_ok!(Processor::process_message(msg, ORIGIN, meter, &mut [0; 32]), true);
+		assert_eq!(meter.consumed(), Weight::from_parts(2030, 30));
+
+		// Over the limits the message is yielded, consuming only the weight of the check.
+		assert_err!(Processor::process_message(msg, ORIGIN, meter, &mut [0; 32]), Yield);
+		assert_eq!(meter.consumed(), Weight::from_parts(2040, 40));
+	}
+
 	#[test]
 	fn process_message_overweight_fails() {
 		for msg in [v3_xcm(true), v3_xcm(false), v3_xcm(false), v2_xcm(false)] {
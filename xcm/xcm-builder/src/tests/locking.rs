@@ -236,3 +236,133 @@ fn remote_unlock_should_fail_correctly() {
 	assert_eq!(sent_xcm(), vec![]);
 	assert_eq!(take_lock_trace(), vec![]);
 }
+
+#[test]
+fn nonfungible_lock_roundtrip_should_work() {
+	// Account #3 and Parachain #1 can execute for free
+	AllowUnpaidFrom::set(vec![(3u64,).into(), (Parent, Parachain(1)).into()]);
+	// Account #3 owns 1000 native parent tokens and instance #42 of the parent's collection #1.
+	let nft: MultiAsset = ((Parent, GeneralIndex(1)), Index(42)).into();
+	add_asset((3u64,), (Parent, 1000u128));
+	add_asset((3u64,), nft.clone());
+	// Sending a message costs 10 parent-tokens.
+	set_send_price((Parent, 10u128));
+
+	// They want to lock their instance to be unlocked only by Parachain #1.
+	let message = Xcm(vec![
+		WithdrawAsset((Parent, 100u128).into()),
+		SetAppendix(
+			vec![DepositAsset { assets: AllCounted(2).into(), beneficiary: (3u64,).into() }].into(),
+		),
+		LockAsset { asset: nft.clone(), unlocker: (Parent, Parachain(1)).into() },
+	]);
+	let hash = fake_message_hash(&message);
+	let r =
+		XcmExecutor::<TestConfig>::execute_xcm((3u64,), message, hash, Weight::from_parts(50, 50));
+	assert_eq!(r, Outcome::Complete(Weight::from_parts(40, 40)));
+	assert_eq!(asset_list((3u64,)), vec![(Parent, 990u128).into(), nft.clone()]);
+
+	let expected_msg = Xcm::<()>(vec![NoteUnlockable {
+		owner: (Parent, Parachain(42), 3u64).into(),
+		asset: nft.clone(),
+	}]);
+	let expected_hash = fake_message_hash(&expected_msg);
+	assert_eq!(sent_xcm(), vec![((Parent, Parachain(1)).into(), expected_msg, expected_hash)]);
+	assert_eq!(
+		take_lock_trace(),
+		vec![Lock {
+			asset: nft.clone(),
+			owner: (3u64,).into(),
+			unlocker: (Parent, Parachain(1)).into(),
+		}]
+	);
+
+	// Now we'll unlock it.
+	let message = Xcm(vec![UnlockAsset { asset: nft.clone(), target: (3u64,).into() }]);
+	let hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		(Parent, Parachain(1)),
+		message,
+		hash,
+		Weight::from_parts(50, 50),
+	);
+	assert_eq!(r, Outcome::Complete(Weight::from_parts(10, 10)));
+
+	// It's no longer locked, so it cannot be unlocked again.
+	let message = Xcm(vec![UnlockAsset { asset: nft, target: (3u64,).into() }]);
+	let hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		(Parent, Parachain(1)),
+		message,
+		hash,
+		Weight::from_parts(50, 50),
+	);
+	assert_eq!(r, Outcome::Incomplete(Weight::from_parts(10, 10), XcmError::LockError));
+}
+
+#[test]
+fn nonfungible_remote_unlock_roundtrip_should_work() {
+	// Account #3 can execute for free
+	AllowUnpaidFrom::set(vec![(3u64,).into(), (Parent, Parachain(1)).into()]);
+	// Account #3 owns 1000 native parent tokens.
+	add_asset((3u64,), (Parent, 1000u128));
+	// Sending a message costs 10 parent-tokens.
+	set_send_price((Parent, 10u128));
+	let nft: MultiAsset = ((Parent, GeneralIndex(1)), Index(42)).into();
+
+	// We have been told by Parachain #1 that Account #3 has locked an instance which we can
+	// unlock.
+	let message = Xcm(vec![NoteUnlockable { asset: nft.clone(), owner: (3u64,).into() }]);
+	let hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		(Parent, Parachain(1)),
+		message,
+		hash,
+		Weight::from_parts(50, 50),
+	);
+	assert_eq!(r, Outcome::Complete(Weight::from_parts(10, 10)));
+	assert_eq!(
+		take_lock_trace(),
+		vec![Note {
+			asset: nft.clone(),
+			owner: (3u64,).into(),
+			locker: (Parent, Parachain(1)).into(),
+		}]
+	);
+
+	// Let's request it be unlocked.
+	let message = Xcm(vec![
+		WithdrawAsset((Parent, 100u128).into()),
+		SetAppendix(
+			vec![DepositAsset { assets: AllCounted(2).into(), beneficiary: (3u64,).into() }].into(),
+		),
+		RequestUnlock { asset: nft.clone(), locker: (Parent, Parachain(1)).into() },
+	]);
+	let hash = fake_message_hash(&message);
+	let r =
+		XcmExecutor::<TestConfig>::execute_xcm((3u64,), message, hash, Weight::from_parts(50, 50));
+	assert_eq!(r, Outcome::Complete(Weight::from_parts(40, 40)));
+	assert_eq!(asset_list((3u64,)), vec![(Parent, 990u128).into()]);
+
+	let expected_msg = Xcm::<()>(vec![UnlockAsset {
+		target: (Parent, Parachain(42), 3u64).into(),
+		asset: nft.clone(),
+	}]);
+	let expected_hash = fake_message_hash(&expected_msg);
+	assert_eq!(sent_xcm(), vec![((Parent, Parachain(1)).into(), expected_msg, expected_hash)]);
+	assert_eq!(
+		take_lock_trace(),
+		vec![Reduce {
+			asset: nft.clone(),
+			owner: (3u64,).into(),
+			locker: (Parent, Parachain(1)).into(),
+		}]
+	);
+
+	// The unlock has been requested, so it cannot be requested again.
+	let message = Xcm(vec![RequestUnlock { asset: nft, locker: (Parent, Parachain(1)).into() }]);
+	let hash = fake_message_hash(&message);
+	let r =
+		XcmExecutor::<TestConfig>::execute_xcm((3u64,), message, hash, Weight::from_parts(50, 50));
+	assert_eq!(r, Outcome::Incomplete(Weight::from_parts(10, 10), XcmError::LockError));
+}
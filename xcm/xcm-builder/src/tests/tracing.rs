@@ -0,0 +1,99 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use xcm_executor::{
+	trace::{self, InstructionTrace, TraceEntry},
+	Assets,
+};
+
+fn instruction(entry: &TraceEntry) -> &InstructionTrace {
+	match entry {
+		TraceEntry::Instruction(trace) => trace,
+		other => panic!("expected an instruction, got {:?}", other),
+	}
+}
+
+#[test]
+fn trace_records_instructions_jumps_and_sent_messages() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	add_asset(Parachain(1), (Here, 100u128));
+	let report = QueryResponseInfo {
+		destination: Parachain(1).into(),
+		query_id: 1,
+		max_weight: Weight::zero(),
+	};
+	let message = Xcm::<TestCall>(vec![
+		WithdrawAsset((Here, 100u128).into()),
+		SetAppendix(Xcm(vec![ReportError(report)])),
+		ClearOrigin,
+		Trap(1),
+	]);
+	let hash = fake_message_hash(&message);
+	let (r, trace) = trace::capture(|| {
+		XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, hash, Weight::MAX)
+	});
+	assert_eq!(r, Outcome::Incomplete(Weight::from_parts(50, 50), XcmError::Trap(1)));
+	assert_eq!(trace.len(), 9);
+	assert_eq!(trace[0], TraceEntry::Started { origin: Parachain(1).into(), message_id: hash });
+
+	let withdraw = instruction(&trace[1]);
+	assert_eq!(withdraw.index, 0);
+	assert_eq!(withdraw.holding_before, Assets::new());
+	assert_eq!(withdraw.holding_after, Assets::from(MultiAsset::from((Here, 100u128))));
+	assert_eq!(withdraw.weight_used, Weight::from_parts(10, 10));
+
+	assert_eq!(instruction(&trace[2]).instruction, SetAppendix(Xcm(vec![ReportError(report)])));
+
+	let clear_origin = instruction(&trace[3]);
+	assert_eq!(clear_origin.origin_before, Some(Parachain(1).into()));
+	assert_eq!(clear_origin.origin_after, None);
+
+	let trap = instruction(&trace[4]);
+	assert_eq!(trap.index, 3);
+	assert_eq!(trap.error, Some(XcmError::Trap(1)));
+
+	assert_eq!(trace[5], TraceEntry::Appendix { len: 1 });
+	let expected_msg = Xcm::<()>(vec![QueryResponse {
+		query_id: 1,
+		response: Response::ExecutionResult(Some((3, XcmError::Trap(1)))),
+		max_weight: Weight::zero(),
+		querier: None,
+	}]);
+	let report_error = instruction(&trace[6]);
+	assert_eq!(report_error.index, 0);
+	assert_eq!(report_error.sent, vec![(Parachain(1).into(), expected_msg)]);
+
+	assert_eq!(trace[7], TraceEntry::Trapped { assets: MultiAsset::from((Here, 100u128)).into() });
+	assert_eq!(trace[8], TraceEntry::Finished { outcome: r });
+}
+
+#[test]
+fn trace_records_barrier_rejections() {
+	let message = Xcm::<TestCall>(vec![ClearOrigin]);
+	let hash = fake_message_hash(&message);
+	let (r, trace) = trace::capture(|| {
+		XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, hash, Weight::MAX)
+	});
+	assert_eq!(r, Outcome::Error(XcmError::Barrier));
+	assert_eq!(
+		trace,
+		vec![
+			TraceEntry::Started { origin: Parachain(1).into(), message_id: hash },
+			TraceEntry::Finished { outcome: r },
+		]
+	);
+}
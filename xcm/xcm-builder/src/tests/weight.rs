@@ -64,6 +64,91 @@ fn fixed_rate_of_fungible_should_work() {
 	);
 }
 
+#[test]
+fn rate_of_fungible_from_should_work() {
+	let para_1: MultiLocation = Parachain(1).into();
+	let para_2: MultiLocation = Parachain(2).into();
+
+	// Our own token is charged at 1 unit per unit of weight, and parachain 1's at twice that.
+	pub struct TestRates;
+	impl WeightFeeRates for TestRates {
+		fn fee_rates(id: &AssetId) -> Option<(u128, u128)> {
+			let (per_second, per_mb) =
+				(WEIGHT_REF_TIME_PER_SECOND as u128, WEIGHT_PROOF_SIZE_PER_MB as u128);
+			match id {
+				Concrete(MultiLocation { parents: 0, interior: Here }) =>
+					Some((per_second, per_mb)),
+				Concrete(MultiLocation { parents: 0, interior: X1(Parachain(1)) }) =>
+					Some((2 * per_second, 2 * per_mb)),
+				_ => None,
+			}
+		}
+	}
+
+	let mut trader = RateOfFungibleFrom::<TestRates, ()>::new();
+	let ctx = XcmContext { origin: None, message_id: XcmHash::default(), topic: None };
+
+	// only the first asset of the payment is considered, even if another one would suffice
+	let payment: Assets =
+		vec![fungible_multi_asset(Here.into(), 5), fungible_multi_asset(para_1, 100)].into();
+	assert_err!(trader.buy_weight(Weight::from_parts(5, 5), payment, &ctx), XcmError::TooExpensive);
+	// parachain 1's token is accepted at its own rate
+	let payment: Assets = fungible_multi_asset(para_1, 100).into();
+	let expected: Assets = fungible_multi_asset(para_1, 80).into();
+	assert_eq!(trader.buy_weight(Weight::from_parts(5, 5), payment, &ctx), Ok(expected));
+	// further weight must be bought with parachain 1's token too
+	assert_err!(
+		trader.buy_weight(
+			Weight::from_parts(1, 1),
+			fungible_multi_asset(Here.into(), 100).into(),
+			&ctx,
+		),
+		XcmError::TooExpensive,
+	);
+	// and is refunded in it
+	assert_eq!(
+		trader.refund_weight(Weight::from_parts(2, 2), &ctx),
+		Some(fungible_multi_asset(para_1, 8))
+	);
+
+	// assets without rates are not accepted
+	let mut trader = RateOfFungibleFrom::<TestRates, ()>::new();
+	assert_err!(
+		trader.buy_weight(Weight::from_parts(5, 5), fungible_multi_asset(para_2, 100).into(), &ctx),
+		XcmError::TooExpensive,
+	);
+	assert_eq!(trader.refund_weight(Weight::from_parts(2, 2), &ctx), None);
+}
+
+#[test]
+fn rate_of_fungible_from_free_weight_should_work() {
+	let para_1: MultiLocation = Parachain(1).into();
+
+	// Our own token is free, and parachain 1's is charged at 1 unit per unit of weight.
+	pub struct TestRates;
+	impl WeightFeeRates for TestRates {
+		fn fee_rates(id: &AssetId) -> Option<(u128, u128)> {
+			match id {
+				Concrete(MultiLocation { parents: 0, interior: Here }) => Some((0, 0)),
+				Concrete(MultiLocation { parents: 0, interior: X1(Parachain(1)) }) =>
+					Some((WEIGHT_REF_TIME_PER_SECOND as u128, WEIGHT_PROOF_SIZE_PER_MB as u128)),
+				_ => None,
+			}
+		}
+	}
+
+	let mut trader = RateOfFungibleFrom::<TestRates, ()>::new();
+	let ctx = XcmContext { origin: None, message_id: XcmHash::default(), topic: None };
+
+	// weight bought for free is still bought with our own token
+	let payment: Assets = fungible_multi_asset(Here.into(), 100).into();
+	assert_eq!(trader.buy_weight(Weight::from_parts(5, 5), payment.clone(), &ctx), Ok(payment));
+	// so further weight is free too, rather than charged in parachain 1's token
+	let payment: Assets = fungible_multi_asset(para_1, 100).into();
+	assert_eq!(trader.buy_weight(Weight::from_parts(5, 5), payment.clone(), &ctx), Ok(payment));
+	assert_eq!(trader.refund_weight(Weight::from_parts(2, 2), &ctx), None);
+}
+
 #[test]
 fn errors_should_return_unused_weight() {
 	// we'll let them have message execution for free.
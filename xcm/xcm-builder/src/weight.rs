@@ -24,10 +24,10 @@ use frame_support::{
 };
 use parity_scale_codec::Decode;
 use sp_runtime::traits::{SaturatedConversion, Saturating, Zero};
-use sp_std::{marker::PhantomData, result::Result};
+use sp_std::{marker::PhantomData, prelude::*, result::Result};
 use xcm::latest::{prelude::*, Weight};
 use xcm_executor::{
-	traits::{WeightBounds, WeightTrader},
+	traits::{WeightBounds, WeightFeeRates, WeightTrader},
 	Assets,
 };
 
@@ -191,6 +191,87 @@ impl<T: Get<(AssetId, u128, u128)>, R: TakeRevenue> Drop for FixedRateOfFungible
 	}
 }
 
+/// Weight trader which accepts payment in any fungible which `Rates` has rates for, at those
+/// rates. The rates may then be kept in storage, to be changed without a runtime upgrade.
+///
+/// Weight is bought with the first fungible asset of the payment, so that buying it looks up at
+/// most one rate, and any more weight bought by the same trader must be paid for in that asset, at
+/// the rates it was first bought at. Weighers should account for looking that rate up.
+pub struct RateOfFungibleFrom<Rates: WeightFeeRates, R: TakeRevenue>(
+	Weight,
+	u128,
+	Option<(AssetId, u128, u128)>,
+	PhantomData<(Rates, R)>,
+);
+impl<Rates: WeightFeeRates, R: TakeRevenue> RateOfFungibleFrom<Rates, R> {
+	fn fee_for(weight: Weight, units_per_second: u128, units_per_mb: u128) -> u128 {
+		let ref_time_fee = units_per_second.saturating_mul(weight.ref_time() as u128) /
+			(WEIGHT_REF_TIME_PER_SECOND as u128);
+		let proof_size_fee = units_per_mb.saturating_mul(weight.proof_size() as u128) /
+			(WEIGHT_PROOF_SIZE_PER_MB as u128);
+		ref_time_fee.saturating_add(proof_size_fee)
+	}
+}
+impl<Rates: WeightFeeRates, R: TakeRevenue> WeightTrader for RateOfFungibleFrom<Rates, R> {
+	fn new() -> Self {
+		Self(Weight::zero(), 0, None, PhantomData)
+	}
+
+	fn buy_weight(
+		&mut self,
+		weight: Weight,
+		payment: Assets,
+		context: &XcmContext,
+	) -> Result<Assets, XcmError> {
+		log::trace!(
+			target: "xcm::weight",
+			"RateOfFungibleFrom::buy_weight weight: {:?}, payment: {:?}, context: {:?}",
+			weight, payment, context,
+		);
+		let (id, units_per_second, units_per_mb) = match self.2 {
+			Some(rates) => rates,
+			None => {
+				let id = payment.fungible_assets_iter().next().ok_or(XcmError::TooExpensive)?.id;
+				let (per_second, per_mb) = Rates::fee_rates(&id).ok_or(XcmError::TooExpensive)?;
+				(id, per_second, per_mb)
+			},
+		};
+		let amount = Self::fee_for(weight, units_per_second, units_per_mb);
+		let unused = if amount == 0 {
+			payment
+		} else {
+			payment.checked_sub((id, amount).into()).map_err(|_| XcmError::TooExpensive)?
+		};
+		self.0 = self.0.saturating_add(weight);
+		self.1 = self.1.saturating_add(amount);
+		self.2 = Some((id, units_per_second, units_per_mb));
+		Ok(unused)
+	}
+
+	fn refund_weight(&mut self, weight: Weight, context: &XcmContext) -> Option<MultiAsset> {
+		log::trace!(target: "xcm::weight", "RateOfFungibleFrom::refund_weight weight: {:?}, context: {:?}", weight, context);
+		let (id, units_per_second, units_per_mb) = self.2?;
+		let weight = weight.min(self.0);
+		let amount = Self::fee_for(weight, units_per_second, units_per_mb).min(self.1);
+		self.0 -= weight;
+		self.1 -= amount;
+		if amount > 0 {
+			Some((id, amount).into())
+		} else {
+			None
+		}
+	}
+}
+
+impl<Rates: WeightFeeRates, R: TakeRevenue> Drop for RateOfFungibleFrom<Rates, R> {
+	fn drop(&mut self) {
+		match self.2 {
+			Some((id, ..)) if self.1 > 0 => R::take_revenue((id, self.1).into()),
+			_ => {},
+		}
+	}
+}
+
 /// Weight trader which uses the configured `WeightToFee` to set the right price for weight and then
 /// places any weight bought into the right account.
 pub struct UsingComponents<
@@ -0,0 +1,121 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Recording of the effects of XCM executions, used to dry-run messages.
+//!
+//! Nothing is recorded outside of [`record`], so executions which are not dry-runs only pay for
+//! checking whether a recording is in progress.
+
+use crate::{trace, Assets};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::latest::prelude::*;
+
+environmental::environmental!(recorder: Effects);
+
+/// The effects of the XCM executions which happened within [`record`].
+#[derive(Default, Clone, Debug, Eq, PartialEq)]
+pub struct Effects {
+	/// The fees charged by the `WeightTrader`, net of any refunds, and the fees handed to the
+	/// `FeeManager`.
+	pub fees: Assets,
+	/// The assets left in holding, which were handed over to the `AssetTrap`.
+	pub trapped: Assets,
+	/// The messages delivered through a [`RecordingSender`], along with their destination, in the
+	/// order they were delivered.
+	pub forwarded: Vec<(MultiLocation, Xcm<()>)>,
+}
+
+/// Run `f`, recording the effects of any XCM executed while doing so.
+///
+/// Note that this does not prevent the executions from having their usual effects on state; the
+/// caller is expected to run this in a transaction which it rolls back.
+pub fn record<R>(f: impl FnOnce() -> R) -> (R, Effects) {
+	let mut effects = Effects::default();
+	let result = recorder::using(&mut effects, f);
+	(result, effects)
+}
+
+fn is_recording() -> bool {
+	recorder::with(|_| ()).is_some()
+}
+
+/// Note that `max_fee` was offered to the trader, which handed `unspent` back.
+pub(crate) fn note_fees_paid(max_fee: Assets, unspent: &Assets) {
+	recorder::with(|effects| {
+		let mut paid = max_fee;
+		for asset in unspent.assets_iter() {
+			paid = paid.checked_sub(asset).unwrap_or_else(|paid| paid);
+		}
+		effects.fees.subsume_assets(paid);
+	});
+}
+
+/// Note that the trader refunded `asset` for unused weight.
+pub(crate) fn note_fees_refunded(asset: &MultiAsset) {
+	recorder::with(|effects| {
+		let fees = sp_std::mem::take(&mut effects.fees);
+		effects.fees = fees.checked_sub(asset.clone()).unwrap_or_else(|fees| fees);
+	});
+}
+
+/// Note that `fees` were handed to the `FeeManager`.
+pub(crate) fn note_fees_handled(fees: &MultiAssets) {
+	recorder::with(|effects| {
+		for asset in fees.inner() {
+			effects.fees.subsume(asset.clone());
+		}
+	});
+}
+
+/// Note that `assets` were trapped.
+pub(crate) fn note_trapped(assets: &Assets) {
+	recorder::with(|effects| effects.trapped.subsume_assets(assets.clone()));
+}
+
+/// A wrapper around an `XcmSender` which records the messages delivered through it, for dry-runs
+/// and traces.
+///
+/// The executor wraps its `XcmSender` with it. Anything else which sends messages while XCM may
+/// be dry-run, e.g. a router used by pallets, should be wrapped too for its messages to be
+/// reported.
+pub struct RecordingSender<S>(PhantomData<S>);
+
+impl<S: SendXcm> SendXcm for RecordingSender<S> {
+	type Ticket = (S::Ticket, Option<(MultiLocation, Xcm<()>)>);
+
+	fn validate(
+		dest: &mut Option<MultiLocation>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		// Only copy the message when it is going to be recorded.
+		let copy = match (dest.as_ref(), msg.as_ref()) {
+			(Some(dest), Some(msg)) if is_recording() || trace::is_tracing() =>
+				Some((*dest, msg.clone())),
+			_ => None,
+		};
+		let (ticket, price) = S::validate(dest, msg)?;
+		Ok(((ticket, copy), price))
+	}
+
+	fn deliver((ticket, copy): Self::Ticket) -> Result<XcmHash, SendError> {
+		let hash = S::deliver(ticket)?;
+		if let Some((dest, msg)) = copy {
+			trace::message_sent(&dest, &msg);
+			recorder::with(|effects| effects.forwarded.push((dest, msg)));
+		}
+		Ok(hash)
+	}
+}
@@ -32,13 +32,17 @@ pub mod traits;
 use traits::{
 	validate_export, AssetExchange, AssetLock, CallDispatcher, ClaimAssets, ConvertOrigin,
 	DropAssets, Enact, ExportXcm, FeeManager, FeeReason, OnResponse, Properties, ShouldExecute,
-	TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader,
+	TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader, XcmAssetTransfers,
 };
 
 mod assets;
 pub use assets::Assets;
 mod config;
 pub use config::Config;
+pub mod dry_run;
+use dry_run::RecordingSender;
+pub mod send_origin;
+pub mod trace;
 
 /// A struct to specify how fees are being paid.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -180,6 +184,12 @@ impl<C> PreparedMessage for WeighedMessage<C> {
 	}
 }
 
+impl<Config: config::Config> XcmAssetTransfers for XcmExecutor<Config> {
+	type IsReserve = Config::IsReserve;
+	type IsTeleporter = Config::IsTeleporter;
+	type AssetTransactor = Config::AssetTransactor;
+}
+
 impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Config> {
 	type Prepared = WeighedMessage<Config::RuntimeCall>;
 	fn prepare(
@@ -205,6 +215,7 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 			weight_credit,
 		);
 		let mut properties = Properties { weight_credit, message_id: None };
+		trace::execution_started(&origin, id);
 		if let Err(e) = Config::Barrier::should_execute(
 			&origin,
 			message.inner_mut(),
@@ -219,27 +230,35 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 				message,
 				properties,
 			);
-			return Outcome::Error(XcmError::Barrier)
+			let outcome = Outcome::Error(XcmError::Barrier);
+			trace::execution_finished(&outcome);
+			return outcome
 		}
 
 		*id = properties.message_id.unwrap_or(*id);
 
 		let mut vm = Self::new(origin, *id);
 
-		while !message.0.is_empty() {
-			let result = vm.process(message);
-			log::trace!(target: "xcm::execute_xcm_in_credit", "result: {:?}", result);
-			message = if let Err(error) = result {
-				vm.total_surplus.saturating_accrue(error.weight);
-				vm.error = Some((error.index, error.xcm_error));
-				vm.take_error_handler().or_else(|| vm.take_appendix())
-			} else {
-				vm.drop_error_handler();
-				vm.take_appendix()
+		// Messages sent by the executor are not sent on behalf of whoever is executing it, even if
+		// they authenticated as some origin.
+		let outcome = send_origin::with_send_origin(None, || {
+			while !message.0.is_empty() {
+				let result = vm.process(message);
+				log::trace!(target: "xcm::execute_xcm_in_credit", "result: {:?}", result);
+				message = if let Err(error) = result {
+					vm.total_surplus.saturating_accrue(error.weight);
+					vm.error = Some((error.index, error.xcm_error));
+					vm.take_error_handler().or_else(|| vm.take_appendix())
+				} else {
+					vm.drop_error_handler();
+					vm.take_appendix()
+				}
 			}
-		}
 
-		vm.post_process(xcm_weight)
+			vm.post_process(xcm_weight)
+		});
+		trace::execution_finished(&outcome);
+		outcome
 	}
 
 	fn charge_fees(origin: impl Into<MultiLocation>, fees: MultiAssets) -> XcmResult {
@@ -248,6 +267,7 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 			for asset in fees.inner() {
 				Config::AssetTransactor::withdraw_asset(&asset, &origin, None)?;
 			}
+			dry_run::note_fees_handled(&fees);
 			Config::FeeManager::handle_fee(fees);
 		}
 		Ok(())
@@ -335,7 +355,22 @@ impl<Config: config::Config> XcmExecutor<Config> {
 							});
 						}
 
-						self.process_instruction(instr)
+						trace::instruction_started(
+							i,
+							&instr,
+							|| Config::Weigher::instr_weight(&instr).unwrap_or_default(),
+							self.origin_ref(),
+							&self.holding,
+							self.total_surplus,
+						);
+						let result = self.process_instruction(instr);
+						trace::instruction_finished(
+							self.origin_ref(),
+							&self.holding,
+							self.total_surplus,
+							result.as_ref().err(),
+						);
+						result
 					});
 					if let Err(e) = inst_res {
 						log::trace!(target: "xcm::execute", "!!! ERROR: {:?}", e);
@@ -373,6 +408,8 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				self.holding, self.context, self.original_origin,
 			);
 			let effective_origin = self.context.origin.as_ref().unwrap_or(&self.original_origin);
+			dry_run::note_trapped(&self.holding);
+			trace::assets_trapped(&self.holding);
 			let trap_weight =
 				Config::AssetTrap::drop_assets(effective_origin, self.holding, &self.context);
 			weight_used.saturating_accrue(trap_weight);
@@ -404,12 +441,14 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		msg: Xcm<()>,
 		reason: FeeReason,
 	) -> Result<XcmHash, XcmError> {
-		let (ticket, fee) = validate_send::<Config::XcmSender>(dest, msg)?;
+		let (ticket, fee) = validate_send::<RecordingSender<Config::XcmSender>>(dest, msg)?;
 		if !Config::FeeManager::is_waived(self.origin_ref(), reason) {
-			let paid = self.holding.try_take(fee.into()).map_err(|_| XcmError::NotHoldingFees)?;
-			Config::FeeManager::handle_fee(paid.into());
+			let paid: MultiAssets =
+				self.holding.try_take(fee.into()).map_err(|_| XcmError::NotHoldingFees)?.into();
+			dry_run::note_fees_handled(&paid);
+			Config::FeeManager::handle_fee(paid);
 		}
-		Config::XcmSender::deliver(ticket).map_err(Into::into)
+		RecordingSender::<Config::XcmSender>::deliver(ticket).map_err(Into::into)
 	}
 
 	/// Remove the registered error handler and return it. Do not refund its weight.
@@ -417,6 +456,9 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		let mut r = Xcm::<Config::RuntimeCall>(vec![]);
 		sp_std::mem::swap(&mut self.error_handler, &mut r);
 		self.error_handler_weight = Weight::zero();
+		if !r.0.is_empty() {
+			trace::jumped_to_error_handler(r.len());
+		}
 		r
 	}
 
@@ -432,6 +474,9 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		let mut r = Xcm::<Config::RuntimeCall>(vec![]);
 		sp_std::mem::swap(&mut self.appendix, &mut r);
 		self.appendix_weight = Weight::zero();
+		if !r.0.is_empty() {
+			trace::jumped_to_appendix(r.len());
+		}
 		r
 	}
 
@@ -459,6 +504,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		if current_surplus.any_gt(Weight::zero()) {
 			self.total_refunded.saturating_accrue(current_surplus);
 			if let Some(w) = self.trader.refund_weight(current_surplus, &self.context) {
+				dry_run::note_fees_refunded(&w);
 				self.subsume_asset(w)?;
 			}
 		}
@@ -691,7 +737,8 @@ impl<Config: config::Config> XcmExecutor<Config> {
 					// pay for `weight` using up to `fees` of the holding register.
 					let max_fee =
 						self.holding.try_take(fees.into()).map_err(|_| XcmError::NotHoldingFees)?;
-					let unspent = self.trader.buy_weight(weight, max_fee, &self.context)?;
+					let unspent = self.trader.buy_weight(weight, max_fee.clone(), &self.context)?;
+					dry_run::note_fees_paid(max_fee, &unspent);
 					self.subsume_assets(unspent)?;
 				}
 				Ok(())
@@ -861,10 +908,11 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				let owner =
 					origin.reanchored(&unlocker, context).map_err(|_| XcmError::ReanchorFailed)?;
 				let msg = Xcm::<()>(vec![NoteUnlockable { asset: remote_asset, owner }]);
-				let (ticket, price) = validate_send::<Config::XcmSender>(unlocker, msg)?;
+				let (ticket, price) =
+					validate_send::<RecordingSender<Config::XcmSender>>(unlocker, msg)?;
 				self.take_fee(price, FeeReason::LockAsset)?;
 				lock_ticket.enact()?;
-				Config::XcmSender::deliver(ticket)?;
+				RecordingSender::<Config::XcmSender>::deliver(ticket)?;
 				Ok(())
 			},
 			UnlockAsset { asset, target } => {
@@ -885,10 +933,11 @@ impl<Config: config::Config> XcmExecutor<Config> {
 					Config::AssetLocker::prepare_reduce_unlockable(locker, asset, origin)?;
 				let msg =
 					Xcm::<()>(vec![UnlockAsset { asset: remote_asset, target: remote_target }]);
-				let (ticket, price) = validate_send::<Config::XcmSender>(locker, msg)?;
+				let (ticket, price) =
+					validate_send::<RecordingSender<Config::XcmSender>>(locker, msg)?;
 				self.take_fee(price, FeeReason::RequestUnlock)?;
 				reduce_ticket.enact()?;
-				Config::XcmSender::deliver(ticket)?;
+				RecordingSender::<Config::XcmSender>::deliver(ticket)?;
 				Ok(())
 			},
 			ExchangeAsset { give, want, maximal } => {
@@ -953,6 +1002,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		} else {
 			self.holding.try_take(fee.into()).map_err(|_| XcmError::NotHoldingFees)?.into()
 		};
+		dry_run::note_fees_handled(&paid);
 		Config::FeeManager::handle_fee(paid);
 		Ok(())
 	}
@@ -985,12 +1035,15 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		let QueryResponseInfo { destination, query_id, max_weight } = info;
 		let instruction = QueryResponse { query_id, response, max_weight, querier };
 		let message = Xcm(vec![instruction]);
-		let (ticket, fee) = validate_send::<Config::XcmSender>(destination, message)?;
+		let (ticket, fee) =
+			validate_send::<RecordingSender<Config::XcmSender>>(destination, message)?;
 		if !Config::FeeManager::is_waived(self.origin_ref(), fee_reason) {
-			let paid = self.holding.try_take(fee.into()).map_err(|_| XcmError::NotHoldingFees)?;
-			Config::FeeManager::handle_fee(paid.into());
+			let paid: MultiAssets =
+				self.holding.try_take(fee.into()).map_err(|_| XcmError::NotHoldingFees)?.into();
+			dry_run::note_fees_handled(&paid);
+			Config::FeeManager::handle_fee(paid);
 		}
-		Config::XcmSender::deliver(ticket).map_err(Into::into)
+		RecordingSender::<Config::XcmSender>::deliver(ticket).map_err(Into::into)
 	}
 
 	fn try_reanchor(
@@ -0,0 +1,38 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The origin on whose behalf messages are sent.
+//!
+//! `SendXcm` implementations only see the destination and the message, whose content is chosen by
+//! whoever sends it. Code which authenticated the sender, like `pallet_xcm::send`, runs the sending
+//! within [`with_send_origin`], so that routers can rely on [`current_send_origin`] to treat
+//! messages differently depending on who sends them.
+
+use xcm::latest::prelude::*;
+
+environmental::environmental!(send_origin: Option<MultiLocation>);
+
+/// Run `f`, with `origin` as the origin of any message sent while doing so.
+///
+/// `None` means the origin is not known, which shadows any origin set by an enclosing call.
+pub fn with_send_origin<R>(mut origin: Option<MultiLocation>, f: impl FnOnce() -> R) -> R {
+	send_origin::using(&mut origin, f)
+}
+
+/// The origin on whose behalf the message currently being sent is sent, if it is known.
+pub fn current_send_origin() -> Option<MultiLocation> {
+	send_origin::with(|origin| *origin).flatten()
+}
@@ -0,0 +1,236 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Instruction-level tracing of XCM executions.
+//!
+//! Tracing is only compiled in with the `trace` feature, and even then nothing is recorded outside
+//! of [`capture`]. Without the feature, the hooks called by the executor do nothing and are
+//! optimised away.
+//!
+//! The XCM simulator enables the feature, so executions of simulated networks can be traced.
+//! Runtimes are built without it, so the dry-run runtime API reports the effects of an execution
+//! but not its trace.
+//!
+//! A trace is a flat list of [`TraceEntry`]s in the order they happened. Executions nested within
+//! an instruction, e.g. through a `Transact`, appear after that instruction's entry.
+
+use crate::Assets;
+use xcm::latest::prelude::*;
+
+#[cfg(feature = "trace")]
+pub use enabled::*;
+
+#[cfg(feature = "trace")]
+mod enabled {
+	use super::*;
+	use sp_std::prelude::*;
+
+	environmental::environmental!(tracer: Tracer);
+
+	/// Everything which happened while executing a single instruction.
+	#[derive(Clone, Debug, Eq, PartialEq)]
+	pub struct InstructionTrace {
+		/// The index of the instruction in the program being executed.
+		pub index: u32,
+		/// The instruction, with any call left encoded.
+		pub instruction: Instruction<()>,
+		/// The origin before the instruction was executed.
+		pub origin_before: Option<MultiLocation>,
+		/// The origin after the instruction was executed.
+		pub origin_after: Option<MultiLocation>,
+		/// The holding register before the instruction was executed.
+		pub holding_before: Assets,
+		/// The holding register after the instruction was executed.
+		pub holding_after: Assets,
+		/// The weight of the instruction, net of any surplus it reported.
+		pub weight_used: Weight,
+		/// The messages sent by the instruction, along with their destination.
+		pub sent: Vec<(MultiLocation, Xcm<()>)>,
+		/// The error the instruction failed with, if any.
+		pub error: Option<XcmError>,
+	}
+
+	/// A step in the execution of an XCM.
+	#[derive(Clone, Debug, Eq, PartialEq)]
+	pub enum TraceEntry {
+		/// Execution of a message started.
+		Started { origin: MultiLocation, message_id: XcmHash },
+		/// An instruction was executed.
+		Instruction(InstructionTrace),
+		/// Execution jumped to the error handler, which has this many instructions.
+		ErrorHandler { len: u32 },
+		/// Execution jumped to the appendix, which has this many instructions.
+		Appendix { len: u32 },
+		/// The assets left in holding were trapped.
+		Trapped { assets: Assets },
+		/// Execution of a message finished.
+		Finished { outcome: Outcome },
+	}
+
+	#[derive(Default)]
+	struct Tracer {
+		trace: Vec<TraceEntry>,
+		/// The entries of the instructions currently being executed, innermost last.
+		open: Vec<(usize, Weight, Weight)>,
+	}
+
+	impl Tracer {
+		fn current(&mut self) -> Option<&mut InstructionTrace> {
+			let (entry, ..) = self.open.last()?;
+			match self.trace.get_mut(*entry) {
+				Some(TraceEntry::Instruction(trace)) => Some(trace),
+				_ => None,
+			}
+		}
+	}
+
+	/// Run `f`, recording a trace of any XCM executed while doing so.
+	pub fn capture<R>(f: impl FnOnce() -> R) -> (R, Vec<TraceEntry>) {
+		let mut tracer = Tracer::default();
+		let result = tracer::using(&mut tracer, f);
+		(result, tracer.trace)
+	}
+
+	pub(crate) fn is_tracing() -> bool {
+		tracer::with(|_| ()).is_some()
+	}
+
+	pub(crate) fn execution_started(origin: &MultiLocation, message_id: &XcmHash) {
+		tracer::with(|t| {
+			t.trace.push(TraceEntry::Started { origin: *origin, message_id: *message_id })
+		});
+	}
+
+	pub(crate) fn instruction_started<Call>(
+		index: usize,
+		instruction: &Instruction<Call>,
+		weight: impl FnOnce() -> Weight,
+		origin: Option<&MultiLocation>,
+		holding: &Assets,
+		surplus: Weight,
+	) {
+		tracer::with(|t| {
+			t.open.push((t.trace.len(), weight(), surplus));
+			t.trace.push(TraceEntry::Instruction(InstructionTrace {
+				index: index as u32,
+				instruction: instruction.clone().into(),
+				origin_before: origin.cloned(),
+				origin_after: None,
+				holding_before: holding.clone(),
+				holding_after: Assets::new(),
+				weight_used: Weight::zero(),
+				sent: Vec::new(),
+				error: None,
+			}));
+		});
+	}
+
+	pub(crate) fn instruction_finished(
+		origin: Option<&MultiLocation>,
+		holding: &Assets,
+		surplus: Weight,
+		error: Option<&XcmError>,
+	) {
+		tracer::with(|t| {
+			let (weight, surplus_before) = match t.open.last() {
+				Some((_, weight, surplus_before)) => (*weight, *surplus_before),
+				None => return,
+			};
+			if let Some(trace) = t.current() {
+				trace.origin_after = origin.cloned();
+				trace.holding_after = holding.clone();
+				trace.weight_used = weight.saturating_sub(surplus.saturating_sub(surplus_before));
+				trace.error = error.cloned();
+			}
+			t.open.pop();
+		});
+	}
+
+	pub(crate) fn message_sent(dest: &MultiLocation, message: &Xcm<()>) {
+		tracer::with(|t| {
+			if let Some(trace) = t.current() {
+				trace.sent.push((*dest, message.clone()));
+			}
+		});
+	}
+
+	pub(crate) fn jumped_to_error_handler(len: usize) {
+		tracer::with(|t| t.trace.push(TraceEntry::ErrorHandler { len: len as u32 }));
+	}
+
+	pub(crate) fn jumped_to_appendix(len: usize) {
+		tracer::with(|t| t.trace.push(TraceEntry::Appendix { len: len as u32 }));
+	}
+
+	pub(crate) fn assets_trapped(assets: &Assets) {
+		tracer::with(|t| t.trace.push(TraceEntry::Trapped { assets: assets.clone() }));
+	}
+
+	pub(crate) fn execution_finished(outcome: &Outcome) {
+		tracer::with(|t| t.trace.push(TraceEntry::Finished { outcome: outcome.clone() }));
+	}
+}
+
+#[cfg(not(feature = "trace"))]
+mod disabled {
+	use super::*;
+
+	#[inline(always)]
+	pub(crate) fn is_tracing() -> bool {
+		false
+	}
+
+	#[inline(always)]
+	pub(crate) fn execution_started(_: &MultiLocation, _: &XcmHash) {}
+
+	#[inline(always)]
+	pub(crate) fn instruction_started<Call>(
+		_: usize,
+		_: &Instruction<Call>,
+		_: impl FnOnce() -> Weight,
+		_: Option<&MultiLocation>,
+		_: &Assets,
+		_: Weight,
+	) {
+	}
+
+	#[inline(always)]
+	pub(crate) fn instruction_finished(
+		_: Option<&MultiLocation>,
+		_: &Assets,
+		_: Weight,
+		_: Option<&XcmError>,
+	) {
+	}
+
+	#[inline(always)]
+	pub(crate) fn message_sent(_: &MultiLocation, _: &Xcm<()>) {}
+
+	#[inline(always)]
+	pub(crate) fn jumped_to_error_handler(_: usize) {}
+
+	#[inline(always)]
+	pub(crate) fn jumped_to_appendix(_: usize) {}
+
+	#[inline(always)]
+	pub(crate) fn assets_trapped(_: &Assets) {}
+
+	#[inline(always)]
+	pub(crate) fn execution_finished(_: &Outcome) {}
+}
+
+#[cfg(not(feature = "trace"))]
+pub(crate) use disabled::*;
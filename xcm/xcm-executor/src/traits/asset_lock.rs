@@ -150,3 +150,28 @@ impl AssetLock for () {
 		Err(LockError::NotApplicable)
 	}
 }
+
+/// Means of locking non-fungible asset instances held by an account, so that they cannot be
+/// transferred until they are unlocked.
+pub trait LockNonFungibles<AccountId> {
+	/// Ensure that `who` holds the non-fungible `asset`, and that it could be locked.
+	fn can_lock(who: &AccountId, asset: &MultiAsset) -> Result<(), LockError>;
+
+	/// Lock the non-fungible `asset` held by `who`.
+	fn lock(who: &AccountId, asset: &MultiAsset) -> Result<(), LockError>;
+
+	/// Unlock the non-fungible `asset` held by `who`.
+	fn unlock(who: &AccountId, asset: &MultiAsset) -> Result<(), LockError>;
+}
+
+impl<AccountId> LockNonFungibles<AccountId> for () {
+	fn can_lock(_: &AccountId, _: &MultiAsset) -> Result<(), LockError> {
+		Err(LockError::UnknownAsset)
+	}
+	fn lock(_: &AccountId, _: &MultiAsset) -> Result<(), LockError> {
+		Err(LockError::UnknownAsset)
+	}
+	fn unlock(_: &AccountId, _: &MultiAsset) -> Result<(), LockError> {
+		Err(LockError::UnknownAsset)
+	}
+}
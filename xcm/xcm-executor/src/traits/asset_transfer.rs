@@ -0,0 +1,104 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::traits::TransactAsset;
+use frame_support::traits::ContainsPair;
+use xcm::latest::prelude::*;
+
+/// The way an asset can be transferred to a destination.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum TransferType {
+	/// Teleport the asset to the destination.
+	Teleport,
+	/// We are the reserve of the asset; move it into the destination's sovereign account here.
+	LocalReserve,
+	/// The destination is the reserve of the asset; withdraw it from our sovereign account there.
+	DestinationReserve,
+	/// Another chain is the reserve of the asset; move it from our sovereign account there into
+	/// the destination's.
+	RemoteReserve(MultiLocation),
+}
+
+/// Error returned when the way to transfer an asset could not be determined.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum TransferTypeError {
+	/// The asset is not identified by a location.
+	NotConcrete,
+	/// The chain holding the reserve of the asset is not trusted as its reserve.
+	UnknownReserve,
+}
+
+/// Exposes the parts of an XCM executor's configuration which decide how assets may be
+/// transferred.
+pub trait XcmAssetTransfers {
+	/// Combinations of (Asset, Location) pairs which we trust as reserves.
+	type IsReserve: ContainsPair<MultiAsset, MultiLocation>;
+	/// Combinations of (Asset, Location) pairs which we trust as teleporters.
+	type IsTeleporter: ContainsPair<MultiAsset, MultiLocation>;
+	/// How to withdraw and deposit an asset, and account for assets teleported out.
+	type AssetTransactor: TransactAsset;
+
+	/// Determine how `asset` should be transferred to `dest`.
+	///
+	/// Teleporting is preferred when `dest` is a trusted teleporter of the asset, then using
+	/// `dest` as the reserve when it is trusted as one. Otherwise the reserve is the chain which
+	/// the asset's location lives in: either us, or a trusted reserve elsewhere.
+	fn determine_for(
+		asset: &MultiAsset,
+		dest: &MultiLocation,
+	) -> Result<TransferType, TransferTypeError> {
+		if Self::IsTeleporter::contains(asset, dest) {
+			return Ok(TransferType::Teleport)
+		}
+		if Self::IsReserve::contains(asset, dest) {
+			return Ok(TransferType::DestinationReserve)
+		}
+		let reserve = match asset.id {
+			Concrete(location) => chain_location(&location),
+			Abstract(_) => return Err(TransferTypeError::NotConcrete),
+		};
+		if reserve == Here.into() {
+			Ok(TransferType::LocalReserve)
+		} else if Self::IsReserve::contains(asset, &reserve) {
+			Ok(TransferType::RemoteReserve(reserve))
+		} else {
+			Err(TransferTypeError::UnknownReserve)
+		}
+	}
+}
+
+/// Trusts no other chain, so assets can only be transferred from their local reserve.
+impl XcmAssetTransfers for () {
+	type IsReserve = ();
+	type IsTeleporter = ();
+	type AssetTransactor = ();
+}
+
+/// The location of the chain which `location` lives in, i.e. `location` up to its first junction
+/// which does not identify a consensus system.
+pub fn chain_location(location: &MultiLocation) -> MultiLocation {
+	let mut chain = MultiLocation::new(location.parents, Here);
+	for junction in location.interior.iter() {
+		match junction {
+			Parachain(_) | GlobalConsensus(_) => {
+				// Cannot overflow, since `chain` is a prefix of `location`.
+				let _ = chain.push_interior(*junction);
+			},
+			_ => break,
+		}
+	}
+	chain
+}
@@ -21,9 +21,11 @@ pub use conversion::{CallDispatcher, ConvertLocation, ConvertOrigin, WithOriginF
 mod drop_assets;
 pub use drop_assets::{ClaimAssets, DropAssets};
 mod asset_lock;
-pub use asset_lock::{AssetLock, Enact, LockError};
+pub use asset_lock::{AssetLock, Enact, LockError, LockNonFungibles};
 mod asset_exchange;
 pub use asset_exchange::AssetExchange;
+mod asset_transfer;
+pub use asset_transfer::{chain_location, TransferType, TransferTypeError, XcmAssetTransfers};
 mod export;
 pub use export::{export_xcm, validate_export, ExportXcm};
 mod fee_manager;
@@ -38,20 +40,21 @@ pub use token_matching::{
 mod on_response;
 pub use on_response::{OnResponse, QueryHandler, QueryResponseStatus, VersionChangeNotifier};
 mod should_execute;
-pub use should_execute::{CheckSuspension, Properties, ShouldExecute};
+pub use should_execute::{CheckSuspension, Properties, RateLimiter, ShouldExecute};
 mod transact_asset;
 pub use transact_asset::TransactAsset;
 mod weight;
 #[deprecated = "Use `sp_runtime::traits::` instead"]
 pub use sp_runtime::traits::{Identity, TryConvertInto as JustTry};
-pub use weight::{WeightBounds, WeightTrader};
+pub use weight::{WeightBounds, WeightFeeRates, WeightTrader};
 
 pub mod prelude {
 	pub use super::{
 		export_xcm, validate_export, AssetExchange, AssetLock, ClaimAssets, ConvertOrigin,
-		DropAssets, Enact, Error, ExportXcm, FeeManager, FeeReason, LockError, MatchesFungible,
-		MatchesFungibles, MatchesNonFungible, MatchesNonFungibles, OnResponse, ShouldExecute,
-		TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader, WithOriginFilter,
+		DropAssets, Enact, Error, ExportXcm, FeeManager, FeeReason, LockError, LockNonFungibles,
+		MatchesFungible, MatchesFungibles, MatchesNonFungible, MatchesNonFungibles, OnResponse,
+		ShouldExecute, TransactAsset, TransferType, VersionChangeNotifier, WeightBounds,
+		WeightFeeRates, WeightTrader, WithOriginFilter, XcmAssetTransfers,
 	};
 	#[allow(deprecated)]
 	pub use super::{Identity, JustTry};
@@ -111,3 +111,25 @@ impl CheckSuspension for Tuple {
 		false
 	}
 }
+
+/// Trait to keep track of the messages executed per origin, and refuse those which would take
+/// their origin over its limits.
+pub trait RateLimiter {
+	/// Note that a message of `weight` from `origin` is about to be executed. Returns `false`,
+	/// without noting anything, if this would take `origin` over its limits.
+	fn try_consume(origin: &MultiLocation, weight: Weight) -> bool;
+
+	/// The weight of checking a message of `len` encoded bytes against the limits: decoding it,
+	/// and calling `try_consume`.
+	fn check_weight(len: u32) -> Weight;
+}
+
+impl RateLimiter for () {
+	fn try_consume(_origin: &MultiLocation, _weight: Weight) -> bool {
+		true
+	}
+
+	fn check_weight(_len: u32) -> Weight {
+		Weight::zero()
+	}
+}
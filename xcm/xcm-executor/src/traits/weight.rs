@@ -36,6 +36,19 @@ pub trait UniversalWeigher {
 	fn weigh(dest: impl Into<MultiLocation>, message: Xcm<()>) -> Result<Weight, ()>;
 }
 
+/// The rates at which weight may be bought with the assets which are accepted for it.
+pub trait WeightFeeRates {
+	/// The units of the asset `id` charged for one second of weight and for 1 MB of proof, or
+	/// `None` if the asset is not accepted as payment for weight.
+	fn fee_rates(id: &AssetId) -> Option<(u128, u128)>;
+}
+
+impl WeightFeeRates for () {
+	fn fee_rates(_: &AssetId) -> Option<(u128, u128)> {
+		None
+	}
+}
+
 /// Charge for weight in order to execute XCM.
 ///
 /// A `WeightTrader` may also be put into a tuple, in which case the default behavior of
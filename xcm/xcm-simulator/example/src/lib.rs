@@ -145,7 +145,11 @@ mod tests {
 	use codec::Encode;
 	use frame_support::{assert_ok, weights::Weight};
 	use xcm::latest::QueryResponseInfo;
-	use xcm_simulator::TestExt;
+	use xcm_simulator::{
+		router::{self, Chain, Fate, Verdict},
+		trace::{self, TraceEntry},
+		HostConfiguration, TestExt, RELAY_MESSAGE_BUS,
+	};
 
 	// Helper function for forming buy execution message
 	fn buy_execution<C>(fees: impl Into<MultiAsset>) -> Instruction<C> {
@@ -187,6 +191,31 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn dmp_execution_can_be_traced() {
+		MockNet::reset();
+
+		let ((), trace) = trace::capture(|| {
+			Relay::execute_with(|| {
+				assert_ok!(RelayChainPalletXcm::send_xcm(Here, Parachain(1), remark(vec![1])));
+			});
+		});
+
+		assert!(matches!(
+			trace.first(),
+			Some(TraceEntry::Started { origin, .. }) if *origin == Parent.into()
+		));
+		assert!(matches!(
+			trace.get(1),
+			Some(TraceEntry::Instruction(instruction))
+				if matches!(instruction.instruction, Transact { .. }) && instruction.error.is_none()
+		));
+		assert!(matches!(
+			trace.last(),
+			Some(TraceEntry::Finished { outcome: Outcome::Complete(_) })
+		));
+	}
+
 	#[test]
 	fn ump() {
 		MockNet::reset();
@@ -243,6 +272,169 @@ mod tests {
 		});
 	}
 
+	fn remark(remark: Vec<u8>) -> Xcm<()> {
+		let call = parachain::RuntimeCall::System(
+			frame_system::Call::<parachain::Runtime>::remark_with_event { remark },
+		);
+		Xcm(vec![Transact {
+			origin_kind: OriginKind::SovereignAccount,
+			require_weight_at_most: Weight::from_parts(INITIAL_BALANCE as u64, 1024 * 1024),
+			call: call.encode().into(),
+		}])
+	}
+
+	fn remarks_on_para_a() -> Vec<sp_core::H256> {
+		ParaA::execute_with(|| {
+			use parachain::{RuntimeEvent, System};
+			System::events()
+				.into_iter()
+				.filter_map(|r| match r.event {
+					RuntimeEvent::System(frame_system::Event::Remarked { hash, .. }) => Some(hash),
+					_ => None,
+				})
+				.collect()
+		})
+	}
+
+	#[test]
+	fn messages_can_be_delivered_one_at_a_time() {
+		MockNet::reset();
+		router::set_manual(true);
+
+		Relay::execute_with(|| {
+			assert_ok!(RelayChainPalletXcm::send_xcm(Here, Parachain(1), remark(vec![1])));
+		});
+		let in_flight = router::in_flight();
+		assert_eq!(in_flight.len(), 1);
+		assert!(in_flight[0].is_on_route(Chain::Relay, Chain::Para(1)));
+		assert!(remarks_on_para_a().is_empty());
+
+		assert_eq!(MockNet::step(), Some(in_flight[0].clone()));
+		assert_eq!(remarks_on_para_a().len(), 1);
+		assert_eq!(MockNet::step(), None);
+		assert_eq!(router::history(), vec![(in_flight[0].clone(), Fate::Delivered)]);
+	}
+
+	#[test]
+	fn messages_can_be_dropped() {
+		MockNet::reset();
+		router::set_filter(|message| {
+			if message.is_on_route(Chain::Relay, Chain::Para(1)) {
+				Verdict::Drop
+			} else {
+				Verdict::Deliver
+			}
+		});
+
+		Relay::execute_with(|| {
+			assert_ok!(RelayChainPalletXcm::send_xcm(Here, Parachain(1), remark(vec![1])));
+			assert_ok!(RelayChainPalletXcm::send_xcm(Here, Parachain(2), remark(vec![2])));
+		});
+
+		assert!(remarks_on_para_a().is_empty());
+		ParaB::execute_with(|| {
+			use parachain::{RuntimeEvent, System};
+			assert!(System::events().iter().any(|r| matches!(
+				r.event,
+				RuntimeEvent::System(frame_system::Event::Remarked { .. })
+			)));
+		});
+		let fates: Vec<_> = router::history().into_iter().map(|(_, fate)| fate).collect();
+		assert_eq!(fates, vec![Fate::Dropped, Fate::Delivered]);
+	}
+
+	#[test]
+	fn held_messages_can_be_reordered() {
+		MockNet::reset();
+		router::set_manual(true);
+		router::set_filter(|_| Verdict::Hold);
+
+		Relay::execute_with(|| {
+			assert_ok!(RelayChainPalletXcm::send_xcm(Here, Parachain(1), remark(vec![1])));
+			assert_ok!(RelayChainPalletXcm::send_xcm(Here, Parachain(1), remark(vec![2])));
+		});
+		MockNet::run_until_quiescent();
+		assert!(remarks_on_para_a().is_empty());
+
+		let held = router::held();
+		assert_eq!(held.len(), 2);
+		assert!(router::release(held[1].id));
+		assert!(router::release(held[0].id));
+		MockNet::run_until_quiescent();
+
+		let hash = |remark: &[u8]| sp_core::H256(sp_io::hashing::blake2_256(remark));
+		assert_eq!(remarks_on_para_a(), vec![hash(&[2]), hash(&[1])]);
+		assert!(router::in_flight().is_empty());
+	}
+
+	#[test]
+	fn messages_on_the_message_bus_are_routed() {
+		MockNet::reset();
+		router::set_manual(true);
+		RELAY_MESSAGE_BUS
+			.with(|b| b.borrow_mut().push_back((Parachain(1).into(), remark(vec![1]))));
+		MockNet::run_until_quiescent();
+
+		let hash = |remark: &[u8]| sp_core::H256(sp_io::hashing::blake2_256(remark));
+		assert_eq!(remarks_on_para_a(), vec![hash(&[1])]);
+		assert_eq!(router::history().len(), 1);
+	}
+
+	#[test]
+	fn messages_exceeding_limits_are_rejected() {
+		MockNet::reset();
+		router::set_manual(true);
+		router::set_limits(Some(HostConfiguration {
+			max_downward_message_size: 512,
+			hrmp_channel_max_message_size: 512,
+			hrmp_channel_max_capacity: 1,
+			hrmp_channel_max_total_size: 1024,
+			..Default::default()
+		}));
+
+		Relay::execute_with(|| {
+			assert_eq!(
+				RelayChainPalletXcm::send_xcm(Here, Parachain(1), remark(vec![1; 1024])),
+				Err(SendError::ExceedsMaxMessageSize),
+			);
+			assert_ok!(RelayChainPalletXcm::send_xcm(Here, Parachain(1), remark(vec![1])));
+		});
+		ParaB::execute_with(|| {
+			let sibling = (Parent, Parachain(1));
+			assert_ok!(ParachainPalletXcm::send_xcm(Here, sibling, remark(vec![2])));
+			assert_eq!(
+				ParachainPalletXcm::send_xcm(Here, sibling, remark(vec![3])),
+				Err(SendError::Transport("HRMP channel is full")),
+			);
+		});
+
+		// Once delivered, messages no longer take up room in the channel.
+		MockNet::run_until_quiescent();
+		ParaB::execute_with(|| {
+			assert_ok!(ParachainPalletXcm::send_xcm(Here, (Parent, Parachain(1)), remark(vec![3])));
+		});
+		MockNet::run_until_quiescent();
+		assert_eq!(remarks_on_para_a().len(), 3);
+	}
+
+	#[test]
+	fn delivery_fees_are_charged() {
+		MockNet::reset();
+		relay_chain::DeliveryFees::set((Here, 100).into());
+
+		Relay::execute_with(|| {
+			assert_ok!(RelayChainPalletXcm::send(
+				relay_chain::RuntimeOrigin::signed(ALICE),
+				Box::new(Parachain(1).into()),
+				Box::new(VersionedXcm::V3(remark(vec![1]))),
+			));
+			assert_eq!(relay_chain::Balances::free_balance(&ALICE), INITIAL_BALANCE - 100);
+		});
+		assert_eq!(remarks_on_para_a().len(), 1);
+
+		relay_chain::DeliveryFees::set(MultiAssets::new());
+	}
+
 	#[test]
 	fn reserve_transfer() {
 		MockNet::reset();
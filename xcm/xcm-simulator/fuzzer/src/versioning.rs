@@ -0,0 +1,72 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Fuzzer checking that XCM instructions convert cleanly between versions.
+
+use codec::DecodeLimit;
+use xcm::{
+	conversion_harness::CheckConversions,
+	v2::Instruction as V2Instruction,
+	v3::{Instruction as V3Instruction, VariantIndexWeigher},
+	MAX_XCM_DECODE_DEPTH,
+};
+
+fn run_input(data: &[u8]) {
+	if let Ok(instruction) =
+		V3Instruction::<()>::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut &data[..])
+	{
+		println!("Checking the conversions of {:?}", instruction);
+		instruction.check_conversions::<VariantIndexWeigher>();
+	}
+	if let Ok(instruction) =
+		V2Instruction::<()>::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut &data[..])
+	{
+		println!("Checking the conversions of {:?}", instruction);
+		instruction.check_conversions::<VariantIndexWeigher>();
+	}
+}
+
+fn main() {
+	#[cfg(fuzzing)]
+	{
+		loop {
+			honggfuzz::fuzz!(|data: &[u8]| {
+				run_input(data);
+			})
+		}
+	}
+	#[cfg(not(fuzzing))]
+	{
+		use std::{env, fs, fs::File, io::Read};
+		let args: Vec<_> = env::args().collect();
+		let md = fs::metadata(&args[1]).unwrap();
+		let all_files = match md.is_dir() {
+			true => fs::read_dir(&args[1])
+				.unwrap()
+				.map(|x| x.unwrap().path().to_str().unwrap().to_string())
+				.collect::<Vec<String>>(),
+			false => (args[1..]).to_vec(),
+		};
+		println!("All_files {:?}", all_files);
+		for argument in all_files {
+			println!("Now doing file {:?}", argument);
+			let mut buffer: Vec<u8> = Vec::new();
+			let mut f = File::open(argument).unwrap();
+			f.read_to_end(&mut buffer).unwrap();
+			run_input(&buffer);
+		}
+	}
+}
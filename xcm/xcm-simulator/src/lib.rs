@@ -31,13 +31,18 @@ pub use polkadot_parachain::primitives::{
 	DmpMessageHandler as DmpMessageHandlerT, Id as ParaId, XcmpMessageFormat,
 	XcmpMessageHandler as XcmpMessageHandlerT,
 };
+pub use polkadot_runtime_common::xcm_sender::PriceForParachainDelivery;
 pub use polkadot_runtime_parachains::{
+	configuration::HostConfiguration,
 	dmp,
 	inclusion::{AggregateMessageOrigin, UmpQueueId},
 };
 pub use xcm::{latest::prelude::*, VersionedXcm};
 pub use xcm_builder::ProcessXcmMessage;
-pub use xcm_executor::XcmExecutor;
+pub use xcm_executor::{trace, XcmExecutor};
+
+pub mod router;
+pub use router::{PARA_MESSAGE_BUS, RELAY_MESSAGE_BUS};
 
 pub trait TestExt {
 	/// Initialize the test environment.
@@ -45,10 +50,11 @@ pub trait TestExt {
 	/// Resets the state of the test environment.
 	fn reset_ext();
 	/// Execute code in the context of the test externalities, without automatic
-	/// message processing. All messages in the message buses can be processed
-	/// by calling `Self::dispatch_xcm_buses()`.
+	/// message processing. All messages in flight can be processed by calling
+	/// `Self::dispatch_xcm_buses()`.
 	fn execute_without_dispatch<R>(execute: impl FnOnce() -> R) -> R;
-	/// Process all messages in the message buses
+	/// Process all messages in flight, unless the router is set to manual
+	/// delivery.
 	fn dispatch_xcm_buses();
 	/// Execute some code in the context of the test externalities, with
 	/// automatic message processing.
@@ -244,32 +250,29 @@ macro_rules! __impl_ext {
 			}
 
 			fn dispatch_xcm_buses() {
-				while exists_messages_in_any_bus() {
-					if let Err(xcm_error) = process_relay_messages() {
-						panic!("Relay chain XCM execution failure: {:?}", xcm_error);
-					}
-					if let Err(xcm_error) = process_para_messages() {
-						panic!("Parachain XCM execution failure: {:?}", xcm_error);
-					}
+				if $crate::router::is_manual() {
+					return
+				}
+				while let Some(message) = $crate::router::next() {
+					deliver_message(message);
 				}
 			}
 		}
 	};
 }
 
-thread_local! {
-	pub static PARA_MESSAGE_BUS: RefCell<VecDeque<(ParaId, MultiLocation, Xcm<()>)>>
-		= RefCell::new(VecDeque::new());
-	pub static RELAY_MESSAGE_BUS: RefCell<VecDeque<(MultiLocation, Xcm<()>)>>
-		= RefCell::new(VecDeque::new());
-}
-
 /// Declares a test network that consists of a relay chain and multiple
 /// parachains. Expects a network struct as an argument and implements testing
 /// functionality, `ParachainXcmRouter` and the `RelayChainXcmRouter`. The
 /// struct needs to contain the relay chain struct and an indexed list of
 /// parachains that are going to be in the network.
 ///
+/// Messages are sent through the [`router`], and may be delivered one at a
+/// time with the `step` and `run_until_quiescent` functions of the network.
+/// The routers reject the messages which exceed the messaging limits set with
+/// [`router::set_limits`], and `RelayChainXcmRouter<P>` prices the delivery
+/// of messages to parachains with `P`, as `ChildParachainRouter` does.
+///
 /// ```ignore
 /// decl_test_network! {
 /// 	    pub struct ExampleNet {
@@ -294,85 +297,107 @@ macro_rules! decl_test_network {
 
 		impl $name {
 			pub fn reset() {
-				use $crate::{TestExt, VecDeque};
-				// Reset relay chain message bus.
-				$crate::RELAY_MESSAGE_BUS.with(|b| b.replace(VecDeque::new()));
-				// Reset parachain message bus.
-				$crate::PARA_MESSAGE_BUS.with(|b| b.replace(VecDeque::new()));
+				use $crate::TestExt;
+				// Reset the messages in flight and how they are routed.
+				$crate::router::reset();
 				<$relay_chain>::reset_ext();
 				$( <$parachain>::reset_ext(); )*
 			}
+
+			/// Deliver the next message in flight, applying the router's filter, and return it.
+			/// Returns `None` if there are no messages to deliver, other than those held back.
+			pub fn step() -> Option<$crate::router::InFlight> {
+				let message = $crate::router::next()?;
+				deliver_message(message.clone());
+				Some(message)
+			}
+
+			/// Deliver messages until there are none left to deliver, other than those held
+			/// back.
+			pub fn run_until_quiescent() {
+				while Self::step().is_some() {}
+			}
 		}
 
-		/// Check if any messages exist in either message bus.
-		fn exists_messages_in_any_bus() -> bool {
-			use $crate::{RELAY_MESSAGE_BUS, PARA_MESSAGE_BUS};
-			let no_relay_messages_left = RELAY_MESSAGE_BUS.with(|b| b.borrow().is_empty());
-			let no_parachain_messages_left = PARA_MESSAGE_BUS.with(|b| b.borrow().is_empty());
-			!(no_relay_messages_left && no_parachain_messages_left)
+		/// Deliver a message to its destination, panicking if it cannot be.
+		fn deliver_message(message: $crate::router::InFlight) {
+			use $crate::router::Chain;
+			let result = match message.from {
+				Chain::Relay => deliver_relay_message(message.destination, message.message),
+				Chain::Para(para_id) =>
+					deliver_para_message(para_id.into(), message.destination, message.message),
+			};
+			match (message.from, result) {
+				(_, Ok(())) => {},
+				(Chain::Relay, Err(xcm_error)) =>
+					panic!("Relay chain XCM execution failure: {:?}", xcm_error),
+				(Chain::Para(_), Err(xcm_error)) =>
+					panic!("Parachain XCM execution failure: {:?}", xcm_error),
+			}
 		}
 
-		/// Process all messages originating from parachains.
-		fn process_para_messages() -> $crate::XcmResult {
+		/// Deliver a message originating from a parachain.
+		fn deliver_para_message(
+			para_id: $crate::ParaId,
+			destination: $crate::MultiLocation,
+			message: $crate::Xcm<()>,
+		) -> $crate::XcmResult {
 			use $crate::{ProcessMessage, XcmpMessageHandlerT};
 
-			while let Some((para_id, destination, message)) = $crate::PARA_MESSAGE_BUS.with(
-				|b| b.borrow_mut().pop_front()) {
-				match destination.interior() {
-					$crate::Junctions::Here if destination.parent_count() == 1 => {
-						let encoded = $crate::encode_xcm(message, $crate::MessageKind::Ump);
-						let mut _id = [0; 32];
-						let r = <$relay_chain>::process_message(
-							encoded.as_slice(), para_id,
-							&mut $crate::WeightMeter::max_limit(),
-							&mut _id,
+			match destination.interior() {
+				$crate::Junctions::Here if destination.parent_count() == 1 => {
+					let encoded = $crate::encode_xcm(message, $crate::MessageKind::Ump);
+					let mut _id = [0; 32];
+					let r = <$relay_chain>::process_message(
+						encoded.as_slice(), para_id,
+						&mut $crate::WeightMeter::max_limit(),
+						&mut _id,
+					);
+					match r {
+						Err($crate::ProcessMessageError::Overweight(required)) =>
+							return Err($crate::XcmError::WeightLimitReached(required)),
+						// Not really the correct error, but there is no "undecodable".
+						Err(_) => return Err($crate::XcmError::Unimplemented),
+						Ok(_) => (),
+					}
+				},
+				$(
+					$crate::X1($crate::Parachain(id)) if *id == $para_id && destination.parent_count() == 1 => {
+						let encoded = $crate::encode_xcm(message, $crate::MessageKind::Xcmp);
+						let messages = vec![(para_id, 1, &encoded[..])];
+						let _weight = <$parachain>::handle_xcmp_messages(
+							messages.into_iter(),
+							$crate::Weight::MAX,
 						);
-						match r {
-							Err($crate::ProcessMessageError::Overweight(required)) =>
-								return Err($crate::XcmError::WeightLimitReached(required)),
-							// Not really the correct error, but there is no "undecodable".
-							Err(_) => return Err($crate::XcmError::Unimplemented),
-							Ok(_) => (),
-						}
 					},
-					$(
-						$crate::X1($crate::Parachain(id)) if *id == $para_id && destination.parent_count() == 1 => {
-							let encoded = $crate::encode_xcm(message, $crate::MessageKind::Xcmp);
-							let messages = vec![(para_id, 1, &encoded[..])];
-							let _weight = <$parachain>::handle_xcmp_messages(
-								messages.into_iter(),
-								$crate::Weight::MAX,
-							);
-						},
-					)*
-					_ => {
-						return Err($crate::XcmError::Unroutable);
-					}
+				)*
+				_ => {
+					return Err($crate::XcmError::Unroutable);
 				}
 			}
 
 			Ok(())
 		}
 
-		/// Process all messages originating from the relay chain.
-		fn process_relay_messages() -> $crate::XcmResult {
+		/// Deliver a message originating from the relay chain.
+		fn deliver_relay_message(
+			destination: $crate::MultiLocation,
+			message: $crate::Xcm<()>,
+		) -> $crate::XcmResult {
 			use $crate::DmpMessageHandlerT;
 
-			while let Some((destination, message)) = $crate::RELAY_MESSAGE_BUS.with(
-				|b| b.borrow_mut().pop_front()) {
-				match destination.interior() {
-					$(
-						$crate::X1($crate::Parachain(id)) if *id == $para_id && destination.parent_count() == 0 => {
-							let encoded = $crate::encode_xcm(message, $crate::MessageKind::Dmp);
-							// NOTE: RelayChainBlockNumber is hard-coded to 1
-							let messages = vec![(1, encoded)];
-							let _weight = <$parachain>::handle_dmp_messages(
-								messages.into_iter(), $crate::Weight::MAX,
-							);
-						},
-					)*
-					_ => return Err($crate::XcmError::Transport("Only sends to children parachain.")),
-				}
+			match destination.interior() {
+				$(
+					$crate::X1($crate::Parachain(id)) if *id == $para_id && destination.parent_count() == 0 => {
+						let encoded = $crate::encode_xcm(message, $crate::MessageKind::Dmp);
+						// NOTE: RelayChainBlockNumber is hard-coded to 1
+						let messages = vec![(1, encoded)];
+						let _weight = <$parachain>::handle_dmp_messages(
+							messages.into_iter(), $crate::Weight::MAX,
+						);
+					},
+				)*
+				_ => return Err($crate::XcmError::Transport("Only sends to children parachain.")),
 			}
 
 			Ok(())
@@ -401,20 +426,26 @@ macro_rules! decl_test_network {
 					},
 				}
 				let m = message.take().ok_or($crate::SendError::MissingArgument)?;
+				$crate::router::check_limits($crate::router::Chain::Para(T::get().into()), &d, &m)?;
 				Ok(((T::get(), d, m), $crate::MultiAssets::new()))
 			}
 			fn deliver(
 				triple: ($crate::ParaId, $crate::MultiLocation, $crate::Xcm<()>),
 			) -> Result<$crate::XcmHash, $crate::SendError> {
-				let hash = $crate::fake_message_hash(&triple.2);
-				$crate::PARA_MESSAGE_BUS.with(|b| b.borrow_mut().push_back(triple));
+				let (para_id, destination, message) = triple;
+				let hash = $crate::fake_message_hash(&message);
+				$crate::router::send(
+					$crate::router::Chain::Para(para_id.into()),
+					destination,
+					message,
+				);
 				Ok(hash)
 			}
 		}
 
-		/// XCM router for relay chain.
-		pub struct RelayChainXcmRouter;
-		impl $crate::SendXcm for RelayChainXcmRouter {
+		/// XCM router for relay chain, pricing the delivery of messages with `P`.
+		pub struct RelayChainXcmRouter<P = ()>($crate::PhantomData<P>);
+		impl<P: $crate::PriceForParachainDelivery> $crate::SendXcm for RelayChainXcmRouter<P> {
 			type Ticket = ($crate::MultiLocation, $crate::Xcm<()>);
 			fn validate(
 				destination: &mut Option<$crate::MultiLocation>,
@@ -423,23 +454,26 @@ macro_rules! decl_test_network {
 				use $crate::DmpMessageHandlerT;
 
 				let d = destination.take().ok_or($crate::SendError::MissingArgument)?;
-				match (d.interior(), d.parent_count()) {
+				let para_id = match (d.interior(), d.parent_count()) {
 					$(
-						($crate::X1($crate::Parachain(id)), 0) if id == &$para_id => {},
+						($crate::X1($crate::Parachain(id)), 0) if id == &$para_id => *id,
 					)*
 					_ => {
 						*destination = Some(d);
 						return Err($crate::SendError::NotApplicable)
 					},
-				}
+				};
 				let m = message.take().ok_or($crate::SendError::MissingArgument)?;
-				Ok(((d, m), $crate::MultiAssets::new()))
+				$crate::router::check_limits($crate::router::Chain::Relay, &d, &m)?;
+				let price = P::price_for_parachain_delivery(para_id.into(), &m);
+				Ok(((d, m), price))
 			}
 			fn deliver(
 				pair: ($crate::MultiLocation, $crate::Xcm<()>),
 			) -> Result<$crate::XcmHash, $crate::SendError> {
-				let hash = $crate::fake_message_hash(&pair.1);
-				$crate::RELAY_MESSAGE_BUS.with(|b| b.borrow_mut().push_back(pair));
+				let (destination, message) = pair;
+				let hash = $crate::fake_message_hash(&message);
+				$crate::router::send($crate::router::Chain::Relay, destination, message);
 				Ok(hash)
 			}
 		}
@@ -0,0 +1,398 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Programmable delivery of the messages sent within a simulated network.
+//!
+//! Every message sent by a chain of the network goes through the router, which keeps it in flight
+//! until the network delivers it. Tests may inspect the messages in flight, and install a filter
+//! deciding for each message whether it is delivered, dropped, delayed or held back until it is
+//! released, e.g. to reorder messages. By default, the network delivers all messages in flight
+//! whenever a chain finishes executing something, but tests may instead deliver them one at a time
+//! with manual delivery.
+//!
+//! The router may also enforce the messaging limits of a relay chain's `HostConfiguration` on the
+//! messages sent, see [`set_limits`].
+
+use crate::{
+	encode_xcm, HostConfiguration, MessageKind, MultiLocation, ParaId, Parachain, RelayBlockNumber,
+	SendError, Xcm, X1,
+};
+use sp_std::{cell::RefCell, collections::vec_deque::VecDeque};
+use xcm::latest::Junctions::Here;
+
+/// A chain of a simulated network.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Chain {
+	/// The relay chain.
+	Relay,
+	/// The parachain with the given ID.
+	Para(u32),
+}
+
+/// A message sent within a simulated network, which is yet to be delivered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InFlight {
+	/// The ID of the message, in the order messages were sent.
+	pub id: u64,
+	/// The chain which sent the message.
+	pub from: Chain,
+	/// The destination of the message, relative to the sender.
+	pub destination: MultiLocation,
+	/// The message.
+	pub message: Xcm<()>,
+}
+
+impl InFlight {
+	/// The chain the message is sent to, if it is one of a simulated network.
+	pub fn to(&self) -> Option<Chain> {
+		destination_chain(self.from, &self.destination)
+	}
+
+	/// The size of the message as it is passed from one chain to the other, if it is sent to one
+	/// of a simulated network.
+	pub fn encoded_size(&self) -> Option<usize> {
+		encoded_size(self.from, self.to()?, &self.message)
+	}
+
+	/// Whether the message is sent from `from` to `to`.
+	pub fn is_on_route(&self, from: Chain, to: Chain) -> bool {
+		self.from == from && self.to() == Some(to)
+	}
+}
+
+/// What to do with a message when it is next to be delivered.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Verdict {
+	/// Deliver the message.
+	Deliver,
+	/// Drop the message, so that it is never delivered.
+	Drop,
+	/// Deliver the message after the given number of other messages have been delivered, or once
+	/// there are no other messages to deliver.
+	Delay(u32),
+	/// Hold the message back until it is released with [`release`].
+	Hold,
+}
+
+/// What happened to a message which is no longer in flight.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fate {
+	/// The message was delivered.
+	Delivered,
+	/// The message was dropped.
+	Dropped,
+}
+
+/// The chain of a simulated network that `destination`, relative to `from`, refers to.
+fn destination_chain(from: Chain, destination: &MultiLocation) -> Option<Chain> {
+	match (from, destination.parent_count(), destination.interior()) {
+		(Chain::Relay, 0, X1(Parachain(id))) => Some(Chain::Para(*id)),
+		(Chain::Para(_), 1, Here) => Some(Chain::Relay),
+		(Chain::Para(_), 1, X1(Parachain(id))) => Some(Chain::Para(*id)),
+		_ => None,
+	}
+}
+
+/// The size of `message` once encoded to be passed from `from` to `to`, i.e. through DMP, UMP or
+/// HRMP.
+fn encoded_size(from: Chain, to: Chain, message: &Xcm<()>) -> Option<usize> {
+	let kind = match (from, to) {
+		(Chain::Relay, Chain::Para(_)) => MessageKind::Dmp,
+		(Chain::Para(_), Chain::Relay) => MessageKind::Ump,
+		(Chain::Para(_), Chain::Para(_)) => MessageKind::Xcmp,
+		(Chain::Relay, Chain::Relay) => return None,
+	};
+	Some(encode_xcm(message.clone(), kind).len())
+}
+
+type Filter = Box<dyn FnMut(&InFlight) -> Verdict>;
+
+#[derive(Default)]
+struct Router {
+	next_id: u64,
+	/// The messages to deliver, in order, along with whether the filter has been applied to them.
+	queue: VecDeque<(InFlight, bool)>,
+	/// The messages delayed, along with the number of deliveries left until they are delivered.
+	delayed: Vec<(InFlight, u32)>,
+	/// The messages held back until they are released.
+	held: Vec<InFlight>,
+	filter: Option<Filter>,
+	manual: bool,
+	history: Vec<(InFlight, Fate)>,
+	/// The configuration whose messaging limits are enforced, if any.
+	limits: Option<HostConfiguration<RelayBlockNumber>>,
+}
+
+impl Router {
+	/// All the messages in flight, in the order they are to be delivered, followed by those
+	/// delayed and those held back.
+	fn in_flight(&self) -> impl Iterator<Item = &InFlight> {
+		self.queue
+			.iter()
+			.map(|(message, _)| message)
+			.chain(self.delayed.iter().map(|(message, _)| message))
+			.chain(self.held.iter())
+	}
+}
+
+thread_local! {
+	static ROUTER: RefCell<Router> = RefCell::new(Router::default());
+	/// Messages sent by parachains without going through the router, e.g. by routers written for
+	/// earlier versions of the simulator. They are sent through the router before any message is
+	/// next delivered.
+	pub static PARA_MESSAGE_BUS: RefCell<VecDeque<(ParaId, MultiLocation, Xcm<()>)>>
+		= RefCell::new(VecDeque::new());
+	/// Messages sent by the relay chain without going through the router, as for
+	/// [`PARA_MESSAGE_BUS`].
+	pub static RELAY_MESSAGE_BUS: RefCell<VecDeque<(MultiLocation, Xcm<()>)>>
+		= RefCell::new(VecDeque::new());
+}
+
+/// Send the messages left on the message buses through the router.
+fn take_message_buses() {
+	for (para_id, destination, message) in PARA_MESSAGE_BUS.with(|b| b.take()) {
+		send(Chain::Para(para_id.into()), destination, message);
+	}
+	for (destination, message) in RELAY_MESSAGE_BUS.with(|b| b.take()) {
+		send(Chain::Relay, destination, message);
+	}
+}
+
+/// Clear the messages in flight and their history, remove the filter and the messaging limits, and
+/// go back to automatic delivery.
+pub fn reset() {
+	ROUTER.with(|r| r.replace(Router::default()));
+	PARA_MESSAGE_BUS.with(|b| b.take());
+	RELAY_MESSAGE_BUS.with(|b| b.take());
+}
+
+/// Set the filter deciding what to do with each message, before it is first to be delivered.
+///
+/// The filter must not call back into the router.
+pub fn set_filter(filter: impl FnMut(&InFlight) -> Verdict + 'static) {
+	ROUTER.with(|r| r.borrow_mut().filter = Some(Box::new(filter)));
+}
+
+/// Remove the filter, so that all messages are delivered.
+pub fn clear_filter() {
+	ROUTER.with(|r| r.borrow_mut().filter = None);
+}
+
+/// Whether messages are only delivered when asked to, rather than whenever a chain of the network
+/// finishes executing something.
+pub fn is_manual() -> bool {
+	ROUTER.with(|r| r.borrow().manual)
+}
+
+/// Set whether messages are only delivered when asked to, e.g. with the `step` and
+/// `run_until_quiescent` functions of the network.
+pub fn set_manual(manual: bool) {
+	ROUTER.with(|r| r.borrow_mut().manual = manual);
+}
+
+/// The messages in flight, in the order they are to be delivered, followed by those delayed and
+/// those held back.
+pub fn in_flight() -> Vec<InFlight> {
+	ROUTER.with(|r| r.borrow().in_flight().cloned().collect())
+}
+
+/// The messages held back until they are released.
+pub fn held() -> Vec<InFlight> {
+	ROUTER.with(|r| r.borrow().held.clone())
+}
+
+/// The messages which are no longer in flight, and what happened to them, in order.
+pub fn history() -> Vec<(InFlight, Fate)> {
+	ROUTER.with(|r| r.borrow().history.clone())
+}
+
+/// Release the message `id` which was held back or delayed, so that it is delivered after the
+/// messages already due, and those released before it. Returns whether there was such a message.
+pub fn release(id: u64) -> bool {
+	ROUTER.with(|r| {
+		let mut r = r.borrow_mut();
+		let message = if let Some(index) = r.held.iter().position(|m| m.id == id) {
+			r.held.remove(index)
+		} else if let Some(index) = r.delayed.iter().position(|(m, _)| m.id == id) {
+			r.delayed.remove(index).0
+		} else {
+			return false
+		};
+		r.queue.push_back((message, true));
+		true
+	})
+}
+
+/// Drop the message `id` in flight, so that it is never delivered. Returns whether there was such
+/// a message.
+pub fn drop_message(id: u64) -> bool {
+	ROUTER.with(|r| {
+		let mut r = r.borrow_mut();
+		let message = if let Some(index) = r.queue.iter().position(|(m, _)| m.id == id) {
+			r.queue.remove(index).map(|(m, _)| m)
+		} else if let Some(index) = r.delayed.iter().position(|(m, _)| m.id == id) {
+			Some(r.delayed.remove(index).0)
+		} else if let Some(index) = r.held.iter().position(|m| m.id == id) {
+			Some(r.held.remove(index))
+		} else {
+			None
+		};
+		match message {
+			Some(message) => {
+				r.history.push((message, Fate::Dropped));
+				true
+			},
+			None => false,
+		}
+	})
+}
+
+/// Enforce the messaging limits of `config` on the messages sent from now on, or stop enforcing
+/// them if `None`.
+///
+/// The limits are those the relay chain enforces on-chain: the maximum size of downward, upward
+/// and HRMP messages, and the maximum number and total size of the messages queued upward from a
+/// parachain and in each HRMP channel. HRMP channels are considered opened with the largest
+/// capacity `config` allows. Messages count towards the queues they are sent through for as long
+/// as they are in flight, including while they are delayed or held back.
+pub fn set_limits(config: Option<HostConfiguration<RelayBlockNumber>>) {
+	ROUTER.with(|r| r.borrow_mut().limits = config);
+}
+
+/// The configuration whose messaging limits are enforced, if any.
+pub fn limits() -> Option<HostConfiguration<RelayBlockNumber>> {
+	ROUTER.with(|r| r.borrow().limits.clone())
+}
+
+/// Check that `message`, sent by `from` to `destination`, is within the messaging limits, if they
+/// are enforced.
+///
+/// Messages which are too large are rejected with `ExceedsMaxMessageSize`, as the relay chain
+/// would, and messages which do not fit in the queue they are sent through are rejected with a
+/// `Transport` error.
+pub fn check_limits(
+	from: Chain,
+	destination: &MultiLocation,
+	message: &Xcm<()>,
+) -> Result<(), SendError> {
+	ROUTER.with(|r| {
+		let r = r.borrow();
+		let config = match &r.limits {
+			Some(config) => config,
+			None => return Ok(()),
+		};
+		let to = destination_chain(from, destination).ok_or(SendError::Unroutable)?;
+		let size = encoded_size(from, to, message).ok_or(SendError::Unroutable)?;
+		let (max_message_size, max_queue) = match (from, to) {
+			(Chain::Relay, _) => (config.max_downward_message_size, None),
+			(_, Chain::Relay) => (
+				config.max_upward_message_size,
+				Some((
+					config.max_upward_queue_count,
+					config.max_upward_queue_size,
+					"Upward message queue is full",
+				)),
+			),
+			_ => (
+				config.hrmp_channel_max_message_size,
+				Some((
+					config.hrmp_channel_max_capacity,
+					config.hrmp_channel_max_total_size,
+					"HRMP channel is full",
+				)),
+			),
+		};
+		if size > max_message_size as usize {
+			return Err(SendError::ExceedsMaxMessageSize)
+		}
+		if let Some((max_count, max_total_size, error)) = max_queue {
+			let (count, total_size) = r
+				.in_flight()
+				.filter(|m| m.is_on_route(from, to))
+				.filter_map(InFlight::encoded_size)
+				.fold((1usize, size), |(count, total), size| (count + 1, total + size));
+			if count > max_count as usize || total_size > max_total_size as usize {
+				return Err(SendError::Transport(error))
+			}
+		}
+		Ok(())
+	})
+}
+
+/// Put a message sent by `from` in flight.
+pub fn send(from: Chain, destination: MultiLocation, message: Xcm<()>) {
+	ROUTER.with(|r| {
+		let mut r = r.borrow_mut();
+		let id = r.next_id;
+		r.next_id += 1;
+		r.queue.push_back((InFlight { id, from, destination, message }, false));
+	})
+}
+
+/// Take the next message to deliver, if any, applying the filter to the messages before it.
+///
+/// Delayed messages are delivered once there are no other messages to deliver, but messages held
+/// back are not.
+pub fn next() -> Option<InFlight> {
+	take_message_buses();
+	ROUTER.with(|r| {
+		let mut r = r.borrow_mut();
+		let r = &mut *r;
+		loop {
+			let (message, filtered) = match r.queue.pop_front() {
+				Some(next) => next,
+				None => {
+					// Nothing else to deliver, so deliver the message which is delayed the least.
+					let index = (0..r.delayed.len()).min_by_key(|&i| r.delayed[i].1)?;
+					(r.delayed.remove(index).0, true)
+				},
+			};
+			let verdict = match (&mut r.filter, filtered) {
+				(Some(filter), false) => filter(&message),
+				_ => Verdict::Deliver,
+			};
+			match verdict {
+				Verdict::Deliver => {
+					for (_, left) in r.delayed.iter_mut() {
+						*left = left.saturating_sub(1);
+					}
+					let (due, delayed) = sp_std::mem::take(&mut r.delayed)
+						.into_iter()
+						.partition::<Vec<_>, _>(|(_, left)| *left == 0);
+					r.delayed = delayed;
+					for (due, _) in due.into_iter().rev() {
+						r.queue.push_front((due, true));
+					}
+					r.history.push((message.clone(), Fate::Delivered));
+					return Some(message)
+				},
+				Verdict::Drop => r.history.push((message, Fate::Dropped)),
+				Verdict::Delay(0) => r.queue.push_front((message, true)),
+				Verdict::Delay(deliveries) => r.delayed.push((message, deliveries)),
+				Verdict::Hold => r.held.push(message),
+			}
+		}
+	})
+}
+
+/// Whether there are messages to deliver, other than those held back.
+pub fn has_pending() -> bool {
+	take_message_buses();
+	ROUTER.with(|r| {
+		let r = r.borrow();
+		!r.queue.is_empty() || !r.delayed.is_empty()
+	})
+}